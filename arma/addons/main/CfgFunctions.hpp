@@ -5,6 +5,10 @@ class CfgFunctions {
                 file = QPATHTO_F(functions\setMission);
                 preInit = 1;
             };
+            class dev {
+                file = QPATHTO_F(functions\dev);
+                preInit = 1;
+            };
         };
     };
 };
@@ -0,0 +1,31 @@
+use arma_rs::{Context, ContextState, Group};
+use hemtt_common::arma::control::fromarma::{Message, Outcome};
+
+pub fn group() -> Group {
+    Group::new()
+        .command("returned", returned)
+        .command("reloaded", reloaded)
+        .command("failed", failed)
+}
+
+pub fn returned(ctx: Context, value: String) {
+    send(&ctx, "dev:returned", Outcome::Returned(value));
+}
+
+pub fn reloaded(ctx: Context, function: String) {
+    send(&ctx, "dev:reloaded", Outcome::Reloaded(function));
+}
+
+pub fn failed(ctx: Context, reason: String) {
+    send(&ctx, "dev:failed", Outcome::Failed(reason));
+}
+
+fn send(ctx: &Context, command: &str, outcome: Outcome) {
+    let Some(sender) = ctx.global().get::<std::sync::mpsc::Sender<Message>>() else {
+        println!("`{command}` called without a sender");
+        return;
+    };
+    if let Err(e) = sender.send(Message::Outcome(outcome)) {
+        println!("`{command}` failed to send: {e}");
+    }
+}
@@ -1,19 +1,22 @@
-use std::io::{Read, Write};
-
 use arma_rs::{Context, ContextState, Extension, arma};
 use hemtt_common::arma::control::{
+    self,
     fromarma::{self, Control, Message},
     toarma,
 };
 use interprocess::local_socket::{GenericNamespaced, Stream, prelude::*};
 
+mod dev;
 mod photoshoot;
+mod test;
 
 #[arma]
 fn init() -> Extension {
     let ext = Extension::build()
         .command("mission", mission)
         .command("log", log)
+        .group("dev", dev::group())
+        .group("test", test::group())
         .group("photoshoot", photoshoot::group())
         .finish();
     let ctx = ext.context();
@@ -24,16 +27,7 @@ fn init() -> Extension {
             Stream::connect("hemtt_arma".to_ns_name::<GenericNamespaced>().unwrap()).unwrap();
         socket.set_nonblocking(true).unwrap();
         loop {
-            let mut len_buf = [0u8; 4];
-            if socket.read_exact(&mut len_buf).is_ok()
-                && !len_buf.is_empty()
-                && len_buf != [255u8; 4]
-            {
-                let len = u32::from_le_bytes(len_buf);
-                let mut buf = vec![0u8; len as usize];
-                socket.read_exact(&mut buf).unwrap();
-                let buf = String::from_utf8(buf).unwrap();
-                let message: toarma::Message = serde_json::from_str(&buf).unwrap();
+            if let Ok(Some(message)) = control::read::<_, toarma::Message>(&mut socket) {
                 match message {
                     toarma::Message::Control(control) => match control {
                         toarma::Control::Exit => {
@@ -65,6 +59,18 @@ fn init() -> Extension {
                             ctx.callback_null("hemtt_ps", "done").unwrap();
                         }
                     },
+                    toarma::Message::Execute(code) => {
+                        ctx.callback_data("hemtt_dev", "execute", code).unwrap();
+                    }
+                    toarma::Message::Test(toarma::Test::Run(paths)) => {
+                        println!("Test: {} files", paths.len());
+                        ctx.callback_data("hemtt_test", "run", paths).unwrap();
+                    }
+                    toarma::Message::Reload(function, code) => {
+                        println!("Reload: {function}");
+                        ctx.callback_data("hemtt_dev", "reload", vec![function, code])
+                            .unwrap();
+                    }
                 }
             }
             if let Ok(message) = recv.recv_timeout(std::time::Duration::from_millis(100)) {
@@ -105,9 +111,5 @@ fn log(ctx: Context, level: String, message: String) {
 }
 
 fn send(message: fromarma::Message, socket: &mut Stream) {
-    let message = serde_json::to_string(&message).unwrap();
-    let len = u32::try_from(message.len()).unwrap();
-    socket.write_all(&u32::to_le_bytes(len)).unwrap();
-    socket.write_all(message.as_bytes()).unwrap();
-    socket.flush().unwrap();
+    control::write(socket, &message).unwrap();
 }
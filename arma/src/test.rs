@@ -0,0 +1,36 @@
+use arma_rs::{Context, ContextState, Group};
+use hemtt_common::arma::control::fromarma::{Message, Test};
+
+pub fn group() -> Group {
+    Group::new()
+        .command("ready", ready)
+        .command("passed", passed)
+        .command("failed", failed)
+        .command("done", done)
+}
+
+pub fn ready(ctx: Context) {
+    send(&ctx, "test:ready", Test::Ready);
+}
+
+pub fn passed(ctx: Context, path: String, seconds: f64) {
+    send(&ctx, "test:passed", Test::Passed(path, seconds));
+}
+
+pub fn failed(ctx: Context, path: String, reason: String, seconds: f64) {
+    send(&ctx, "test:failed", Test::Failed(path, reason, seconds));
+}
+
+pub fn done(ctx: Context) {
+    send(&ctx, "test:done", Test::Done);
+}
+
+fn send(ctx: &Context, command: &str, test: Test) {
+    let Some(sender) = ctx.global().get::<std::sync::mpsc::Sender<Message>>() else {
+        println!("`{command}` called without a sender");
+        return;
+    };
+    if let Err(e) = sender.send(Message::Test(test)) {
+        println!("`{command}` failed to send: {e}");
+    }
+}
@@ -0,0 +1,50 @@
+use hemtt_common::changelog::Changelog;
+
+use crate::{
+    context::{Context, PreservePrevious},
+    error::Error,
+    report::Report,
+};
+
+#[derive(clap::Parser)]
+#[command(verbatim_doc_comment)]
+/// Create release notes from the git history
+///
+/// The release notes contain the commits since the last tag, or since the tag before it if the
+/// current commit is tagged. Commits following [Conventional Commits](https://www.conventionalcommits.org)
+/// are grouped into breaking changes, features, fixes, and performance improvements.
+///
+/// ```markdown
+/// ## 1.3.0
+///
+/// ### Features
+///
+/// - **medical:** add splints (1a2b3c4)
+/// ```
+///
+/// The release notes are also available to hooks as `HEMTT_CHANGELOG`, and can be written to
+/// the `releases` folder by [`hemtt release`](release.md).
+pub struct Command {
+    #[arg(short, long)]
+    /// Write the release notes to a file instead of printing them
+    output: Option<String>,
+
+    #[clap(flatten)]
+    global: crate::GlobalArgs,
+}
+
+/// Execute the changelog command
+///
+/// # Errors
+/// [`Error`] if the project is not in a git repository
+pub fn execute(cmd: &Command) -> Result<Report, Error> {
+    let ctx = Context::new(None, PreservePrevious::Keep, false)?;
+    let changelog = Changelog::discover(ctx.project_folder())?;
+    let markdown = changelog.to_markdown(&ctx.config().version().get(ctx.workspace_path().vfs())?);
+    if let Some(output) = &cmd.output {
+        std::fs::write(output, markdown)?;
+    } else {
+        print!("{markdown}");
+    }
+    Ok(Report::new())
+}
@@ -0,0 +1,56 @@
+use crate::{
+    commands::global_modules,
+    context::Context,
+    error::Error,
+    executor::Executor,
+    modules::{Binarize, Docs, Rapifier, docs::Format, pbo::Collapse},
+    report::Report,
+};
+
+#[derive(clap::Parser)]
+/// Generate an API reference from function headers
+///
+/// `hemtt docs` reads the header at the start of each function in
+/// the functions folder of every addon, and generates a reference
+/// for each addon in `.hemttout/docs`.
+///
+/// The same checks as [`hemtt check`](./check.md) are run, including
+/// the lints that compare each header with the function's `params`
+/// and report public functions that are not documented.
+pub struct Command {
+    #[arg(long, default_value = "markdown")]
+    /// Output format
+    format: Format,
+
+    #[arg(long, action = clap::ArgAction::SetTrue)]
+    /// Only include functions marked with `Public: Yes`
+    public: bool,
+
+    #[clap(flatten)]
+    global: crate::GlobalArgs,
+}
+
+/// Execute the docs command
+///
+/// # Errors
+/// [`Error`] depending on the modules
+pub fn execute(cmd: &Command) -> Result<Report, Error> {
+    let ctx = Context::new(Some("docs"), crate::context::PreservePrevious::Remove, true)?;
+
+    let mut executor = Executor::new(ctx);
+    global_modules(&mut executor);
+
+    executor.collapse(Collapse::Yes);
+
+    executor.add_module(Box::<Rapifier>::default());
+    executor.add_module(Box::<Binarize>::new(Binarize::new(true)));
+    executor.add_module(Box::new(Docs::new(cmd.format, cmd.public)));
+
+    info!("Generating documentation");
+
+    executor.init();
+    executor.check();
+    executor.build(false);
+
+    executor.run()
+}
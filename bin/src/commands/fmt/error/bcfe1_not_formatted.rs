@@ -0,0 +1,32 @@
+use std::sync::Arc;
+
+use hemtt_workspace::reporting::{Code, Diagnostic};
+
+pub struct NotFormatted {
+    path: String,
+}
+
+impl Code for NotFormatted {
+    fn ident(&self) -> &'static str {
+        "BCFE1"
+    }
+
+    fn message(&self) -> String {
+        format!("`{}` is not formatted", self.path)
+    }
+
+    fn help(&self) -> Option<String> {
+        Some("run `hemtt fmt` to format it".to_string())
+    }
+
+    fn diagnostic(&self) -> Option<Diagnostic> {
+        Some(Diagnostic::from_code(self))
+    }
+}
+
+impl NotFormatted {
+    #[must_use]
+    pub fn code(path: String) -> Arc<dyn Code> {
+        Arc::new(Self { path })
+    }
+}
@@ -0,0 +1 @@
+pub mod bcfe1_not_formatted;
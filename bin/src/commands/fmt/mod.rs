@@ -0,0 +1,105 @@
+use std::path::{Path, PathBuf};
+
+use hemtt_format::{Language, Options, format};
+use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
+
+use crate::{
+    context::{Context, PreservePrevious},
+    error::Error,
+    report::Report,
+};
+
+use self::error::bcfe1_not_formatted::NotFormatted;
+
+pub mod error;
+
+#[derive(clap::Parser)]
+#[command(verbatim_doc_comment)]
+/// Format configs and SQF
+///
+/// `hemtt fmt` formats `config.cpp`, `.hpp`, `.ext`, and `.sqf` files in the
+/// `addons` and `optionals` folders.
+///
+/// Files are indented with 4 spaces, opening braces are kept on the same line,
+/// and blank lines are collapsed. In configs, `=` is surrounded by single spaces,
+/// and arrays that do not fit on a line are wrapped with one item per line.
+///
+/// The source is formatted as written, before preprocessing, so comments,
+/// macros, and preprocessor directives are kept as they are.
+pub struct Command {
+    #[arg(long)]
+    /// Report files that are not formatted, without changing them
+    ///
+    /// Fails if any file would be changed, for use in CI
+    check: bool,
+
+    /// Files or folders to format, instead of the addons
+    paths: Vec<String>,
+}
+
+/// Execute the fmt command
+///
+/// # Errors
+/// [`Error`] if a file could not be read or written
+pub fn execute(cmd: &Command) -> Result<Report, Error> {
+    let ctx = Context::new(None, PreservePrevious::Keep, false)?;
+    let roots = if cmd.paths.is_empty() {
+        ["addons", "optionals"]
+            .iter()
+            .map(|root| ctx.project_folder().join(root))
+            .filter(|root| root.exists())
+            .collect::<Vec<_>>()
+    } else {
+        cmd.paths.iter().map(PathBuf::from).collect()
+    };
+    let mut files = Vec::new();
+    for root in roots {
+        for entry in walkdir::WalkDir::new(&root).sort_by_file_name() {
+            let entry = entry?;
+            if let Some(language) =
+                Language::from_path(entry.path()).filter(|_| entry.file_type().is_file())
+            {
+                files.push((entry.into_path(), language));
+            }
+        }
+    }
+    let options = Options::default();
+    let changed = files
+        .par_iter()
+        .map(|(path, language)| {
+            let source = std::fs::read_to_string(path)?;
+            let formatted = format(&source, *language, &options);
+            if formatted == source {
+                return Ok(None);
+            }
+            if !cmd.check {
+                std::fs::write(path, formatted)?;
+            }
+            Ok(Some(display(ctx.project_folder(), path)))
+        })
+        .collect::<Result<Vec<_>, std::io::Error>>()?
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>();
+    let mut report = Report::new();
+    if cmd.check {
+        for path in changed {
+            report.push(NotFormatted::code(path));
+        }
+    } else {
+        for path in &changed {
+            debug!("Formatted {path}");
+        }
+        info!("Formatted {} of {} files", changed.len(), files.len());
+    }
+    Ok(report)
+}
+
+/// The path of a file, relative to the project when it is inside it
+fn display(project: &Path, path: &Path) -> String {
+    path.strip_prefix(project)
+        .unwrap_or(path)
+        .display()
+        .to_string()
+        .replace('\\', "/")
+}
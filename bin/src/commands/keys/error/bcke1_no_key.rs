@@ -0,0 +1,32 @@
+use std::sync::Arc;
+
+use hemtt_workspace::reporting::{Code, Diagnostic};
+
+pub struct NoKey;
+
+impl Code for NoKey {
+    fn ident(&self) -> &'static str {
+        "BCKE1"
+    }
+
+    fn message(&self) -> String {
+        "No persistent signing key is configured.".to_string()
+    }
+
+    fn help(&self) -> Option<String> {
+        Some(
+            "set `private_key` or `private_key_env` in the `[signing]` section of `.hemtt/project.toml`, or pass the path to a key"
+                .to_string(),
+        )
+    }
+
+    fn diagnostic(&self) -> Option<Diagnostic> {
+        Some(Diagnostic::from_code(self))
+    }
+}
+
+impl NoKey {
+    pub fn code() -> Arc<dyn Code> {
+        Arc::new(Self)
+    }
+}
@@ -0,0 +1,31 @@
+use std::sync::Arc;
+
+use hemtt_workspace::reporting::{Code, Diagnostic};
+
+pub struct FileExists {
+    path: String,
+}
+
+impl Code for FileExists {
+    fn ident(&self) -> &'static str {
+        "BCKE2"
+    }
+
+    fn message(&self) -> String {
+        format!("File `{}` already exists.", self.path)
+    }
+
+    fn help(&self) -> Option<String> {
+        Some("use `--force` to overwrite it".to_string())
+    }
+
+    fn diagnostic(&self) -> Option<Diagnostic> {
+        Some(Diagnostic::from_code(self))
+    }
+}
+
+impl FileExists {
+    pub fn code(path: String) -> Arc<dyn Code> {
+        Arc::new(Self { path })
+    }
+}
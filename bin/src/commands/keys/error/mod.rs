@@ -0,0 +1,2 @@
+pub mod bcke1_no_key;
+pub mod bcke2_file_exists;
@@ -0,0 +1,41 @@
+use std::{fs::File, path::PathBuf};
+
+use crate::{Error, report::Report};
+
+use super::load;
+
+#[derive(clap::Parser)]
+#[allow(clippy::module_name_repetitions)]
+/// Export the public key
+///
+/// Writes `{authority}.bikey` for the private key, by default to the
+/// `keys` folder of the project, where `hemtt release` checks the
+/// persistent key against it.
+///
+/// Without `--key`, the persistent key configured for the project is used.
+pub struct Command {
+    #[arg(long)]
+    /// Path to a `.biprivatekey`
+    key: Option<String>,
+
+    #[arg(long, short, default_value = "keys")]
+    /// Folder to write the `.bikey` to
+    output: String,
+}
+
+/// Execute the export-public command
+///
+/// # Errors
+/// [`Error`] if the key can not be read or written
+pub fn execute(cmd: &Command) -> Result<Report, Error> {
+    let mut report = Report::new();
+    let Some(key) = load(cmd.key.as_deref(), &mut report)? else {
+        return Ok(report);
+    };
+    let folder = PathBuf::from(&cmd.output);
+    std::fs::create_dir_all(&folder)?;
+    let path = folder.join(format!("{}.bikey", key.authority()));
+    key.to_public_key().write(&mut File::create(&path)?)?;
+    info!("Exported public key to {}", path.display());
+    Ok(report)
+}
@@ -0,0 +1,71 @@
+use std::{
+    fs::{File, create_dir_all},
+    path::PathBuf,
+};
+
+use clap::builder::TypedValueParser as _;
+use hemtt_signing::BIPrivateKey;
+
+use crate::{Error, commands::keys::error::bcke2_file_exists::FileExists, report::Report};
+
+use super::project_config;
+
+#[derive(clap::Parser)]
+#[allow(clippy::module_name_repetitions)]
+/// Generate a new private key
+///
+/// The authority defaults to the `authority` in the `[signing]` section
+/// of the project, or the project's prefix. Unlike the keys generated for
+/// each release, the version is not included.
+pub struct Command {
+    /// Authority of the key
+    authority: Option<String>,
+
+    #[arg(long, short)]
+    /// Where to write the key, defaults to `{authority}.biprivatekey`
+    output: Option<String>,
+
+    #[arg(long, default_value = "1024", value_parser = clap::builder::PossibleValuesParser::new(["1024", "2048", "4096"]).map(|length| length.parse::<u32>().expect("possible values are numbers")))]
+    /// Length of the key in bits
+    length: u32,
+
+    #[arg(long, action = clap::ArgAction::SetTrue)]
+    /// Overwrite an existing key
+    force: bool,
+}
+
+/// Execute the generate command
+///
+/// # Errors
+/// [`Error`] if the key can not be generated or written
+pub fn execute(cmd: &Command) -> Result<Report, Error> {
+    let mut report = Report::new();
+    let authority = if let Some(authority) = &cmd.authority {
+        authority.clone()
+    } else {
+        let config = project_config()?;
+        config.signing().authority().map_or_else(
+            || config.prefix().to_string(),
+            std::string::ToString::to_string,
+        )
+    };
+    let output = cmd.output.as_ref().map_or_else(
+        || PathBuf::from(format!("{authority}.biprivatekey")),
+        PathBuf::from,
+    );
+    if output.exists() && !cmd.force {
+        report.push(FileExists::code(output.display().to_string()));
+        return Ok(report);
+    }
+    let key = BIPrivateKey::generate(cmd.length, &authority)?;
+    if let Some(parent) = output
+        .parent()
+        .filter(|parent| !parent.as_os_str().is_empty())
+    {
+        create_dir_all(parent)?;
+    }
+    key.write_danger(&mut File::create(&output)?)?;
+    info!("Generated key `{authority}` in {}", output.display());
+    warn!("Keep the private key secret, do not commit it to your repository");
+    Ok(report)
+}
@@ -0,0 +1,174 @@
+use std::{fs::File, path::Path};
+
+use hemtt_common::config::ProjectConfig;
+use hemtt_signing::BIPrivateKey;
+
+use crate::{
+    Error, commands::keys::error::bcke1_no_key::NoKey, modules::sign::private_key, report::Report,
+};
+
+mod error;
+pub mod export_public;
+pub mod generate;
+pub mod show;
+
+#[derive(clap::Parser)]
+#[command(arg_required_else_help = true)]
+/// Manage a persistent signing key
+///
+/// See [Signing](../../configuration/signing.md) for configuring the key.
+pub struct Command {
+    #[command(subcommand)]
+    commands: Subcommands,
+
+    #[clap(flatten)]
+    global: crate::GlobalArgs,
+}
+
+#[derive(clap::Subcommand)]
+enum Subcommands {
+    Generate(generate::Command),
+    Show(show::Command),
+    ExportPublic(export_public::Command),
+}
+
+/// Execute the keys command
+///
+/// # Errors
+/// [`Error`] depending on the modules
+pub fn execute(cmd: &Command) -> Result<Report, Error> {
+    match &cmd.commands {
+        Subcommands::Generate(cmd) => generate::execute(cmd),
+        Subcommands::Show(cmd) => show::execute(cmd),
+        Subcommands::ExportPublic(cmd) => export_public::execute(cmd),
+    }
+}
+
+fn project_config() -> Result<ProjectConfig, Error> {
+    let path = Path::new(".hemtt").join("project.toml");
+    if !path.exists() {
+        return Err(Error::ConfigNotFound);
+    }
+    Ok(ProjectConfig::from_file(&path)?)
+}
+
+/// Load the key at the path, or the persistent key configured for the project
+fn load(path: Option<&str>, report: &mut Report) -> Result<Option<BIPrivateKey>, Error> {
+    if let Some(path) = path {
+        return Ok(Some(BIPrivateKey::read(&mut File::open(path)?)?));
+    }
+    match private_key(&project_config()?) {
+        Ok(Some(key)) => Ok(Some(key)),
+        Ok(None) => {
+            report.push(NoKey::code());
+            Ok(None)
+        }
+        Err(code) => {
+            report.push(code);
+            Ok(None)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use std::{fs::File, path::Path};
+
+    use clap::Parser;
+    use hemtt_signing::{BIPrivateKey, BIPublicKey};
+    use sealed_test::prelude::*;
+
+    use super::{export_public, generate, show};
+    use crate::modules::sign::check_published;
+
+    fn project(signing: &str) {
+        std::fs::create_dir_all(".hemtt").unwrap();
+        std::fs::write(
+            ".hemtt/project.toml",
+            format!("name = \"Test\"\nprefix = \"abe\"\n\n[signing]\n{signing}\n"),
+        )
+        .unwrap();
+    }
+
+    #[sealed_test]
+    fn round_trip() {
+        project("authority = \"abe_keys\"\nprivate_key = \"private/abe.biprivatekey\"");
+        let report = generate::execute(&generate::Command::parse_from([
+            "generate",
+            "-o",
+            "private/abe.biprivatekey",
+        ]))
+        .unwrap();
+        assert!(!report.failed());
+        let key = BIPrivateKey::read(&mut File::open("private/abe.biprivatekey").unwrap()).unwrap();
+        assert_eq!(key.authority(), "abe_keys");
+
+        // a key is not overwritten without `--force`
+        let report = generate::execute(&generate::Command::parse_from([
+            "generate",
+            "-o",
+            "private/abe.biprivatekey",
+        ]))
+        .unwrap();
+        assert_eq!(report.errors()[0].ident(), "BCKE2");
+
+        assert!(
+            !show::execute(&show::Command::parse_from(["show"]))
+                .unwrap()
+                .failed()
+        );
+        assert!(
+            !show::execute(&show::Command::parse_from([
+                "show",
+                "--key",
+                "private/abe.biprivatekey"
+            ]))
+            .unwrap()
+            .failed()
+        );
+
+        // the project's key, to the default `keys` folder
+        let report =
+            export_public::execute(&export_public::Command::parse_from(["export-public"])).unwrap();
+        assert!(!report.failed());
+        let public = BIPublicKey::read(&mut File::open("keys/abe_keys.bikey").unwrap()).unwrap();
+        assert_eq!(public.modulus(), key.to_public_key().modulus());
+        assert!(
+            check_published(&key.to_public_key(), Path::new("keys"))
+                .unwrap()
+                .is_none()
+        );
+    }
+
+    #[sealed_test]
+    fn generate_authority() {
+        project("");
+        generate::execute(&generate::Command::parse_from(["generate"])).unwrap();
+        let key = BIPrivateKey::read(&mut File::open("abe.biprivatekey").unwrap()).unwrap();
+        assert_eq!(key.authority(), "abe");
+        generate::execute(&generate::Command::parse_from([
+            "generate", "other", "--length", "2048",
+        ]))
+        .unwrap();
+        let key = BIPrivateKey::read(&mut File::open("other.biprivatekey").unwrap()).unwrap();
+        assert_eq!(key.authority(), "other");
+        assert_eq!(key.to_public_key().length(), 2048);
+    }
+
+    #[sealed_test]
+    fn no_key() {
+        project("");
+        let report = show::execute(&show::Command::parse_from(["show"])).unwrap();
+        assert_eq!(report.errors()[0].ident(), "BCKE1");
+        let report =
+            export_public::execute(&export_public::Command::parse_from(["export-public"])).unwrap();
+        assert_eq!(report.errors()[0].ident(), "BCKE1");
+        assert!(!Path::new("keys").exists());
+
+        project("private_key = \"missing.biprivatekey\"");
+        let report = show::execute(&show::Command::parse_from(["show"])).unwrap();
+        assert_eq!(report.errors()[0].ident(), "BSE2");
+    }
+}
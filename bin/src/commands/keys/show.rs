@@ -0,0 +1,32 @@
+use crate::{Error, report::Report};
+
+use super::load;
+
+#[derive(clap::Parser)]
+#[allow(clippy::module_name_repetitions)]
+/// Show information about a private key
+///
+/// Without `--key`, the persistent key configured for the project is used.
+pub struct Command {
+    #[arg(long)]
+    /// Path to a `.biprivatekey`
+    key: Option<String>,
+}
+
+/// Execute the show command
+///
+/// # Errors
+/// [`Error`] if the key can not be read
+pub fn execute(cmd: &Command) -> Result<Report, Error> {
+    let mut report = Report::new();
+    let Some(key) = load(cmd.key.as_deref(), &mut report)? else {
+        return Ok(report);
+    };
+    let public = key.to_public_key();
+    println!("Private Key: {}", cmd.key.as_deref().unwrap_or("project"));
+    println!("  - Authority: {}", public.authority());
+    println!("  - Length: {}", public.length());
+    println!("  - Exponent: {}", public.exponent());
+    println!("  - Modulus: {}", public.modulus_display(13));
+    Ok(report)
+}
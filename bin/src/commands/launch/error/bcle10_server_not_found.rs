@@ -0,0 +1,43 @@
+use std::{path::PathBuf, sync::Arc};
+
+use hemtt_workspace::reporting::{Code, Diagnostic};
+
+pub struct ServerNotFound {
+    path: Option<PathBuf>,
+}
+
+impl Code for ServerNotFound {
+    fn ident(&self) -> &'static str {
+        "BCLE10"
+    }
+
+    fn link(&self) -> Option<&str> {
+        Some("/commands/launch.html#server")
+    }
+
+    fn message(&self) -> String {
+        self.path.as_ref().map_or_else(
+            || "Arma 3 Server not found.".to_string(),
+            |path| format!("Arma 3 Server not found at `{}`.", path.display()),
+        )
+    }
+
+    fn help(&self) -> Option<String> {
+        Some(if cfg!(windows) {
+            "The server is included with Arma 3, check the `executable` option in the `server` configuration.".to_owned()
+        } else {
+            "Install the native Arma 3 Server (app 233780) via Steam.".to_owned()
+        })
+    }
+
+    fn diagnostic(&self) -> Option<Diagnostic> {
+        Some(Diagnostic::from_code(self))
+    }
+}
+
+impl ServerNotFound {
+    #[must_use]
+    pub fn code(path: Option<PathBuf>) -> Arc<dyn Code> {
+        Arc::new(Self { path })
+    }
+}
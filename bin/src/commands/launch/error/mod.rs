@@ -1,3 +1,4 @@
+pub mod bcle10_server_not_found;
 pub mod bcle1_preset_not_found;
 pub mod bcle2_workshop_not_found;
 pub mod bcle3_workshop_mod_not_found;
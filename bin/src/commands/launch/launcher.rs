@@ -3,13 +3,20 @@ use std::{
     process::Child,
 };
 
-use hemtt_common::{arma::dlc::DLC, config::LaunchOptions, steam};
+use hemtt_common::{
+    arma::{dlc::DLC, server},
+    config::{LaunchOptions, ServerOptions},
+    steam,
+};
 use regex::Regex;
 
 use crate::{
     Error,
     commands::launch::{
-        error::{bcle1_preset_not_found::PresetNotFound, bcle4_arma_not_found::ArmaNotFound},
+        error::{
+            bcle1_preset_not_found::PresetNotFound, bcle4_arma_not_found::ArmaNotFound,
+            bcle10_server_not_found::ServerNotFound,
+        },
         preset,
     },
     report::Report,
@@ -24,6 +31,33 @@ use super::{
     },
 };
 
+/// The dedicated server and headless clients of a launch
+///
+/// They are stopped when dropped, unless detached
+#[derive(Default)]
+pub struct Servers(Vec<Child>);
+
+impl Servers {
+    /// Leave the processes running after HEMTT exits
+    pub fn detach(mut self) {
+        self.0.clear();
+    }
+}
+
+impl Drop for Servers {
+    fn drop(&mut self) {
+        for child in &mut self.0 {
+            if matches!(child.try_wait(), Ok(None)) {
+                debug!("Stopping server process {}", child.id());
+                if let Err(e) = child.kill() {
+                    warn!("Failed to stop server process {}: {e}", child.id());
+                }
+            }
+            let _ = child.wait();
+        }
+    }
+}
+
 pub struct Launcher {
     executable: String,
     dlc: Vec<DLC>,
@@ -33,6 +67,7 @@ pub struct Launcher {
     mission: Option<String>,
     instances: u8,
     file_patching: bool,
+    server: Option<ServerOptions>,
 }
 
 impl Launcher {
@@ -57,6 +92,7 @@ impl Launcher {
             dlc: options.dlc().to_vec(),
             workshop: options.workshop().to_vec(),
             mission: options.mission().map(std::string::ToString::to_string),
+            server: options.server().cloned(),
             options: {
                 let mut args = ["-skipIntro", "-noSplash", "-showScriptErrors", "-debug"]
                     .iter()
@@ -118,131 +154,138 @@ impl Launcher {
         Ok(())
     }
 
-    #[allow(clippy::too_many_lines)]
-    /// Launches the game
+    /// Resolves the paths of the mods to load
     ///
     /// # Errors
     /// [`Error::Io`] if the current directory could not be determined
     ///
     /// # Panics
     /// If regex fails to compile
-    pub fn launch(
-        &self,
-        mut args: Vec<String>,
-        report: &mut Report,
-    ) -> Result<Option<Child>, Error> {
+    pub fn paths(&self, report: &mut Report) -> Result<Option<Vec<PathBuf>>, Error> {
         let mut mods = Vec::new();
-        if !self.workshop.is_empty() {
-            let Some(common) = self.arma3.parent() else {
-                report.push(WorkshopNotFound::code());
-                return Ok(None);
-            };
-            let Some(root) = common.parent() else {
-                report.push(WorkshopNotFound::code());
-                return Ok(None);
-            };
-            let workshop_folder = root.join("workshop").join("content").join("107410");
-            if !workshop_folder.exists() {
-                report.push(WorkshopNotFound::code());
-                return Ok(None);
-            }
+        if self.workshop.is_empty() {
+            return Ok(Some(mods));
+        }
+        let Some(common) = self.arma3.parent() else {
+            report.push(WorkshopNotFound::code());
+            return Ok(None);
+        };
+        let Some(root) = common.parent() else {
+            report.push(WorkshopNotFound::code());
+            return Ok(None);
+        };
+        let workshop_folder = root.join("workshop").join("content").join("107410");
+        if !workshop_folder.exists() {
+            report.push(WorkshopNotFound::code());
+            return Ok(None);
+        }
 
-            let mut meta = None;
-            let meta_path = std::env::current_dir()?.join("meta.cpp");
-            if meta_path.exists() {
-                let content = std::fs::read_to_string(meta_path)?;
-                let regex = Regex::new(r"publishedid\s*=\s*(\d+);").expect("meta regex compiles");
-                if let Some(id) = regex.captures(&content).map(|c| c[1].to_string()) {
-                    meta = Some(id);
-                }
+        let mut meta = None;
+        let meta_path = std::env::current_dir()?.join("meta.cpp");
+        if meta_path.exists() {
+            let content = std::fs::read_to_string(meta_path)?;
+            let regex = Regex::new(r"publishedid\s*=\s*(\d+);").expect("meta regex compiles");
+            if let Some(id) = regex.captures(&content).map(|c| c[1].to_string()) {
+                meta = Some(id);
             }
+        }
 
-            for load_mod in &self.workshop {
-                if Some(load_mod.clone()) == meta {
-                    warn!(
-                        "Skipping mod {} as it is the same as the project's meta.cpp id",
-                        load_mod
-                    );
-                    continue;
-                }
-                let mod_path = workshop_folder.join(load_mod);
-                if !mod_path.exists() {
-                    report.push(WorkshopModNotFound::code(load_mod.to_string()));
-                }
-                if cfg!(windows) {
-                    mods.push(mod_path.display().to_string());
-                } else {
-                    mods.push(format!("Z:{}", mod_path.display()));
-                }
+        for load_mod in &self.workshop {
+            if Some(load_mod.clone()) == meta {
+                warn!(
+                    "Skipping mod {} as it is the same as the project's meta.cpp id",
+                    load_mod
+                );
+                continue;
             }
+            let mod_path = workshop_folder.join(load_mod);
+            if !mod_path.exists() {
+                report.push(WorkshopModNotFound::code(load_mod.to_string()));
+            }
+            mods.push(mod_path);
         }
         if report.failed() {
             return Ok(None);
         }
+        mods.sort();
+        mods.dedup();
+        Ok(Some(mods))
+    }
+
+    /// Launches the game
+    ///
+    /// When a server is configured, it is launched first with its headless clients,
+    /// and each instance of the game connects to it
+    ///
+    /// # Errors
+    /// [`Error::Io`] if the current directory could not be determined
+    ///
+    /// # Panics
+    /// If regex fails to compile
+    pub fn launch(
+        &self,
+        mut args: Vec<String>,
+        report: &mut Report,
+    ) -> Result<Option<(Child, Servers)>, Error> {
+        let Some(mods) = self.paths(report)? else {
+            return Ok(None);
+        };
 
         let mut dlc = self.dlc.clone();
         dlc.sort();
         dlc.dedup();
-        for dlc in dlc {
-            args.push(format!("-mod=\"{}\"", dlc.to_mod()));
-        }
-        mods.sort();
-        mods.dedup();
-        for m in mods {
-            args.push(format!("-mod=\"{m}\""));
-        }
+        let mod_args = dlc
+            .iter()
+            .map(|dlc| format!("-mod=\"{}\"", dlc.to_mod()))
+            .chain(mods.iter().map(|m| {
+                if cfg!(windows) {
+                    format!("-mod=\"{}\"", m.display())
+                } else {
+                    format!("-mod=\"Z:{}\"", m.display())
+                }
+            }))
+            .collect::<Vec<_>>();
+        args.extend(mod_args);
         args.extend(self.options.clone());
 
-        if let Some(mission) = &self.mission {
-            let mut path = PathBuf::from(mission);
-
-            if path.is_absolute() {
-                report.push(MissionAbsolutePath::code(mission.to_string()));
+        let mut servers = Servers::default();
+        if let Some(server) = &self.server {
+            let Some(launched) = self.launch_server(server, &dlc, &mods, report)? else {
                 return Ok(None);
-            }
-            path = std::env::current_dir()?.join(mission);
-
-            if !path.ends_with("mission.sqm") {
-                path.push("mission.sqm");
-            }
-
-            if !path.is_file() {
-                path = std::env::current_dir()?
-                    .join(".hemtt")
-                    .join("missions")
-                    .join(mission)
-                    .join("mission.sqm");
-            }
-
-            if path.is_file() {
-                args.push(format!("\"{}\"", path.display()));
-            } else {
-                report.push(MissionNotFound::code(
-                    mission.to_string(),
-                    &std::env::current_dir()?,
-                ));
+            };
+            servers = launched;
+        } else if let Some(mission) = &self.mission {
+            let Some(path) = find_mission(mission, report)? else {
                 return Ok(None);
-            }
+            };
+            args.push(format!("\"{}\"", path.join("mission.sqm").display()));
         }
 
         let mut instances = Vec::new();
         for _ in 0..self.instances {
             let mut args = args.clone();
-            // if with_server {
-            if false {
+            if let Some(server) = &self.server {
                 args.push("-connect=127.0.0.1".to_string());
-            } else if self.file_patching {
+                args.push(format!("-port={}", server.port()));
+                if !server.password().is_empty() {
+                    args.push(format!("-password={}", server.password()));
+                }
+            }
+            if self.file_patching {
                 args.push("-filePatching".to_string());
             }
             instances.push(args);
         }
 
         if instances.len() == 1 {
-            Ok(Some(if cfg!(target_os = "windows") {
-                super::platforms::windows(&self.arma3, &self.executable, &instances[0])?
-            } else {
-                super::platforms::linux(&instances[0])?
-            }))
+            Ok(Some((
+                if cfg!(target_os = "windows") {
+                    super::platforms::windows(&self.arma3, &self.executable, &instances[0])?
+                } else {
+                    super::platforms::linux(&instances[0])?
+                },
+                servers,
+            )))
         } else {
             let mut children = Vec::new();
             for instance in instances {
@@ -252,10 +295,121 @@ impl Launcher {
                     super::platforms::linux(&instance)?
                 });
             }
-            Ok(Some(
+            Ok(Some((
                 children.into_iter().next().expect("At least one child"),
-            ))
+                servers,
+            )))
+        }
+    }
+
+    /// Launches a dedicated server and its headless clients
+    ///
+    /// Returns `None` if the server could not be launched
+    fn launch_server(
+        &self,
+        server: &ServerOptions,
+        dlc: &[DLC],
+        mods: &[PathBuf],
+        report: &mut Report,
+    ) -> Result<Option<Servers>, Error> {
+        let server_dir = if cfg!(windows) {
+            Some(self.arma3.clone())
+        } else {
+            steam::find_app(233_780)
+        };
+        let Some(server_dir) = server_dir else {
+            report.push(ServerNotFound::code(None));
+            return Ok(None);
+        };
+        let mut executable = server_dir.join(server.executable());
+        if cfg!(windows) {
+            executable.set_extension("exe");
+        }
+        if !executable.is_file() {
+            report.push(ServerNotFound::code(Some(executable)));
+            return Ok(None);
+        }
+
+        let mission = if let Some(mission) = server.mission() {
+            let Some(path) = find_mission(mission, report)? else {
+                return Ok(None);
+            };
+            // A name of our own, so a mission of the server's is never replaced
+            let name = format!(
+                "hemtt_{}",
+                path.file_name()
+                    .map(|n| n.to_string_lossy().to_string())
+                    .unwrap_or_default()
+            );
+            let target = server_dir.join("MPMissions").join(&name);
+            if target.exists() {
+                std::fs::remove_dir_all(&target)?;
+            }
+            copy_dir(&path, &target)?;
+            debug!("Copied mission to {}", target.display());
+            Some(name)
+        } else {
+            None
+        };
+
+        let folder = std::env::current_dir()?.join(".hemttout").join("server");
+        std::fs::create_dir_all(&folder)?;
+        std::fs::write(
+            folder.join("server.cfg"),
+            server::server_cfg(server, mission.as_deref()),
+        )?;
+        std::fs::write(folder.join("basic.cfg"), server::basic_cfg())?;
+
+        let mod_args = dlc
+            .iter()
+            .map(DLC::to_mod)
+            .map(ToString::to_string)
+            .chain(mods.iter().map(|m| m.display().to_string()))
+            .map(|m| {
+                if cfg!(windows) {
+                    format!("-mod=\"{m}\"")
+                } else {
+                    format!("-mod={m}")
+                }
+            })
+            .collect::<Vec<_>>();
+        let mut common = mod_args;
+        common.push(format!("-port={}", server.port()));
+        if self.file_patching {
+            common.push("-filePatching".to_string());
         }
+
+        let mut servers = Servers::default();
+        let mut args = common.clone();
+        args.push(format!("-config={}", folder.join("server.cfg").display()));
+        args.push(format!("-cfg={}", folder.join("basic.cfg").display()));
+        args.push(format!("-profiles={}", folder.display()));
+        args.push("-name=server".to_string());
+        args.extend(server.parameters().iter().cloned());
+        servers.0.push(super::platforms::server(
+            &executable,
+            &args,
+            &folder.join("server.log"),
+        )?);
+
+        for i in 1..=server.headless_clients() {
+            let mut args = common.clone();
+            args.push("-client".to_string());
+            args.push("-connect=127.0.0.1".to_string());
+            if !server.password().is_empty() {
+                args.push(format!("-password={}", server.password()));
+            }
+            let profile = folder.join(format!("hc{i}"));
+            std::fs::create_dir_all(&profile)?;
+            args.push(format!("-profiles={}", profile.display()));
+            args.push(format!("-name=hc{i}"));
+            servers.0.push(super::platforms::server(
+                &executable,
+                &args,
+                &profile.join("hc.log"),
+            )?);
+        }
+        Ok(Some(servers))
     }
 
     #[must_use]
@@ -263,6 +417,32 @@ impl Launcher {
         &self.arma3
     }
 
+    #[must_use]
+    /// The folder the game will write its RPT files to
+    ///
+    /// Respects `-profiles=` if it was passed to the game
+    pub fn rpt_folder(&self) -> Option<PathBuf> {
+        if let Some(profiles) = self
+            .options
+            .iter()
+            .find_map(|o| o.strip_prefix("-profiles="))
+        {
+            let profiles = profiles.trim_matches('"');
+            return Some(if cfg!(windows) {
+                PathBuf::from(profiles)
+            } else {
+                PathBuf::from(profiles.trim_start_matches("Z:").replace('\\', "/"))
+            });
+        }
+        if cfg!(windows) {
+            dirs::data_local_dir().map(|d| d.join("Arma 3"))
+        } else {
+            self.arma3.parent()?.parent().map(|steamapps| {
+                steamapps.join("compatdata/107410/pfx/drive_c/users/steamuser/AppData/Local/Arma 3")
+            })
+        }
+    }
+
     pub fn add_dlcs(&mut self, dlcs: Vec<DLC>) {
         self.dlc.extend(dlcs);
     }
@@ -329,6 +509,50 @@ impl Launcher {
     }
 }
 
+/// Find a mission folder, either relative to the project or in `.hemtt/missions`
+fn find_mission(mission: &str, report: &mut Report) -> Result<Option<PathBuf>, Error> {
+    let mut path = PathBuf::from(mission);
+    if path.is_absolute() {
+        report.push(MissionAbsolutePath::code(mission.to_string()));
+        return Ok(None);
+    }
+    path = std::env::current_dir()?.join(mission);
+    if path.ends_with("mission.sqm") {
+        path.pop();
+    }
+    if !path.join("mission.sqm").is_file() {
+        path = std::env::current_dir()?
+            .join(".hemtt")
+            .join("missions")
+            .join(mission);
+    }
+    if path.join("mission.sqm").is_file() {
+        Ok(Some(path))
+    } else {
+        report.push(MissionNotFound::code(
+            mission.to_string(),
+            &std::env::current_dir()?,
+        ));
+        Ok(None)
+    }
+}
+
+fn copy_dir(from: &Path, to: &Path) -> Result<(), Error> {
+    for entry in walkdir::WalkDir::new(from) {
+        let entry = entry.map_err(std::io::Error::from)?;
+        let Ok(relative) = entry.path().strip_prefix(from) else {
+            continue;
+        };
+        let target = to.join(relative);
+        if entry.file_type().is_dir() {
+            std::fs::create_dir_all(&target)?;
+        } else {
+            std::fs::copy(entry.path(), &target)?;
+        }
+    }
+    Ok(())
+}
+
 pub enum Mod {
     Workshop(String),
     Local(String),
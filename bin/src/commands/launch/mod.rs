@@ -2,6 +2,8 @@ use std::path::Path;
 
 use hemtt_common::config::{LaunchOptions, ProjectConfig};
 use launcher::Launcher;
+use monitor::Monitor;
+use watch::Watcher;
 
 use crate::{
     commands::launch::error::{
@@ -9,6 +11,8 @@ use crate::{
         bcle6_launch_config_not_found::LaunchConfigNotFound,
         bcle7_can_not_quicklaunch::CanNotQuickLaunch,
     },
+    context::{Context, PreservePrevious},
+    controller,
     error::Error,
     report::Report,
 };
@@ -16,8 +20,10 @@ use crate::{
 pub mod error;
 
 pub mod launcher;
+pub mod monitor;
 mod platforms;
 pub mod preset;
+pub mod watch;
 
 #[derive(clap::Parser)]
 #[command(verbatim_doc_comment)]
@@ -170,6 +176,43 @@ pub mod preset;
 /// ### rapify
 ///
 /// Provides the ability to disable rapify for the launch command. Equivalent to `--no-rap`.
+///
+/// ### server
+///
+/// Launches a dedicated server with your mod, and connects each instance of the game to it.
+/// The `mission` is found the same way as above, and copied into the server's `MPMissions` folder as `hemtt_{mission}`.
+/// The server and headless clients are stopped when the game exits, unless `--detach` is used.
+///
+/// ```toml
+/// [hemtt.launch.server]
+/// extends = "default"
+/// instances = 2
+///
+/// [hemtt.launch.server.server]
+/// mission = "test.VR"    # Mission to host
+/// headless_clients = 1   # Default: 0
+/// port = 2302            # Default: 2302
+/// password = "hemtt"     # Default: no password
+/// executable = "arma3server" # Default: "arma3server_x64"
+/// parameters = ["-autoInit"]
+/// ```
+///
+/// `server.cfg` and `basic.cfg` are generated in `.hemttout/server`, along with the
+/// profiles and logs of the server and each headless client.
+/// On Windows the server is started from the Arma 3 directory,
+/// on Linux the native Arma 3 Server (app 233780) must be installed through Steam.
+///
+/// ## Monitoring
+///
+/// Once the game has started, HEMTT stays attached until it exits.
+/// Script errors and warnings from the RPT are printed as they happen,
+/// mapped back to the file, line and macros in your project that produced them,
+/// and messages logged through the HEMTT extension are shown at their log level.
+/// Use `--detach` to return as soon as the game has started.
+///
+/// With `--watch`, functions are reloaded in the running game as they are saved,
+/// and SQF entered into the terminal is executed in the mission, with the result printed.
+/// Functions compiled with `compileFinal` can not be replaced, so they are reported instead.
 pub struct Command {
     #[clap(flatten)]
     launch: LaunchArgs,
@@ -188,7 +231,7 @@ pub struct Command {
 }
 
 #[derive(Default, clap::Args)]
-#[allow(clippy::module_name_repetitions)]
+#[allow(clippy::module_name_repetitions, clippy::struct_excessive_bools)]
 pub struct LaunchArgs {
     #[arg(action = clap::ArgAction::Append, verbatim_doc_comment)]
     /// Launches with the specified configurations
@@ -232,6 +275,16 @@ pub struct LaunchArgs {
     #[arg(long = "no-filepatching", short = 'F')]
     /// Disables file patching
     no_filepatching: bool,
+    #[arg(long)]
+    /// Return once the game has started, instead of following its RPT and logs
+    detach: bool,
+    #[arg(long, conflicts_with = "detach", verbatim_doc_comment)]
+    /// Reload functions in the running game when they are changed
+    ///
+    /// Functions in `fnc_*.sqf` files are preprocessed and checked by HEMTT,
+    /// then replaced in the mission namespace as `TAG_fnc_name`, using the tag from `[functions]`.
+    /// Lines entered into the terminal are executed as SQF, and their return value is printed.
+    watch: bool,
 }
 
 #[allow(clippy::too_many_lines)]
@@ -269,7 +322,7 @@ pub fn execute(cmd: &Command) -> Result<Report, Error> {
 
     launcher.add_self_mod()?;
 
-    if cmd.launch.no_build {
+    let ctx = if cmd.launch.no_build {
         warn!("Using Quick Launch! HEMTT will not rebuild the project");
         if !std::env::current_dir()?.join(".hemttout/dev").exists() {
             report.push(CanNotQuickLaunch::code(
@@ -286,6 +339,7 @@ pub fn execute(cmd: &Command) -> Result<Report, Error> {
             ));
             return Ok(report);
         }
+        Context::new(Some("dev"), PreservePrevious::Keep, false)?
     } else {
         let mut executor = super::dev::context(
             &cmd.dev,
@@ -301,9 +355,32 @@ pub fn execute(cmd: &Command) -> Result<Report, Error> {
         if report.failed() {
             return Ok(report);
         }
+        executor.into_ctx()
+    };
+
+    if cmd.launch.detach {
+        if let Some((_, servers)) = launcher.launch(Vec::new(), &mut report)? {
+            servers.detach();
+        }
+        return Ok(report);
     }
 
-    launcher.launch(Vec::new(), &mut report)?;
+    launcher.add_external_mod(controller::extension(&ctx)?.display().to_string());
+    let watcher = if cmd.launch.watch {
+        Some(Watcher::new(&ctx)?)
+    } else {
+        None
+    };
+    let monitor = Monitor::listen()?;
+    // The server and headless clients are stopped once the game exits
+    if let Some((child, _servers)) = launcher.launch(Vec::new(), &mut report)? {
+        monitor.attach(
+            child,
+            launcher.rpt_folder(),
+            Some(ctx.workspace_path()),
+            watcher,
+        )?;
+    }
 
     Ok(report)
 }
@@ -0,0 +1,320 @@
+use std::{
+    io::{Read, Seek, SeekFrom},
+    path::{Path, PathBuf},
+    process::Child,
+    time::{Duration, Instant, SystemTime},
+};
+
+use hemtt_common::arma::{
+    control::{self, fromarma, toarma},
+    rpt::{self, Entry, Level},
+};
+use hemtt_workspace::{WorkspacePath, reporting::WorkspaceFiles};
+use interprocess::local_socket::{
+    GenericNamespaced, Listener, ListenerNonblockingMode, ListenerOptions, Stream, ToNsName,
+    traits::Listener as _,
+};
+
+use super::watch::Watcher;
+use crate::{Error, utils::rpt::RptEntry};
+
+/// Stays attached to a running game, streaming its RPT and extension logs
+pub struct Monitor {
+    listener: Listener,
+    started: SystemTime,
+}
+
+impl Monitor {
+    /// Start listening for the extension, before the game is launched
+    ///
+    /// # Errors
+    /// [`Error::Io`] if the local socket could not be created
+    pub fn listen() -> Result<Self, Error> {
+        let listener = ListenerOptions::new()
+            .name("hemtt_arma".to_ns_name::<GenericNamespaced>()?)
+            .create_sync()?;
+        listener.set_nonblocking(ListenerNonblockingMode::Both)?;
+        Ok(Self {
+            listener,
+            started: SystemTime::now(),
+        })
+    }
+
+    /// Follow the game until it exits
+    ///
+    /// Script errors from the RPT are mapped to the project source when a workspace is provided
+    ///
+    /// With a [`Watcher`], changed functions are reloaded in the game,
+    /// and lines entered on stdin are executed as SQF
+    ///
+    /// # Errors
+    /// [`Error::Io`] if the RPT could not be read
+    pub fn attach(
+        self,
+        mut child: Child,
+        rpt_folder: Option<PathBuf>,
+        workspace: Option<&WorkspacePath>,
+        mut watcher: Option<Watcher>,
+    ) -> Result<(), Error> {
+        info!("Attached to Arma, press Ctrl+C to detach");
+        let mut follower = rpt_folder.map(|folder| Follower::new(folder, self.started));
+        let mut socket: Option<Stream> = None;
+        let mut status = Status::new();
+        let mut entries: Vec<Entry> = Vec::new();
+        let console = watcher.as_ref().map(|_| {
+            info!("Enter SQF to execute it in the mission namespace");
+            console()
+        });
+        loop {
+            if socket.is_none() {
+                socket = self.listener.accept().ok();
+                if socket.is_some() {
+                    debug!("Extension connected");
+                }
+            }
+            if socket.as_mut().is_some_and(|stream| !receive(stream)) {
+                debug!("Extension disconnected");
+                socket = None;
+            }
+            let mut outgoing = watcher.as_mut().map(Watcher::poll).unwrap_or_default();
+            if let Some(console) = &console {
+                outgoing.extend(console.try_iter().map(toarma::Message::Execute));
+            }
+            if !outgoing.is_empty() {
+                if let Some(stream) = &mut socket {
+                    for message in &outgoing {
+                        trace!("sending: {:?}", message);
+                        control::write(stream, message)?;
+                    }
+                } else {
+                    warn!("The extension is not connected, the game may still be loading");
+                }
+            }
+            if let Some(follower) = &mut follower {
+                for entry in follower.poll()? {
+                    show(&entry, &entries, workspace);
+                    entries.push(entry);
+                }
+            }
+            if !status.running(&mut child) {
+                break;
+            }
+            std::thread::sleep(Duration::from_millis(100));
+        }
+        if let Some(follower) = &mut follower {
+            for entry in follower.poll()?.into_iter().chain(follower.finish()) {
+                show(&entry, &entries, workspace);
+                entries.push(entry);
+            }
+        }
+        let groups = rpt::group(entries);
+        let errors = groups
+            .iter()
+            .filter(|g| g.entry().level() == Level::Error)
+            .count();
+        info!(
+            "Arma has exited, {} unique errors and {} unique warnings",
+            errors,
+            groups.len() - errors
+        );
+        Ok(())
+    }
+}
+
+/// Read SQF from stdin, one statement per line
+fn console() -> std::sync::mpsc::Receiver<String> {
+    let (send, recv) = std::sync::mpsc::channel();
+    std::thread::spawn(move || {
+        for line in std::io::stdin().lines().map_while(Result::ok) {
+            let line = line.trim();
+            if !line.is_empty() && send.send(line.to_string()).is_err() {
+                break;
+            }
+        }
+    });
+    recv
+}
+
+/// Handle all waiting messages from the extension, returns false once it has disconnected
+fn receive(stream: &mut Stream) -> bool {
+    loop {
+        match control::read::<_, fromarma::Message>(stream) {
+            Ok(Some(fromarma::Message::Log(level, text))) => match level {
+                fromarma::Level::Trace => trace!("arma: {}", text),
+                fromarma::Level::Debug => debug!("arma: {}", text),
+                fromarma::Level::Info => info!("arma: {}", text),
+                fromarma::Level::Warn => warn!("arma: {}", text),
+                fromarma::Level::Error => error!("arma: {}", text),
+            },
+            Ok(Some(fromarma::Message::Control(fromarma::Control::Mission(mission)))) => {
+                info!("Mission: {}", mission.trim_end_matches('\\'));
+            }
+            Ok(Some(fromarma::Message::Outcome(outcome))) => match outcome {
+                fromarma::Outcome::Returned(value) => info!("Returned: {}", value),
+                fromarma::Outcome::Reloaded(function) => info!("Reloaded {}", function),
+                fromarma::Outcome::Failed(reason) => error!("{}", reason),
+            },
+            Ok(Some(message)) => trace!("Ignoring message: {:?}", message),
+            Ok(None) => return true,
+            Err(e) => {
+                trace!("Extension read failed: {}", e);
+                return false;
+            }
+        }
+    }
+}
+
+/// Print an entry, unless an identical one has already been printed
+fn show(entry: &Entry, previous: &[Entry], workspace: Option<&WorkspacePath>) {
+    let key = |e: &Entry| {
+        (
+            e.level(),
+            e.message().to_string(),
+            e.file().map(ToString::to_string),
+            e.line(),
+        )
+    };
+    if previous.iter().any(|p| key(p) == key(entry)) {
+        return;
+    }
+    let Some(group) = rpt::group(vec![entry.clone()]).pop() else {
+        return;
+    };
+    if let Some(diag) = RptEntry::code(workspace, group).diagnostic() {
+        eprintln!("{}", diag.to_string(&WorkspaceFiles::new()));
+    }
+}
+
+/// Tracks whether the game is still running
+pub struct Status {
+    started: Instant,
+    seen: bool,
+    warned: bool,
+}
+
+impl Default for Status {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Status {
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            started: Instant::now(),
+            seen: false,
+            warned: false,
+        }
+    }
+
+    /// Check if the game is running, `child` is the process returned by the launcher
+    pub fn running(&mut self, child: &mut Child) -> bool {
+        match child.try_wait() {
+            Ok(None) => return true,
+            Ok(Some(_)) => {}
+            Err(_) => {
+                warn!("No longer able to determine Arma's status");
+                return false;
+            }
+        }
+        if cfg!(windows) {
+            return false;
+        }
+        // Steam returns immediately on Linux, watch for the game itself
+        if super::platforms::linux_running() {
+            self.seen = true;
+            return true;
+        }
+        if self.seen {
+            return false;
+        }
+        if !self.warned && self.started.elapsed() > Duration::from_mins(2) {
+            warn!("Arma has not started after 120 seconds, still waiting");
+            self.warned = true;
+        }
+        true
+    }
+}
+
+/// Follows the newest RPT in a folder, as it is written
+struct Follower {
+    folder: PathBuf,
+    since: SystemTime,
+    current: Option<PathBuf>,
+    offset: u64,
+    partial: Vec<u8>,
+    parser: rpt::Parser,
+}
+
+impl Follower {
+    fn new(folder: PathBuf, since: SystemTime) -> Self {
+        debug!("Watching for RPTs in {}", folder.display());
+        Self {
+            folder,
+            since,
+            current: None,
+            offset: 0,
+            partial: Vec::new(),
+            parser: rpt::Parser::new(),
+        }
+    }
+
+    /// Read any new lines, returning the completed entries
+    fn poll(&mut self) -> Result<Vec<Entry>, Error> {
+        let mut entries = Vec::new();
+        if let Some(newest) = newest_rpt(&self.folder, self.since)
+            .filter(|newest| self.current.as_ref() != Some(newest))
+        {
+            info!("Following {}", newest.display());
+            entries.extend(self.finish());
+            self.current = Some(newest);
+            self.offset = 0;
+        }
+        let Some(current) = &self.current else {
+            return Ok(entries);
+        };
+        let mut file = std::fs::File::open(current)?;
+        if file.metadata()?.len() < self.offset {
+            self.offset = 0;
+        }
+        file.seek(SeekFrom::Start(self.offset))?;
+        let mut buffer = Vec::new();
+        self.offset += file.read_to_end(&mut buffer)? as u64;
+        self.partial.extend(buffer);
+        while let Some(end) = self.partial.iter().position(|b| *b == b'\n') {
+            let line = self.partial.drain(..=end).collect::<Vec<_>>();
+            let line = String::from_utf8_lossy(&line);
+            let completed = self.parser.push(&line);
+            if completed.is_empty() {
+                let line = line.trim_end();
+                if !line.is_empty() {
+                    debug!("rpt: {}", line);
+                }
+            }
+            entries.extend(completed);
+        }
+        Ok(entries)
+    }
+
+    fn finish(&mut self) -> Option<Entry> {
+        self.partial.clear();
+        self.parser.finish()
+    }
+}
+
+/// The newest `.rpt` in the folder, written after `since`
+fn newest_rpt(folder: &Path, since: SystemTime) -> Option<PathBuf> {
+    std::fs::read_dir(folder)
+        .ok()?
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.extension()
+                .is_some_and(|ext| ext.eq_ignore_ascii_case("rpt"))
+        })
+        .filter_map(|path| Some((path.metadata().ok()?.modified().ok()?, path)))
+        .filter(|(modified, _)| *modified >= since)
+        .max_by_key(|(modified, _)| *modified)
+        .map(|(_, path)| path)
+}
@@ -22,6 +22,25 @@ pub fn windows(arma3: &Path, executable: &str, args: &[String]) -> Result<Child,
     Ok(std::process::Command::new(path).args(args).spawn()?)
 }
 
+/// Launches a dedicated server or headless client, with its output written to `log`
+pub fn server(executable: &Path, args: &[String], log: &Path) -> Result<Child, Error> {
+    info!(
+        "Launching {:?} with:\n  {}",
+        executable.display(),
+        args.join("\n  ")
+    );
+    let log = std::fs::File::create(log)?;
+    let mut command = std::process::Command::new(executable);
+    if let Some(dir) = executable.parent() {
+        command.current_dir(dir);
+    }
+    Ok(command
+        .args(args)
+        .stdout(log.try_clone()?)
+        .stderr(log)
+        .spawn()?)
+}
+
 pub fn linux(args: &[String]) -> Result<Child, Error> {
     // check if flatpak steam is installed
     let flatpak = std::process::Command::new("flatpak")
@@ -84,3 +103,27 @@ pub fn linux(args: &[String]) -> Result<Child, Error> {
     };
     Ok(child)
 }
+
+/// Check if the game is running, by looking for its process
+///
+/// The game is started through Steam on Linux, so there is no child process to wait on
+pub fn linux_running() -> bool {
+    let Ok(entries) = std::fs::read_dir("/proc") else {
+        return false;
+    };
+    entries.filter_map(Result::ok).any(|entry| {
+        std::fs::read(entry.path().join("cmdline")).is_ok_and(|cmdline| {
+            cmdline
+                .split(|b| *b == 0)
+                .next()
+                .map(String::from_utf8_lossy)
+                .and_then(|exe| exe.rsplit(['/', '\\']).next().map(str::to_ascii_lowercase))
+                .is_some_and(|exe| {
+                    exe.starts_with("arma3")
+                        && Path::new(&exe)
+                            .extension()
+                            .is_some_and(|ext| ext.eq_ignore_ascii_case("exe"))
+                })
+        })
+    })
+}
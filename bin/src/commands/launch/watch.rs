@@ -0,0 +1,145 @@
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::{Duration, Instant, SystemTime},
+};
+
+use hemtt_common::{arma::control::toarma, config::FunctionsConfig};
+use hemtt_preprocessor::Processor;
+use hemtt_sqf::parser::{ParserError, database::Database};
+use hemtt_workspace::{
+    WorkspacePath,
+    addons::Addon,
+    reporting::{Code, WorkspaceFiles},
+};
+
+use crate::{context::Context, error::Error};
+
+/// Watches the project's functions, reloading them in the running game when they change
+pub struct Watcher {
+    database: Arc<Database>,
+    workspace: WorkspacePath,
+    root: PathBuf,
+    prefix: String,
+    functions: FunctionsConfig,
+    addons: Vec<Addon>,
+    modified: HashMap<PathBuf, SystemTime>,
+    last_scan: Instant,
+}
+
+impl Watcher {
+    /// Create a new watcher, recording the current state of the functions
+    ///
+    /// # Errors
+    /// [`Error`] if the command database could not be loaded
+    pub fn new(ctx: &Context) -> Result<Self, Error> {
+        let mut watcher = Self {
+            database: Arc::new(Database::a3_with_workspace(ctx.workspace_path(), false)?),
+            workspace: ctx.workspace_path().clone(),
+            root: ctx.project_folder().clone(),
+            prefix: ctx.config().prefix().to_string(),
+            functions: ctx.config().functions().clone(),
+            addons: ctx.addons().to_vec(),
+            modified: HashMap::new(),
+            last_scan: Instant::now(),
+        };
+        watcher.modified = watcher.scan();
+        info!(
+            "Watching {} functions, changes will be reloaded in game",
+            watcher.modified.len()
+        );
+        Ok(watcher)
+    }
+
+    /// Check for changed functions, returning the messages to reload them
+    pub fn poll(&mut self) -> Vec<toarma::Message> {
+        if self.last_scan.elapsed() < Duration::from_millis(500) {
+            return Vec::new();
+        }
+        self.last_scan = Instant::now();
+        let current = self.scan();
+        let mut changed = current
+            .iter()
+            .filter(|(path, modified)| self.modified.get(*path) != Some(modified))
+            .map(|(path, _)| path.clone())
+            .collect::<Vec<_>>();
+        changed.sort();
+        self.modified = current;
+        changed
+            .into_iter()
+            .filter_map(|path| self.reload(&path))
+            .collect()
+    }
+
+    /// Find all `fnc_*.sqf` files in the project's addons
+    fn scan(&self) -> HashMap<PathBuf, SystemTime> {
+        self.addons
+            .iter()
+            .flat_map(|addon| walkdir::WalkDir::new(self.root.join(addon.folder())))
+            .filter_map(Result::ok)
+            .filter(|entry| {
+                let name = entry.file_name().to_string_lossy();
+                name.starts_with("fnc_")
+                    && Path::new(name.as_ref())
+                        .extension()
+                        .is_some_and(|ext| ext.eq_ignore_ascii_case("sqf"))
+            })
+            .filter_map(|entry| {
+                let modified = entry.metadata().ok()?.modified().ok()?;
+                Some((entry.into_path(), modified))
+            })
+            .collect()
+    }
+
+    /// Preprocess and parse a function, printing any errors instead of sending it
+    fn reload(&self, path: &Path) -> Option<toarma::Message> {
+        let relative = path.strip_prefix(&self.root).ok()?;
+        let addon = self
+            .addons
+            .iter()
+            .find(|addon| relative.starts_with(addon.folder_pathbuf()))?;
+        let entry = self
+            .workspace
+            .join(relative.to_string_lossy().replace('\\', "/"))
+            .ok()?;
+        let processed = match Processor::run(&entry) {
+            Ok(processed) => processed,
+            Err((_, hemtt_preprocessor::Error::Code(code))) => {
+                emit(&code);
+                return None;
+            }
+            Err((_, e)) => {
+                error!("Failed to preprocess {}: {}", entry, e);
+                return None;
+            }
+        };
+        match hemtt_sqf::parser::run(&self.database, &processed) {
+            Ok(_) => {}
+            Err(ParserError::ParsingError(codes) | ParserError::LexingError(codes)) => {
+                codes.iter().for_each(emit);
+                warn!("Not reloading {}, it contains errors", entry);
+                return None;
+            }
+        }
+        let function = format!(
+            "{}_fnc_{}",
+            self.functions.tag(&self.prefix, addon.name()),
+            entry
+                .filename()
+                .trim_start_matches("fnc_")
+                .trim_end_matches(".sqf")
+        );
+        info!("Reloading {}", function);
+        Some(toarma::Message::Reload(
+            function,
+            processed.as_str().to_string(),
+        ))
+    }
+}
+
+fn emit(code: &Arc<dyn Code>) {
+    if let Some(diag) = code.diagnostic() {
+        eprintln!("{}", diag.to_string(&WorkspaceFiles::new()));
+    }
+}
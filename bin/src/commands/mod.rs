@@ -1,14 +1,20 @@
 pub mod book;
 pub mod build;
+pub mod changelog;
 pub mod check;
 pub mod dev;
+pub mod docs;
+pub mod fmt;
+pub mod keys;
 pub mod launch;
 pub mod localization;
 pub mod new;
 pub mod release;
 pub mod script;
+pub mod test;
 pub mod utils;
 pub mod value;
+pub mod version;
 pub mod wiki;
 
 #[cfg(windows)]
@@ -23,6 +29,7 @@ pub fn global_modules(executor: &mut crate::executor::Executor) {
     executor.add_module(Box::<crate::modules::bom::BOMCheck>::default());
     executor.add_module(Box::<crate::modules::fnl::FineNewLineCheck>::default());
     executor.add_module(Box::<crate::modules::Hooks>::default());
+    executor.add_module(Box::<crate::modules::Functions>::default());
     executor.add_module(Box::<crate::modules::Stringtables>::default());
     executor.add_module(Box::<crate::modules::SQFCompiler>::default());
 }
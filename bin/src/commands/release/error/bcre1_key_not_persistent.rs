@@ -0,0 +1,36 @@
+use std::sync::Arc;
+
+use hemtt_workspace::reporting::{Code, Diagnostic};
+
+pub struct KeyNotPersistent;
+
+impl Code for KeyNotPersistent {
+    fn ident(&self) -> &'static str {
+        "BCRE1"
+    }
+
+    fn message(&self) -> String {
+        "Signed releases are only reproducible with a persistent signing key.".to_string()
+    }
+
+    fn note(&self) -> Option<String> {
+        Some("a new key is generated for each release".to_string())
+    }
+
+    fn help(&self) -> Option<String> {
+        Some(
+            "configure a key with `private_key` in the `[signing]` section, or use `--no-sign`"
+                .to_string(),
+        )
+    }
+
+    fn diagnostic(&self) -> Option<Diagnostic> {
+        Some(Diagnostic::from_code(self))
+    }
+}
+
+impl KeyNotPersistent {
+    pub fn code() -> Arc<dyn Code> {
+        Arc::new(Self)
+    }
+}
@@ -0,0 +1,40 @@
+use std::sync::Arc;
+
+use hemtt_workspace::reporting::{Code, Diagnostic};
+
+pub struct NotReproducible {
+    files: Vec<String>,
+}
+
+impl Code for NotReproducible {
+    fn ident(&self) -> &'static str {
+        "BCRE2"
+    }
+
+    fn message(&self) -> String {
+        format!(
+            "The release is not reproducible, {} file{} differ{}.",
+            self.files.len(),
+            if self.files.len() == 1 { "" } else { "s" },
+            if self.files.len() == 1 { "s" } else { "" },
+        )
+    }
+
+    fn note(&self) -> Option<String> {
+        Some(format!("different:\n{}", self.files.join("\n")))
+    }
+
+    fn help(&self) -> Option<String> {
+        Some("hooks and binarized files can include the time or other values that change between builds".to_string())
+    }
+
+    fn diagnostic(&self) -> Option<Diagnostic> {
+        Some(Diagnostic::from_code(self))
+    }
+}
+
+impl NotReproducible {
+    pub fn code(files: Vec<String>) -> Arc<dyn Code> {
+        Arc::new(Self { files })
+    }
+}
@@ -0,0 +1,2 @@
+pub mod bcre1_key_not_persistent;
+pub mod bcre2_not_reproducible;
@@ -0,0 +1,206 @@
+use crate::{
+    context::Context,
+    error::Error,
+    modules::{Sign, Workshop},
+    report::Report,
+};
+
+use super::build;
+
+mod error;
+mod reproducible;
+
+#[derive(clap::Parser)]
+#[command(verbatim_doc_comment)]
+/// Build the project for release
+///
+/// `hemtt release` will build your mod into `.hemttout/release`.
+/// It will create `bisign` files for all addons, and a `bikey` for validation.
+///
+/// It is intended to be used for releasing your mod.
+///
+/// It will create two zip archives in the `releases` folder: - `{name}-latest.zip` - `{name}-{version}.zip`
+///
+/// A SHA-256 manifest, compatible with `sha256sum -c`, is written next to each archive as `{archive}.sha256`.
+///
+/// ## Configuration
+///
+/// `hemtt release` is built the same way as [`hemtt build`](build.md), and will use its configuration.
+///
+/// ```toml
+/// [hemtt.release]
+/// sign = false # Default: true
+/// archive = false # Default: true
+/// changelog = "{prefix}-{version}" # Optional
+/// ```
+///
+/// ### sign
+///
+/// If `sign` is set to `false`, a `bikey` will not be created, and the PBOs will not be signed.
+///
+/// ```admonish danger
+/// All public releases of your mods should be signed. This will be a requirement of
+/// many communities, and is an important security feature. Do not use this
+/// unless you know what you are doing.
+/// ```
+///
+/// ### archive
+///
+/// If `archive` is set to `false`, a zip archive will not be created. The output will be in `.hemttout/release`.
+///
+/// ### changelog
+///
+/// If `changelog` is set, the release notes created by [`hemtt changelog`](changelog.md) are written to `releases/{changelog}.md`.
+/// It can use the same fields as the names of the archives.
+///
+/// ### archives
+///
+/// The archives to create can be configured, replacing the two default zip archives.
+///
+/// ```toml
+/// [[hemtt.release.archives]]
+/// name = "{prefix}-{version}"
+///
+/// [[hemtt.release.archives]]
+/// format = "tar.zst"
+/// name = "{prefix}-{major}.{minor}"
+/// folder = "" # Default: "@{folder}"
+/// optionals = false # Default: true
+/// keys = false # Default: true
+///
+/// [[hemtt.release.archives]]
+/// format = "folder"
+/// name = "workshop"
+/// folder = ""
+/// ```
+///
+/// `format` is one of `zip` (the default), `tar.zst`, `7z`, `folder`, which copies the release to a folder in `releases`, or `swifty`.
+///
+/// `name` is the name of the archive, without the extension. `folder` is the folder in the archive that contains the release, an empty string places the release at the root of the archive.
+/// Both can use `{prefix}`, `{folder}` (the release folder), `{version}`, `{major}`, `{minor}`, `{patch}`, `{build}`, and `{hash}`.
+///
+/// `optionals` and `keys` include the `optionals` folder and the `.bikey` files in the archive.
+///
+/// ### Swifty
+///
+/// `swifty` creates a [Swifty](https://getswifty.net) repository in `releases/{name}`, ready to be uploaded to a web server.
+/// `folder` is the name of the mod in the repository, and each optional mod folder is added as an optional mod.
+///
+/// ```toml
+/// [[hemtt.release.archives]]
+/// format = "swifty"
+/// name = "repository"
+/// previous = "mirror/repository" # Default: the repository from the last release
+/// ```
+///
+/// PBOs with the same files as in the `previous` repository are copied from it with their signatures, so clients only download the PBOs that changed.
+/// The signatures can only be reused with a [persistent signing key](../configuration/signing.md), otherwise every PBO is updated.
+///
+/// ```admonish note
+/// Arma3Sync repositories, the `.a3s` sync and autoconfig files, are not supported.
+/// A `folder` archive can be used as the source of a repository built with Arma3Sync.
+/// ```
+///
+/// ## Reproducible Releases
+///
+/// Releases of the same commit are identical when `SOURCE_DATE_EPOCH` is set. It is used instead of the
+/// current time for the timestamps of files in PBOs and the archives, and by the `date` function in hooks.
+///
+/// ```bash
+/// SOURCE_DATE_EPOCH=$(git log -1 --format=%ct) hemtt release
+/// ```
+///
+/// `--verify-reproducible` builds the release twice and checks that the output is identical.
+/// `SOURCE_DATE_EPOCH` is set to the time of the last commit if it is not already set.
+/// Signatures are only reproducible with a [persistent signing key](../configuration/signing.md).
+///
+/// ## Steam Workshop
+///
+/// `--workshop` uploads the release to the Steam Workshop with `steamcmd`, after the archives are created.
+/// The content is prepared in `.hemttout/workshop`, see [Workshop](../configuration/workshop.md) for the configuration.
+pub struct Command {
+    #[clap(flatten)]
+    build: build::BuildArgs,
+
+    #[clap(flatten)]
+    release: ReleaseArgs,
+
+    #[clap(flatten)]
+    global: crate::GlobalArgs,
+}
+
+#[derive(clap::Args)]
+#[allow(clippy::module_name_repetitions, clippy::struct_excessive_bools)]
+pub struct ReleaseArgs {
+    #[arg(long, action = clap::ArgAction::SetTrue, verbatim_doc_comment)]
+    /// Do not sign the PBOs or create a `bikey`.
+    ///
+    /// ```admonish danger
+    /// All public releases of your mods should be signed. This will be a requirement of
+    /// many communities, and is an important security feature. Do not use this
+    /// unless you know what you are doing.
+    /// ```
+    no_sign: bool,
+    #[arg(long, action = clap::ArgAction::SetTrue)]
+    /// Do not create a zip archive of the release.
+    ///
+    /// The output will be in `.hemttout/release`.
+    no_archive: bool,
+    #[arg(long, action = clap::ArgAction::SetTrue)]
+    /// Build the release twice, and check that the output is identical
+    ///
+    /// `SOURCE_DATE_EPOCH` is set to the time of the last commit if it is not already set.
+    verify_reproducible: bool,
+    #[arg(long, action = clap::ArgAction::SetTrue, conflicts_with = "verify_reproducible")]
+    /// Upload the release to the Steam Workshop
+    ///
+    /// `steamcmd` is found in `PATH` or the Steam installation, or set with `HEMTT_STEAMCMD`.
+    workshop: bool,
+}
+
+/// Execute the release command
+///
+/// # Errors
+/// [`Error`] depending on the modules
+pub fn execute(cmd: &Command) -> Result<Report, Error> {
+    if cmd.release.verify_reproducible {
+        return reproducible::verify(cmd);
+    }
+    release(cmd, None)
+}
+
+/// Build the release, `source_date_epoch` overrides the environment variable
+fn release(cmd: &Command, source_date_epoch: Option<u32>) -> Result<Report, Error> {
+    let mut ctx = Context::new(
+        Some("release"),
+        crate::context::PreservePrevious::Remove,
+        true,
+    )?;
+
+    let mut runtime = ctx.config().runtime().clone().with_release(true);
+    if source_date_epoch.is_some() {
+        runtime = runtime.with_source_date_epoch(source_date_epoch);
+    }
+    let config = ctx.config().clone().with_runtime(runtime);
+    ctx = ctx.with_config(config);
+
+    let mut executor = build::executor(ctx, &cmd.build);
+
+    if !cmd.release.no_sign && executor.ctx().config().hemtt().release().sign() {
+        executor.add_module(Box::new(Sign::new()));
+    }
+
+    if cmd.release.workshop {
+        executor.add_module(Box::new(Workshop::new()));
+    }
+
+    let archive = if cmd.release.no_archive {
+        false
+    } else {
+        executor.ctx().config().hemtt().release().archive()
+    };
+
+    executor.release(archive);
+
+    executor.run()
+}
@@ -0,0 +1,141 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    path::{Path, PathBuf},
+};
+
+use git2::Repository;
+use walkdir::WalkDir;
+
+use crate::{
+    commands::release::error::{
+        bcre1_key_not_persistent::KeyNotPersistent, bcre2_not_reproducible::NotReproducible,
+    },
+    context::{Context, PreservePrevious},
+    error::Error,
+    report::Report,
+};
+
+use super::{Command, release};
+
+/// Build the release twice, and compare the output
+pub fn verify(cmd: &Command) -> Result<Report, Error> {
+    let ctx = Context::new(None, PreservePrevious::Keep, false)?;
+    let mut report = Report::new();
+    let sign = !cmd.release.no_sign && ctx.config().hemtt().release().sign();
+    if sign && !ctx.config().signing().is_persistent() {
+        report.push(KeyNotPersistent::code());
+        return Ok(report);
+    }
+    let archive = !cmd.release.no_archive && ctx.config().hemtt().release().archive();
+    let source_date_epoch = ctx
+        .config()
+        .runtime()
+        .source_date_epoch()
+        .unwrap_or_else(last_commit_time);
+    info!("Building the release twice, with SOURCE_DATE_EPOCH={source_date_epoch}");
+
+    let first = release(cmd, Some(source_date_epoch))?;
+    if first.failed() {
+        return Ok(first);
+    }
+    let output = ctx.out_folder().join("release");
+    let previous = ctx.out_folder().join("release-reproducible");
+    if previous.exists() {
+        std::fs::remove_dir_all(&previous)?;
+    }
+    std::fs::rename(&output, &previous)?;
+    let releases = ctx.project_folder().join("releases");
+    let previous_manifests = if archive {
+        manifests(&releases)?
+    } else {
+        BTreeMap::new()
+    };
+
+    let mut second = release(cmd, Some(source_date_epoch))?;
+    if second.failed() {
+        std::fs::remove_dir_all(&previous)?;
+        return Ok(second);
+    }
+    let mut different = compare(&previous, &output)?;
+    if archive {
+        for (name, manifest) in manifests(&releases)? {
+            if previous_manifests.get(&name) != Some(&manifest) {
+                different.push(format!("releases/{name}"));
+            }
+        }
+    }
+    std::fs::remove_dir_all(&previous)?;
+    if different.is_empty() {
+        info!("The release is reproducible");
+    } else {
+        second.push(NotReproducible::code(different));
+    }
+    Ok(second)
+}
+
+/// The time of the last commit, or 0 outside of a git repository
+fn last_commit_time() -> u32 {
+    Repository::discover(".")
+        .ok()
+        .and_then(|repo| {
+            repo.head()
+                .ok()?
+                .peel_to_commit()
+                .ok()
+                .map(|commit| commit.time().seconds())
+        })
+        .and_then(|seconds| u32::try_from(seconds).ok())
+        .unwrap_or_default()
+}
+
+/// The files that are different, or only in one of the folders
+fn compare(left: &Path, right: &Path) -> Result<Vec<String>, Error> {
+    let files = files(left)?
+        .into_iter()
+        .chain(files(right)?)
+        .collect::<BTreeSet<_>>();
+    let mut different = Vec::new();
+    for file in files {
+        let (left_file, right_file) = (left.join(&file), right.join(&file));
+        if !left_file.is_file()
+            || !right_file.is_file()
+            || std::fs::read(&left_file)? != std::fs::read(&right_file)?
+        {
+            different.push(file.display().to_string().replace('\\', "/"));
+        }
+    }
+    Ok(different)
+}
+
+/// The SHA-256 manifest of each archive, by the name of the archive
+fn manifests(releases: &Path) -> Result<BTreeMap<String, String>, Error> {
+    let mut manifests = BTreeMap::new();
+    if !releases.is_dir() {
+        return Ok(manifests);
+    }
+    for entry in std::fs::read_dir(releases)? {
+        let path = entry?.path();
+        let name = path.file_name().unwrap_or_default().to_string_lossy();
+        if let Some(archive) = name.strip_suffix(".sha256") {
+            manifests.insert(archive.to_string(), std::fs::read_to_string(&path)?);
+        }
+    }
+    Ok(manifests)
+}
+
+fn files(folder: &Path) -> Result<Vec<PathBuf>, Error> {
+    let mut files = Vec::new();
+    for entry in WalkDir::new(folder) {
+        let entry = entry?;
+        if entry.file_type().is_file() {
+            files.push(
+                entry
+                    .path()
+                    .strip_prefix(folder)
+                    .expect("walked from the folder")
+                    .to_path_buf(),
+            );
+        }
+    }
+    Ok(files)
+}
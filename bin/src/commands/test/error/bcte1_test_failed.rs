@@ -0,0 +1,39 @@
+use std::sync::Arc;
+
+use hemtt_common::arma::testing::TestResult;
+use hemtt_workspace::reporting::{Code, Diagnostic};
+
+pub struct TestFailed {
+    suite: String,
+    name: String,
+    reason: String,
+}
+
+impl Code for TestFailed {
+    fn ident(&self) -> &'static str {
+        "BCTE1"
+    }
+
+    fn message(&self) -> String {
+        format!("Test `{}` in `{}` failed.", self.name, self.suite)
+    }
+
+    fn note(&self) -> Option<String> {
+        Some(self.reason.clone())
+    }
+
+    fn diagnostic(&self) -> Option<Diagnostic> {
+        Some(Diagnostic::from_code(self))
+    }
+}
+
+impl TestFailed {
+    #[must_use]
+    pub fn code(result: &TestResult) -> Arc<dyn Code> {
+        Arc::new(Self {
+            suite: result.test().suite().to_string(),
+            name: result.test().name().to_string(),
+            reason: result.failure().unwrap_or_default().to_string(),
+        })
+    }
+}
@@ -0,0 +1,40 @@
+use std::sync::Arc;
+
+use hemtt_workspace::reporting::{Code, Diagnostic};
+
+pub struct TestsIncomplete {
+    ran: usize,
+    total: usize,
+}
+
+impl Code for TestsIncomplete {
+    fn ident(&self) -> &'static str {
+        "BCTE2"
+    }
+
+    fn message(&self) -> String {
+        "Arma exited before the tests finished.".to_string()
+    }
+
+    fn note(&self) -> Option<String> {
+        Some(format!(
+            "{} of {} tests reported a result",
+            self.ran, self.total
+        ))
+    }
+
+    fn help(&self) -> Option<String> {
+        Some("check the RPT for errors in the tests or the mod".to_string())
+    }
+
+    fn diagnostic(&self) -> Option<Diagnostic> {
+        Some(Diagnostic::from_code(self))
+    }
+}
+
+impl TestsIncomplete {
+    #[must_use]
+    pub fn code(ran: usize, total: usize) -> Arc<dyn Code> {
+        Arc::new(Self { ran, total })
+    }
+}
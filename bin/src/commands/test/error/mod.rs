@@ -0,0 +1,2 @@
+pub mod bcte1_test_failed;
+pub mod bcte2_tests_incomplete;
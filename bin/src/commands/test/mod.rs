@@ -0,0 +1,331 @@
+use std::{
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+};
+
+use hemtt_common::{
+    arma::{
+        control::{fromarma, toarma},
+        testing::{Session, Test},
+    },
+    config::ProjectConfig,
+};
+
+use crate::{
+    context::Context,
+    controller::{Action, AutotestMission, Controller},
+    error::Error,
+    report::Report,
+};
+
+use self::error::{bcte1_test_failed::TestFailed, bcte2_tests_incomplete::TestsIncomplete};
+
+use super::{
+    JustArgs,
+    dev::{self, BinarizeArgs, DevArgs},
+    launch::{LaunchArgs, read_config},
+};
+
+pub mod error;
+
+#[derive(clap::Parser)]
+#[command(verbatim_doc_comment)]
+/// Run SQF tests in game
+///
+/// `hemtt test` builds a dev version of your mod, launches Arma 3 into a test mission,
+/// and runs each test file through the HEMTT extension, collecting the results.
+///
+/// Test files are `.sqf` files in a `tests` folder in an addon, `addons/main/tests/*.sqf`.
+/// Each file is one test, named after the file and grouped by addon.
+/// A test passes when it returns `true`, any other value fails the test
+/// with the value as the reason.
+///
+/// ```sqf
+/// // addons/main/tests/add.sqf
+/// private _result = [1, 2] call abe_main_fnc_add;
+/// if (_result != 3) exitWith { format ["expected 3, got %1", _result] };
+/// true
+/// ```
+///
+/// Results are written as `JUnit` XML to `.hemttout/tests.xml`, for use in CI.
+///
+/// The launch configuration is read the same way as [`hemtt launch`](launch.md),
+/// a `test` configuration is used automatically if it exists.
+pub struct Command {
+    #[arg(action = clap::ArgAction::Append, verbatim_doc_comment)]
+    /// Launches with the specified configurations
+    ///
+    /// Configured in either:
+    /// - `.hemtt/project.toml` under `hemtt.launch`
+    /// - `.hemtt/launch.toml`
+    config: Option<Vec<String>>,
+
+    #[arg(long, default_value = ".hemttout/tests.xml")]
+    /// Where to write the `JUnit` XML results
+    junit: PathBuf,
+
+    #[clap(flatten)]
+    dev: DevArgs,
+
+    #[clap(flatten)]
+    binarize: BinarizeArgs,
+
+    #[clap(flatten)]
+    just: JustArgs,
+
+    #[clap(flatten)]
+    global: crate::GlobalArgs,
+}
+
+/// Execute the test command
+///
+/// # Errors
+/// [`Error`] depending on the modules
+/// [`Error::Io`] if the results can not be written
+///
+/// # Panics
+/// If the session lock is poisoned
+pub fn execute(cmd: &Command) -> Result<Report, Error> {
+    let mut report = Report::new();
+    let config = ProjectConfig::from_file(&Path::new(".hemtt").join("project.toml"))?;
+    let mut configs = cmd.config.clone().unwrap_or_default();
+    if configs.is_empty() && config.hemtt().launch().contains_key("test") {
+        configs.push("test".to_string());
+    }
+    let Some(mut launch) = read_config(&config, &configs, &mut report) else {
+        return Ok(report);
+    };
+    launch.set_mission(None);
+
+    let mut executor = dev::context(
+        &cmd.dev,
+        &cmd.binarize,
+        &cmd.just,
+        launch.optionals(),
+        launch.binarize(),
+        launch.rapify(),
+    )?;
+    report.merge(executor.run()?);
+    if report.failed() {
+        return Ok(report);
+    }
+    let ctx = executor.into_ctx();
+
+    let tests = discover(&ctx)?;
+    if tests.is_empty() {
+        warn!("No tests found, tests are .sqf files in addons/*/tests");
+        return Ok(report);
+    }
+    info!("Found {} tests", tests.len());
+
+    let session = Arc::new(Mutex::new(Session::new(tests)));
+    let mut controller = Controller::new();
+    controller.add_action(Box::new(Tests {
+        session: session.clone(),
+    }));
+    report.merge(controller.run(&ctx, &LaunchArgs::default(), &launch)?);
+
+    let session = std::mem::take(&mut *session.lock().expect("session lock"));
+    report.merge(results(&session, &cmd.junit)?);
+    Ok(report)
+}
+
+/// Report the results of a session, and write them as `JUnit` XML
+fn results(session: &Session, junit: &Path) -> Result<Report, Error> {
+    let mut report = Report::new();
+    if !session.done() {
+        report.push(TestsIncomplete::code(
+            session.results().len(),
+            session.tests().len(),
+        ));
+    }
+    for result in session.results() {
+        if result.passed() {
+            debug!(
+                "passed {}/{} in {:.3}s",
+                result.test().suite(),
+                result.test().name(),
+                result.seconds()
+            );
+        } else {
+            report.push(TestFailed::code(result));
+        }
+    }
+    let passed = session.results().iter().filter(|r| r.passed()).count();
+    info!(
+        "{} passed, {} failed",
+        passed,
+        session.results().len() - passed
+    );
+
+    if let Some(parent) = junit.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(junit, session.junit())?;
+    info!("Results written to {}", junit.display());
+
+    Ok(report)
+}
+
+/// Find the `tests/*.sqf` files in each addon
+fn discover(ctx: &Context) -> Result<Vec<Test>, Error> {
+    let mut tests = Vec::new();
+    for addon in ctx.addons() {
+        let folder = ctx.project_folder().join(addon.folder()).join("tests");
+        if !folder.is_dir() {
+            continue;
+        }
+        let mut files = std::fs::read_dir(&folder)?
+            .filter_map(Result::ok)
+            .map(|entry| entry.path())
+            .filter(|path| {
+                path.is_file()
+                    && path
+                        .extension()
+                        .is_some_and(|ext| ext.eq_ignore_ascii_case("sqf"))
+                    && !path.to_string_lossy().ends_with(".inc.sqf")
+            })
+            .collect::<Vec<_>>();
+        files.sort();
+        for file in files {
+            let (Some(name), Some(stem)) = (file.file_name(), file.file_stem()) else {
+                continue;
+            };
+            tests.push(Test::new(
+                addon.name().to_string(),
+                stem.to_string_lossy().to_string(),
+                format!("\\{}\\tests\\{}", addon.prefix(), name.to_string_lossy()),
+            ));
+        }
+    }
+    Ok(tests)
+}
+
+/// Answers the test mission, recording the results in the session
+struct Tests {
+    session: Arc<Mutex<Session>>,
+}
+
+impl Action for Tests {
+    fn missions(&self, _: &Context) -> Vec<(String, AutotestMission)> {
+        vec![(
+            String::from("tests"),
+            AutotestMission::Internal(String::from("tests.VR")),
+        )]
+    }
+
+    fn incoming(&self, _: &Context, msg: fromarma::Message) -> Vec<toarma::Message> {
+        let fromarma::Message::Test(msg) = msg else {
+            return Vec::new();
+        };
+        match &msg {
+            fromarma::Test::Passed(path, _) => debug!("passed: {}", path),
+            fromarma::Test::Failed(path, reason, _) => warn!("failed: {} - {}", path, reason),
+            _ => {}
+        }
+        self.session.lock().expect("session lock").incoming(msg)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use std::sync::{Arc, Mutex};
+
+    use hemtt_common::arma::{
+        control::{fromarma, toarma},
+        testing::Session,
+    };
+    use sealed_test::prelude::*;
+
+    use crate::{
+        context::{Context, PreservePrevious},
+        controller::Action,
+    };
+
+    use super::{Tests, discover, results};
+
+    #[sealed_test]
+    fn session() {
+        let out = std::env::current_dir().unwrap().join("tests.xml");
+        std::env::set_current_dir(format!("{}/tests/echo", env!("CARGO_MANIFEST_DIR"))).unwrap();
+        let ctx = Context::new(Some("test"), PreservePrevious::Remove, false).unwrap();
+
+        let tests = discover(&ctx).unwrap();
+        let paths = tests
+            .iter()
+            .map(|test| test.path().to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            paths,
+            [
+                r"\z\echo\addons\main\tests\peel.sqf",
+                r"\z\echo\addons\main\tests\slice.sqf"
+            ]
+        );
+
+        // Answer the test mission as the controller would
+        let session = Arc::new(Mutex::new(Session::new(tests)));
+        let action = Tests {
+            session: session.clone(),
+        };
+        assert!(
+            action
+                .incoming(
+                    &ctx,
+                    fromarma::Message::Log(fromarma::Level::Info, String::new())
+                )
+                .is_empty()
+        );
+        let run = action.incoming(&ctx, fromarma::Message::Test(fromarma::Test::Ready));
+        let [toarma::Message::Test(toarma::Test::Run(run))] = run.as_slice() else {
+            panic!("expected the tests to run, got {run:?}");
+        };
+        assert_eq!(run, &paths);
+        for message in [
+            fromarma::Test::Passed(paths[0].clone(), 0.5),
+            fromarma::Test::Failed(paths[1].clone(), "false".to_string(), 0.25),
+        ] {
+            assert!(
+                action
+                    .incoming(&ctx, fromarma::Message::Test(message))
+                    .is_empty()
+            );
+        }
+        assert!(matches!(
+            action
+                .incoming(&ctx, fromarma::Message::Test(fromarma::Test::Done))
+                .as_slice(),
+            [toarma::Message::Control(toarma::Control::Exit)]
+        ));
+
+        let session = std::mem::take(&mut *session.lock().unwrap());
+        let report = results(&session, &out).unwrap();
+        let errors = report.errors();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].ident(), "BCTE1");
+        assert_eq!(errors[0].note().unwrap(), "false");
+        let junit = std::fs::read_to_string(out).unwrap();
+        assert!(junit.contains(r#"<testsuite name="main" tests="2" failures="1" time="0.750">"#));
+        assert!(junit.contains(r#"<failure message="false"/>"#));
+    }
+
+    #[sealed_test]
+    fn incomplete() {
+        let out = std::env::current_dir().unwrap().join("tests.xml");
+        std::env::set_current_dir(format!("{}/tests/echo", env!("CARGO_MANIFEST_DIR"))).unwrap();
+        let ctx = Context::new(Some("test"), PreservePrevious::Remove, false).unwrap();
+        // Arma exited before the mission finished
+        let mut session = Session::new(discover(&ctx).unwrap());
+        session.incoming(fromarma::Test::Passed(
+            r"\z\echo\addons\main\tests\peel.sqf".to_string(),
+            0.5,
+        ));
+        let report = results(&session, &out).unwrap();
+        let errors = report.errors();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].ident(), "BCTE2");
+        assert!(out.exists());
+    }
+}
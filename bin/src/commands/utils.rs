@@ -13,6 +13,7 @@ pub struct Command {
 
 #[derive(clap::Subcommand)]
 enum Subcommands {
+    Addons(utils::addons::Command),
     Audio(utils::audio::Command),
     Bom(utils::bom::Command),
     Config(utils::config::Command),
@@ -21,8 +22,10 @@ enum Subcommands {
     P3d(utils::p3d::Command),
     Paa(utils::paa::Command),
     Pbo(utils::pbo::Command),
+    Rpt(utils::rpt::Command),
     Sqf(utils::sqf::Command),
     Verify(utils::verify::Command),
+    VerifyMod(utils::verify_mod::Command),
 }
 
 /// Execute the utils command
@@ -31,6 +34,9 @@ enum Subcommands {
 /// [`Error`] depending on the modules
 pub fn execute(cmd: &Command) -> Result<Report, Error> {
     match &cmd.commands {
+        Subcommands::Addons(cmd) => {
+            return utils::addons::execute(cmd);
+        }
         Subcommands::Audio(cmd) => {
             utils::audio::execute(cmd)?;
         }
@@ -38,7 +44,7 @@ pub fn execute(cmd: &Command) -> Result<Report, Error> {
             utils::bom::execute(cmd)?;
         }
         Subcommands::Config(cmd) => {
-            utils::config::execute(cmd)?;
+            return utils::config::execute(cmd);
         }
         Subcommands::Fnl(cmd) => {
             utils::fnl::execute(cmd)?;
@@ -55,12 +61,18 @@ pub fn execute(cmd: &Command) -> Result<Report, Error> {
         Subcommands::Pbo(cmd) => {
             utils::pbo::execute(cmd)?;
         }
+        Subcommands::Rpt(cmd) => {
+            return utils::rpt::execute(cmd);
+        }
         Subcommands::Sqf(cmd) => {
             utils::sqf::execute(cmd)?;
         }
         Subcommands::Verify(cmd) => {
             utils::verify::execute(cmd)?;
         }
+        Subcommands::VerifyMod(cmd) => {
+            return utils::verify_mod::execute(cmd);
+        }
     }
     Ok(Report::new())
 }
@@ -20,6 +20,7 @@ pub struct Command {
 /// # Errors
 /// [`Error`] depending on the modules
 pub fn execute(cmd: &Command) -> Result<Report, Error> {
+    let mut report = Report::new();
     let default = String::new();
     let ctx = Context::new(None, crate::context::PreservePrevious::Remove, false)?;
     match cmd.name.as_str() {
@@ -80,9 +81,11 @@ pub fn execute(cmd: &Command) -> Result<Report, Error> {
         "project.version.git_hash" => {
             println!("{}", ctx.config().version().git_hash().unwrap_or(0));
         }
-        "project.signing.authority" => {
-            println!("{}", crate::modules::sign::get_authority(&ctx, None)?);
-        }
+        "project.signing.authority" => match crate::modules::sign::private_key(ctx.config()) {
+            Ok(Some(key)) => println!("{}", key.authority()),
+            Ok(None) => println!("{}", crate::modules::sign::get_authority(&ctx, None)?),
+            Err(code) => report.push(code),
+        },
         "project.signing.version" => {
             println!("{}", ctx.config().signing().version());
         }
@@ -115,7 +118,7 @@ pub fn execute(cmd: &Command) -> Result<Report, Error> {
         }
     }
 
-    Ok(Report::new())
+    Ok(report)
 }
 
 fn version(ctx: &Context) -> Version {
@@ -127,3 +130,39 @@ fn version(ctx: &Context) -> Version {
             std::process::exit(1);
         })
 }
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use clap::Parser;
+    use sealed_test::prelude::*;
+
+    use super::{Command, execute};
+
+    #[sealed_test]
+    fn invalid_key() {
+        std::fs::create_dir_all(".hemtt").unwrap();
+        std::fs::create_dir_all("addons").unwrap();
+        std::fs::write(
+            ".hemtt/project.toml",
+            r#"name = "Test"
+prefix = "abe"
+
+[version]
+major = 1
+minor = 0
+patch = 0
+git_hash = 0
+
+[signing]
+private_key = "missing.biprivatekey"
+"#,
+        )
+        .unwrap();
+        let report = execute(&Command::parse_from(["value", "project.signing.authority"])).unwrap();
+        let errors = report.errors();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].ident(), "BSE2");
+    }
+}
@@ -0,0 +1,60 @@
+use hemtt_common::version::Component;
+
+use crate::{
+    Error,
+    context::{Context, PreservePrevious},
+    report::Report,
+};
+
+#[derive(clap::Parser)]
+#[command(verbatim_doc_comment)]
+/// Bump the version of the project
+///
+/// The version is written to `project.toml` if it is defined there,
+/// otherwise to the version macro file, `addons/main/script_version.hpp` by default.
+///
+/// The components after the bumped component are reset to 0.
+/// The build number is only reset if it is set.
+///
+/// ```bash
+/// hemtt version bump minor # 1.2.3.4 -> 1.3.0.0
+/// hemtt version bump build # 1.2.3 -> 1.2.3.1
+/// ```
+pub struct Command {
+    #[arg(value_enum)]
+    /// The component to bump
+    component: BumpComponent,
+}
+
+#[derive(clap::ValueEnum, Clone, Copy)]
+enum BumpComponent {
+    Major,
+    Minor,
+    Patch,
+    Build,
+}
+
+impl From<BumpComponent> for Component {
+    fn from(component: BumpComponent) -> Self {
+        match component {
+            BumpComponent::Major => Self::Major,
+            BumpComponent::Minor => Self::Minor,
+            BumpComponent::Patch => Self::Patch,
+            BumpComponent::Build => Self::Build,
+        }
+    }
+}
+
+/// Execute the bump command
+///
+/// # Errors
+/// [`Error`] if the version can not be read or written
+pub fn execute(cmd: &Command) -> Result<Report, Error> {
+    let ctx = Context::new(None, PreservePrevious::Keep, false)?;
+    let (current, next) = ctx
+        .config()
+        .version()
+        .bump(ctx.project_folder(), cmd.component.into())?;
+    info!("Bumped version from {current} to {next}");
+    Ok(Report::new())
+}
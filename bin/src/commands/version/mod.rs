@@ -0,0 +1,31 @@
+use crate::{Error, report::Report};
+
+pub mod bump;
+
+#[derive(clap::Parser)]
+#[command(arg_required_else_help = true)]
+/// Manage the version of the project
+///
+/// See [Version](../../configuration/version.md) for where the version is defined.
+pub struct Command {
+    #[command(subcommand)]
+    commands: Subcommands,
+
+    #[clap(flatten)]
+    global: crate::GlobalArgs,
+}
+
+#[derive(clap::Subcommand)]
+enum Subcommands {
+    Bump(bump::Command),
+}
+
+/// Execute the version command
+///
+/// # Errors
+/// [`Error`] depending on the modules
+pub fn execute(cmd: &Command) -> Result<Report, Error> {
+    match &cmd.commands {
+        Subcommands::Bump(cmd) => bump::execute(cmd),
+    }
+}
@@ -59,7 +59,12 @@ impl Context {
             if !path.exists() {
                 return Err(Error::ConfigNotFound);
             }
-            ProjectConfig::from_file(&path)?
+            let config = ProjectConfig::from_file(&path)?;
+            let runtime = config
+                .runtime()
+                .clone()
+                .with_source_date_epoch(source_date_epoch());
+            config.with_runtime(runtime)
         };
         let tmp = {
             let mut tmp = temp_dir().join("hemtt");
@@ -105,6 +110,9 @@ impl Context {
             if include.is_dir() {
                 builder = builder.physical(&include, LayerType::Include);
             }
+            for pbos in config.hemtt().include_pbos() {
+                builder = builder.pbos(&root.join(pbos))?;
+            }
             maybe_build_folder = Some(build_folder);
         }
         let workspace = builder.memory().finish(
@@ -197,6 +205,12 @@ impl Context {
         &self.hemtt_folder
     }
 
+    #[must_use]
+    /// The .hemtt/baseline folder, configs from outside the project
+    pub fn baseline_folder(&self) -> PathBuf {
+        self.hemtt_folder.join("baseline")
+    }
+
     #[must_use]
     /// The .hemttout folder
     pub const fn out_folder(&self) -> &PathBuf {
@@ -302,3 +316,17 @@ fn version_check(
     }
     Ok(())
 }
+
+/// `SOURCE_DATE_EPOCH`, used instead of the current time for reproducible output
+fn source_date_epoch() -> Option<u32> {
+    let value = std::env::var("SOURCE_DATE_EPOCH")
+        .ok()
+        .filter(|value| !value.trim().is_empty())?;
+    value.trim().parse().map_or_else(
+        |_| {
+            warn!("`SOURCE_DATE_EPOCH` is not a valid timestamp: {value}");
+            None
+        },
+        Some,
+    )
+}
@@ -1,12 +1,9 @@
 #![allow(clippy::unwrap_used)] // Experimental feature
 
-use std::{
-    io::{Read, Write},
-    process::Child,
-};
+use std::process::Child;
 
 use hemtt_common::{
-    arma::control::{fromarma, toarma},
+    arma::control::{self, fromarma, toarma},
     config::LaunchOptions,
 };
 use interprocess::local_socket::{
@@ -14,7 +11,11 @@ use interprocess::local_socket::{
 };
 
 use crate::{
-    commands::launch::{LaunchArgs, launcher::Launcher},
+    commands::launch::{
+        LaunchArgs,
+        launcher::{Launcher, Servers},
+        monitor::Status,
+    },
     context::Context,
     error::Error,
     report::Report,
@@ -24,7 +25,7 @@ mod action;
 mod profile;
 
 pub use action::Action;
-pub use profile::AutotestMission;
+pub use profile::{AutotestMission, extension};
 
 #[derive(Default)]
 pub struct Controller {
@@ -41,15 +42,15 @@ impl Controller {
         self.actions.push(action);
     }
 
-    /// Run the controller
+    /// Run the controller, until Arma exits or closes the socket
     ///
     /// # Errors
     /// - [`Error::Io`] if profile files cannot be written to disk in the temporary directory
-    /// - [`Error::Io`] if there is an issue with the local socket
+    /// - [`Error::Io`] if there is an issue with the local socket, or a message can not be decoded
     ///
     /// # Panics
-    /// - If an message is not able to be read from the local socket
-    /// - If a message is in an unexpected format
+    /// - If a message can not be written to the local socket
+    /// - If a message is for a mission without an action
     pub fn run(
         self,
         ctx: &Context,
@@ -66,7 +67,8 @@ impl Controller {
         profile::setup(ctx)?;
         profile::autotest(ctx, &missions)?;
         let (report, child) = launch(ctx, launch_args, launch_options)?;
-        let Some(mut child) = child else {
+        // The server and headless clients are stopped once the game exits
+        let Some((mut child, _servers)) = child else {
             return Ok(report);
         };
         let opts = ListenerOptions::new().name("hemtt_arma".to_ns_name::<GenericNamespaced>()?);
@@ -88,26 +90,23 @@ impl Controller {
         info!("Connected!");
 
         let mut current = None;
+        let mut status = Status::new();
 
         loop {
-            let status = child.try_wait();
-            if status.is_err() {
-                warn!("No longer able to determine Arma's status");
-                break;
-            }
-            if let Ok(Some(_)) = status {
+            if !status.running(&mut child) {
                 info!("Arma has exited");
                 break;
             }
 
-            let mut len_buf = [0u8; 4];
-            if socket.read_exact(&mut len_buf).is_ok() && !len_buf.is_empty() {
-                let len = u32::from_le_bytes(len_buf);
-                trace!("Receiving: {}", len);
-                let mut buf = vec![0u8; len as usize];
-                socket.read_exact(&mut buf).expect("Failed to read message");
-                let buf = String::from_utf8(buf).expect("Failed to parse message");
-                let message: fromarma::Message = serde_json::from_str(&buf)?;
+            let message = match control::read::<_, fromarma::Message>(&mut socket) {
+                Ok(message) => message,
+                Err(e) if control::closed(&e) => {
+                    info!("Arma has disconnected");
+                    break;
+                }
+                Err(e) => return Err(e.into()),
+            };
+            if let Some(message) = message {
                 trace!("Received: {:?}", message);
                 if let fromarma::Message::Control(control) = message {
                     match control {
@@ -151,7 +150,7 @@ fn launch(
     ctx: &Context,
     launch_args: &LaunchArgs,
     launch_options: &LaunchOptions,
-) -> Result<(Report, Option<Child>), Error> {
+) -> Result<(Report, Option<(Child, Servers)>), Error> {
     let (mut report, launcher) = Launcher::new(launch_args, launch_options)?;
 
     let Some(mut launcher) = launcher else {
@@ -179,17 +178,11 @@ fn launch(
     Ok((report, child))
 }
 
-#[allow(clippy::cast_possible_truncation)]
 fn send(
     message: &toarma::Message,
     socket: &mut interprocess::local_socket::prelude::LocalSocketStream,
 ) {
-    let message = serde_json::to_string(message).unwrap();
-    trace!("sending: {}", message);
-    socket
-        .write_all(&u32::to_le_bytes(message.len() as u32))
-        .unwrap();
-    socket.write_all(message.as_bytes()).unwrap();
-    socket.flush().unwrap();
+    trace!("sending: {:?}", message);
+    control::write(socket, message).unwrap();
     std::thread::sleep(std::time::Duration::from_millis(100));
 }
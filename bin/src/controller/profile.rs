@@ -1,6 +1,6 @@
 #![allow(clippy::unwrap_used)] // Experimental feature
 
-use std::{fs::File, io::Write};
+use std::{fs::File, io::Write, path::PathBuf};
 
 use rust_embed::RustEmbed;
 
@@ -26,6 +26,25 @@ pub fn setup(ctx: &Context) -> Result<(), Error> {
     Ok(())
 }
 
+/// Unpack only the `@hemtt` mod, leaving the rest of the profile untouched
+///
+/// # Errors
+/// [`Error::Io`] if the files cannot be written
+///
+/// # Panics
+/// If an embedded file cannot be read
+pub fn extension(ctx: &Context) -> Result<PathBuf, Error> {
+    for file in Distributables::iter().filter(|f| f.starts_with("@hemtt/")) {
+        let file = file.to_string();
+        trace!("unpacking {:?}", file);
+        let path = ctx.profile().join(&file);
+        std::fs::create_dir_all(path.parent().unwrap())?;
+        let mut f = File::create(&path)?;
+        f.write_all(&Distributables::get(&file).unwrap().data)?;
+    }
+    Ok(ctx.profile().join("@hemtt"))
+}
+
 #[derive(Debug, Clone)]
 pub enum AutotestMission {
     Internal(String),
@@ -1,9 +1,15 @@
+use std::{
+    panic::{self, AssertUnwindSafe},
+    sync::{Mutex, mpsc},
+};
+
 use crate::error::Error;
 
 use crate::report::Report;
 use crate::{
     context::Context,
-    modules::{self, Module, pbo::Collapse},
+    modules::{self, Access, Module, pbo::Collapse},
+    timings,
 };
 
 pub struct Executor {
@@ -81,12 +87,14 @@ impl Executor {
         let mut report = Report::new();
         for stage in self.stages.clone() {
             report.merge(match stage {
-                "init" => self.run_modules("init")?,
+                "init" => self.init_modules()?,
                 "check" => self.run_modules("check")?,
                 "pre_build" => self.run_modules("pre_build")?,
                 "build" => {
                     trace!("phase: build (start)");
-                    let report = modules::pbo::build(&self.ctx, self.collapse)?;
+                    let report = timings::time("build", "PBO", None, || {
+                        modules::pbo::build(&self.ctx, self.collapse)
+                    })?;
                     trace!("phase: build (done)");
                     report
                 }
@@ -95,7 +103,9 @@ impl Executor {
                 "archive" => {
                     trace!("phase: archive (start)");
                     self.run_modules("archive")?;
-                    let report = modules::archive::release(&self.ctx)?;
+                    let report = timings::time("archive", "Archive", None, || {
+                        modules::archive::release(&self.ctx)
+                    })?;
                     trace!("phase: archive (done)");
                     report
                 }
@@ -109,26 +119,519 @@ impl Executor {
         Ok(report)
     }
 
-    fn run_modules(&mut self, phase: &str) -> Result<Report, Error> {
+    /// `init` can change the modules, so they run one at a time
+    fn init_modules(&mut self) -> Result<Report, Error> {
         let mut report = Report::new();
         for module in &mut self.modules {
-            trace!("phase: {} ({}) (start)", phase, module.name());
-            report.merge(match phase {
-                "init" => module.init(&self.ctx)?,
-                "check" => module.check(&self.ctx)?,
-                "pre_build" => module.pre_build(&self.ctx)?,
-                "post_build" => module.post_build(&self.ctx)?,
-                "pre_release" => module.pre_release(&self.ctx)?,
-                "archive" => module.archive(&self.ctx)?,
-                "post_release" => module.post_release(&self.ctx)?,
-                _ => unreachable!(),
-            });
+            trace!("phase: init ({}) (start)", module.name());
+            report.merge(timings::time("init", module.name(), None, || {
+                module.init(&self.ctx)
+            })?);
             if report.failed() {
-                trace!("phase: {} ({}) (failed)", phase, module.name());
+                trace!("phase: init ({}) (failed)", module.name());
                 break;
             }
-            trace!("phase: {} ({}) (done)", phase, module.name());
+            trace!("phase: init ({}) (done)", module.name());
         }
         Ok(report)
     }
+
+    /// Run the modules of a phase, those that do not conflict at the same time
+    ///
+    /// The reports are merged in priority order, no matter which module finished first
+    fn run_modules(&self, phase: &'static str) -> Result<Report, Error> {
+        let tasks = tasks(&self.modules, self.ctx.addons().len(), phase);
+        merge(schedule(&tasks, rayon::current_num_threads(), |task| {
+            self.run_task(phase, task)
+        }))
+    }
+
+    fn run_task(&self, phase: &'static str, task: &Task) -> Result<Report, Error> {
+        let module = &self.modules[task.module];
+        if let Some(addon) = task.addon {
+            let addon = &self.ctx.addons()[addon];
+            trace!(
+                "phase: {} ({}) ({}) (start)",
+                phase,
+                module.name(),
+                addon.name()
+            );
+            let report = timings::time(phase, module.name(), Some(addon.name()), || {
+                module.pre_build_addon(&self.ctx, addon)
+            });
+            trace!(
+                "phase: {} ({}) ({}) (done)",
+                phase,
+                module.name(),
+                addon.name()
+            );
+            return report;
+        }
+        trace!("phase: {} ({}) (start)", phase, module.name());
+        let report = timings::time(phase, module.name(), None, || match phase {
+            "check" => module.check(&self.ctx),
+            "pre_build" => module.pre_build(&self.ctx),
+            "post_build" => module.post_build(&self.ctx),
+            "pre_release" => module.pre_release(&self.ctx),
+            "archive" => module.archive(&self.ctx),
+            "post_release" => module.post_release(&self.ctx),
+            _ => unreachable!(),
+        });
+        if report.as_ref().map_or(true, Report::failed) {
+            trace!("phase: {} ({}) (failed)", phase, module.name());
+        } else {
+            trace!("phase: {} ({}) (done)", phase, module.name());
+        }
+        report
+    }
+}
+
+/// The tasks of a phase in priority order, and the earlier tasks each has to wait for
+fn tasks(modules: &[Box<dyn Module>], addons: usize, phase: &str) -> Vec<Task> {
+    let mut tasks: Vec<Task> = Vec::new();
+    for (module, m) in modules.iter().enumerate() {
+        if phase == "pre_build" && m.pre_build_per_addon() {
+            let access = m.access("pre_build_addon");
+            for addon in 0..addons {
+                tasks.push(Task {
+                    module,
+                    addon: Some(addon),
+                    access: access.clone(),
+                    after: Vec::new(),
+                });
+            }
+        }
+        tasks.push(Task {
+            module,
+            addon: None,
+            access: m.access(phase),
+            after: Vec::new(),
+        });
+    }
+    for later in 0..tasks.len() {
+        let after = (0..later)
+            .filter(|earlier| tasks[*earlier].before(&tasks[later]))
+            .collect();
+        tasks[later].after = after;
+    }
+    tasks
+}
+
+/// Run the tasks on up to `threads` threads, each once the tasks it waits for are done
+///
+/// Once a task fails, the rest of its module's phase and of the modules before it still run,
+/// so their diagnostics are complete, but no task of a later module is started.
+/// The results of tasks that did not run, or that belong to a module after the first to fail,
+/// are `None`, so the results do not depend on which tasks happened to run at the same time.
+/// A panic in a task is resumed once the running tasks are done
+fn schedule(
+    tasks: &[Task],
+    threads: usize,
+    run: impl Fn(&Task) -> Result<Report, Error> + Sync,
+) -> Vec<Option<Result<Report, Error>>> {
+    let mut results: Vec<Option<Result<Report, Error>>> =
+        std::iter::repeat_with(|| None).take(tasks.len()).collect();
+    // the first module to fail, no later module is started
+    let mut cutoff = usize::MAX;
+    if threads <= 1 {
+        // the only rayon thread might be this one, it can not wait on another
+        for (i, task) in tasks.iter().enumerate() {
+            if task.module > cutoff {
+                break;
+            }
+            let result = run(task);
+            if result.as_ref().map_or(true, Report::failed) {
+                cutoff = cutoff.min(task.module);
+            }
+            results[i] = Some(result);
+        }
+        return results;
+    }
+    let (queue, jobs) = mpsc::channel::<usize>();
+    let (done, finished) = mpsc::channel();
+    let jobs = Mutex::new(jobs);
+    let mut panicked = None;
+    std::thread::scope(|s| {
+        for _ in 0..threads.min(tasks.len()) {
+            let done = done.clone();
+            let (jobs, run) = (&jobs, &run);
+            s.spawn(move || {
+                loop {
+                    let job = jobs.lock().expect("jobs are poisoned").recv();
+                    let Ok(i) = job else {
+                        break;
+                    };
+                    let result = panic::catch_unwind(AssertUnwindSafe(|| run(&tasks[i])));
+                    if done.send((i, result)).is_err() {
+                        break;
+                    }
+                }
+            });
+        }
+        // only the workers can send, so a worker that is gone can not be waited on forever
+        drop(done);
+        let mut started = vec![false; tasks.len()];
+        let mut running = 0;
+        let mut stop = false;
+        loop {
+            if !stop {
+                for (i, task) in tasks.iter().enumerate() {
+                    if running >= threads {
+                        break;
+                    }
+                    if started[i]
+                        || task.module > cutoff
+                        || task.after.iter().any(|after| results[*after].is_none())
+                    {
+                        continue;
+                    }
+                    started[i] = true;
+                    running += 1;
+                    if queue.send(i).is_err() {
+                        stop = true;
+                        break;
+                    }
+                }
+            }
+            if running == 0 {
+                break;
+            }
+            let Ok((i, result)) = finished.recv() else {
+                // every worker is gone, the scope reports why
+                break;
+            };
+            running -= 1;
+            match result {
+                Ok(result) => {
+                    if result.as_ref().map_or(true, Report::failed) {
+                        cutoff = cutoff.min(tasks[i].module);
+                    }
+                    results[i] = Some(result);
+                }
+                Err(payload) => {
+                    stop = true;
+                    panicked.get_or_insert(payload);
+                }
+            }
+        }
+        drop(queue);
+    });
+    if let Some(payload) = panicked {
+        panic::resume_unwind(payload);
+    }
+    // later modules may have run alongside the one that failed
+    for (result, task) in results.iter_mut().zip(tasks) {
+        if task.module > cutoff {
+            *result = None;
+        }
+    }
+    results
+}
+
+/// Merge the reports in the order of the tasks, stopping at the first error
+fn merge(results: Vec<Option<Result<Report, Error>>>) -> Result<Report, Error> {
+    let mut report = Report::new();
+    for result in results.into_iter().flatten() {
+        report.merge(result?);
+    }
+    Ok(report)
+}
+
+/// A module's work in a phase, for the whole project or for one addon
+struct Task {
+    module: usize,
+    addon: Option<usize>,
+    access: Access,
+    /// The earlier tasks that have to be done first
+    after: Vec<usize>,
+}
+
+impl Task {
+    /// Does this earlier task have to be done before the later one starts
+    fn before(&self, later: &Self) -> bool {
+        if self.module == later.module {
+            // a module's addons come before the rest of its phase
+            return later.addon.is_none();
+        }
+        let overlap = match (self.addon, later.addon) {
+            (Some(a), Some(b)) => a == b,
+            _ => true,
+        };
+        overlap && self.access.conflicts(&later.access)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use std::{sync::Mutex, time::Duration};
+
+    use hemtt_workspace::reporting::{Code, Severity};
+
+    use super::{Task, merge, schedule, tasks};
+    use crate::{
+        error::Error,
+        modules::{Access, Module, Resource},
+        report::{Report, WithIncludes},
+    };
+
+    struct Mock {
+        access: Access,
+        /// The access of `pre_build_addon`, if the module runs per addon
+        addon: Option<Access>,
+    }
+
+    impl Module for Mock {
+        fn name(&self) -> &'static str {
+            "Mock"
+        }
+
+        fn access(&self, phase: &str) -> Access {
+            if phase == "pre_build_addon" {
+                self.addon.clone().unwrap_or_default()
+            } else {
+                self.access.clone()
+            }
+        }
+
+        fn pre_build_per_addon(&self) -> bool {
+            self.addon.is_some()
+        }
+    }
+
+    struct Message(String, Severity);
+
+    impl Code for Message {
+        fn ident(&self) -> &'static str {
+            "TEST"
+        }
+
+        fn message(&self) -> String {
+            self.0.clone()
+        }
+
+        fn severity(&self) -> Severity {
+            self.1
+        }
+    }
+
+    fn report(module: usize, severity: Severity) -> Report {
+        let mut report = Report::new();
+        report.push(std::sync::Arc::new(Message(module.to_string(), severity)));
+        report
+    }
+
+    fn task(module: usize, addon: Option<usize>, access: Access) -> Task {
+        Task {
+            module,
+            addon,
+            access,
+            after: Vec::new(),
+        }
+    }
+
+    /// Tasks of different modules, that wait for the given tasks
+    fn waiting(after: &[&[usize]]) -> Vec<Task> {
+        after
+            .iter()
+            .enumerate()
+            .map(|(module, after)| Task {
+                after: after.to_vec(),
+                ..task(module, None, Access::new())
+            })
+            .collect()
+    }
+
+    #[test]
+    fn before() {
+        let writes = Access::new().with_writes(&[Resource::Config]);
+        let reads = Access::new().with_reads(&[Resource::Config]);
+        // a module's addons come before the rest of its phase, and do not wait for each other
+        assert!(task(0, Some(0), Access::new()).before(&task(0, None, Access::new())));
+        assert!(!task(0, None, Access::new()).before(&task(0, Some(0), Access::new())));
+        assert!(!task(0, Some(0), writes.clone()).before(&task(0, Some(1), writes.clone())));
+        // other modules only wait when they conflict
+        assert!(task(0, None, writes.clone()).before(&task(1, None, reads.clone())));
+        assert!(!task(0, None, reads.clone()).before(&task(1, None, reads.clone())));
+        assert!(task(0, None, writes.clone()).before(&task(1, Some(0), reads.clone())));
+        assert!(task(0, Some(0), writes.clone()).before(&task(1, None, reads.clone())));
+        // the same addon conflicts, different addons do not
+        assert!(task(0, Some(0), writes.clone()).before(&task(1, Some(0), reads.clone())));
+        assert!(!task(0, Some(0), writes).before(&task(1, Some(1), reads)));
+        assert!(task(0, Some(0), Access::exclusive()).before(&task(1, None, Access::new())));
+    }
+
+    #[test]
+    fn after() {
+        let modules: Vec<Box<dyn Module>> = vec![
+            // functions, generating configs from the SQF
+            Box::new(Mock {
+                access: Access::new()
+                    .with_reads(&[Resource::Sqf])
+                    .with_writes(&[Resource::Config]),
+                addon: None,
+            }),
+            // rapifier, each addon's config
+            Box::new(Mock {
+                access: Access::new(),
+                addon: Some(
+                    Access::new()
+                        .with_reads(&[Resource::Config])
+                        .with_writes(&[Resource::Binarized]),
+                ),
+            }),
+            // sqf, each addon's functions, then the project
+            Box::new(Mock {
+                access: Access::new().with_reads(&[Resource::FunctionsDefined]),
+                addon: Some(
+                    Access::new()
+                        .with_reads(&[Resource::Sqf])
+                        .with_writes(&[Resource::FunctionsDefined]),
+                ),
+            }),
+            Box::new(Mock {
+                access: Access::exclusive(),
+                addon: None,
+            }),
+        ];
+        let after = |phase| {
+            tasks(&modules, 2, phase)
+                .into_iter()
+                .map(|task| (task.module, task.addon, task.after))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            after("pre_build"),
+            vec![
+                (0, None, vec![]),
+                (1, Some(0), vec![0]),
+                (1, Some(1), vec![0]),
+                (1, None, vec![1, 2]),
+                (2, Some(0), vec![]),
+                (2, Some(1), vec![]),
+                (2, None, vec![4, 5]),
+                (3, None, vec![0, 1, 2, 3, 4, 5, 6]),
+            ]
+        );
+        // only `pre_build` runs per addon
+        assert_eq!(
+            after("check"),
+            vec![
+                (0, None, vec![]),
+                (1, None, vec![]),
+                (2, None, vec![]),
+                (3, None, vec![0, 1, 2]),
+            ]
+        );
+    }
+
+    #[test]
+    fn stop_on_failure() {
+        for threads in [1, 2] {
+            let tasks = waiting(&[&[], &[0], &[1]]);
+            let ran = Mutex::new(Vec::new());
+            let results = schedule(&tasks, threads, |task| {
+                ran.lock().unwrap().push(task.module);
+                Ok(report(task.module, Severity::Error))
+            });
+            assert_eq!(*ran.lock().unwrap(), vec![0]);
+            assert!(results[0].is_some());
+            assert!(results[1].is_none() && results[2].is_none());
+            assert!(merge(results).unwrap().failed());
+
+            let results = schedule(&tasks, threads, |task| {
+                if task.module == 1 {
+                    Err(Error::Io(std::io::Error::other("failed")))
+                } else {
+                    Ok(Report::new())
+                }
+            });
+            assert!(results[2].is_none());
+            assert!(merge(results).is_err());
+        }
+    }
+
+    #[test]
+    fn failed_addons_finish_the_phase() {
+        for threads in [1, 2, 4] {
+            // two addons of the first module fail, its project task still runs,
+            // the second module runs alongside but its results are dropped
+            let tasks = vec![
+                task(0, Some(0), Access::new()),
+                task(0, Some(1), Access::new()),
+                task(0, Some(2), Access::new()),
+                Task {
+                    after: vec![0, 1, 2],
+                    ..task(0, None, Access::new())
+                },
+                task(1, None, Access::new()),
+                Task {
+                    after: vec![3, 4],
+                    ..task(2, None, Access::new())
+                },
+            ];
+            let ran = Mutex::new(Vec::new());
+            let results = schedule(&tasks, threads, |task| {
+                ran.lock().unwrap().push((task.module, task.addon));
+                if task.addon.is_some_and(|addon| addon != 1) {
+                    // the failures finish in a different order each time
+                    std::thread::sleep(Duration::from_millis(
+                        50 * u64::try_from(2 - task.addon.unwrap()).unwrap(),
+                    ));
+                    Ok(report(task.addon.unwrap(), Severity::Error))
+                } else {
+                    Ok(report(task.module * 10, Severity::Warning))
+                }
+            });
+            let ran = ran.into_inner().unwrap();
+            assert!(ran.contains(&(0, None)));
+            assert!(!ran.contains(&(2, None)));
+            assert!(results[..4].iter().all(Option::is_some));
+            assert!(results[4..].iter().all(Option::is_none));
+            let report = merge(results).unwrap();
+            let messages = |codes: Vec<std::sync::Arc<dyn Code>>| {
+                codes.iter().map(|code| code.message()).collect::<Vec<_>>()
+            };
+            assert_eq!(messages(report.errors()), vec!["0", "2"]);
+            assert_eq!(messages(report.warnings(WithIncludes::Yes)), vec!["0", "0"]);
+        }
+    }
+
+    #[test]
+    fn merged_in_order() {
+        let tasks = waiting(&[&[], &[], &[], &[2]]);
+        let finished = Mutex::new(Vec::new());
+        let results = schedule(&tasks, 3, |task| {
+            // the first task finishes last
+            if task.module == 0 {
+                std::thread::sleep(Duration::from_millis(200));
+            }
+            finished.lock().unwrap().push(task.module);
+            Ok(report(task.module, Severity::Warning))
+        });
+        assert_eq!(finished.lock().unwrap().last(), Some(&0));
+        let messages = merge(results)
+            .unwrap()
+            .warnings(WithIncludes::Yes)
+            .iter()
+            .map(|code| code.message())
+            .collect::<Vec<_>>();
+        assert_eq!(messages, vec!["0", "1", "2", "3"]);
+    }
+
+    #[test]
+    fn panic_is_resumed() {
+        let tasks = waiting(&[&[], &[0], &[]]);
+        let ran = Mutex::new(Vec::new());
+        let panicked = std::panic::catch_unwind(|| {
+            schedule(&tasks, 2, |task| {
+                ran.lock().unwrap().push(task.module);
+                assert!(task.module != 0, "task panicked");
+                Ok(Report::new())
+            })
+        });
+        assert!(panicked.is_err());
+        assert!(!ran.lock().unwrap().contains(&1));
+    }
 }
@@ -14,6 +14,7 @@ pub mod logging;
 pub mod modules;
 mod progress;
 pub mod report;
+pub mod timings;
 pub mod update;
 pub mod utils;
 
@@ -40,6 +41,9 @@ pub struct GlobalArgs {
     #[arg(global = true, hide = true, long)]
     /// Directory to run in
     dir: Option<String>,
+    #[arg(global = true, long, value_enum, num_args = 0..=1, default_missing_value = "table")]
+    /// Report how long each module and addon took, as a table or a Chrome trace
+    timings: Option<timings::TimingsFormat>,
     #[cfg(debug_assertions)]
     #[arg(global = true, long, hide = true, action = clap::ArgAction::SetTrue)]
     /// we are in a test
@@ -50,16 +54,22 @@ pub struct GlobalArgs {
 enum Commands {
     Book(commands::book::Command),
     New(commands::new::Command),
+    Changelog(commands::changelog::Command),
     Check(commands::check::Command),
     Dev(commands::dev::Command),
+    Docs(commands::docs::Command),
+    Fmt(commands::fmt::Command),
+    Keys(commands::keys::Command),
     Launch(commands::launch::Command),
     Build(commands::build::Command),
     Release(commands::release::Command),
     #[clap(alias = "ln")]
     Localization(commands::localization::Command),
     Script(commands::script::Command),
+    Test(commands::test::Command),
     Utils(commands::utils::Command),
     Value(commands::value::Command),
+    Version(commands::version::Command),
     Wiki(commands::wiki::Command),
     #[cfg(windows)]
     Photoshoot(commands::photoshoot::Command),
@@ -72,6 +82,7 @@ enum Commands {
 ///
 /// # Panics
 /// If the number passed to `--threads` is not a valid number
+#[allow(clippy::too_many_lines)]
 pub fn execute(cli: &Cli) -> Result<(), Error> {
     // check for -v with no command and show version
     if cli.command.is_none() {
@@ -94,12 +105,23 @@ pub fn execute(cli: &Cli) -> Result<(), Error> {
     #[cfg(not(debug_assertions))]
     let in_test = false;
 
-    if !in_test && !matches!(cli.command, Some(Commands::Value(_))) {
+    if !in_test
+        && !matches!(
+            cli.command,
+            Some(Commands::Value(_) | Commands::Changelog(_))
+        )
+    {
         logging::init(
             cli.global.verbosity,
             !matches!(
                 cli.command,
-                Some(Commands::Utils(_) | Commands::Wiki(_) | Commands::New(_) | Commands::Book(_))
+                Some(
+                    Commands::Utils(_)
+                        | Commands::Wiki(_)
+                        | Commands::New(_)
+                        | Commands::Book(_)
+                        | Commands::Keys(_)
+                )
             ),
         )?;
     }
@@ -123,29 +145,43 @@ pub fn execute(cli: &Cli) -> Result<(), Error> {
         }
     }
 
+    if cli.global.timings.is_some() {
+        timings::enable();
+    }
+
     let report = match cli.command.as_ref().expect("Handled above") {
         Commands::Book(cmd) => commands::book::execute(cmd),
         Commands::New(cmd) => commands::new::execute(cmd, in_test),
+        Commands::Changelog(cmd) => commands::changelog::execute(cmd),
         Commands::Check(cmd) => commands::check::execute(cmd),
         Commands::Dev(cmd) => commands::dev::execute(cmd, &[], false).map(|(r, _)| r),
+        Commands::Docs(cmd) => commands::docs::execute(cmd),
+        Commands::Fmt(cmd) => commands::fmt::execute(cmd),
+        Commands::Keys(cmd) => commands::keys::execute(cmd),
         Commands::Launch(cmd) => commands::launch::execute(cmd),
         Commands::Build(cmd) => commands::build::execute(cmd),
         Commands::Release(cmd) => commands::release::execute(cmd),
         Commands::Localization(cmd) => commands::localization::execute(cmd),
         Commands::Script(cmd) => commands::script::execute(cmd),
+        Commands::Test(cmd) => commands::test::execute(cmd),
         Commands::Utils(cmd) => commands::utils::execute(cmd),
         Commands::Value(cmd) => commands::value::execute(cmd),
+        Commands::Version(cmd) => commands::version::execute(cmd),
         Commands::Wiki(cmd) => commands::wiki::execute(cmd),
         #[cfg(windows)]
         Commands::Photoshoot(cmd) => commands::photoshoot::execute(cmd),
     };
 
+    if let Some(format) = cli.global.timings {
+        timings::report(format, &std::env::current_dir()?.join(".hemttout"))?;
+    }
+
     match report {
         Ok(report) => {
             report.write_to_stdout();
             if !matches!(
                 cli.command,
-                Some(Commands::New(_) | Commands::Utils(_) | Commands::Wiki(_))
+                Some(Commands::New(_) | Commands::Utils(_) | Commands::Wiki(_) | Commands::Keys(_))
             ) {
                 report.write_ci_annotations()?;
             }
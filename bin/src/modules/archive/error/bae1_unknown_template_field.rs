@@ -0,0 +1,50 @@
+use std::sync::Arc;
+
+use hemtt_common::similar_values;
+use hemtt_workspace::reporting::{Code, Diagnostic};
+
+use super::super::FIELDS;
+
+pub struct UnknownTemplateField {
+    field: String,
+    template: String,
+}
+
+impl Code for UnknownTemplateField {
+    fn ident(&self) -> &'static str {
+        "BAE1"
+    }
+
+    fn message(&self) -> String {
+        format!(
+            "Unknown field `{{{}}}` in template `{}`",
+            self.field, self.template
+        )
+    }
+
+    fn note(&self) -> Option<String> {
+        Some(format!(
+            "available fields are `{{{}}}`",
+            FIELDS.join("}`, `{")
+        ))
+    }
+
+    fn help(&self) -> Option<String> {
+        let similar = similar_values(&self.field, FIELDS);
+        if similar.is_empty() {
+            None
+        } else {
+            Some(format!("did you mean `{{{}}}`?", similar.join("}`, `{")))
+        }
+    }
+
+    fn diagnostic(&self) -> Option<Diagnostic> {
+        Some(Diagnostic::from_code(self))
+    }
+}
+
+impl UnknownTemplateField {
+    pub fn code(field: String, template: String) -> Arc<dyn Code> {
+        Arc::new(Self { field, template })
+    }
+}
@@ -0,0 +1,31 @@
+use std::sync::Arc;
+
+use hemtt_workspace::reporting::{Code, Diagnostic};
+
+pub struct DuplicateArchive {
+    path: String,
+}
+
+impl Code for DuplicateArchive {
+    fn ident(&self) -> &'static str {
+        "BAE2"
+    }
+
+    fn message(&self) -> String {
+        format!("More than one archive is named `{}`", self.path)
+    }
+
+    fn help(&self) -> Option<String> {
+        Some("give each archive a different `name`".to_string())
+    }
+
+    fn diagnostic(&self) -> Option<Diagnostic> {
+        Some(Diagnostic::from_code(self))
+    }
+}
+
+impl DuplicateArchive {
+    pub fn code(path: String) -> Arc<dyn Code> {
+        Arc::new(Self { path })
+    }
+}
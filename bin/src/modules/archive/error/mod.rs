@@ -0,0 +1,2 @@
+pub mod bae1_unknown_template_field;
+pub mod bae2_duplicate_archive;
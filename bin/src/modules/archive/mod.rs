@@ -0,0 +1,705 @@
+use std::{
+    collections::HashMap,
+    fmt::Write as _,
+    fs::{File, create_dir_all},
+    hash::BuildHasher,
+    io::Read,
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::{Duration, SystemTime},
+};
+
+use hemtt_common::{
+    changelog::Changelog,
+    config::{ArchiveFormat, ArchiveTarget},
+};
+use hemtt_workspace::reporting::Code;
+use sevenz_rust::{SevenZArchiveEntry, SevenZWriter, nt_time::FileTime};
+use sha2::{Digest, Sha256};
+use time::{OffsetDateTime, PrimitiveDateTime};
+use walkdir::WalkDir;
+use zip::{DateTime, ZipWriter, write::SimpleFileOptions};
+
+use crate::{context::Context, error::Error, progress::progress_bar, report::Report};
+
+use self::error::{
+    bae1_unknown_template_field::UnknownTemplateField, bae2_duplicate_archive::DuplicateArchive,
+};
+
+mod error;
+mod swifty;
+
+/// The fields that can be used in the name and folder templates of an archive
+pub const FIELDS: &[&str] = &[
+    "prefix", "folder", "version", "major", "minor", "patch", "build", "hash",
+];
+
+/// A file or directory of the release, relative to the build folder
+struct Entry {
+    path: PathBuf,
+    relative: String,
+    directory: bool,
+}
+
+impl Entry {
+    /// Is the entry included, with or without the optionals and keys
+    fn included(&self, optionals: bool, keys: bool) -> bool {
+        let components = self.relative.split('/').collect::<Vec<_>>();
+        if !optionals && components[0] == "optionals" {
+            return false;
+        }
+        // The keys of the mod, or of an optional mod folder
+        !(!keys
+            && (components[0] == "keys"
+                || (components[0] == "optionals" && components.get(2) == Some(&"keys"))))
+    }
+}
+
+/// Creates the release archives
+///
+/// # Errors
+/// [`Error`] depending on the modules
+/// [`Error::Zip`] if the zip fails to create
+/// [`Error::Io`] if an archive fails to write
+/// [`Error::Version`] if the version is invalid
+///
+/// # Panics
+/// If we are somehow not in the HEMTT folder
+pub fn release(ctx: &Context) -> Result<Report, Error> {
+    let output = ctx.project_folder().join("releases");
+    trace!("using releases folder: {:?}", output.display());
+    if !output.exists() {
+        create_dir_all(&output)?;
+    }
+    let mut report = Report::new();
+    let fields = fields(ctx)?;
+    let targets = targets(ctx, &output, &fields, &mut report);
+    let changelog = match ctx
+        .config()
+        .hemtt()
+        .release()
+        .changelog()
+        .map(|template| render(template, &fields))
+        .transpose()
+    {
+        Ok(changelog) => changelog,
+        Err(code) => {
+            report.push(code);
+            None
+        }
+    };
+    if report.failed() {
+        return Ok(report);
+    }
+
+    let entries = entries(ctx.build_folder().expect("build folder exists"))?;
+    let epoch = ctx.config().runtime().source_date_epoch();
+    // Archives with the same contents are copied instead of being created again
+    let mut created: HashMap<(ArchiveFormat, String, bool, bool), PathBuf> = HashMap::new();
+    for (target, path, folder) in targets {
+        let key = (target.format(), folder, target.optionals(), target.keys());
+        debug!("creating release at {:?}", path.display());
+        if let Some(existing) = created.get(&key) {
+            std::fs::copy(existing, &path)?;
+        } else {
+            let included = entries
+                .iter()
+                .filter(|entry| entry.included(target.optionals(), target.keys()))
+                .collect::<Vec<_>>();
+            match target.format() {
+                ArchiveFormat::Zip => zip(&path, &key.1, &included, epoch)?,
+                ArchiveFormat::TarZst => tar_zst(&path, &key.1, &included, epoch)?,
+                ArchiveFormat::SevenZ => seven_z(&path, &key.1, &included, epoch)?,
+                ArchiveFormat::Folder => copy_folder(&path, &key.1, &included)?,
+                ArchiveFormat::Swifty => {
+                    let previous = target.previous().map_or_else(
+                        || path.clone(),
+                        |previous| ctx.project_folder().join(previous),
+                    );
+                    // The mods of a repository need a name
+                    let folder = if key.1.is_empty() {
+                        format!("@{}", ctx.config().hemtt().release().folder())
+                    } else {
+                        key.1.clone()
+                    };
+                    let reused = swifty::write(
+                        &path,
+                        Some(&previous),
+                        ctx.config().name(),
+                        &folder,
+                        &included,
+                    )?;
+                    if reused != 0 {
+                        info!("Reused {reused} unchanged PBOs from {}", previous.display());
+                    }
+                }
+            }
+            if target.format().extension().is_some() {
+                created.insert(key, path.clone());
+            }
+        }
+        manifest(&path, &output)?;
+        info!("Created release: {}", path.display());
+    }
+
+    if let Some(name) = changelog {
+        let path = output.join(format!("{name}.md"));
+        let version = ctx.config().version().get(ctx.workspace_path().vfs())?;
+        std::fs::write(
+            &path,
+            Changelog::discover(ctx.project_folder())?.to_markdown(&version),
+        )?;
+        manifest(&path, &output)?;
+        info!("Created release notes: {}", path.display());
+    }
+    Ok(report)
+}
+
+/// The archives of the release, with their path and folder
+fn targets<'a>(
+    ctx: &'a Context,
+    output: &Path,
+    fields: &HashMap<&'static str, String>,
+    report: &mut Report,
+) -> Vec<(&'a ArchiveTarget, PathBuf, String)> {
+    let mut targets = Vec::new();
+    for target in ctx.config().hemtt().release().archives() {
+        match (
+            render(target.name(), fields),
+            render(target.folder(), fields),
+        ) {
+            (Ok(name), Ok(folder)) => {
+                let path = target.format().extension().map_or_else(
+                    || output.join(&name),
+                    |extension| output.join(format!("{name}.{extension}")),
+                );
+                if targets.iter().any(|(_, existing, _)| *existing == path) {
+                    report.push(DuplicateArchive::code(
+                        path.strip_prefix(output)
+                            .unwrap_or(&path)
+                            .display()
+                            .to_string(),
+                    ));
+                }
+                targets.push((target, path, folder));
+            }
+            (Err(code), _) | (_, Err(code)) => report.push(code),
+        }
+    }
+    targets
+}
+
+/// The values of the template fields
+///
+/// # Errors
+/// [`Error::Version`] if the version is invalid
+pub fn fields(ctx: &Context) -> Result<HashMap<&'static str, String>, Error> {
+    let version = ctx.config().version().get(ctx.workspace_path().vfs())?;
+    Ok(HashMap::from([
+        ("prefix", ctx.config().prefix().to_string()),
+        (
+            "folder",
+            ctx.config().hemtt().release().folder().to_string(),
+        ),
+        ("version", version.to_string()),
+        ("major", version.major().to_string()),
+        ("minor", version.minor().to_string()),
+        ("patch", version.patch().to_string()),
+        (
+            "build",
+            version
+                .build()
+                .map(|build| build.to_string())
+                .unwrap_or_default(),
+        ),
+        ("hash", version.hash().unwrap_or_default()),
+    ]))
+}
+
+/// Replace the `{field}`s of a template
+///
+/// # Errors
+/// [`UnknownTemplateField`] if a field is not one of [`FIELDS`]
+pub fn render<S: BuildHasher>(
+    template: &str,
+    fields: &HashMap<&'static str, String, S>,
+) -> Result<String, Arc<dyn Code>> {
+    let mut out = String::new();
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        let Some(end) = rest[start..].find('}') else {
+            break;
+        };
+        out.push_str(&rest[..start]);
+        let field = &rest[start + 1..start + end];
+        let Some(value) = fields.get(field) else {
+            return Err(UnknownTemplateField::code(
+                field.to_string(),
+                template.to_string(),
+            ));
+        };
+        out.push_str(value);
+        rest = &rest[start + end + 1..];
+    }
+    out.push_str(rest);
+    Ok(out)
+}
+
+/// Every file and directory in the build folder, sorted so archives are reproducible
+fn entries(build_folder: &Path) -> Result<Vec<Entry>, Error> {
+    let mut entries = Vec::new();
+    for entry in WalkDir::new(build_folder).sort_by_file_name() {
+        let entry = entry?;
+        let relative = entry
+            .path()
+            .strip_prefix(build_folder)
+            .expect("We are in the HEMTT folder, the prefix should always exist")
+            .display()
+            .to_string()
+            .replace('\\', "/");
+        if relative.is_empty() {
+            continue;
+        }
+        entries.push(Entry {
+            directory: entry.file_type().is_dir(),
+            path: entry.into_path(),
+            relative,
+        });
+    }
+    Ok(entries)
+}
+
+/// The name of an entry in the archive, inside the release folder of the target
+fn archive_name(folder: &str, entry: &Entry) -> String {
+    if folder.is_empty() {
+        entry.relative.clone()
+    } else {
+        format!("{folder}/{}", entry.relative)
+    }
+}
+
+fn zip(path: &Path, folder: &str, entries: &[&Entry], epoch: Option<u32>) -> Result<(), Error> {
+    let options = SimpleFileOptions::default()
+        .compression_level(Some(9))
+        .last_modified_time(modified_time(epoch));
+    let progress = progress_bar(entries.len() as u64).with_message("Creating release");
+    let mut zip = ZipWriter::new(File::create(path)?);
+    for entry in entries {
+        let name = archive_name(folder, entry);
+        if entry.directory {
+            trace!("zip: creating directory {:?}", name);
+            zip.add_directory(name, options)?;
+        } else {
+            trace!("zip: adding file {:?}", name);
+            zip.start_file(name, options)?;
+            std::io::copy(&mut File::open(&entry.path)?, &mut zip)?;
+        }
+        progress.inc(1);
+    }
+    progress.finish_and_clear();
+    zip.finish()?;
+    Ok(())
+}
+
+fn tar_zst(path: &Path, folder: &str, entries: &[&Entry], epoch: Option<u32>) -> Result<(), Error> {
+    let mtime = u64::from(epoch.unwrap_or_else(now));
+    let progress = progress_bar(entries.len() as u64).with_message("Creating release");
+    let mut tar = tar::Builder::new(zstd::Encoder::new(File::create(path)?, 19)?);
+    for entry in entries {
+        let name = archive_name(folder, entry);
+        let mut header = tar::Header::new_gnu();
+        header.set_mtime(mtime);
+        if entry.directory {
+            trace!("tar: creating directory {:?}", name);
+            header.set_entry_type(tar::EntryType::Directory);
+            header.set_mode(0o755);
+            header.set_size(0);
+            tar.append_data(&mut header, name, std::io::empty())?;
+        } else {
+            trace!("tar: adding file {:?}", name);
+            let file = File::open(&entry.path)?;
+            header.set_mode(0o644);
+            header.set_size(file.metadata()?.len());
+            tar.append_data(&mut header, name, file)?;
+        }
+        progress.inc(1);
+    }
+    progress.finish_and_clear();
+    tar.into_inner()?.finish()?;
+    Ok(())
+}
+
+fn seven_z(path: &Path, folder: &str, entries: &[&Entry], epoch: Option<u32>) -> Result<(), Error> {
+    let progress = progress_bar(entries.len() as u64).with_message("Creating release");
+    let mut archive = SevenZWriter::create(path).map_err(std::io::Error::other)?;
+    let modified = FileTime::from_unix_time(i64::from(epoch.unwrap_or_else(now)))
+        .unwrap_or(FileTime::UNIX_EPOCH);
+    for entry in entries {
+        let mut archive_entry = SevenZArchiveEntry::new();
+        archive_entry.name = archive_name(folder, entry);
+        archive_entry.is_directory = entry.directory;
+        archive_entry.has_stream = !entry.directory;
+        archive_entry.has_last_modified_date = true;
+        archive_entry.last_modified_date = modified;
+        if entry.directory {
+            trace!("7z: creating directory {:?}", archive_entry.name);
+            archive
+                .push_archive_entry::<File>(archive_entry, None)
+                .map_err(std::io::Error::other)?;
+        } else {
+            trace!("7z: adding file {:?}", archive_entry.name);
+            archive
+                .push_archive_entry(archive_entry, Some(File::open(&entry.path)?))
+                .map_err(std::io::Error::other)?;
+        }
+        progress.inc(1);
+    }
+    progress.finish_and_clear();
+    archive.finish()?;
+    Ok(())
+}
+
+/// Copy the release to a folder, with or without the optionals and keys
+///
+/// # Errors
+/// [`Error::Io`] if the release can not be copied
+///
+/// # Panics
+/// If we are somehow not in the HEMTT folder
+pub fn copy_release(ctx: &Context, path: &Path, optionals: bool, keys: bool) -> Result<(), Error> {
+    let entries = entries(ctx.build_folder().expect("build folder exists"))?;
+    copy_folder(
+        path,
+        "",
+        &entries
+            .iter()
+            .filter(|entry| entry.included(optionals, keys))
+            .collect::<Vec<_>>(),
+    )
+}
+
+/// Copy the release to a folder
+fn copy_folder(path: &Path, folder: &str, entries: &[&Entry]) -> Result<(), Error> {
+    if path.exists() {
+        std::fs::remove_dir_all(path)?;
+    }
+    let root = path.join(folder);
+    create_dir_all(&root)?;
+    for entry in entries {
+        let target = root.join(&entry.relative);
+        if entry.directory {
+            create_dir_all(target)?;
+        } else {
+            std::fs::copy(&entry.path, target)?;
+        }
+    }
+    Ok(())
+}
+
+/// Write a `sha256sum` compatible manifest next to the archive, with a line for each file of a folder
+fn manifest(path: &Path, releases: &Path) -> Result<(), Error> {
+    let mut files = Vec::new();
+    if path.is_dir() {
+        for entry in WalkDir::new(path).sort_by_file_name() {
+            let entry = entry?;
+            if entry.file_type().is_file() {
+                files.push(entry.into_path());
+            }
+        }
+    } else {
+        files.push(path.to_path_buf());
+    }
+    let mut content = String::new();
+    for file in files {
+        let _ = writeln!(
+            content,
+            "{}  {}",
+            sha256(&file)?,
+            file.strip_prefix(releases)
+                .unwrap_or(&file)
+                .display()
+                .to_string()
+                .replace('\\', "/")
+        );
+    }
+    let mut manifest = path.as_os_str().to_owned();
+    manifest.push(".sha256");
+    std::fs::write(manifest, content)?;
+    Ok(())
+}
+
+fn sha256(path: &Path) -> Result<String, Error> {
+    let mut hasher = Sha256::new();
+    let mut file = File::open(path)?;
+    let mut buffer = vec![0; 64 * 1024];
+    loop {
+        let read = file.read(&mut buffer)?;
+        if read == 0 {
+            break;
+        }
+        hasher.update(&buffer[..read]);
+    }
+    Ok(hasher
+        .finalize()
+        .iter()
+        .fold(String::new(), |mut out, byte| {
+            let _ = write!(out, "{byte:02x}");
+            out
+        }))
+}
+
+fn now() -> u32 {
+    SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .unwrap_or(Duration::ZERO)
+        .as_secs()
+        .try_into()
+        .unwrap_or(u32::MAX)
+}
+
+/// The modification time of every entry, `SOURCE_DATE_EPOCH` for reproducible output
+///
+/// Zip files can not store times before 1980, earlier times are stored as 1980-01-01
+fn modified_time(source_date_epoch: Option<u32>) -> DateTime {
+    let time = source_date_epoch.map_or_else(OffsetDateTime::now_utc, |epoch| {
+        OffsetDateTime::from_unix_timestamp(i64::from(epoch))
+            .expect("a u32 timestamp is always in range")
+    });
+    DateTime::try_from(PrimitiveDateTime::new(time.date(), time.time())).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use std::{
+        collections::HashMap,
+        fmt::Write as _,
+        fs::File,
+        io::Read,
+        path::{Path, PathBuf},
+    };
+
+    use sealed_test::prelude::*;
+    use sha2::{Digest, Sha256};
+
+    use super::{Entry, entries, manifest, render};
+
+    const EPOCH: Option<u32> = Some(1_700_000_000);
+
+    /// A release with a key and an optional mod
+    fn build() -> Vec<Entry> {
+        for (path, content) in [
+            ("build/mod.cpp", "name = \"Abe\";"),
+            ("build/addons/abe_main.pbo", "main"),
+            ("build/keys/abe.bikey", "key"),
+            ("build/optionals/@abe_extra/addons/abe_extra.pbo", "extra"),
+            ("build/optionals/@abe_extra/keys/abe.bikey", "key"),
+        ] {
+            let path = Path::new(path);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, content).unwrap();
+        }
+        std::fs::create_dir_all("releases").unwrap();
+        entries(Path::new("build")).unwrap()
+    }
+
+    fn included(entries: &[Entry], optionals: bool, keys: bool) -> Vec<&Entry> {
+        entries
+            .iter()
+            .filter(|entry| entry.included(optionals, keys))
+            .collect()
+    }
+
+    /// The files of an extracted archive, with their contents
+    fn files(root: &Path) -> Vec<(String, String)> {
+        walkdir::WalkDir::new(root)
+            .sort_by_file_name()
+            .into_iter()
+            .map(Result::unwrap)
+            .filter(|entry| entry.file_type().is_file())
+            .map(|entry| {
+                (
+                    entry
+                        .path()
+                        .strip_prefix(root)
+                        .unwrap()
+                        .display()
+                        .to_string()
+                        .replace('\\', "/"),
+                    std::fs::read_to_string(entry.path()).unwrap(),
+                )
+            })
+            .collect()
+    }
+
+    fn expected(folder: &str, files: &[(&str, &str)]) -> Vec<(String, String)> {
+        files
+            .iter()
+            .map(|(path, content)| (format!("{folder}/{path}"), (*content).to_string()))
+            .collect()
+    }
+
+    fn sha256(path: &Path) -> String {
+        Sha256::digest(std::fs::read(path).unwrap())
+            .iter()
+            .fold(String::new(), |mut out, byte| {
+                let _ = write!(out, "{byte:02x}");
+                out
+            })
+    }
+
+    fn read_manifest(path: &Path) -> String {
+        let mut manifest = path.as_os_str().to_owned();
+        manifest.push(".sha256");
+        std::fs::read_to_string(PathBuf::from(manifest)).unwrap()
+    }
+
+    #[sealed_test]
+    fn filtered() {
+        let entries = build();
+        let names = |optionals, keys| {
+            included(&entries, optionals, keys)
+                .iter()
+                .filter(|entry| !entry.directory)
+                .map(|entry| entry.relative.as_str())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            names(true, true),
+            vec![
+                "addons/abe_main.pbo",
+                "keys/abe.bikey",
+                "mod.cpp",
+                "optionals/@abe_extra/addons/abe_extra.pbo",
+                "optionals/@abe_extra/keys/abe.bikey",
+            ]
+        );
+        assert_eq!(
+            names(true, false),
+            vec![
+                "addons/abe_main.pbo",
+                "mod.cpp",
+                "optionals/@abe_extra/addons/abe_extra.pbo",
+            ]
+        );
+        assert_eq!(
+            names(false, true),
+            vec!["addons/abe_main.pbo", "keys/abe.bikey", "mod.cpp"]
+        );
+    }
+
+    #[sealed_test]
+    fn zip() {
+        let entries = build();
+        let path = Path::new("releases/abe.zip");
+        super::zip(path, "@abe", &included(&entries, false, true), EPOCH).unwrap();
+        let mut archive = zip::ZipArchive::new(File::open(path).unwrap()).unwrap();
+        let mut found = Vec::new();
+        for index in 0..archive.len() {
+            let mut file = archive.by_index(index).unwrap();
+            if file.is_dir() {
+                continue;
+            }
+            let mut content = String::new();
+            file.read_to_string(&mut content).unwrap();
+            found.push((file.name().to_string(), content));
+        }
+        assert_eq!(
+            found,
+            expected(
+                "@abe",
+                &[
+                    ("addons/abe_main.pbo", "main"),
+                    ("keys/abe.bikey", "key"),
+                    ("mod.cpp", "name = \"Abe\";"),
+                ]
+            )
+        );
+    }
+
+    #[sealed_test]
+    fn tar_zst() {
+        let entries = build();
+        let path = Path::new("releases/abe.tar.zst");
+        super::tar_zst(path, "@abe", &included(&entries, true, false), EPOCH).unwrap();
+        tar::Archive::new(zstd::Decoder::new(File::open(path).unwrap()).unwrap())
+            .unpack("extracted")
+            .unwrap();
+        assert_eq!(
+            files(Path::new("extracted")),
+            expected(
+                "@abe",
+                &[
+                    ("addons/abe_main.pbo", "main"),
+                    ("mod.cpp", "name = \"Abe\";"),
+                    ("optionals/@abe_extra/addons/abe_extra.pbo", "extra"),
+                ]
+            )
+        );
+    }
+
+    #[sealed_test]
+    fn seven_z() {
+        let entries = build();
+        let path = Path::new("releases/abe.7z");
+        super::seven_z(path, "@abe", &included(&entries, true, true), EPOCH).unwrap();
+        sevenz_rust::decompress_file(path, "extracted").unwrap();
+        assert_eq!(
+            files(Path::new("extracted")),
+            expected(
+                "@abe",
+                &[
+                    ("addons/abe_main.pbo", "main"),
+                    ("keys/abe.bikey", "key"),
+                    ("mod.cpp", "name = \"Abe\";"),
+                    ("optionals/@abe_extra/addons/abe_extra.pbo", "extra"),
+                    ("optionals/@abe_extra/keys/abe.bikey", "key"),
+                ]
+            )
+        );
+    }
+
+    #[sealed_test]
+    fn manifests() {
+        let entries = build();
+        let releases = Path::new("releases");
+        let archive = releases.join("abe.zip");
+        super::zip(&archive, "", &included(&entries, false, false), EPOCH).unwrap();
+        manifest(&archive, releases).unwrap();
+        assert_eq!(
+            read_manifest(&archive),
+            format!("{}  abe.zip\n", sha256(&archive))
+        );
+
+        let folder = releases.join("abe");
+        super::copy_folder(&folder, "@abe", &included(&entries, false, false)).unwrap();
+        manifest(&folder, releases).unwrap();
+        let main = folder.join("@abe/addons/abe_main.pbo");
+        let mod_cpp = folder.join("@abe/mod.cpp");
+        assert_eq!(
+            read_manifest(&folder),
+            format!(
+                "{}  abe/@abe/addons/abe_main.pbo\n{}  abe/@abe/mod.cpp\n",
+                sha256(&main),
+                sha256(&mod_cpp)
+            )
+        );
+    }
+
+    #[test]
+    fn rendered() {
+        let fields = HashMap::from([
+            ("prefix", "abe".to_string()),
+            ("version", "1.2.3".to_string()),
+        ]);
+        assert_eq!(render("{prefix}_{version}", &fields).unwrap(), "abe_1.2.3");
+        assert_eq!(render("@{prefix}", &fields).unwrap(), "@abe");
+        assert_eq!(render("no fields", &fields).unwrap(), "no fields");
+        // An unclosed brace is kept as written
+        assert_eq!(render("{prefix}_{open", &fields).unwrap(), "abe_{open");
+        let code = render("{prefix}_{verison}", &fields).unwrap_err();
+        assert_eq!(code.ident(), "BAE1");
+        assert_eq!(code.help().unwrap(), "did you mean `{version}`?");
+    }
+}
@@ -0,0 +1,560 @@
+//! A Swifty repository, a folder for each mod with a `mod.srf` listing the MD5 of the parts of every file
+//!
+//! Clients compare the parts with their local files, and only download the parts that changed.
+//! PBOs are split into their header, each of their files, and their checksum, other files are
+//! split into parts of 5 MB.
+//!
+//! The `.a3s` files of `Arma3Sync` are not written, only Swifty can read the repository.
+
+use std::{
+    collections::BTreeMap,
+    fmt::Write as _,
+    fs::{File, create_dir_all},
+    io::{Read, Seek, SeekFrom},
+    path::Path,
+};
+
+use hemtt_pbo::ReadablePbo;
+use md5::{Digest, Md5};
+use serde::Serialize;
+use walkdir::WalkDir;
+
+use crate::error::Error;
+
+use super::Entry;
+
+/// The size of the parts of files that are not PBOs
+const PART_SIZE: u64 = 5_000_000;
+/// The size of the end of a PBO, a null byte and the SHA-1 checksum
+const PBO_END_SIZE: u64 = 21;
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+/// `repo.json`, the mods of the repository
+struct Repository {
+    repo_name: String,
+    checksum: String,
+    required_mods: Vec<RepositoryMod>,
+    optional_mods: Vec<RepositoryMod>,
+    client_parameters: String,
+    repo_basic_authentication: Option<String>,
+    version: String,
+    servers: Vec<String>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct RepositoryMod {
+    mod_name: String,
+    check_sum: String,
+    enabled: bool,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "PascalCase")]
+/// `mod.srf`, the files of a mod
+struct Mod {
+    name: String,
+    checksum: String,
+    files: Vec<ModFile>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "PascalCase")]
+struct ModFile {
+    path: String,
+    length: u64,
+    checksum: String,
+    r#type: &'static str,
+    parts: Vec<Part>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "PascalCase")]
+struct Part {
+    path: String,
+    length: u64,
+    start: u64,
+    checksum: String,
+}
+
+/// Create the repository, `folder` is the name of the main mod
+///
+/// Optional mod folders are added as optional mods of the repository. PBOs whose files are
+/// unchanged from the `previous` repository are copied from it, with their signatures, so
+/// clients do not download them again.
+///
+/// Returns the number of PBOs copied from the previous repository
+pub fn write(
+    path: &Path,
+    previous: Option<&Path>,
+    name: &str,
+    folder: &str,
+    entries: &[&Entry],
+) -> Result<usize, Error> {
+    let mut partial = path.as_os_str().to_owned();
+    partial.push(".partial");
+    let partial = Path::new(&partial);
+    if partial.exists() {
+        std::fs::remove_dir_all(partial)?;
+    }
+
+    // The main mod, then the optional mod folders
+    let mut mods: BTreeMap<(bool, String), Vec<(String, &Entry)>> = BTreeMap::new();
+    mods.insert((false, folder.to_string()), Vec::new());
+    for entry in entries {
+        let (optional, folder, relative) = match entry.relative.split_once('/') {
+            Some(("optionals", rest)) if rest.starts_with('@') => match rest.split_once('/') {
+                Some((optional, relative)) => (true, optional, relative),
+                None => continue,
+            },
+            _ if entry.relative == "optionals" && entry.directory => continue,
+            _ => (false, folder, entry.relative.as_str()),
+        };
+        mods.entry((optional, folder.to_string()))
+            .or_default()
+            .push((relative.to_string(), entry));
+    }
+
+    let mut reused = 0;
+    let mut repository = Repository {
+        repo_name: name.to_string(),
+        checksum: String::new(),
+        required_mods: Vec::new(),
+        optional_mods: Vec::new(),
+        client_parameters: "-noSplash -skipIntro".to_string(),
+        repo_basic_authentication: None,
+        version: "3.0.0.0".to_string(),
+        servers: Vec::new(),
+    };
+    for ((optional, folder), files) in mods {
+        let root = partial.join(&folder);
+        let previous = previous
+            .map(|previous| previous.join(&folder))
+            .filter(|previous| keys_match(&files, previous));
+        reused += copy_mod(&root, previous.as_deref(), &files)?;
+        let srf = scan_mod(&root, &folder)?;
+        std::fs::write(root.join("mod.srf"), serde_json::to_string(&srf)?)?;
+        let repository_mod = RepositoryMod {
+            mod_name: folder,
+            check_sum: srf.checksum,
+            enabled: !optional,
+        };
+        if optional {
+            repository.optional_mods.push(repository_mod);
+        } else {
+            repository.required_mods.push(repository_mod);
+        }
+    }
+    repository.checksum = md5(repository
+        .required_mods
+        .iter()
+        .chain(&repository.optional_mods)
+        .map(|repository_mod| repository_mod.check_sum.as_bytes()));
+    std::fs::write(
+        partial.join("repo.json"),
+        serde_json::to_string_pretty(&repository)?,
+    )?;
+
+    if path.exists() {
+        std::fs::remove_dir_all(path)?;
+    }
+    std::fs::rename(partial, path)?;
+    Ok(reused)
+}
+
+/// Are the keys of the mod the same as in the previous repository, the signatures of unchanged PBOs can only be reused if they are
+fn keys_match(files: &[(String, &Entry)], previous: &Path) -> bool {
+    if !previous.is_dir() {
+        return false;
+    }
+    let previous_keys = std::fs::read_dir(previous.join("keys"))
+        .map(|keys| keys.filter_map(Result::ok).count())
+        .unwrap_or_default();
+    let keys = files
+        .iter()
+        .filter(|(relative, entry)| !entry.directory && relative.starts_with("keys/"))
+        .collect::<Vec<_>>();
+    keys.len() == previous_keys
+        && keys.iter().all(|(relative, entry)| {
+            std::fs::read(&entry.path).ok() == std::fs::read(previous.join(relative)).ok()
+        })
+}
+
+/// Copy the files of a mod, with unchanged PBOs from the previous repository
+fn copy_mod(
+    root: &Path,
+    previous: Option<&Path>,
+    files: &[(String, &Entry)],
+) -> Result<usize, Error> {
+    create_dir_all(root)?;
+    let mut reused: Vec<&str> = Vec::new();
+    for (relative, entry) in files {
+        let target = root.join(relative);
+        if entry.directory {
+            create_dir_all(target)?;
+            continue;
+        }
+        // The signatures of a reused PBO are copied with it
+        if reused
+            .iter()
+            .any(|pbo| relative.starts_with(&format!("{pbo}.")) && relative.ends_with(".bisign"))
+        {
+            continue;
+        }
+        let unchanged = previous
+            .map(|previous| previous.join(relative))
+            .filter(|previous| is_pbo(previous) && previous.is_file())
+            .map(|previous| {
+                unchanged(&entry.path, &previous).map(|unchanged| (previous, unchanged))
+            })
+            .transpose()?;
+        if let Some((previous, true)) = unchanged {
+            trace!("swifty: reusing unchanged {:?}", relative);
+            std::fs::copy(&previous, &target)?;
+            copy_signatures(&previous, &target)?;
+            reused.push(relative);
+        } else {
+            std::fs::copy(&entry.path, target)?;
+        }
+    }
+    Ok(reused.len())
+}
+
+/// Copy the `.bisign` files of a PBO
+fn copy_signatures(previous: &Path, target: &Path) -> Result<(), Error> {
+    let (Some(folder), Some(pbo), Some(target_folder)) =
+        (previous.parent(), previous.file_name(), target.parent())
+    else {
+        return Ok(());
+    };
+    let prefix = format!("{}.", pbo.to_string_lossy());
+    for file in std::fs::read_dir(folder)? {
+        let file = file?;
+        let name = file.file_name().to_string_lossy().to_string();
+        if name.starts_with(&prefix) && name.ends_with(".bisign") {
+            std::fs::copy(file.path(), target_folder.join(name))?;
+        }
+    }
+    Ok(())
+}
+
+/// Are the files of the PBOs the same, the headers contain the time of the build and can differ
+fn unchanged(pbo: &Path, previous: &Path) -> Result<bool, Error> {
+    let (Some(parts), Some(previous_parts)) = (pbo_parts(pbo)?, pbo_parts(previous)?) else {
+        return Ok(false);
+    };
+    let files = |parts: &[Part]| {
+        parts[1..parts.len() - 1]
+            .iter()
+            .map(|part| (part.path.clone(), part.length, part.checksum.clone()))
+            .collect::<Vec<_>>()
+    };
+    Ok(files(&parts) == files(&previous_parts))
+}
+
+/// The `mod.srf` of a mod folder
+fn scan_mod(root: &Path, name: &str) -> Result<Mod, Error> {
+    let mut files = Vec::new();
+    for entry in WalkDir::new(root).sort_by_file_name() {
+        let entry = entry?;
+        if !entry.file_type().is_file() {
+            continue;
+        }
+        let relative = entry
+            .path()
+            .strip_prefix(root)
+            .expect("walked from the root")
+            .display()
+            .to_string()
+            .replace('/', "\\");
+        let (r#type, parts) = match pbo_parts(entry.path())? {
+            Some(parts) => ("SwiftyPbo", parts),
+            None => ("SwiftyFile", file_parts(entry.path(), &relative)?),
+        };
+        files.push(ModFile {
+            checksum: md5(parts.iter().map(|part| part.checksum.as_bytes())),
+            length: entry.metadata()?.len(),
+            path: relative,
+            r#type,
+            parts,
+        });
+    }
+    Ok(Mod {
+        name: name.to_string(),
+        checksum: md5(files.iter().map(|file| file.checksum.as_bytes())),
+        files,
+    })
+}
+
+/// The header, files, and end of a PBO, or `None` if the file is not a PBO
+fn pbo_parts(path: &Path) -> Result<Option<Vec<Part>>, Error> {
+    if !is_pbo(path) {
+        return Ok(None);
+    }
+    let length = std::fs::metadata(path)?.len();
+    let Ok(pbo) = ReadablePbo::from(File::open(path)?) else {
+        return Ok(None);
+    };
+    let headers = pbo.files();
+    let data = headers
+        .iter()
+        .map(|header| u64::from(header.size()))
+        .sum::<u64>();
+    let Some(header_length) = length.checked_sub(data + PBO_END_SIZE) else {
+        return Ok(None);
+    };
+    let mut file = File::open(path)?;
+    let mut parts = vec![part(&mut file, "$$HEADER$$".to_string(), 0, header_length)?];
+    let mut start = header_length;
+    for header in headers {
+        let size = u64::from(header.size());
+        parts.push(part(&mut file, header.filename().to_string(), start, size)?);
+        start += size;
+    }
+    parts.push(part(
+        &mut file,
+        "$$END$$".to_string(),
+        start,
+        length - start,
+    )?);
+    Ok(Some(parts))
+}
+
+fn is_pbo(path: &Path) -> bool {
+    path.extension()
+        .is_some_and(|extension| extension.eq_ignore_ascii_case("pbo"))
+}
+
+/// Parts of 5 MB of a file
+fn file_parts(path: &Path, relative: &str) -> Result<Vec<Part>, Error> {
+    let length = std::fs::metadata(path)?.len();
+    let name = relative.rsplit('\\').next().unwrap_or(relative);
+    let mut file = File::open(path)?;
+    let mut parts = Vec::new();
+    let mut start = 0;
+    loop {
+        let size = PART_SIZE.min(length - start);
+        parts.push(part(
+            &mut file,
+            format!("{name}_{}", start + size),
+            start,
+            size,
+        )?);
+        start += size;
+        if start >= length {
+            break;
+        }
+    }
+    Ok(parts)
+}
+
+fn part(file: &mut File, path: String, start: u64, length: u64) -> Result<Part, Error> {
+    file.seek(SeekFrom::Start(start))?;
+    let mut hasher = Md5::new();
+    std::io::copy(&mut file.by_ref().take(length), &mut hasher)?;
+    Ok(Part {
+        path,
+        length,
+        start,
+        checksum: hex(&hasher.finalize()),
+    })
+}
+
+/// The MD5 of the values, as uppercase hex
+fn md5<'a>(values: impl Iterator<Item = &'a [u8]>) -> String {
+    let mut hasher = Md5::new();
+    for value in values {
+        hasher.update(value);
+    }
+    hex(&hasher.finalize())
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().fold(String::new(), |mut out, byte| {
+        let _ = write!(out, "{byte:02X}");
+        out
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use std::{fs::File, io::Cursor, path::Path};
+
+    use hemtt_pbo::WritablePbo;
+    use sealed_test::prelude::*;
+    use serde_json::Value;
+
+    use super::{super::entries, md5, write};
+
+    fn pbo(path: &str, timestamp: u32, content: &str) {
+        let path = Path::new(path);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        let mut pbo = WritablePbo::new();
+        pbo.timestamp(timestamp);
+        pbo.add_property("prefix", "z\\abe\\addons\\main");
+        pbo.add_file("config.cpp", Cursor::new(content.as_bytes().to_vec()))
+            .unwrap();
+        pbo.add_file("fnc_a.sqf", Cursor::new(b"a".to_vec()))
+            .unwrap();
+        pbo.write(&mut File::create(path).unwrap(), true).unwrap();
+    }
+
+    fn file(path: &str, content: &str) {
+        let path = Path::new(path);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, content).unwrap();
+    }
+
+    /// A release with a signed PBO, a key, and an optional mod
+    fn build(timestamp: u32, content: &str) {
+        if Path::new("build").exists() {
+            std::fs::remove_dir_all("build").unwrap();
+        }
+        pbo("build/addons/abe_main.pbo", timestamp, content);
+        file(
+            "build/addons/abe_main.pbo.abe.bisign",
+            &format!("signed {timestamp}"),
+        );
+        file("build/keys/abe.bikey", "key");
+        file("build/mod.cpp", "name = \"Abe\";");
+        pbo(
+            "build/optionals/@abe_extra/addons/abe_extra.pbo",
+            timestamp,
+            "extra",
+        );
+    }
+
+    fn release(path: &str, previous: Option<&str>) -> usize {
+        let entries = entries(Path::new("build")).unwrap();
+        write(
+            Path::new(path),
+            previous.map(Path::new),
+            "My Mod",
+            "@abe",
+            &entries.iter().collect::<Vec<_>>(),
+        )
+        .unwrap()
+    }
+
+    fn json(path: &str) -> Value {
+        serde_json::from_str(&std::fs::read_to_string(path).unwrap()).unwrap()
+    }
+
+    #[sealed_test]
+    fn repository() {
+        build(1, "main");
+        assert_eq!(release("repo", None), 0);
+
+        let repo = json("repo/repo.json");
+        let srf = json("repo/@abe/mod.srf");
+        let extra = json("repo/@abe_extra/mod.srf");
+        assert_eq!(repo["repoName"], "My Mod");
+        assert_eq!(repo["requiredMods"][0]["modName"], "@abe");
+        assert_eq!(repo["requiredMods"][0]["checkSum"], srf["Checksum"]);
+        assert_eq!(repo["requiredMods"][0]["enabled"], true);
+        assert_eq!(repo["optionalMods"][0]["modName"], "@abe_extra");
+        assert_eq!(repo["optionalMods"][0]["checkSum"], extra["Checksum"]);
+        assert_eq!(repo["optionalMods"][0]["enabled"], false);
+        assert_eq!(
+            repo["checksum"],
+            md5([&srf["Checksum"], &extra["Checksum"]]
+                .iter()
+                .map(|checksum| checksum.as_str().unwrap().as_bytes()))
+        );
+
+        assert_eq!(srf["Name"], "@abe");
+        let paths = srf["Files"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|file| file["Path"].as_str().unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            paths,
+            vec![
+                "addons\\abe_main.pbo",
+                "addons\\abe_main.pbo.abe.bisign",
+                "keys\\abe.bikey",
+                "mod.cpp",
+            ]
+        );
+        for file in srf["Files"].as_array().unwrap() {
+            let parts = file["Parts"].as_array().unwrap();
+            // The parts cover the whole file, one after another
+            let mut start = 0;
+            for part in parts {
+                assert_eq!(part["Start"], start);
+                start += part["Length"].as_u64().unwrap();
+            }
+            assert_eq!(file["Length"], start);
+            assert_eq!(
+                file["Checksum"],
+                md5(parts
+                    .iter()
+                    .map(|part| part["Checksum"].as_str().unwrap().as_bytes()))
+            );
+        }
+        let main = &srf["Files"][0];
+        assert_eq!(main["Type"], "SwiftyPbo");
+        let parts = main["Parts"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|part| part["Path"].as_str().unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            parts,
+            vec!["$$HEADER$$", "config.cpp", "fnc_a.sqf", "$$END$$"]
+        );
+        let mod_cpp = &srf["Files"][3];
+        assert_eq!(mod_cpp["Type"], "SwiftyFile");
+        assert_eq!(mod_cpp["Parts"][0]["Path"], "mod.cpp_13");
+    }
+
+    #[sealed_test]
+    fn reuse() {
+        build(1, "main");
+        release("previous", None);
+        let previous = std::fs::read("previous/@abe/addons/abe_main.pbo").unwrap();
+
+        // Only the time in the headers changed
+        build(2, "main");
+        assert_ne!(
+            std::fs::read("build/addons/abe_main.pbo").unwrap(),
+            previous
+        );
+        assert_eq!(release("repo", Some("previous")), 2);
+        assert_eq!(
+            std::fs::read("repo/@abe/addons/abe_main.pbo").unwrap(),
+            previous
+        );
+        assert_eq!(
+            std::fs::read_to_string("repo/@abe/addons/abe_main.pbo.abe.bisign").unwrap(),
+            "signed 1"
+        );
+        assert_eq!(
+            json("repo/@abe/mod.srf")["Checksum"],
+            json("previous/@abe/mod.srf")["Checksum"]
+        );
+
+        // A file of the PBO changed
+        build(3, "changed");
+        assert_eq!(release("repo", Some("previous")), 1);
+        assert_eq!(
+            std::fs::read_to_string("repo/@abe/addons/abe_main.pbo.abe.bisign").unwrap(),
+            "signed 3"
+        );
+
+        // The signatures of the previous repository are from another key
+        build(4, "main");
+        file("build/keys/abe.bikey", "new key");
+        assert_eq!(release("repo", Some("previous")), 1);
+        assert_eq!(
+            std::fs::read_to_string("repo/@abe/addons/abe_main.pbo.abe.bisign").unwrap(),
+            "signed 4"
+        );
+    }
+}
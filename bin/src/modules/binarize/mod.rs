@@ -21,7 +21,7 @@ use self::error::{
     bbw2_platform_not_supported::PlatformNotSupported,
 };
 use self::error::{bbe4_missing_textures::MissingTextures, bbe6_missing_pdrive::MissingPDrive};
-use super::Module;
+use super::{Access, Module, Resource};
 use crate::{
     context::Context, error::Error, link::create_link,
     modules::binarize::error::bbe5_missing_material::MissingMaterials, report::Report,
@@ -54,6 +54,13 @@ impl Module for Binarize {
         "Binarize"
     }
 
+    fn access(&self, phase: &str) -> Access {
+        match phase {
+            "check" | "pre_build" => Access::new().with_writes(&[Resource::Binarized]),
+            _ => Access::new(),
+        }
+    }
+
     #[cfg(windows)]
     fn init(&mut self, ctx: &Context) -> Result<Report, Error> {
         let mut report = Report::new();
@@ -4,7 +4,7 @@ use hemtt_workspace::reporting::Code;
 
 use crate::{context::Context, report::Report};
 
-use super::Module;
+use super::{Access, Module, Resource};
 
 #[derive(Default)]
 pub struct BOMCheck {}
@@ -14,6 +14,15 @@ impl Module for BOMCheck {
         "BOM Check"
     }
 
+    fn access(&self, phase: &str) -> Access {
+        match phase {
+            "check" => {
+                Access::new().with_reads(&[Resource::Sqf, Resource::Config, Resource::Stringtables])
+            }
+            _ => Access::new(),
+        }
+    }
+
     fn check(&self, ctx: &Context) -> Result<Report, crate::Error> {
         fn files_to_check(root: &PathBuf) -> Vec<PathBuf> {
             const IGNORED_EXTENSIONS: [&str; 4] = ["p3d", "rtm", "bin", "paa"];
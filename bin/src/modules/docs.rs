@@ -0,0 +1,248 @@
+use std::fmt::Write as _;
+
+use hemtt_sqf::header::{Argument, Header};
+
+use crate::{context::Context, error::Error, report::Report};
+
+use super::{Access, Module, Resource, functions::functions};
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, clap::ValueEnum)]
+/// The format of the generated documentation
+pub enum Format {
+    #[default]
+    Markdown,
+    Html,
+}
+
+impl Format {
+    const fn extension(self) -> &'static str {
+        match self {
+            Self::Markdown => "md",
+            Self::Html => "html",
+        }
+    }
+}
+
+#[derive(Default)]
+/// Generates an API reference for each addon from the headers of its functions
+pub struct Docs {
+    format: Format,
+    public: bool,
+}
+
+impl Docs {
+    #[must_use]
+    pub const fn new(format: Format, public: bool) -> Self {
+        Self { format, public }
+    }
+}
+
+struct Documented {
+    name: String,
+    header: Option<Header>,
+}
+
+impl Module for Docs {
+    fn name(&self) -> &'static str {
+        "Docs"
+    }
+    fn priority(&self) -> i32 {
+        // After the SQF lints have checked the headers
+        5000
+    }
+
+    fn access(&self, phase: &str) -> Access {
+        match phase {
+            "pre_build" => Access::new()
+                .with_reads(&[Resource::Sqf])
+                .with_writes(&[Resource::Output]),
+            _ => Access::new(),
+        }
+    }
+
+    fn pre_build(&self, ctx: &Context) -> Result<Report, Error> {
+        let Some(folder) = ctx.build_folder() else {
+            return Ok(Report::new());
+        };
+        let config = ctx.config().functions();
+        let mut index = Vec::new();
+        let mut total = 0;
+        for addon in ctx.addons() {
+            let tag = config.tag(ctx.config().prefix(), addon.name());
+            let mut documented = Vec::new();
+            for function in functions(ctx, addon)?.into_values().flatten() {
+                let header = Header::parse(&function.path.read_to_string()?);
+                if self.public && header.as_ref().and_then(Header::public) != Some(true) {
+                    continue;
+                }
+                documented.push(Documented {
+                    name: format!("{tag}_fnc_{}", function.name),
+                    header,
+                });
+            }
+            if documented.is_empty() {
+                continue;
+            }
+            documented.sort_by_cached_key(|function| function.name.to_lowercase());
+            total += documented.len();
+            let content = match self.format {
+                Format::Markdown => markdown(addon.name(), &documented),
+                Format::Html => html(addon.name(), &documented),
+            };
+            let path = folder.join(format!("{}.{}", addon.name(), self.format.extension()));
+            std::fs::write(&path, content)?;
+            debug!("generated documentation for {}", addon.name());
+            index.push((addon.name().to_string(), documented.len()));
+        }
+        let content = match self.format {
+            Format::Markdown => markdown_index(ctx.config().name(), &index),
+            Format::Html => html_index(ctx.config().name(), &index),
+        };
+        std::fs::write(
+            folder.join(format!("index.{}", self.format.extension())),
+            content,
+        )?;
+        info!(
+            "Generated documentation for {total} functions in {}",
+            folder.display()
+        );
+        Ok(Report::new())
+    }
+}
+
+fn markdown_index(project: &str, index: &[(String, usize)]) -> String {
+    let mut out = format!("# {project}\n\n");
+    for (addon, count) in index {
+        let _ = writeln!(out, "- [{addon}]({addon}.md) - {count} functions");
+    }
+    out
+}
+
+fn markdown(addon: &str, functions: &[Documented]) -> String {
+    let mut out = format!("# {addon}\n\n");
+    for function in functions {
+        match &function.header {
+            Some(header) => out.push_str(&header.markdown(&function.name)),
+            None => {
+                let _ = write!(out, "### {}\n\n*Not documented*", function.name);
+            }
+        }
+        out.push_str("\n\n");
+    }
+    out.trim_end().to_string() + "\n"
+}
+
+const STYLE: &str = "body{font-family:sans-serif;max-width:60em;margin:auto;padding:1em}\
+table{border-collapse:collapse}td,th{border:1px solid #ccc;padding:.25em .5em}\
+pre{background:#f4f4f4;padding:.5em}";
+
+fn page(title: &str, body: &str) -> String {
+    format!(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{}</title>\n<style>{STYLE}</style>\n</head>\n<body>\n{body}</body>\n</html>\n",
+        escape(title)
+    )
+}
+
+fn html_index(project: &str, index: &[(String, usize)]) -> String {
+    let mut body = format!("<h1>{}</h1>\n<ul>\n", escape(project));
+    for (addon, count) in index {
+        let addon = escape(addon);
+        let _ = writeln!(
+            body,
+            "<li><a href=\"{addon}.html\">{addon}</a> - {count} functions</li>"
+        );
+    }
+    body.push_str("</ul>\n");
+    page(project, &body)
+}
+
+fn html(addon: &str, functions: &[Documented]) -> String {
+    let mut body = format!("<h1>{}</h1>\n", escape(addon));
+    for function in functions {
+        let name = escape(&function.name);
+        let _ = writeln!(body, "<h2 id=\"{name}\">{name}</h2>");
+        let Some(header) = &function.header else {
+            body.push_str("<p><em>Not documented</em></p>\n");
+            continue;
+        };
+        if !header.description().is_empty() {
+            let _ = writeln!(
+                body,
+                "<p>{}</p>",
+                escape(header.description()).replace('\n', "<br>\n")
+            );
+        }
+        if let Some(arguments) = header.arguments() {
+            body.push_str("<h3>Arguments</h3>\n");
+            if arguments.is_empty() {
+                body.push_str("<p>None</p>\n");
+            } else {
+                body.push_str(
+                    "<table>\n<tr><th>#</th><th>Description</th><th>Type</th><th>Default</th></tr>\n",
+                );
+                for (index, argument) in arguments.iter().enumerate() {
+                    let _ = writeln!(
+                        body,
+                        "<tr><td>{index}</td><td>{}</td><td>{}</td><td>{}</td></tr>",
+                        html_description(argument),
+                        escape(&argument.types().join(", ")),
+                        argument.default().map_or_else(
+                            || if argument.optional() {
+                                "<em>optional</em>"
+                            } else {
+                                ""
+                            }
+                            .to_string(),
+                            |default| format!("<code>{}</code>", escape(default))
+                        ),
+                    );
+                }
+                body.push_str("</table>\n");
+            }
+        }
+        if let Some(return_value) = header.return_value() {
+            let _ = writeln!(
+                body,
+                "<h3>Return Value</h3>\n<p>{}</p>",
+                escape(return_value).replace('\n', "<br>\n")
+            );
+        }
+        for example in header.examples() {
+            let _ = writeln!(
+                body,
+                "<h3>Example</h3>\n<pre><code>{}</code></pre>",
+                escape(example)
+            );
+        }
+        if let Some(public) = header.public() {
+            let _ = writeln!(
+                body,
+                "<p><strong>Public</strong>: {}</p>",
+                if public { "Yes" } else { "No" }
+            );
+        }
+        if !header.authors().is_empty() {
+            let _ = writeln!(
+                body,
+                "<p><strong>Author</strong>: {}</p>",
+                escape(&header.authors().join(", "))
+            );
+        }
+    }
+    page(addon, &body)
+}
+
+fn html_description(argument: &Argument) -> String {
+    let description = escape(argument.description()).replace('\n', "<br>");
+    match argument.name() {
+        Some(name) => format!("<code>{}</code> {description}", escape(name)),
+        None => description,
+    }
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
@@ -5,7 +5,7 @@ use rayon::prelude::{IntoParallelRefIterator, ParallelIterator};
 
 use crate::{context::Context, error::Error, link::create_link, report::Report};
 
-use super::Module;
+use super::{Access, Module, Resource};
 
 pub struct FilePatching {
     arma3dir: Option<std::path::PathBuf>,
@@ -24,6 +24,13 @@ impl Module for FilePatching {
         "FilePatching"
     }
 
+    fn access(&self, phase: &str) -> Access {
+        match phase {
+            "pre_build" | "post_build" => Access::new().with_writes(&[Resource::Output]),
+            _ => Access::new(),
+        }
+    }
+
     fn pre_build(&self, ctx: &Context) -> Result<Report, Error> {
         create_dir_all(
             ctx.build_folder()
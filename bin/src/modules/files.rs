@@ -2,7 +2,7 @@ use std::fs::create_dir_all;
 
 use crate::{context::Context, error::Error, progress::progress_bar, report::Report};
 
-use super::Module;
+use super::{Access, Module, Resource};
 
 #[derive(Default)]
 pub struct Files;
@@ -12,6 +12,13 @@ impl Module for Files {
         "Files"
     }
 
+    fn access(&self, phase: &str) -> Access {
+        match phase {
+            "post_build" => Access::new().with_writes(&[Resource::Output]),
+            _ => Access::new(),
+        }
+    }
+
     fn post_build(&self, ctx: &Context) -> Result<Report, Error> {
         let glob_options = glob::MatchOptions {
             require_literal_separator: true,
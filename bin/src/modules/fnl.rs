@@ -8,7 +8,7 @@ use hemtt_workspace::reporting::{Code, Severity};
 
 use crate::{context::Context, report::Report};
 
-use super::Module;
+use super::{Access, Module, Resource};
 
 pub const TEXT_EXTENSIONS: [&str; 7] = ["sqf", "txt", "hpp", "cpp", "rvmat", "ext", "inc"];
 
@@ -20,6 +20,13 @@ impl Module for FineNewLineCheck {
         "Final New Line Check"
     }
 
+    fn access(&self, phase: &str) -> Access {
+        match phase {
+            "check" => Access::new().with_reads(&[Resource::Sqf, Resource::Config]),
+            _ => Access::new(),
+        }
+    }
+
     fn check(&self, ctx: &Context) -> Result<Report, crate::Error> {
         fn files_to_check(root: &PathBuf) -> Vec<PathBuf> {
             walkdir::WalkDir::new(root)
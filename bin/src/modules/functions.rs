@@ -0,0 +1,313 @@
+use std::{
+    collections::BTreeMap,
+    fmt::Write as _,
+    io::Write as _,
+    sync::{Arc, LazyLock},
+};
+
+use hemtt_preprocessor::Processor;
+use hemtt_workspace::{
+    WorkspacePath,
+    addons::Addon,
+    reporting::{Code, Diagnostic},
+};
+use regex::Regex;
+
+use crate::{context::Context, error::Error, report::Report};
+
+use super::{Access, Module, Resource};
+
+/// Attributes that can be set with a marker in a function's header comment, `@preInit`
+const MARKERS: [&str; 3] = ["preInit", "postInit", "preStart"];
+
+/// A `CfgFunctions` class with a body, not only declared
+static DEFINED: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)\bclass\s+CfgFunctions\s*(:\s*\w+\s*)?\{").expect("valid regex")
+});
+
+#[derive(Default)]
+/// Generates `CfgFunctions` from the `fnc_*.sqf` files in each addon
+pub struct Functions;
+
+impl Module for Functions {
+    fn name(&self) -> &'static str {
+        "Functions"
+    }
+    fn priority(&self) -> i32 {
+        // Before the configs are rapified
+        1000
+    }
+
+    fn access(&self, phase: &str) -> Access {
+        match phase {
+            "pre_build" => Access::new()
+                .with_reads(&[Resource::Sqf])
+                .with_writes(&[Resource::Config]),
+            _ => Access::new(),
+        }
+    }
+
+    fn pre_build(&self, ctx: &Context) -> Result<Report, Error> {
+        let config = ctx.config().functions();
+        if !config.enabled() {
+            return Ok(Report::new());
+        }
+        let mut report = Report::new();
+        let mut total = 0;
+        for addon in ctx.addons() {
+            let functions = functions(ctx, addon)?;
+            if functions.is_empty() {
+                continue;
+            }
+            let path = ctx
+                .workspace_path()
+                .join(addon.folder())?
+                .join("config.cpp")?;
+            let source = path.read_to_string()?;
+            // Includes are followed, `CfgFunctions.hpp` is a common place for it
+            let defined = Processor::run(&path).map_or_else(
+                |_| DEFINED.is_match(&source),
+                |processed| DEFINED.is_match(processed.as_str()),
+            );
+            if defined {
+                report.push(AlreadyDefined::code(addon.name().to_string()));
+                continue;
+            }
+            total += functions.values().map(Vec::len).sum::<usize>();
+            let tag = config.tag(ctx.config().prefix(), addon.name());
+            let generated = generate(&tag, &functions);
+            // Appended, so diagnostics for the rest of the config keep their lines
+            let mut file = path.create_file()?;
+            file.write_all(format!("{}\n{generated}", source.trim_end()).as_bytes())?;
+            debug!("generated CfgFunctions for {}", addon.name());
+        }
+        info!("Generated CfgFunctions for {total} functions");
+        Ok(report)
+    }
+}
+
+pub(super) struct Function {
+    pub(super) name: String,
+    pub(super) path: WorkspacePath,
+    pub(super) file: String,
+    pub(super) attributes: Vec<&'static str>,
+}
+
+/// The functions of an addon, by category
+pub(super) fn functions(
+    ctx: &Context,
+    addon: &Addon,
+) -> Result<BTreeMap<String, Vec<Function>>, Error> {
+    let config = ctx.config().functions();
+    let root = ctx.workspace_path().join(addon.folder())?;
+    let folder = root.join(config.folder())?;
+    let mut functions: BTreeMap<String, Vec<Function>> = BTreeMap::new();
+    if !folder.exists()? {
+        return Ok(functions);
+    }
+    let mut entries = folder.walk_dir()?;
+    entries.sort_by(|a, b| a.as_str().cmp(b.as_str()));
+    for entry in entries {
+        let filename = entry.filename();
+        let Some(name) = function_name(&filename) else {
+            continue;
+        };
+        if !entry.is_file()? {
+            continue;
+        }
+        let subfolder = entry.parent().as_str()[folder.as_str().len()..]
+            .trim_start_matches('/')
+            .to_string();
+        let category = config.category(
+            addon.name(),
+            Some(subfolder.as_str()).filter(|subfolder| !subfolder.is_empty()),
+        );
+        functions.entry(category).or_default().push(Function {
+            name: name.to_string(),
+            file: format!(
+                "\\{}{}",
+                addon.prefix(),
+                entry.as_str()[root.as_str().len()..].replace('/', "\\")
+            ),
+            attributes: markers(&entry)?,
+            path: entry,
+        });
+    }
+    Ok(functions)
+}
+
+/// `fnc_name.sqf` to `name`
+fn function_name(filename: &str) -> Option<&str> {
+    let (stem, extension) = filename.rsplit_once('.')?;
+    if !extension.eq_ignore_ascii_case("sqf") {
+        return None;
+    }
+    stem.get(..4)
+        .filter(|start| start.eq_ignore_ascii_case("fnc_"))
+        .map(|_| &stem[4..])
+        .filter(|name| !name.is_empty())
+}
+
+/// The markers in the comments at the start of a function
+fn markers(path: &WorkspacePath) -> Result<Vec<&'static str>, Error> {
+    let source = path.read_to_string()?;
+    let mut header = String::new();
+    let mut block = false;
+    for line in source.lines() {
+        let trimmed = line.trim();
+        if block {
+            header.push_str(trimmed);
+            header.push('\n');
+            block = !trimmed.contains("*/");
+        } else if trimmed.starts_with("//") || trimmed.starts_with("#include") {
+            header.push_str(trimmed);
+            header.push('\n');
+        } else if trimmed.starts_with("/*") {
+            header.push_str(trimmed);
+            header.push('\n');
+            block = !trimmed.contains("*/");
+        } else if !trimmed.is_empty() {
+            break;
+        }
+    }
+    Ok(MARKERS
+        .into_iter()
+        .filter(|marker| {
+            header
+                .split(|c: char| c.is_whitespace() || c == '*')
+                .any(|word| {
+                    word.strip_prefix('@')
+                        .is_some_and(|word| word.eq_ignore_ascii_case(marker))
+                })
+        })
+        .collect())
+}
+
+fn generate(tag: &str, functions: &BTreeMap<String, Vec<Function>>) -> String {
+    let mut out = String::from("class CfgFunctions {\n");
+    let _ = writeln!(out, "    class {tag} {{");
+    for (category, functions) in functions {
+        let _ = writeln!(out, "        class {category} {{");
+        for function in functions {
+            let _ = writeln!(out, "            class {} {{", function.name);
+            let _ = writeln!(out, "                file = \"{}\";", function.file);
+            for attribute in &function.attributes {
+                let _ = writeln!(out, "                {attribute} = 1;");
+            }
+            out.push_str("            };\n");
+        }
+        out.push_str("        };\n");
+    }
+    out.push_str("    };\n};\n");
+    out
+}
+
+pub struct AlreadyDefined {
+    addon: String,
+}
+impl Code for AlreadyDefined {
+    fn ident(&self) -> &'static str {
+        "BFE1"
+    }
+
+    fn message(&self) -> String {
+        format!(
+            "Addon `{}` already defines `CfgFunctions`, it was not generated",
+            self.addon
+        )
+    }
+
+    fn help(&self) -> Option<String> {
+        Some("remove `CfgFunctions` from the addon's config, or disable generation with `functions.enabled = false`".to_string())
+    }
+
+    fn diagnostic(&self) -> Option<Diagnostic> {
+        Some(Diagnostic::from_code(self))
+    }
+}
+
+impl AlreadyDefined {
+    #[must_use]
+    pub fn code(addon: String) -> Arc<dyn Code> {
+        Arc::new(Self { addon })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use sealed_test::prelude::*;
+
+    use super::{Functions, function_name};
+    use crate::{
+        context::{Context, PreservePrevious},
+        modules::Module,
+    };
+
+    #[test]
+    fn names() {
+        assert_eq!(function_name("fnc_spawn.sqf"), Some("spawn"));
+        assert_eq!(function_name("FNC_Spawn.SQF"), Some("Spawn"));
+        assert_eq!(function_name("fnc_.sqf"), None);
+        assert_eq!(function_name("fnc_spawn.hpp"), None);
+        assert_eq!(function_name("spawn.sqf"), None);
+        assert_eq!(function_name("fnc_spawn"), None);
+    }
+
+    #[sealed_test]
+    fn generated() {
+        std::env::set_current_dir(format!("{}/tests/echo", env!("CARGO_MANIFEST_DIR"))).unwrap();
+        let ctx = Context::new(Some("build"), PreservePrevious::Remove, false).unwrap();
+        let report = Functions.pre_build(&ctx).unwrap();
+        let errors = report.errors();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].ident(), "BFE1");
+        assert!(errors[0].message().contains("`legacy`"));
+        // left as it was
+        let legacy = ctx
+            .workspace_path()
+            .join("addons/legacy/config.cpp")
+            .unwrap()
+            .read_to_string()
+            .unwrap();
+        assert!(legacy.ends_with("#include \"CfgFunctions.hpp\"\n"));
+
+        let config = ctx
+            .workspace_path()
+            .join("addons/main/config.cpp")
+            .unwrap()
+            .read_to_string()
+            .unwrap();
+        let (_, generated) = config.split_once("};\n};\n").unwrap();
+        assert_eq!(
+            generated,
+            r#"class CfgFunctions {
+    class echo_main {
+        class Core {
+            class init {
+                file = "\z\echo\addons\main\functions\fnc_init.sqf";
+                preInit = 1;
+                postInit = 1;
+            };
+            class notes {
+                file = "\z\echo\addons\main\functions\fnc_notes.sqf";
+                preStart = 1;
+            };
+        };
+        class vehicles {
+            class Spawn {
+                file = "\z\echo\addons\main\functions\vehicles\FNC_Spawn.sqf";
+            };
+        };
+        class vehicles_land {
+            class drive {
+                file = "\z\echo\addons\main\functions\vehicles\land\fnc_drive.sqf";
+            };
+        };
+    };
+};
+"#
+        );
+    }
+}
@@ -0,0 +1,226 @@
+use std::{cell::OnceCell, path::PathBuf, rc::Rc};
+
+use hemtt_config::database::Database;
+use hemtt_stringtable::{Package, Project};
+use hemtt_workspace::{
+    WorkspacePath,
+    addons::{Addon, Location},
+};
+use rhai::{
+    Map,
+    plugin::{
+        Dynamic, FnNamespace, FuncRegistration, ImmutableString, Module, NativeCallContext,
+        PluginFunc, RhaiResult, TypeId, export_module, mem,
+    },
+};
+
+use crate::{context::Context, report::Report, utils::config::addon_config};
+
+use super::config;
+
+#[allow(clippy::module_name_repetitions)]
+#[derive(Debug, Clone)]
+pub struct RhaiAddon {
+    ctx: Rc<Context>,
+    addon: Addon,
+    database: Rc<OnceCell<Result<Database, String>>>,
+}
+
+impl RhaiAddon {
+    pub fn new(ctx: Rc<Context>, addon: Addon) -> Self {
+        Self {
+            ctx,
+            addon,
+            database: Rc::new(OnceCell::new()),
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        self.addon.name()
+    }
+
+    /// The addon's own `config.cpp`, parsed on first use
+    fn database(&self) -> Result<&Database, String> {
+        self.database
+            .get_or_init(|| {
+                let mut report = Report::new();
+                config::load(
+                    addon_config(&self.ctx, &self.addon, &mut report)
+                        .map(|configs| (Database::new(configs.into_iter().collect()), report)),
+                )
+            })
+            .as_ref()
+            .map_err(Clone::clone)
+    }
+
+    /// The built PBO, in the build output
+    fn pbo_path(&self) -> Option<PathBuf> {
+        let target = self.ctx.build_folder()?;
+        let pbo = format!("{}.pbo", self.addon.pbo_name(self.ctx.config().prefix()));
+        let mut paths = vec![target.join("addons").join(&pbo)];
+        if self.addon.location() == &Location::Optionals {
+            paths.insert(0, target.join("optionals").join(&pbo));
+            paths.insert(
+                0,
+                target
+                    .join("optionals")
+                    .join(format!(
+                        "@{}",
+                        self.addon
+                            .pbo_name(self.ctx.config().hemtt().release().folder())
+                    ))
+                    .join("addons")
+                    .join(&pbo),
+            );
+        }
+        paths.into_iter().find(|path| path.is_file())
+    }
+
+    /// The keys of the addon's `stringtable.xml`, empty if it has none
+    ///
+    /// Read from the project, the build replaces it with `stringtable.bin`
+    fn stringtable(&self) -> Result<Map, String> {
+        let path = self
+            .ctx
+            .project_folder()
+            .join(self.addon.folder())
+            .join("stringtable.xml");
+        if !path.is_file() {
+            return Ok(Map::new());
+        }
+        let project = WorkspacePath::slim_file(&path)
+            .map_err(|e| e.to_string())
+            .and_then(|file| Project::read(file).map_err(|e| e.to_string()))
+            .map_err(|e| format!("{}: {e}", path.display()))?;
+        let mut keys = Map::new();
+        for package in project.packages() {
+            package_keys(package, &mut keys);
+        }
+        Ok(keys)
+    }
+}
+
+fn package_keys(package: &Package, keys: &mut Map) {
+    for key in package.keys() {
+        keys.insert(
+            key.id().into(),
+            Dynamic::from_map(
+                key.translations()
+                    .into_iter()
+                    .map(|(language, value)| (language.into(), value.into()))
+                    .collect::<Map>(),
+            ),
+        );
+    }
+    for container in package.containers() {
+        package_keys(container, keys);
+    }
+}
+
+#[allow(clippy::needless_pass_by_ref_mut)]
+#[allow(clippy::missing_const_for_fn)] // rhai functions can not be const
+#[allow(clippy::unwrap_used)] // coming from rhai codegen
+#[export_module]
+pub mod addon_functions {
+    use hemtt_common::config::AddonConfig;
+    use rhai::{Array, EvalAltResult};
+
+    use crate::modules::hook::libraries::{addon::RhaiAddon, pbo::RhaiPbo};
+
+    #[rhai_fn(global, pure)]
+    pub fn name(addon: &mut RhaiAddon) -> String {
+        addon.addon.name().to_string()
+    }
+
+    #[rhai_fn(global, pure)]
+    pub fn location(addon: &mut RhaiAddon) -> String {
+        addon.addon.location().to_string()
+    }
+
+    #[rhai_fn(global, pure)]
+    pub fn folder(addon: &mut RhaiAddon) -> String {
+        addon.addon.folder()
+    }
+
+    #[rhai_fn(global, pure)]
+    pub fn prefix(addon: &mut RhaiAddon) -> String {
+        addon.addon.prefix().to_string()
+    }
+
+    #[rhai_fn(global, pure)]
+    pub fn pbo_name(addon: &mut RhaiAddon) -> String {
+        addon.addon.pbo_name(addon.ctx.config().prefix())
+    }
+
+    #[rhai_fn(global, pure)]
+    pub fn config(addon: &mut RhaiAddon) -> Dynamic {
+        addon
+            .addon
+            .config()
+            .cloned()
+            .map_or(Dynamic::UNIT, Dynamic::from)
+    }
+
+    #[rhai_fn(global, pure, return_raw)]
+    pub fn patches(addon: &mut RhaiAddon) -> Result<Array, Box<EvalAltResult>> {
+        let database = addon.database()?;
+        Ok(database
+            .addons()
+            .iter()
+            .flat_map(|config| config.patches())
+            .map(|patch| Dynamic::from(patch.as_str().to_string()))
+            .collect())
+    }
+
+    #[rhai_fn(global, pure, return_raw)]
+    pub fn config_value(addon: &mut RhaiAddon, query: &str) -> Result<Dynamic, Box<EvalAltResult>> {
+        Ok(super::config::query(addon.database()?, query))
+    }
+
+    #[rhai_fn(global, pure, return_raw)]
+    pub fn stringtable(addon: &mut RhaiAddon) -> Result<Map, Box<EvalAltResult>> {
+        addon.stringtable().map_err(Into::into)
+    }
+
+    #[rhai_fn(global, pure, return_raw)]
+    pub fn pbo(addon: &mut RhaiAddon) -> Result<RhaiPbo, Box<EvalAltResult>> {
+        let Some(path) = addon.pbo_path() else {
+            return Err(format!(
+                "{} has not been built",
+                addon.addon.pbo_name(addon.ctx.config().prefix())
+            )
+            .into());
+        };
+        RhaiPbo::open(&path).map_err(Into::into)
+    }
+
+    #[rhai_fn(global, pure)]
+    pub fn rapify(config: &mut AddonConfig) -> bool {
+        config.rapify().enabled()
+    }
+
+    #[rhai_fn(global, pure)]
+    pub fn binarize(config: &mut AddonConfig) -> bool {
+        config.binarize().enabled()
+    }
+
+    #[rhai_fn(global, pure)]
+    pub fn exclude(config: &mut AddonConfig) -> Array {
+        config
+            .files()
+            .exclude()
+            .iter()
+            .cloned()
+            .map(Dynamic::from)
+            .collect()
+    }
+
+    #[rhai_fn(global, pure)]
+    pub fn properties(config: &mut AddonConfig) -> Map {
+        config
+            .properties()
+            .iter()
+            .map(|(key, value)| (key.into(), value.clone().into()))
+            .collect()
+    }
+}
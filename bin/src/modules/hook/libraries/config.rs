@@ -0,0 +1,67 @@
+use hemtt_config::{
+    Item, Number, Value,
+    database::{Database, Found},
+};
+use rhai::{Array, Dynamic, Map};
+
+use crate::{Error, report::Report};
+
+/// The value at a path of the config, `CfgPatches >> abe_main >> units`
+///
+/// Classes are a map of their effective properties, `()` if nothing is found
+pub fn query(database: &Database, query: &str) -> Dynamic {
+    match database.query(query) {
+        Some(Found::Property(property)) => value(property.value()),
+        Some(Found::Class(class)) => Dynamic::from_map(
+            class
+                .properties()
+                .iter()
+                .map(|property| (property.name().into(), value(property.value())))
+                .collect::<Map>(),
+        ),
+        None => Dynamic::UNIT,
+    }
+}
+
+fn value(value: &Value) -> Dynamic {
+    match value {
+        Value::Str(value) => value.value().into(),
+        Value::Number(number) => self::number(number),
+        Value::Expression(expression) => expression.to_string().into(),
+        Value::Array(array) | Value::UnexpectedArray(array) => {
+            Dynamic::from_array(array.items().iter().map(item).collect::<Array>())
+        }
+        Value::Invalid(_) => Dynamic::UNIT,
+    }
+}
+
+fn item(item: &Item) -> Dynamic {
+    match item {
+        Item::Str(value) => value.value().into(),
+        Item::Number(number) => self::number(number),
+        Item::Array(items) => Dynamic::from_array(items.iter().map(self::item).collect::<Array>()),
+        Item::Invalid(_) => Dynamic::UNIT,
+    }
+}
+
+fn number(number: &Number) -> Dynamic {
+    match number {
+        Number::Int32 { value, .. } => i64::from(*value).into(),
+        Number::Int64 { value, .. } => (*value).into(),
+        // Through the decimal text, `2.14` is not `2.140000104904175`
+        Number::Float32 { value, .. } => value
+            .to_string()
+            .parse()
+            .unwrap_or_else(|_| f64::from(*value))
+            .into(),
+    }
+}
+
+/// The database, or the first error of the report if a config could not be parsed
+pub fn load(loaded: Result<(Database, Report), Error>) -> Result<Database, String> {
+    let (database, report) = loaded.map_err(|e| e.to_string())?;
+    report
+        .errors()
+        .first()
+        .map_or_else(|| Ok(database), |code| Err(code.message()))
+}
@@ -1,6 +1,9 @@
 use rhai::{combine_with_exported_module, def_package};
 
+mod addon;
+mod config;
 pub mod hemtt;
+mod pbo;
 mod project;
 mod rfs;
 mod version;
@@ -11,6 +14,8 @@ def_package! {
         combine_with_exported_module!(lib, "hemtt", hemtt::project_functions);
         combine_with_exported_module!(lib, "hemtt_version", version::version_functions);
         combine_with_exported_module!(lib, "hemtt_project", project::project_functions);
+        combine_with_exported_module!(lib, "hemtt_addon", addon::addon_functions);
+        combine_with_exported_module!(lib, "hemtt_pbo", pbo::pbo_functions);
     }
 }
 
@@ -0,0 +1,81 @@
+use std::{fs::File, path::Path};
+
+use hemtt_pbo::ReadablePbo;
+use rhai::plugin::{
+    Dynamic, FnNamespace, FuncRegistration, ImmutableString, Module, NativeCallContext, PluginFunc,
+    RhaiResult, TypeId, export_module, mem,
+};
+
+#[allow(clippy::module_name_repetitions)]
+#[derive(Debug, Clone)]
+/// The files and properties of a built PBO
+pub struct RhaiPbo {
+    files: Vec<String>,
+    properties: Vec<(String, String)>,
+}
+
+impl RhaiPbo {
+    pub fn open(path: &Path) -> Result<Self, String> {
+        let file = File::open(path).map_err(|e| format!("{}: {e}", path.display()))?;
+        let pbo = ReadablePbo::from(file).map_err(|e| format!("{}: {e}", path.display()))?;
+        Ok(Self {
+            files: pbo
+                .files()
+                .iter()
+                .map(|header| header.filename().to_string())
+                .collect(),
+            properties: pbo
+                .properties()
+                .iter()
+                .map(|(key, value)| (key.clone(), value.clone()))
+                .collect(),
+        })
+    }
+}
+
+#[allow(clippy::needless_pass_by_ref_mut)]
+#[allow(clippy::ptr_arg)]
+#[allow(clippy::unwrap_used)] // coming from rhai codegen
+#[export_module]
+pub mod pbo_functions {
+    use std::path::PathBuf;
+
+    use rhai::{Array, EvalAltResult, Map};
+
+    use crate::modules::hook::libraries::pbo::RhaiPbo;
+
+    #[rhai_fn(global, return_raw)]
+    pub fn open_pbo(path: &mut PathBuf) -> Result<RhaiPbo, Box<EvalAltResult>> {
+        RhaiPbo::open(path).map_err(Into::into)
+    }
+
+    #[rhai_fn(global, pure)]
+    pub fn files(pbo: &mut RhaiPbo) -> Array {
+        pbo.files.iter().cloned().map(Dynamic::from).collect()
+    }
+
+    #[rhai_fn(global, pure)]
+    pub fn has_file(pbo: &mut RhaiPbo, name: &str) -> bool {
+        let name = name.replace('/', "\\");
+        pbo.files
+            .iter()
+            .any(|file| file.eq_ignore_ascii_case(&name))
+    }
+
+    #[rhai_fn(global, pure)]
+    pub fn properties(pbo: &mut RhaiPbo) -> Map {
+        pbo.properties
+            .iter()
+            .map(|(key, value)| (key.into(), value.clone().into()))
+            .collect()
+    }
+
+    #[rhai_fn(global, pure)]
+    pub fn prefix(pbo: &mut RhaiPbo) -> String {
+        pbo.properties
+            .iter()
+            .find(|(key, _)| key == "prefix")
+            .map(|(_, value)| value.clone())
+            .unwrap_or_default()
+    }
+}
@@ -1,10 +1,15 @@
+use std::{cell::OnceCell, rc::Rc};
+
 use hemtt_common::version::Version;
+use hemtt_config::database::Database;
 use rhai::plugin::{
-    Dynamic, FnNamespace, FuncRegistration, Module, NativeCallContext, PluginFunc, RhaiResult,
-    TypeId, export_module,
+    Dynamic, FnNamespace, FuncRegistration, ImmutableString, Module, NativeCallContext, PluginFunc,
+    RhaiResult, TypeId, export_module, mem,
 };
 
-use crate::context::Context;
+use crate::{context::Context, utils::config::database};
+
+use super::{addon::RhaiAddon, config};
 
 #[allow(clippy::module_name_repetitions)]
 #[derive(Debug, Clone)]
@@ -14,11 +19,14 @@ pub struct RhaiProject {
     prefix: String,
     mainprefix: String,
     version: Version,
-    // addons: Vec<Addon>,
+    addons: Vec<RhaiAddon>,
+    ctx: Rc<Context>,
+    database: Rc<OnceCell<Result<Database, String>>>,
 }
 
 impl RhaiProject {
     pub fn new(ctx: &Context) -> Self {
+        let shared = Rc::new(ctx.clone());
         Self {
             name: ctx.config().name().to_string(),
             author: ctx
@@ -35,16 +43,32 @@ impl RhaiProject {
                 .version()
                 .get(ctx.workspace_path().vfs())
                 .expect("version config is valid to get to rhai module"),
-            // addons: ctx.addons().to_vec(),
+            addons: ctx
+                .addons()
+                .iter()
+                .map(|addon| RhaiAddon::new(shared.clone(), addon.clone()))
+                .collect(),
+            ctx: shared,
+            database: Rc::new(OnceCell::new()),
         }
     }
+
+    /// Every addon's `config.cpp` merged on top of the baseline, parsed on first use
+    fn database(&self) -> Result<&Database, String> {
+        self.database
+            .get_or_init(|| config::load(database(&self.ctx)))
+            .as_ref()
+            .map_err(Clone::clone)
+    }
 }
 
 #[allow(clippy::needless_pass_by_ref_mut)]
 #[allow(clippy::unwrap_used)] // coming from rhai codegen
 #[export_module]
 pub mod project_functions {
-    use crate::modules::hook::libraries::project::RhaiProject;
+    use rhai::{Array, EvalAltResult};
+
+    use crate::modules::hook::libraries::{addon::RhaiAddon, project::RhaiProject};
 
     #[rhai_fn(global, pure)]
     pub fn name(project: &mut RhaiProject) -> String {
@@ -71,9 +95,26 @@ pub mod project_functions {
         project.version.clone()
     }
 
-    // TODO: Add functions to addons
-    // #[rhai_fn(global, pure)]
-    // pub fn addons(project: &mut RhaiProject) -> Vec<Addon> {
-    //     project.addons.clone()
-    // }
+    #[rhai_fn(global, pure)]
+    pub fn addons(project: &mut RhaiProject) -> Array {
+        project.addons.iter().cloned().map(Dynamic::from).collect()
+    }
+
+    #[rhai_fn(global, pure, return_raw)]
+    pub fn addon(project: &mut RhaiProject, name: &str) -> Result<RhaiAddon, Box<EvalAltResult>> {
+        project
+            .addons
+            .iter()
+            .find(|addon| addon.name() == name)
+            .cloned()
+            .ok_or_else(|| format!("Addon not found: {name}").into())
+    }
+
+    #[rhai_fn(global, pure, return_raw)]
+    pub fn config_value(
+        project: &mut RhaiProject,
+        query: &str,
+    ) -> Result<Dynamic, Box<EvalAltResult>> {
+        Ok(super::config::query(project.database()?, query))
+    }
 }
@@ -1,6 +1,7 @@
 use std::sync::{Arc, Mutex};
 
 use ::rhai::{Engine, Scope, packages::Package};
+use hemtt_common::changelog::Changelog;
 use hemtt_workspace::WorkspacePath;
 use rhai::Dynamic;
 
@@ -14,7 +15,7 @@ use self::{
     libraries::hemtt::RhaiHemtt,
 };
 
-use super::Module;
+use super::{Access, Module};
 
 mod error;
 mod libraries;
@@ -45,10 +46,19 @@ pub fn scope(ctx: &Context, vfs: bool) -> Result<Scope, Error> {
 
     scope.push_constant("HEMTT", RhaiHemtt::new(ctx));
 
+    // Empty outside of a git repository
+    let version = ctx.config().version().get(ctx.workspace_path().vfs())?;
+    scope.push_constant(
+        "HEMTT_CHANGELOG",
+        Changelog::discover(ctx.project_folder())
+            .map(|changelog| changelog.to_markdown(&version))
+            .unwrap_or_default(),
+    );
+
     Ok(scope)
 }
 
-fn engine(vfs: bool) -> Engine {
+fn engine(ctx: &Context, vfs: bool) -> Engine {
     let mut engine = Engine::new();
     if vfs {
         let virt = libraries::VfsPackage::new();
@@ -56,7 +66,10 @@ fn engine(vfs: bool) -> Engine {
     }
     engine.register_static_module("hemtt_rfs", libraries::RfsPackage::new().as_shared_module());
     engine.register_static_module("hemtt", libraries::HEMTTPackage::new().as_shared_module());
-    engine.register_fn("date", time::date);
+    let source_date_epoch = ctx.config().runtime().source_date_epoch();
+    engine.register_fn("date", move |format: &str| {
+        time::date(format, source_date_epoch)
+    });
     engine
 }
 
@@ -135,7 +148,7 @@ impl Hooks {
     #[allow(clippy::needless_pass_by_value)] // rhai things
     fn run(ctx: &Context, path: WorkspacePath, vfs: bool) -> Result<(Report, Dynamic), Error> {
         let mut report = Report::new();
-        let mut engine = engine(vfs);
+        let mut engine = engine(ctx, vfs);
         let mut scope = scope(ctx, vfs)?;
         let told_to_fail = Arc::new(Mutex::new(false));
         let parts = path.as_str().split('/');
@@ -192,12 +205,20 @@ impl Module for Hooks {
         "Hooks"
     }
 
+    fn access(&self, phase: &str) -> Access {
+        if !self.0 || phase == "check" {
+            return Access::new();
+        }
+        // Scripts can touch anything
+        Access::exclusive()
+    }
+
     fn init(&mut self, ctx: &Context) -> Result<Report, Error> {
         let mut report = Report::new();
         self.0 = ctx.hemtt_folder().join("hooks").exists();
         if self.0 {
             for phase in &["pre_build", "post_build", "pre_release", "post_release"] {
-                let engine = engine(phase != &"post_release");
+                let engine = engine(ctx, phase != &"post_release");
                 let dir = ctx.hemtt_folder().join("hooks").join(phase);
                 if !dir.exists() {
                     continue;
@@ -3,8 +3,15 @@ use std::time::SystemTime;
 use rhai::EvalAltResult;
 use time::{OffsetDateTime, format_description};
 
-pub fn date(format: &str) -> Result<String, Box<EvalAltResult>> {
-    let now: OffsetDateTime = SystemTime::now().into();
+/// Format the current time, or `SOURCE_DATE_EPOCH` for reproducible output
+pub fn date(format: &str, source_date_epoch: Option<u32>) -> Result<String, Box<EvalAltResult>> {
+    let now: OffsetDateTime = source_date_epoch.map_or_else(
+        || SystemTime::now().into(),
+        |epoch| {
+            OffsetDateTime::from_unix_timestamp(i64::from(epoch))
+                .expect("a u32 timestamp is always in range")
+        },
+    );
     let fmt = format_description::parse(format).map_err(|e| e.to_string())?;
     Ok(now.format(&fmt).map_err(|e| e.to_string())?)
 }
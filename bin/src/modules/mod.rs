@@ -1,36 +1,136 @@
+use hemtt_workspace::addons::Addon;
+
 use crate::{context::Context, error::Error, report::Report};
 
 mod binarize;
 mod file_patching;
 mod files;
+mod functions;
 mod new;
 mod rapifier;
 mod sqf;
 mod stringtables;
+mod workshop;
 
 pub mod archive;
 pub mod bom;
+pub mod docs;
 pub mod fnl;
 pub mod hook;
 pub mod pbo;
 pub(crate) mod sign;
 
 pub use binarize::Binarize;
+pub use docs::Docs;
 pub use file_patching::FilePatching;
 pub use files::Files;
+pub use functions::Functions;
 pub use hook::Hooks;
 pub use new::Licenses;
 pub use rapifier::{AddonConfigs, Rapifier};
 pub use sign::Sign;
 pub use sqf::SQFCompiler;
 pub use stringtables::Stringtables;
+pub use workshop::Workshop;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// Something shared between modules, that a module reads or writes during a phase
+pub enum Resource {
+    /// The `.sqf` files of the addons and their compiled `.sqfc`
+    Sqf,
+    /// The config files of the addons and their rapified `.bin`
+    Config,
+    /// The `stringtable.xml` files and their `stringtable.bin`
+    Stringtables,
+    /// The outputs of binarize.exe
+    Binarized,
+    /// The build and release folders
+    Output,
+    /// [`BuildData::required_version`](hemtt_workspace::addons::BuildData::required_version)
+    RequiredVersion,
+    /// [`BuildData::localizations`](hemtt_workspace::addons::BuildData::localizations)
+    Localizations,
+    /// [`BuildData::functions_defined`](hemtt_workspace::addons::BuildData::functions_defined)
+    FunctionsDefined,
+    /// [`BuildData::functions_used`](hemtt_workspace::addons::BuildData::functions_used)
+    FunctionsUsed,
+    /// [`BuildData::magazine_well_info`](hemtt_workspace::addons::BuildData::magazine_well_info)
+    MagazineWells,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+/// The resources a module touches during a phase
+///
+/// The executor runs modules of the same phase concurrently when their access does not conflict
+pub struct Access {
+    reads: Vec<Resource>,
+    writes: Vec<Resource>,
+    exclusive: bool,
+}
+
+impl Access {
+    #[must_use]
+    /// Touches nothing that another module uses
+    pub const fn new() -> Self {
+        Self {
+            reads: Vec::new(),
+            writes: Vec::new(),
+            exclusive: false,
+        }
+    }
 
-pub trait Module {
+    #[must_use]
+    /// Might touch anything, runs after and before every other module
+    pub const fn exclusive() -> Self {
+        Self {
+            reads: Vec::new(),
+            writes: Vec::new(),
+            exclusive: true,
+        }
+    }
+
+    #[must_use]
+    pub fn with_reads(mut self, reads: &[Resource]) -> Self {
+        self.reads.extend_from_slice(reads);
+        self
+    }
+
+    #[must_use]
+    pub fn with_writes(mut self, writes: &[Resource]) -> Self {
+        self.writes.extend_from_slice(writes);
+        self
+    }
+
+    #[must_use]
+    /// Does one of the two need to wait for the other
+    pub fn conflicts(&self, other: &Self) -> bool {
+        self.exclusive
+            || other.exclusive
+            || self
+                .writes
+                .iter()
+                .any(|r| other.reads.contains(r) || other.writes.contains(r))
+            || other.writes.iter().any(|r| self.reads.contains(r))
+    }
+}
+
+pub trait Module: Send + Sync {
     fn name(&self) -> &'static str;
     /// priority (lower goes first)
     fn priority(&self) -> i32 {
         0
     }
+    /// The resources the module touches during a phase
+    ///
+    /// `pre_build_addon` is asked for separately from `pre_build`.
+    /// Defaults to exclusive, so the module never runs alongside another
+    fn access(&self, _phase: &str) -> Access {
+        Access::exclusive()
+    }
+    /// Does the module implement [`Module::pre_build_addon`]
+    fn pre_build_per_addon(&self) -> bool {
+        false
+    }
     /// Executes the module's `init` phase
     ///
     /// # Errors
@@ -45,6 +145,16 @@ pub trait Module {
     fn check(&self, _ctx: &Context) -> Result<Report, Error> {
         Ok(Report::new())
     }
+    /// Executes the module's `pre_build` phase for one addon
+    ///
+    /// Only called if [`Module::pre_build_per_addon`] is true, for every addon before `pre_build`.
+    /// Addons of the same module can run concurrently
+    ///
+    /// # Errors
+    /// Any error that the module encounters
+    fn pre_build_addon(&self, _ctx: &Context, _addon: &Addon) -> Result<Report, Error> {
+        Ok(Report::new())
+    }
     /// Executes the module's `pre_build` phase
     ///
     /// # Errors
@@ -81,3 +191,27 @@ pub trait Module {
         Ok(Report::new())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{Access, Resource};
+
+    #[test]
+    fn conflicts() {
+        let reads = Access::new().with_reads(&[Resource::Config]);
+        let writes = Access::new().with_writes(&[Resource::Config]);
+        let other = Access::new().with_writes(&[Resource::Sqf]);
+        for (a, b, conflicts) in [
+            (&Access::new(), &Access::new(), false),
+            (&Access::exclusive(), &Access::new(), true),
+            (&reads, &reads, false),
+            (&reads, &writes, true),
+            (&writes, &writes, true),
+            (&writes, &other, false),
+            (&reads, &other, false),
+        ] {
+            assert_eq!(a.conflicts(b), conflicts, "{a:?} and {b:?}");
+            assert_eq!(b.conflicts(a), conflicts, "{b:?} and {a:?}");
+        }
+    }
+}
@@ -12,7 +12,7 @@ use hemtt_pbo::WritablePbo;
 use hemtt_workspace::addons::{Addon, Location};
 use vfs::VfsFileType;
 
-use crate::{context::Context, error::Error, progress::progress_bar, report::Report};
+use crate::{context::Context, error::Error, progress::progress_bar, report::Report, timings};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 /// Should the optional and compat PBOs be collapsed into the addons folder
@@ -47,7 +47,9 @@ pub fn build(ctx: &Context, collapse: Collapse) -> Result<Report, Error> {
         .to_vec()
         .iter()
         .map(|addon| {
-            internal_build(ctx, addon, collapse, &version, git_hash.as_deref())?;
+            timings::time("build", "PBO", Some(addon.name()), || {
+                internal_build(ctx, addon, collapse, &version, git_hash.as_deref())
+            })?;
             progress.inc(1);
             counter.fetch_add(1, Ordering::Relaxed);
             Ok(())
@@ -112,6 +114,9 @@ fn internal_build(
         target_pbo.display()
     );
 
+    if let Some(timestamp) = ctx.config().runtime().source_date_epoch() {
+        pbo.timestamp(timestamp);
+    }
     pbo.add_property("hemtt", env!("HEMTT_VERSION"));
     pbo.add_property("version", version.to_string());
 
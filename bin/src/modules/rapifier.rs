@@ -2,28 +2,31 @@ use std::{
     collections::HashMap,
     path::PathBuf,
     sync::{
-        RwLock,
+        Arc, Mutex, OnceLock, RwLock,
         atomic::{AtomicU16, Ordering},
     },
 };
 
 use hemtt_config::{
     Config,
-    analyze::{lint_all, lint_check},
+    analyze::{lint_all, lint_check, lint_database},
+    database::{AddonConfig, Database, read_baseline},
     parse,
     rapify::Rapify,
+    schema::Schema,
 };
 use hemtt_preprocessor::Processor;
 use hemtt_workspace::{
     WorkspacePath,
     addons::{Addon, Location},
 };
+use indicatif::ProgressBar;
 use rayon::prelude::{IntoParallelRefIterator, ParallelIterator};
 use vfs::VfsFileType;
 
 use crate::{context::Context, error::Error, progress::progress_bar, report::Report};
 
-use super::Module;
+use super::{Access, Module, Resource};
 
 type InnerAddonConfig = RwLock<HashMap<(String, Location), Vec<(WorkspacePath, Config)>>>;
 
@@ -38,8 +41,23 @@ impl std::ops::Deref for AddonConfigs {
     }
 }
 
+/// The addon configs waiting to be merged into the [`Database`]
 #[derive(Default)]
-pub struct Rapifier;
+struct DatabaseConfigs(Mutex<Vec<AddonConfig>>);
+
+#[derive(Default)]
+pub struct Rapifier {
+    counter: AtomicU16,
+    progress: OnceLock<ProgressBar>,
+}
+
+impl Rapifier {
+    /// The progress of every addon, their files are added as they start
+    fn progress(&self) -> &ProgressBar {
+        self.progress
+            .get_or_init(|| progress_bar(0).with_message("Rapifying Configs"))
+    }
+}
 
 impl Module for Rapifier {
     fn name(&self) -> &'static str {
@@ -49,6 +67,30 @@ impl Module for Rapifier {
         2000
     }
 
+    fn access(&self, phase: &str) -> Access {
+        match phase {
+            "pre_build_addon" => Access::new().with_reads(&[Resource::Config]).with_writes(&[
+                Resource::Config,
+                Resource::RequiredVersion,
+                Resource::Localizations,
+                Resource::FunctionsDefined,
+                Resource::MagazineWells,
+            ]),
+            "pre_build" => Access::new().with_reads(&[Resource::Config, Resource::MagazineWells]),
+            _ => Access::new(),
+        }
+    }
+
+    fn pre_build_per_addon(&self) -> bool {
+        true
+    }
+
+    fn init(&mut self, ctx: &Context) -> Result<Report, Error> {
+        ctx.state().set(AddonConfigs::default());
+        ctx.state().set(DatabaseConfigs::default());
+        Ok(Report::new())
+    }
+
     fn check(&self, ctx: &Context) -> Result<Report, Error> {
         let mut report = Report::new();
         report.extend(lint_check(
@@ -58,50 +100,43 @@ impl Module for Rapifier {
         Ok(report)
     }
 
-    fn pre_build(&self, ctx: &Context) -> Result<Report, Error> {
-        ctx.state().set(AddonConfigs::default());
+    fn pre_build_addon(&self, ctx: &Context, addon: &Addon) -> Result<Report, Error> {
         let mut report = Report::new();
-        let counter = AtomicU16::new(0);
         let glob_options = glob::MatchOptions {
             require_literal_separator: true,
             ..Default::default()
         };
+        let mut globs = Vec::new();
+        if let Some(config) = addon.config() {
+            if !config.rapify().enabled() {
+                debug!("rapify disabled for {}", addon.name());
+                return Ok(report);
+            }
+            for file in config.rapify().exclude() {
+                globs.push(glob::Pattern::new(file)?);
+            }
+        }
         let mut entries = Vec::new();
-        ctx.addons()
-            .iter()
-            .map(|addon| {
-                let mut globs = Vec::new();
-                if let Some(config) = addon.config() {
-                    if !config.rapify().enabled() {
-                        debug!("rapify disabled for {}", addon.name());
-                        return Ok(());
-                    }
-                    for file in config.rapify().exclude() {
-                        globs.push(glob::Pattern::new(file)?);
-                    }
+        for entry in ctx.workspace_path().join(addon.folder())?.walk_dir()? {
+            if entry.metadata()?.file_type == VfsFileType::File && can_rapify(&entry)? {
+                if globs
+                    .iter()
+                    .any(|pat| pat.matches_with(entry.as_str(), glob_options))
+                {
+                    debug!("skipping {}", entry.as_str());
+                    continue;
                 }
-                for entry in ctx.workspace_path().join(addon.folder())?.walk_dir()? {
-                    if entry.metadata()?.file_type == VfsFileType::File && can_rapify(&entry)? {
-                        if globs
-                            .iter()
-                            .any(|pat| pat.matches_with(entry.as_str(), glob_options))
-                        {
-                            debug!("skipping {}", entry.as_str());
-                            continue;
-                        }
-                        entries.push((addon, entry));
-                    }
-                }
-                Ok(())
-            })
-            .collect::<Result<Vec<_>, Error>>()?;
+                entries.push(entry);
+            }
+        }
 
-        let progress = progress_bar(entries.len() as u64).with_message("Rapifying Configs");
+        let progress = self.progress();
+        progress.inc_length(entries.len() as u64);
         let reports = entries
             .par_iter()
-            .map(|(addon, entry)| {
+            .map(|entry| {
                 let report = rapify(addon, entry, ctx)?;
-                counter.fetch_add(1, Ordering::Relaxed);
+                self.counter.fetch_add(1, Ordering::Relaxed);
                 progress.inc(1);
                 Ok(report)
             })
@@ -110,10 +145,40 @@ impl Module for Rapifier {
         for new_report in reports {
             report.merge(new_report);
         }
+        Ok(report)
+    }
 
-        progress.finish_and_clear();
-        info!("Rapified {} addon configs", counter.load(Ordering::Relaxed));
+    fn pre_build(&self, ctx: &Context) -> Result<Report, Error> {
+        let mut report = Report::new();
+        self.progress().finish_and_clear();
+        info!(
+            "Rapified {} addon configs",
+            self.counter.load(Ordering::Relaxed)
+        );
         report.extend(lint_all(Some(ctx.config()), &ctx.addons().to_vec()));
+
+        let mut configs = std::mem::take(
+            &mut *ctx
+                .state()
+                .get::<DatabaseConfigs>()
+                .0
+                .lock()
+                .expect("state is poisoned"),
+        );
+        configs.sort_by_key(|config| {
+            ctx.addons()
+                .iter()
+                .position(|addon| addon.name() == config.addon())
+        });
+        configs.extend(read_baseline(&ctx.baseline_folder())?);
+        let database = Database::new(configs);
+        let schema = Schema::with_folder(&ctx.hemtt_folder().join("schemas"))?;
+        report.extend(lint_database(
+            Some(ctx.config()),
+            &database,
+            Arc::new(schema),
+        ));
+        ctx.state().set(database);
         Ok(report)
     }
 }
@@ -181,6 +246,16 @@ pub fn rapify(addon: &Addon, path: &WorkspacePath, ctx: &Context) -> Result<Repo
                 .entry((addon.name().to_owned(), *addon.location()))
                 .or_default()
                 .push((file.to_owned(), configreport.config().clone()));
+            ctx.state()
+                .get::<DatabaseConfigs>()
+                .0
+                .lock()
+                .expect("state is poisoned")
+                .push(AddonConfig::new(
+                    addon.name(),
+                    configreport.config().clone(),
+                    Some(&processed),
+                ));
         }
         path.with_extension("bin")?
     } else {
@@ -1,12 +1,15 @@
 use std::{
     fs::{File, create_dir_all},
+    io::Cursor,
+    path::Path,
     sync::Arc,
 };
 
+use base64::{Engine, prelude::BASE64_STANDARD};
 use git2::Repository;
-use hemtt_common::prefix::FILES;
+use hemtt_common::{config::ProjectConfig, prefix::FILES};
 use hemtt_pbo::ReadablePbo;
-use hemtt_signing::BIPrivateKey;
+use hemtt_signing::{BIPrivateKey, BIPublicKey};
 use hemtt_workspace::{
     addons::Location,
     reporting::{Code, Diagnostic},
@@ -15,7 +18,7 @@ use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
 
 use crate::{context::Context, error::Error, report::Report};
 
-use super::Module;
+use super::{Access, Module, Resource};
 
 #[derive(Debug, Default)]
 pub struct Sign;
@@ -31,6 +34,13 @@ impl Module for Sign {
         "Sign"
     }
 
+    fn access(&self, phase: &str) -> Access {
+        match phase {
+            "pre_release" => Access::new().with_writes(&[Resource::Output]),
+            _ => Access::new(),
+        }
+    }
+
     fn check(&self, ctx: &Context) -> Result<Report, Error> {
         if ctx.config().version().git_hash().is_some() {
             Repository::discover(".")?;
@@ -60,12 +70,41 @@ impl Module for Sign {
             }
         });
 
+        if ctx.config().signing().is_persistent() {
+            match private_key(ctx.config()) {
+                Ok(Some(key)) => {
+                    if let Some(code) =
+                        check_published(&key.to_public_key(), &ctx.project_folder().join("keys"))?
+                    {
+                        report.push(code);
+                    }
+                }
+                Ok(None) => {}
+                Err(code) => report.push(code),
+            }
+        }
+
         Ok(report)
     }
 
     fn pre_release(&self, ctx: &Context) -> Result<Report, Error> {
-        let authority = get_authority(ctx, None)?;
-        let addons_key = BIPrivateKey::generate(1024, &authority)?;
+        let persistent = match private_key(ctx.config()) {
+            Ok(key) => key,
+            Err(code) => {
+                let mut report = Report::new();
+                report.push(code);
+                return Ok(report);
+            }
+        };
+        let (authority, addons_key) = if let Some(key) = persistent {
+            debug!("signing with persistent key `{}`", key.authority());
+            (key.authority().to_string(), key)
+        } else {
+            let authority = get_authority(ctx, None)?;
+            let key = BIPrivateKey::generate(1024, &authority)?;
+            (authority, key)
+        };
+        let persistent = ctx.config().signing().is_persistent();
         create_dir_all(
             ctx.build_folder()
                 .expect("build folder exists")
@@ -99,8 +138,13 @@ impl Module for Sign {
                 Location::Optionals => {
                     let (mut target_pbo, key, authority) =
                         if ctx.config().hemtt().build().optional_mod_folders() {
-                            let authority = get_authority(ctx, Some(&pbo_name))?;
-                            let key = BIPrivateKey::generate(1024, &authority)?;
+                            let (authority, key) = if persistent {
+                                (authority.clone(), addons_key.clone())
+                            } else {
+                                let authority = get_authority(ctx, Some(&pbo_name))?;
+                                let key = BIPrivateKey::generate(1024, &authority)?;
+                                (authority, key)
+                            };
                             let mod_root = ctx
                                 .build_folder()
                                 .expect("build folder exists")
@@ -157,6 +201,82 @@ pub fn get_authority(ctx: &Context, suffix: Option<&str>) -> Result<String, Erro
     Ok(authority)
 }
 
+/// Load the persistent private key from the project's signing configuration
+///
+/// The environment variable is used when it is set, otherwise the key is read from the path.
+/// `Ok(None)` is returned when no persistent key is configured.
+///
+/// # Errors
+/// [`InvalidKey`] if the key is configured but can not be loaded
+pub fn private_key(config: &ProjectConfig) -> Result<Option<BIPrivateKey>, Arc<dyn Code>> {
+    let signing = config.signing();
+    let from_env = signing.private_key_env().and_then(|var| {
+        std::env::var(var)
+            .ok()
+            .filter(|encoded| !encoded.trim().is_empty())
+            .map(|encoded| (var, encoded))
+    });
+    if let Some((var, encoded)) = from_env {
+        let source = format!("environment variable `{var}`");
+        let bytes = BASE64_STANDARD
+            .decode(encoded.trim())
+            .map_err(|e| InvalidKey::code(source.clone(), format!("not valid base64: {e}")))?;
+        return BIPrivateKey::read(&mut Cursor::new(bytes))
+            .map(Some)
+            .map_err(|e| InvalidKey::code(source, e.to_string()));
+    }
+    let Some(path) = signing.private_key() else {
+        return signing.private_key_env().map_or(Ok(None), |var| {
+            Err(InvalidKey::code(
+                format!("environment variable `{var}`"),
+                "it is not set".to_string(),
+            ))
+        });
+    };
+    let source = format!("`{path}`");
+    let mut file = File::open(path).map_err(|e| InvalidKey::code(source.clone(), e.to_string()))?;
+    BIPrivateKey::read(&mut file)
+        .map(Some)
+        .map_err(|e| InvalidKey::code(source, e.to_string()))
+}
+
+/// Check the key against the published `.bikey` files in the folder
+///
+/// Nothing is reported if no keys have been published yet.
+///
+/// # Errors
+/// [`Error`] if a published key can not be read
+pub fn check_published(key: &BIPublicKey, folder: &Path) -> Result<Option<Arc<dyn Code>>, Error> {
+    if !folder.is_dir() {
+        return Ok(None);
+    }
+    let mut published = Vec::new();
+    for entry in std::fs::read_dir(folder)? {
+        let path = entry?.path();
+        if !path
+            .extension()
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("bikey"))
+        {
+            continue;
+        }
+        let public = BIPublicKey::read(&mut File::open(&path)?)?;
+        if public.authority() == key.authority() && public.modulus() == key.modulus() {
+            return Ok(None);
+        }
+        published.push(public);
+    }
+    if published.is_empty() {
+        return Ok(None);
+    }
+    Ok(Some(KeyMismatch::code(
+        key.authority().to_string(),
+        published
+            .iter()
+            .map(|public| public.authority().to_string())
+            .collect(),
+    )))
+}
+
 pub struct EmptyAddon {
     file: String,
 }
@@ -184,3 +304,233 @@ impl EmptyAddon {
         Arc::new(Self { file })
     }
 }
+
+pub struct InvalidKey {
+    source: String,
+    reason: String,
+}
+impl Code for InvalidKey {
+    fn ident(&self) -> &'static str {
+        "BSE2"
+    }
+
+    fn message(&self) -> String {
+        format!("Unable to load the signing key from {}", self.source)
+    }
+
+    fn note(&self) -> Option<String> {
+        Some(self.reason.clone())
+    }
+
+    fn help(&self) -> Option<String> {
+        Some("a key can be created with `hemtt keys generate`".to_string())
+    }
+
+    fn diagnostic(&self) -> Option<Diagnostic> {
+        Some(Diagnostic::from_code(self))
+    }
+}
+
+impl InvalidKey {
+    #[must_use]
+    pub fn code(source: String, reason: String) -> Arc<dyn Code> {
+        Arc::new(Self { source, reason })
+    }
+}
+
+pub struct KeyMismatch {
+    authority: String,
+    published: Vec<String>,
+}
+impl Code for KeyMismatch {
+    fn ident(&self) -> &'static str {
+        "BSE3"
+    }
+
+    fn message(&self) -> String {
+        format!(
+            "Signing key `{}` does not match the published keys",
+            self.authority
+        )
+    }
+
+    fn note(&self) -> Option<String> {
+        if self.published.contains(&self.authority) {
+            Some(format!(
+                "a published key has the authority `{}`, but is a different key",
+                self.authority
+            ))
+        } else {
+            Some(format!("published: {}", self.published.join(", ")))
+        }
+    }
+
+    fn help(&self) -> Option<String> {
+        Some("servers using the published keys will reject this release, use the same key or publish the new one with `hemtt keys export-public`".to_string())
+    }
+
+    fn diagnostic(&self) -> Option<Diagnostic> {
+        Some(Diagnostic::from_code(self))
+    }
+}
+
+impl KeyMismatch {
+    #[must_use]
+    pub fn code(authority: String, published: Vec<String>) -> Arc<dyn Code> {
+        Arc::new(Self {
+            authority,
+            published,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use std::{fs::File, path::Path};
+
+    use base64::{Engine, prelude::BASE64_STANDARD};
+    use hemtt_common::config::ProjectConfig;
+    use hemtt_signing::BIPrivateKey;
+    use sealed_test::prelude::*;
+
+    use super::{check_published, private_key};
+
+    /// A project with the given `[signing]` section, in the current folder
+    fn config(signing: &str) -> ProjectConfig {
+        std::fs::create_dir_all(".hemtt").unwrap();
+        std::fs::write(
+            ".hemtt/project.toml",
+            format!("name = \"Test\"\nprefix = \"abe\"\n\n[signing]\n{signing}\n"),
+        )
+        .unwrap();
+        ProjectConfig::from_file(Path::new(".hemtt/project.toml")).unwrap()
+    }
+
+    fn key(authority: &str) -> BIPrivateKey {
+        BIPrivateKey::generate(1024, authority).unwrap()
+    }
+
+    #[sealed_test]
+    fn from_file() {
+        let key = key("abe");
+        key.write_danger(&mut File::create("abe.biprivatekey").unwrap())
+            .unwrap();
+        let loaded = private_key(&config("private_key = \"abe.biprivatekey\""))
+            .unwrap()
+            .unwrap();
+        assert_eq!(loaded.authority(), "abe");
+        assert_eq!(
+            loaded.to_public_key().modulus(),
+            key.to_public_key().modulus()
+        );
+    }
+
+    #[sealed_test]
+    fn from_env() {
+        let key = key("abe");
+        let mut bytes = Vec::new();
+        key.write_danger(&mut bytes).unwrap();
+        // SAFETY: the test runs in its own process
+        unsafe {
+            std::env::set_var("ABE_PRIVATE_KEY", BASE64_STANDARD.encode(bytes));
+        }
+        // the environment is used over the path
+        let loaded = private_key(&config(
+            "private_key = \"missing.biprivatekey\"\nprivate_key_env = \"ABE_PRIVATE_KEY\"",
+        ))
+        .unwrap()
+        .unwrap();
+        assert_eq!(
+            loaded.to_public_key().modulus(),
+            key.to_public_key().modulus()
+        );
+    }
+
+    #[sealed_test]
+    fn not_configured() {
+        assert!(private_key(&config("")).unwrap().is_none());
+    }
+
+    #[sealed_test]
+    fn invalid() {
+        std::fs::write("abe.biprivatekey", b"not a key").unwrap();
+        // SAFETY: the test runs in its own process
+        unsafe {
+            std::env::set_var("ABE_NOT_BASE64", "not base64!");
+            std::env::remove_var("ABE_UNSET");
+        }
+        for (signing, source, reason) in [
+            (
+                "private_key = \"abe.biprivatekey\"",
+                "`abe.biprivatekey`",
+                None,
+            ),
+            (
+                "private_key = \"missing.biprivatekey\"",
+                "`missing.biprivatekey`",
+                None,
+            ),
+            (
+                "private_key_env = \"ABE_NOT_BASE64\"",
+                "environment variable `ABE_NOT_BASE64`",
+                Some("not valid base64"),
+            ),
+            (
+                "private_key_env = \"ABE_UNSET\"",
+                "environment variable `ABE_UNSET`",
+                Some("it is not set"),
+            ),
+        ] {
+            let Err(code) = private_key(&config(signing)) else {
+                panic!("{signing} loaded a key");
+            };
+            assert_eq!(code.ident(), "BSE2");
+            assert!(code.message().ends_with(source), "{}", code.message());
+            if let Some(reason) = reason {
+                assert!(code.note().unwrap().starts_with(reason));
+            }
+        }
+    }
+
+    #[sealed_test]
+    fn published() {
+        let public = key("abe").to_public_key();
+        // nothing published yet
+        assert!(
+            check_published(&public, Path::new("keys"))
+                .unwrap()
+                .is_none()
+        );
+        std::fs::create_dir("keys").unwrap();
+        assert!(
+            check_published(&public, Path::new("keys"))
+                .unwrap()
+                .is_none()
+        );
+
+        public
+            .write(&mut File::create("keys/abe.bikey").unwrap())
+            .unwrap();
+        assert!(
+            check_published(&public, Path::new("keys"))
+                .unwrap()
+                .is_none()
+        );
+
+        // the same authority, but a different key
+        let other = key("abe").to_public_key();
+        let code = check_published(&other, Path::new("keys")).unwrap().unwrap();
+        assert_eq!(code.ident(), "BSE3");
+        assert_eq!(
+            code.note().unwrap(),
+            "a published key has the authority `abe`, but is a different key"
+        );
+
+        let other = key("xyz").to_public_key();
+        let code = check_published(&other, Path::new("keys")).unwrap().unwrap();
+        assert_eq!(code.ident(), "BSE3");
+        assert_eq!(code.note().unwrap(), "published: abe");
+    }
+}
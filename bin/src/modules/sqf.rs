@@ -1,5 +1,5 @@
 use std::sync::{
-    Arc,
+    Arc, OnceLock,
     atomic::{AtomicU16, Ordering},
 };
 
@@ -9,22 +9,38 @@ use hemtt_sqf::{
     analyze::{analyze, lint_all, lint_check},
     parser::{ParserError, database::Database},
 };
-use hemtt_workspace::reporting::{Code, CodesExt, Diagnostic, Severity};
+use hemtt_workspace::{
+    addons::Addon,
+    reporting::{Code, CodesExt, Diagnostic, Severity},
+};
+use indicatif::ProgressBar;
 use rayon::prelude::{IntoParallelRefIterator, ParallelIterator};
 
 use crate::{context::Context, error::Error, progress::progress_bar, report::Report};
 
-use super::Module;
+use super::{Access, Module, Resource};
 
 #[derive(Default)]
 pub struct SQFCompiler {
     pub database: Option<Arc<Database>>,
+    counter: AtomicU16,
+    progress: OnceLock<ProgressBar>,
 }
 
 impl SQFCompiler {
     #[must_use]
     pub const fn new() -> Self {
-        Self { database: None }
+        Self {
+            database: None,
+            counter: AtomicU16::new(0),
+            progress: OnceLock::new(),
+        }
+    }
+
+    /// The progress of every addon, their files are added as they start
+    fn progress(&self) -> &ProgressBar {
+        self.progress
+            .get_or_init(|| progress_bar(0).with_message("Compiling SQF"))
     }
 }
 
@@ -36,6 +52,30 @@ impl Module for SQFCompiler {
         3000
     }
 
+    fn access(&self, phase: &str) -> Access {
+        match phase {
+            "pre_build_addon" => Access::new()
+                .with_reads(&[Resource::Sqf, Resource::RequiredVersion])
+                .with_writes(&[
+                    Resource::Sqf,
+                    Resource::Localizations,
+                    Resource::FunctionsDefined,
+                    Resource::FunctionsUsed,
+                ]),
+            "pre_build" => Access::new().with_reads(&[
+                Resource::Sqf,
+                Resource::FunctionsDefined,
+                Resource::FunctionsUsed,
+            ]),
+            "post_build" => Access::new().with_reads(&[Resource::RequiredVersion]),
+            _ => Access::new(),
+        }
+    }
+
+    fn pre_build_per_addon(&self) -> bool {
+        true
+    }
+
     fn init(&mut self, ctx: &Context) -> Result<Report, Error> {
         self.database = Some(Arc::new(Database::a3_with_workspace(
             ctx.workspace_path(),
@@ -53,21 +93,17 @@ impl Module for SQFCompiler {
         Ok(report)
     }
 
-    #[allow(clippy::too_many_lines)]
-    fn pre_build(&self, ctx: &Context) -> Result<Report, Error> {
+    fn pre_build_addon(&self, ctx: &Context, addon: &Addon) -> Result<Report, Error> {
         let mut report = Report::new();
         let sqf_ext = Some(String::from("sqf"));
-        let counter = AtomicU16::new(0);
+        let addon = Arc::new(addon.clone());
         let mut entries = Vec::new();
-        for addon in ctx.addons() {
-            let addon = Arc::new(addon.clone());
-            for entry in ctx.workspace_path().join(addon.folder())?.walk_dir()? {
-                if entry.is_file()? {
-                    if entry.extension() != sqf_ext || entry.filename().ends_with(".inc.sqf") {
-                        continue;
-                    }
-                    entries.push((addon.clone(), entry));
+        for entry in ctx.workspace_path().join(addon.folder())?.walk_dir()? {
+            if entry.is_file()? {
+                if entry.extension() != sqf_ext || entry.filename().ends_with(".inc.sqf") {
+                    continue;
                 }
+                entries.push(entry);
             }
         }
         let database = self
@@ -75,10 +111,11 @@ impl Module for SQFCompiler {
             .as_ref()
             .expect("database not initialized")
             .clone();
-        let progress = progress_bar(entries.len() as u64).with_message("Compiling SQF");
+        let progress = self.progress();
+        progress.inc_length(entries.len() as u64);
         let reports = entries
             .par_iter()
-            .map(|(addon, entry)| {
+            .map(|entry| {
                 trace!("sqf compiling {}", entry);
                 let mut report = Report::new();
                 let processed = match Processor::run(entry).map_err(|(_, e)| e) {
@@ -104,12 +141,12 @@ impl Module for SQFCompiler {
                             database.clone(),
                         );
                         if let Some(sqf_report) = sqf_report {
-                            sqf_report.push_to_addon(addon);
+                            sqf_report.push_to_addon(&addon);
                         }
                         if !codes.failed() {
                             let mut out = entry.with_extension("sqfc")?.create_file()?;
                             sqf.optimize().compile_to_writer(&processed, &mut out)?;
-                            counter.fetch_add(1, Ordering::Relaxed);
+                            self.counter.fetch_add(1, Ordering::Relaxed);
                             progress.inc(1);
                         }
                         for code in codes {
@@ -141,8 +178,21 @@ impl Module for SQFCompiler {
         for new_report in reports {
             report.merge(new_report);
         }
-        progress.finish_and_clear();
-        info!("Compiled {} sqf files", counter.load(Ordering::Relaxed));
+        Ok(report)
+    }
+
+    fn pre_build(&self, ctx: &Context) -> Result<Report, Error> {
+        let mut report = Report::new();
+        self.progress().finish_and_clear();
+        info!(
+            "Compiled {} sqf files",
+            self.counter.load(Ordering::Relaxed)
+        );
+        let database = self
+            .database
+            .as_ref()
+            .expect("database not initialized")
+            .clone();
 
         report.extend(lint_all(
             Some(ctx.config()),
@@ -16,7 +16,7 @@ use rayon::iter::{IntoParallelIterator, ParallelIterator};
 
 use crate::{Error, context::Context, progress::progress_bar, report::Report};
 
-use super::Module;
+use super::{Access, Module, Resource};
 
 #[derive(Debug, Default)]
 pub struct Stringtables;
@@ -35,6 +35,16 @@ impl Module for Stringtables {
         4000
     }
 
+    fn access(&self, phase: &str) -> Access {
+        match phase {
+            "check" => Access::new().with_reads(&[Resource::Stringtables]),
+            "pre_build" => Access::new()
+                .with_reads(&[Resource::Localizations])
+                .with_writes(&[Resource::Stringtables]),
+            _ => Access::new(),
+        }
+    }
+
     fn check(&self, ctx: &crate::context::Context) -> Result<crate::report::Report, crate::Error> {
         let mut report = Report::new();
         report.extend(lint_check(
@@ -0,0 +1,31 @@
+use std::sync::Arc;
+
+use hemtt_workspace::reporting::{Code, Diagnostic};
+
+pub struct SteamCmdNotFound;
+
+impl Code for SteamCmdNotFound {
+    fn ident(&self) -> &'static str {
+        "BWE1"
+    }
+
+    fn message(&self) -> String {
+        String::from("`steamcmd` not found.")
+    }
+
+    fn help(&self) -> Option<String> {
+        Some(String::from(
+            "Install `steamcmd` and add it to the `PATH`, or set `HEMTT_STEAMCMD` to its path.",
+        ))
+    }
+
+    fn diagnostic(&self) -> Option<Diagnostic> {
+        Some(Diagnostic::from_code(self))
+    }
+}
+
+impl SteamCmdNotFound {
+    pub fn code() -> Arc<dyn Code> {
+        Arc::new(Self)
+    }
+}
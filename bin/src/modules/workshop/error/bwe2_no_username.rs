@@ -0,0 +1,31 @@
+use std::sync::Arc;
+
+use hemtt_workspace::reporting::{Code, Diagnostic};
+
+pub struct NoUsername;
+
+impl Code for NoUsername {
+    fn ident(&self) -> &'static str {
+        "BWE2"
+    }
+
+    fn message(&self) -> String {
+        String::from("No Steam account to upload to the Workshop with.")
+    }
+
+    fn help(&self) -> Option<String> {
+        Some(String::from(
+            "Set `username` in `[workshop]`, or set `HEMTT_STEAM_USERNAME`.",
+        ))
+    }
+
+    fn diagnostic(&self) -> Option<Diagnostic> {
+        Some(Diagnostic::from_code(self))
+    }
+}
+
+impl NoUsername {
+    pub fn code() -> Arc<dyn Code> {
+        Arc::new(Self)
+    }
+}
@@ -0,0 +1,35 @@
+use std::sync::Arc;
+
+use hemtt_workspace::reporting::{Code, Diagnostic};
+
+pub struct FileNotFound {
+    key: &'static str,
+    path: String,
+}
+
+impl Code for FileNotFound {
+    fn ident(&self) -> &'static str {
+        "BWE3"
+    }
+
+    fn message(&self) -> String {
+        format!("Workshop {} `{}` not found.", self.key, self.path)
+    }
+
+    fn help(&self) -> Option<String> {
+        Some(format!(
+            "`{}` in `[workshop]` is relative to the root of the project.",
+            self.key
+        ))
+    }
+
+    fn diagnostic(&self) -> Option<Diagnostic> {
+        Some(Diagnostic::from_code(self))
+    }
+}
+
+impl FileNotFound {
+    pub fn code(key: &'static str, path: String) -> Arc<dyn Code> {
+        Arc::new(Self { key, path })
+    }
+}
@@ -0,0 +1,37 @@
+use std::sync::Arc;
+
+use hemtt_workspace::reporting::{Code, Diagnostic};
+
+pub struct UploadFailed {
+    reason: String,
+}
+
+impl Code for UploadFailed {
+    fn ident(&self) -> &'static str {
+        "BWE4"
+    }
+
+    fn message(&self) -> String {
+        String::from("Failed to upload to the Steam Workshop.")
+    }
+
+    fn note(&self) -> Option<String> {
+        Some(self.reason.clone())
+    }
+
+    fn help(&self) -> Option<String> {
+        Some(String::from(
+            "Run with `-v` to see the output of `steamcmd`. Log in with `steamcmd` once to cache the credentials of the account.",
+        ))
+    }
+
+    fn diagnostic(&self) -> Option<Diagnostic> {
+        Some(Diagnostic::from_code(self))
+    }
+}
+
+impl UploadFailed {
+    pub fn code(reason: String) -> Arc<dyn Code> {
+        Arc::new(Self { reason })
+    }
+}
@@ -0,0 +1,4 @@
+pub mod bwe1_steamcmd_not_found;
+pub mod bwe2_no_username;
+pub mod bwe3_file_not_found;
+pub mod bwe4_upload_failed;
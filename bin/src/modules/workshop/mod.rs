@@ -0,0 +1,253 @@
+use std::{
+    fmt::Write as _,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+use hemtt_common::{
+    config::{ProjectConfig, Visibility},
+    steam,
+};
+
+use crate::{context::Context, error::Error, report::Report};
+
+use self::error::{
+    bwe1_steamcmd_not_found::SteamCmdNotFound, bwe2_no_username::NoUsername,
+    bwe3_file_not_found::FileNotFound, bwe4_upload_failed::UploadFailed,
+};
+
+use super::{Access, Module, Resource, archive};
+
+mod error;
+
+/// The app id of Arma 3
+const APP_ID: u32 = 107_410;
+
+#[derive(Default)]
+/// Uploads the release to the Steam Workshop with `steamcmd`
+pub struct Workshop;
+
+impl Workshop {
+    #[must_use]
+    pub const fn new() -> Self {
+        Self
+    }
+}
+
+impl Module for Workshop {
+    fn name(&self) -> &'static str {
+        "Workshop"
+    }
+
+    fn priority(&self) -> i32 {
+        // After every other release task
+        10000
+    }
+
+    fn access(&self, phase: &str) -> Access {
+        match phase {
+            "post_release" => Access::new().with_reads(&[Resource::Output]),
+            _ => Access::new(),
+        }
+    }
+
+    fn check(&self, ctx: &Context) -> Result<Report, Error> {
+        let mut report = Report::new();
+        if steamcmd().is_none() {
+            report.push(SteamCmdNotFound::code());
+        }
+        if username(ctx.config()).is_none() {
+            report.push(NoUsername::code());
+        }
+        let config = ctx.config().workshop();
+        for (key, path) in [
+            ("preview", config.preview()),
+            ("description", config.description()),
+            ("changelog", config.changelog()),
+        ] {
+            if let Some(path) = path.filter(|path| !ctx.project_folder().join(path).is_file()) {
+                report.push(FileNotFound::code(key, path.to_string()));
+            }
+        }
+        Ok(report)
+    }
+
+    fn post_release(&self, ctx: &Context) -> Result<Report, Error> {
+        let mut report = Report::new();
+        let folder = std::path::absolute(ctx.out_folder().join("workshop"))?;
+        if folder.exists() {
+            std::fs::remove_dir_all(&folder)?;
+        }
+        let content = folder.join("content");
+        archive::copy_release(ctx, &content, ctx.config().workshop().optionals(), true)?;
+        debug!("created workshop content at {:?}", content.display());
+
+        let item = Item::new(ctx, content, &mut report)?;
+        if report.failed() {
+            return Ok(report);
+        }
+        let vdf = folder.join("item.vdf");
+        std::fs::write(&vdf, item.vdf())?;
+        report.merge(upload(ctx.config(), &vdf)?);
+        Ok(report)
+    }
+}
+
+/// A `workshop_build_item` for `steamcmd`
+struct Item {
+    id: u64,
+    content: PathBuf,
+    preview: Option<PathBuf>,
+    visibility: Option<u8>,
+    title: Option<String>,
+    description: Option<String>,
+    changenote: Option<String>,
+}
+
+impl Item {
+    /// The item from the config, with the templates rendered
+    fn new(ctx: &Context, content: PathBuf, report: &mut Report) -> Result<Self, Error> {
+        let config = ctx.config().workshop();
+        let fields = archive::fields(ctx)?;
+        let mut render = |template: &str| match archive::render(template, &fields) {
+            Ok(rendered) => Some(rendered),
+            Err(code) => {
+                report.push(code);
+                None
+            }
+        };
+        let title = match (config.title(), config.item_id()) {
+            (Some(title), _) => render(title),
+            // A new item needs a title
+            (None, None) => Some(ctx.config().name().to_string()),
+            (None, Some(_)) => None,
+        };
+        let description = read(ctx, config.description())?.and_then(|text| render(&text));
+        let changenote = read(ctx, config.changelog())?.and_then(|text| render(&text));
+        Ok(Self {
+            id: config.item_id().unwrap_or_default(),
+            content,
+            preview: config
+                .preview()
+                .map(|path| std::path::absolute(ctx.project_folder().join(path)))
+                .transpose()?,
+            visibility: config.visibility().map(Visibility::steam_value),
+            title,
+            description,
+            changenote,
+        })
+    }
+
+    /// The VDF file read by `steamcmd`, values that are not set are not changed on the workshop
+    fn vdf(&self) -> String {
+        let mut values = vec![
+            ("appid", APP_ID.to_string()),
+            ("publishedfileid", self.id.to_string()),
+            ("contentfolder", self.content.display().to_string()),
+        ];
+        if let Some(preview) = &self.preview {
+            values.push(("previewfile", preview.display().to_string()));
+        }
+        if let Some(visibility) = self.visibility {
+            values.push(("visibility", visibility.to_string()));
+        }
+        for (key, value) in [
+            ("title", &self.title),
+            ("description", &self.description),
+            ("changenote", &self.changenote),
+        ] {
+            if let Some(value) = value {
+                values.push((key, value.clone()));
+            }
+        }
+        let mut out = String::from("\"workshopitem\"\n{\n");
+        for (key, value) in values {
+            let _ = writeln!(out, "\t\"{key}\"\t\t\"{}\"", escape(&value));
+        }
+        out.push_str("}\n");
+        out
+    }
+}
+
+fn read(ctx: &Context, path: Option<&str>) -> Result<Option<String>, Error> {
+    path.map(|path| {
+        std::fs::read_to_string(ctx.project_folder().join(path))
+            .map(|text| text.trim_end().to_string())
+    })
+    .transpose()
+    .map_err(Into::into)
+}
+
+fn escape(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+/// `HEMTT_STEAMCMD`, or `steamcmd` from the usual locations
+fn steamcmd() -> Option<PathBuf> {
+    std::env::var("HEMTT_STEAMCMD")
+        .ok()
+        .map(PathBuf::from)
+        .or_else(steam::find_steamcmd)
+}
+
+/// `HEMTT_STEAM_USERNAME`, or the username in the config
+fn username(config: &ProjectConfig) -> Option<String> {
+    std::env::var("HEMTT_STEAM_USERNAME")
+        .ok()
+        .filter(|username| !username.is_empty())
+        .or_else(|| config.workshop().username().map(ToString::to_string))
+}
+
+/// Run `steamcmd` to build the item
+fn upload(config: &ProjectConfig, vdf: &Path) -> Result<Report, Error> {
+    let mut report = Report::new();
+    let Some(steamcmd) = steamcmd() else {
+        report.push(SteamCmdNotFound::code());
+        return Ok(report);
+    };
+    let Some(username) = username(config) else {
+        report.push(NoUsername::code());
+        return Ok(report);
+    };
+    let mut command = Command::new(steamcmd);
+    command.arg("+login").arg(username);
+    // Without a password, steamcmd uses the cached credentials of the account
+    if let Ok(password) = std::env::var("HEMTT_STEAM_PASSWORD") {
+        command.arg(password);
+    }
+    command.arg("+workshop_build_item").arg(vdf).arg("+quit");
+    info!("Uploading to the Steam Workshop");
+    let output = command.output()?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    for line in stdout.lines() {
+        debug!("steamcmd: {}", line);
+    }
+    let error = stdout
+        .lines()
+        .find(|line| line.contains("ERROR") || line.starts_with("FAILED"))
+        .map(|line| line.trim().to_string());
+    if let Some(reason) = error.or_else(|| {
+        (!output.status.success()).then(|| format!("`steamcmd` exited with {}", output.status))
+    }) {
+        report.push(UploadFailed::code(reason));
+        return Ok(report);
+    }
+
+    // steamcmd writes the id of a new item to the VDF
+    let id = published_file_id(&std::fs::read_to_string(vdf)?);
+    info!("Uploaded workshop item {}", id.unwrap_or_default());
+    if let Some(id) = id.filter(|_| config.workshop().item_id().is_none()) {
+        info!(
+            "Created workshop item {id}, set `item_id = {id}` in `[workshop]` to update it on the next release"
+        );
+    }
+    Ok(report)
+}
+
+fn published_file_id(vdf: &str) -> Option<u64> {
+    vdf.lines()
+        .find(|line| line.trim_start().starts_with("\"publishedfileid\""))
+        .and_then(|line| line.split('"').nth(3))
+        .and_then(|id| id.parse().ok())
+        .filter(|id| *id != 0)
+}
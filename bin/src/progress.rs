@@ -1,8 +1,13 @@
-use indicatif::{ProgressBar, ProgressStyle};
+use std::sync::LazyLock;
+
+use indicatif::{MultiProgress, ProgressBar, ProgressStyle};
+
+/// Modules running at the same time each get a line
+static PROGRESS: LazyLock<MultiProgress> = LazyLock::new(MultiProgress::new);
 
 #[allow(clippy::module_name_repetitions)]
 pub fn progress_bar(size: u64) -> ProgressBar {
-    ProgressBar::new(size).with_style(
+    PROGRESS.add(ProgressBar::new(size)).with_style(
         ProgressStyle::with_template(
             if std::env::var("CI").is_ok()
                 || std::env::args().any(|a| a.starts_with("-v") && a.ends_with('v'))
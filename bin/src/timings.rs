@@ -0,0 +1,237 @@
+//! How long each module and addon took, reported with `--timings`
+
+use std::{
+    path::Path,
+    sync::{
+        Mutex, OnceLock,
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+    },
+    time::{Duration, Instant},
+};
+
+use serde_json::json;
+use tabled::{
+    Table, Tabled,
+    settings::{Alignment, Style, object::Columns},
+};
+
+use crate::Error;
+
+#[derive(clap::ValueEnum, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum TimingsFormat {
+    /// a table for the terminal, printed when the command is done
+    #[default]
+    Table,
+    /// a Chrome trace at .hemttout/timings.json, for `chrome://tracing` or Perfetto
+    Chrome,
+}
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+static START: OnceLock<Instant> = OnceLock::new();
+static SPANS: Mutex<Vec<Span>> = Mutex::new(Vec::new());
+static THREADS: AtomicUsize = AtomicUsize::new(0);
+
+thread_local! {
+    /// A short id of the current thread, the lanes of the trace
+    static THREAD: usize = THREADS.fetch_add(1, Ordering::Relaxed);
+}
+
+struct Span {
+    phase: &'static str,
+    module: String,
+    addon: Option<String>,
+    thread: usize,
+    start: Duration,
+    duration: Duration,
+}
+
+#[derive(Tabled)]
+struct Row {
+    #[tabled(rename = "Phase")]
+    phase: &'static str,
+    #[tabled(rename = "Module")]
+    module: String,
+    #[tabled(rename = "Addon")]
+    addon: String,
+    #[tabled(rename = "Start")]
+    start: String,
+    #[tabled(rename = "Duration")]
+    duration: String,
+}
+
+/// Start recording, everything run with [`time`] from now on is reported
+pub fn enable() {
+    START.get_or_init(Instant::now);
+    ENABLED.store(true, Ordering::Relaxed);
+}
+
+/// Run the function, recording how long it took if timings are enabled
+///
+/// # Panics
+/// If another thread panicked while recording
+pub fn time<T>(phase: &'static str, module: &str, addon: Option<&str>, f: impl FnOnce() -> T) -> T {
+    if !ENABLED.load(Ordering::Relaxed) {
+        return f();
+    }
+    let start = Instant::now();
+    let ret = f();
+    let duration = start.elapsed();
+    let span = Span {
+        phase,
+        module: module.to_string(),
+        addon: addon.map(ToString::to_string),
+        thread: THREAD.with(|thread| *thread),
+        start: start.duration_since(*START.get_or_init(Instant::now)),
+        duration,
+    };
+    SPANS.lock().expect("timings are poisoned").push(span);
+    ret
+}
+
+/// Print the table, or write the trace to the out folder
+///
+/// # Errors
+/// [`Error::Io`] if the trace can not be written
+///
+/// # Panics
+/// If another thread panicked while recording
+pub fn report(format: TimingsFormat, out: &Path) -> Result<(), Error> {
+    let mut spans = std::mem::take(&mut *SPANS.lock().expect("timings are poisoned"));
+    spans.sort_by_key(|span| span.start);
+    match format {
+        TimingsFormat::Table => println!("{}", table(&spans)),
+        TimingsFormat::Chrome => {
+            std::fs::create_dir_all(out)?;
+            let path = out.join("timings.json");
+            std::fs::write(&path, serde_json::to_string(&trace(&spans))?)?;
+            info!("Wrote timings to {}", path.display());
+        }
+    }
+    Ok(())
+}
+
+fn table(spans: &[Span]) -> String {
+    let rows = spans
+        .iter()
+        .map(|span| Row {
+            phase: span.phase,
+            module: span.module.clone(),
+            addon: span.addon.clone().unwrap_or_default(),
+            start: format!("{:.2?}", span.start),
+            duration: format!("{:.2?}", span.duration),
+        })
+        .collect::<Vec<_>>();
+    Table::new(rows)
+        .with(Style::modern())
+        .modify(Columns::new(3..), Alignment::right())
+        .to_string()
+}
+
+/// The spans in the Chrome trace event format, as complete events
+fn trace(spans: &[Span]) -> serde_json::Value {
+    let events = spans
+        .iter()
+        .map(|span| {
+            json!({
+                "name": span.addon.as_ref().map_or_else(
+                    || span.module.clone(),
+                    |addon| format!("{} ({addon})", span.module),
+                ),
+                "cat": span.phase,
+                "ph": "X",
+                "ts": span.start.as_micros(),
+                "dur": span.duration.as_micros(),
+                "pid": 1,
+                "tid": span.thread,
+                "args": {
+                    "phase": span.phase,
+                    "module": span.module,
+                    "addon": span.addon,
+                },
+            })
+        })
+        .collect::<Vec<_>>();
+    json!({
+        "traceEvents": events,
+        "displayTimeUnit": "ms",
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use std::time::Duration;
+
+    use sealed_test::prelude::*;
+
+    use super::{SPANS, TimingsFormat, enable, report, table, time};
+
+    fn record() {
+        time("pre_build", "SQF", Some("main"), || {
+            std::thread::sleep(Duration::from_millis(20));
+        });
+        std::thread::spawn(|| {
+            time("pre_build", "Rapifier", None, || {
+                std::thread::sleep(Duration::from_millis(10));
+            });
+        })
+        .join()
+        .unwrap();
+    }
+
+    #[sealed_test]
+    fn disabled() {
+        record();
+        assert!(SPANS.lock().unwrap().is_empty());
+    }
+
+    #[sealed_test]
+    fn rows() {
+        enable();
+        record();
+        let table = table(&std::mem::take(&mut *SPANS.lock().unwrap()));
+        let rows = table
+            .lines()
+            .filter(|line| line.starts_with('│'))
+            .map(|line| {
+                let cells = line.split('│').map(str::trim).collect::<Vec<_>>();
+                cells[1..cells.len() - 1].to_vec()
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(rows.len(), 3);
+        assert_eq!(rows[0], ["Phase", "Module", "Addon", "Start", "Duration"]);
+        assert_eq!(rows[1][..3], ["pre_build", "SQF", "main"]);
+        assert_eq!(rows[2][..3], ["pre_build", "Rapifier", ""]);
+        assert!(rows[1][4].ends_with("ms"));
+        assert!(rows[2][3].ends_with("ms"));
+    }
+
+    #[sealed_test]
+    fn chrome() {
+        enable();
+        record();
+        let out = std::env::current_dir().unwrap().join(".hemttout");
+        report(TimingsFormat::Chrome, &out).unwrap();
+        let trace: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(out.join("timings.json")).unwrap())
+                .unwrap();
+        assert_eq!(trace["displayTimeUnit"], "ms");
+        let events = trace["traceEvents"].as_array().unwrap();
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0]["name"], "SQF (main)");
+        assert_eq!(events[1]["name"], "Rapifier");
+        for event in events {
+            assert_eq!(event["ph"], "X");
+            assert_eq!(event["cat"], "pre_build");
+            assert!(event["ts"].is_u64());
+        }
+        assert!(events[0]["dur"].as_u64().unwrap() >= 20_000);
+        assert!(events[1]["dur"].as_u64().unwrap() >= 10_000);
+        assert!(events[1]["ts"].as_u64().unwrap() >= events[0]["dur"].as_u64().unwrap());
+        // each thread is its own lane
+        assert_ne!(events[0]["tid"], events[1]["tid"]);
+        // the recorded spans are only reported once
+        assert!(SPANS.lock().unwrap().is_empty());
+    }
+}
@@ -0,0 +1,136 @@
+use std::fmt::Write;
+
+use hemtt_config::database::Database;
+use serde::Serialize;
+
+use crate::{
+    Error,
+    context::{Context, PreservePrevious},
+    report::Report,
+    utils::config::database,
+};
+
+#[derive(clap::Args)]
+#[allow(clippy::module_name_repetitions)]
+pub struct GraphArgs {
+    #[clap(long, default_value = "dot")]
+    /// Output format
+    pub(crate) format: GraphFormat,
+}
+
+#[derive(clap::ValueEnum, Clone, Debug)]
+pub enum GraphFormat {
+    /// Graphviz, render with `dot -Tsvg`
+    Dot,
+    /// json, ideal for machines
+    Json,
+}
+
+#[derive(Serialize)]
+struct Graph {
+    addons: Vec<Node>,
+    cycles: Vec<Vec<String>>,
+}
+
+#[derive(Serialize)]
+struct Node {
+    name: String,
+    patches: Vec<String>,
+    requires: Vec<String>,
+    external: Vec<String>,
+}
+
+/// Prints the dependency graph of the project's addons to stdout
+///
+/// # Errors
+/// [`Error`] if the project could not be loaded
+pub fn graph(args: &GraphArgs) -> Result<Report, Error> {
+    let ctx = Context::new(Some("check"), PreservePrevious::Keep, false)?;
+    let (database, report) = database(&ctx)?;
+    let graph = build(&database);
+    match args.format {
+        GraphFormat::Dot => print!("{}", dot(&graph)),
+        GraphFormat::Json => println!("{}", serde_json::to_string_pretty(&graph)?),
+    }
+    Ok(report)
+}
+
+fn build(database: &Database) -> Graph {
+    let addons = database
+        .addons()
+        .iter()
+        .map(|addon| {
+            let mut external = Vec::new();
+            for (_, required) in addon.required_addons() {
+                for required in required {
+                    if database.patch_addon(required.value()).is_none()
+                        && !external.contains(&required.value().to_string())
+                    {
+                        external.push(required.value().to_string());
+                    }
+                }
+            }
+            Node {
+                name: addon.addon().to_string(),
+                patches: addon
+                    .patches()
+                    .iter()
+                    .map(|patch| patch.as_str().to_string())
+                    .collect(),
+                requires: database
+                    .requires(addon.addon())
+                    .into_iter()
+                    .map(ToString::to_string)
+                    .collect(),
+                external,
+            }
+        })
+        .collect();
+    Graph {
+        addons,
+        cycles: database
+            .cycles()
+            .into_iter()
+            .map(|cycle| cycle.into_iter().map(ToString::to_string).collect())
+            .collect(),
+    }
+}
+
+fn dot(graph: &Graph) -> String {
+    let in_cycle = |from: &str, to: &str| {
+        graph.cycles.iter().any(|cycle| {
+            cycle.iter().any(|addon| addon == from) && cycle.iter().any(|addon| addon == to)
+        })
+    };
+    let mut out = String::from("digraph addons {\n    rankdir=LR;\n    node [shape=box];\n");
+    let mut externals: Vec<&String> = Vec::new();
+    for node in &graph.addons {
+        let _ = writeln!(out, "    \"{}\";", node.name);
+        for external in &node.external {
+            if !externals.contains(&external) {
+                externals.push(external);
+            }
+        }
+    }
+    for external in externals {
+        let _ = writeln!(out, "    \"{external}\" [style=dashed];");
+    }
+    for node in &graph.addons {
+        for required in &node.requires {
+            if in_cycle(&node.name, required) {
+                let _ = writeln!(out, "    \"{}\" -> \"{required}\" [color=red];", node.name);
+            } else {
+                let _ = writeln!(out, "    \"{}\" -> \"{required}\";", node.name);
+            }
+        }
+        for external in &node.external {
+            let _ = writeln!(
+                out,
+                "    \"{}\" -> \"{external}\" [style=dashed];",
+                node.name
+            );
+        }
+    }
+    out.push_str("}\n");
+    out
+}
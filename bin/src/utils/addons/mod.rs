@@ -0,0 +1,31 @@
+use crate::{Error, report::Report};
+
+mod graph;
+mod unused;
+
+#[derive(clap::Parser)]
+#[command(arg_required_else_help = true)]
+/// Commands for the project's addons
+pub struct Command {
+    #[command(subcommand)]
+    commands: Subcommands,
+}
+
+#[derive(clap::Subcommand)]
+enum Subcommands {
+    /// Show how the addons depend on each other through `requiredAddons`
+    Graph(graph::GraphArgs),
+    /// Find files in the addons that nothing uses, and uses of other addons that are not required
+    UnusedFiles(unused::UnusedArgs),
+}
+
+/// Execute the addons command
+///
+/// # Errors
+/// [`Error`] depending on the modules
+pub fn execute(cmd: &Command) -> Result<Report, Error> {
+    match &cmd.commands {
+        Subcommands::Graph(args) => graph::graph(args),
+        Subcommands::UnusedFiles(args) => unused::unused(args),
+    }
+}
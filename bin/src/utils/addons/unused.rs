@@ -0,0 +1,618 @@
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    io::Read,
+    sync::Arc,
+};
+
+use hemtt_config::{Class, Property, Value, database::Database};
+use hemtt_preprocessor::Processor;
+use hemtt_workspace::{
+    WorkspacePath,
+    reporting::{Code, Diagnostic, Severity},
+};
+use serde::Serialize;
+
+use crate::{
+    Error,
+    context::{Context, PreservePrevious},
+    report::Report,
+    utils::config::database,
+};
+
+/// Files that are used without being referenced
+const ROOTS: [&str; 5] = [
+    "config.cpp",
+    "model.cfg",
+    "stringtable.xml",
+    "$pboprefix$",
+    "texheaders.bin",
+];
+
+/// Files that are only used when something references them
+const CHECKED: [&str; 14] = [
+    "sqf", "hpp", "inc", "h", "ext", "fsm", "paa", "p3d", "rvmat", "rtm", "ogg", "wss", "wav",
+    "bikb",
+];
+
+#[derive(clap::Args)]
+#[allow(clippy::module_name_repetitions)]
+pub struct UnusedArgs {
+    #[clap(long, default_value = "text")]
+    /// Output format
+    pub(crate) format: UnusedFormat,
+}
+
+#[derive(clap::ValueEnum, Clone, Debug)]
+pub enum UnusedFormat {
+    /// A list of files, ideal for humans
+    Text,
+    /// json, including every reference between files
+    Json,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "lowercase")]
+/// How one file uses another
+enum Kind {
+    /// `#include`
+    Include,
+    /// A function in `CfgFunctions`
+    Function,
+    /// A path in a string, `execVM`, `QPATHTOF`, `model = ...`
+    Path,
+    /// A texture, material, or proxy of a model, or a texture of a material
+    Model,
+}
+
+#[derive(Serialize)]
+struct Reference {
+    from: String,
+    to: String,
+    kind: Kind,
+}
+
+#[derive(Serialize)]
+struct Output {
+    unused: Vec<String>,
+    references: Vec<Reference>,
+}
+
+struct File {
+    path: WorkspacePath,
+    addon: String,
+}
+
+#[derive(Default)]
+/// Every file in the project's addons, and how they use each other
+struct Usage {
+    files: Vec<File>,
+    /// Files by their lowercase path in game, `/z/abe/addons/main/fnc_x.sqf`
+    by_virtual: HashMap<String, usize>,
+    /// Files by their lowercase path in the project, `/addons/main/fnc_x.sqf`
+    by_path: HashMap<String, usize>,
+    references: Vec<(usize, usize, Kind)>,
+    /// Files reachable from the roots
+    used: HashSet<usize>,
+}
+
+/// Prints the files in the project's addons that nothing uses
+///
+/// # Errors
+/// [`Error`] if the project could not be loaded
+pub fn unused(args: &UnusedArgs) -> Result<Report, Error> {
+    let ctx = Context::new(Some("check"), PreservePrevious::Keep, false)?;
+    let (database, mut report) = database(&ctx)?;
+    let usage = Usage::build(&ctx, &database, &mut report)?;
+    let unused = usage.unused();
+    match args.format {
+        UnusedFormat::Text => {
+            if unused.is_empty() {
+                info!("Every file is used");
+            }
+            for file in &unused {
+                println!("{file}");
+            }
+        }
+        UnusedFormat::Json => {
+            let output = Output {
+                unused,
+                references: usage
+                    .references
+                    .iter()
+                    .map(|(from, to, kind)| Reference {
+                        from: display(&usage.files[*from].path),
+                        to: display(&usage.files[*to].path),
+                        kind: *kind,
+                    })
+                    .collect(),
+            };
+            println!("{}", serde_json::to_string_pretty(&output)?);
+        }
+    }
+    for code in usage.crossings(&database) {
+        report.push(code);
+    }
+    Ok(report)
+}
+
+impl Usage {
+    fn build(ctx: &Context, database: &Database, report: &mut Report) -> Result<Self, Error> {
+        let mut usage = Self::default();
+        for addon in ctx.addons() {
+            let mut paths = ctx.workspace_path().join(addon.folder())?.walk_dir()?;
+            paths.sort_by_key(|path| path.as_str().to_lowercase());
+            for path in paths {
+                if !path.is_file()? {
+                    continue;
+                }
+                let index = usage.files.len();
+                usage
+                    .by_virtual
+                    .insert(path.as_virtual_str().to_lowercase(), index);
+                usage.by_path.insert(path.as_str().to_lowercase(), index);
+                usage.files.push(File {
+                    path,
+                    addon: addon.name().to_string(),
+                });
+            }
+        }
+        let mut functions: HashMap<usize, Vec<usize>> = HashMap::new();
+        for (from, to) in usage.functions(database) {
+            functions.entry(from).or_default().push(to);
+        }
+        // Only files reachable from the roots are scanned, so anything only used by an unused
+        // file is unused as well
+        let mut queue = usage
+            .files
+            .iter()
+            .enumerate()
+            .filter(|(_, file)| is_root(&file.path))
+            .map(|(index, _)| index)
+            .collect::<VecDeque<_>>();
+        usage.used = queue.iter().copied().collect();
+        let mut known = HashSet::new();
+        while let Some(index) = queue.pop_front() {
+            let found = usage.scan(index, report)?.into_iter().chain(
+                functions
+                    .remove(&index)
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(|to| (to, Kind::Function)),
+            );
+            for (to, kind) in found {
+                if to == index || !known.insert((index, to, kind)) {
+                    continue;
+                }
+                usage.references.push((index, to, kind));
+                if usage.used.insert(to) {
+                    queue.push_back(to);
+                }
+            }
+        }
+        Ok(usage)
+    }
+
+    /// Files of a checked type that are not reachable from the roots
+    fn unused(&self) -> Vec<String> {
+        self.files
+            .iter()
+            .enumerate()
+            .filter(|(index, file)| {
+                !self.used.contains(index)
+                    && file
+                        .path
+                        .extension()
+                        .is_some_and(|ext| CHECKED.contains(&ext.to_lowercase().as_str()))
+            })
+            .map(|(_, file)| display(&file.path))
+            .collect()
+    }
+
+    /// The files used by a file
+    fn scan(&self, index: usize, report: &mut Report) -> Result<Vec<(usize, Kind)>, Error> {
+        let path = &self.files[index].path;
+        let extension = path.extension().unwrap_or_default().to_lowercase();
+        let mut found = Vec::new();
+        match extension.as_str() {
+            "cpp" | "sqf" | "ext" => {
+                let processed = match Processor::run(path) {
+                    Ok(processed) => processed,
+                    Err((_, hemtt_preprocessor::Error::Code(code))) => {
+                        report.push(code);
+                        return Ok(found);
+                    }
+                    Err((_, e)) => return Err(e.into()),
+                };
+                for included in processed.included() {
+                    if let Some(to) = self.by_path.get(&included.as_str().to_lowercase()) {
+                        found.push((*to, Kind::Include));
+                    }
+                }
+                found.extend(
+                    self.paths(processed.as_str())
+                        .into_iter()
+                        .map(|to| (to, Kind::Path)),
+                );
+            }
+            "rvmat" | "bikb" | "fsm" => {
+                if let Ok(content) = path.read_to_string() {
+                    found.extend(self.paths(&content).into_iter().map(|to| (to, Kind::Model)));
+                }
+            }
+            "p3d" => {
+                let mut buffer = Vec::new();
+                path.open_file()?.read_to_end(&mut buffer)?;
+                // Binarized models can not be read
+                let Ok(p3d) = hemtt_p3d::P3D::read(&mut std::io::Cursor::new(buffer)) else {
+                    return Ok(found);
+                };
+                let mut paths = Vec::new();
+                for lod in &p3d.lods {
+                    for face in &lod.faces {
+                        paths.push(face.texture.clone());
+                        paths.push(face.material.clone());
+                    }
+                    for (name, _) in &lod.taggs {
+                        if let Some(proxy) = name.strip_prefix("proxy:") {
+                            // `\z\abe\addons\main\proxy.001`, the number is the proxy's index
+                            let proxy = proxy.rsplit_once('.').map_or(proxy, |(path, _)| path);
+                            paths.push(format!("{proxy}.p3d"));
+                        }
+                    }
+                }
+                found.extend(
+                    self.paths(&paths.join("\n"))
+                        .into_iter()
+                        .map(|to| (to, Kind::Model)),
+                );
+            }
+            _ => {}
+        }
+        Ok(found)
+    }
+
+    /// Files of the project referenced by their path in game
+    fn paths(&self, text: &str) -> Vec<usize> {
+        let mut found = Vec::new();
+        for token in text.split(|c: char| {
+            c.is_whitespace()
+                || matches!(
+                    c,
+                    '"' | '\'' | ',' | ';' | '[' | ']' | '{' | '}' | '(' | ')' | '='
+                )
+        }) {
+            if !token.contains(['\\', '/']) {
+                continue;
+            }
+            let path = format!(
+                "/{}",
+                token
+                    .replace('\\', "/")
+                    .trim_start_matches('/')
+                    .to_lowercase()
+            );
+            // Models are often referenced without their extension
+            if let Some(index) = self
+                .by_virtual
+                .get(&path)
+                .or_else(|| self.by_virtual.get(&format!("{path}.p3d")))
+                .filter(|index| !found.contains(*index))
+            {
+                found.push(*index);
+            }
+        }
+        found
+    }
+
+    /// Functions in `CfgFunctions`, used by the addon's `config.cpp`
+    fn functions(&self, database: &Database) -> Vec<(usize, usize)> {
+        let mut functions = Vec::new();
+        for config in database.addons() {
+            let Some(from) = self.files.iter().position(|file| {
+                file.addon == config.addon()
+                    && file.path.filename().eq_ignore_ascii_case("config.cpp")
+            }) else {
+                continue;
+            };
+            let mut paths = Vec::new();
+            for tag in classes(&config.config().0, "cfgfunctions") {
+                for category in children(tag) {
+                    let folder = string(category, "file");
+                    for function in children(category) {
+                        let Some(name) = function.name() else {
+                            continue;
+                        };
+                        let extension =
+                            string(function, "ext").unwrap_or_else(|| ".sqf".to_string());
+                        if let Some(file) = string(function, "file") {
+                            paths.push(file);
+                        } else if let Some(folder) = &folder {
+                            paths.push(format!("{folder}\\fn_{}{extension}", name.as_str()));
+                        }
+                    }
+                }
+            }
+            for to in self.paths(&paths.join("\n")) {
+                functions.push((from, to));
+            }
+        }
+        functions
+    }
+
+    /// References between addons, where the addon using the file does not require the other
+    fn crossings(&self, database: &Database) -> Vec<Arc<dyn Code>> {
+        let mut codes: Vec<Arc<dyn Code>> = Vec::new();
+        let mut requires: HashMap<&str, HashSet<String>> = HashMap::new();
+        let mut seen = HashSet::new();
+        for (from, to, _) in &self.references {
+            if !seen.insert((from, to)) {
+                continue;
+            }
+            let (from, to) = (&self.files[*from], &self.files[*to]);
+            if from.addon == to.addon {
+                continue;
+            }
+            if requires
+                .entry(from.addon.as_str())
+                .or_insert_with(|| required(database, &from.addon))
+                .contains(&to.addon)
+            {
+                continue;
+            }
+            codes.push(Arc::new(RequiredMissing {
+                from: display(&from.path),
+                to: display(&to.path),
+                addon: from.addon.clone(),
+                required: to.addon.clone(),
+                patch: database
+                    .addons()
+                    .iter()
+                    .find(|config| config.addon() == to.addon)
+                    .and_then(|config| {
+                        config
+                            .patches()
+                            .first()
+                            .map(|patch| patch.as_str().to_string())
+                    }),
+            }));
+        }
+        codes
+    }
+}
+
+/// Every project addon an addon requires, directly or through another addon
+fn required(database: &Database, addon: &str) -> HashSet<String> {
+    let mut required = HashSet::new();
+    let mut queue = vec![addon.to_string()];
+    while let Some(addon) = queue.pop() {
+        for next in database.requires(&addon) {
+            if required.insert(next.to_string()) {
+                queue.push(next.to_string());
+            }
+        }
+    }
+    required
+}
+
+fn is_root(path: &WorkspacePath) -> bool {
+    let name = path.filename().to_lowercase();
+    ROOTS.contains(&name.as_str())
+        // Tests are run by `hemtt test`
+        || (path.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("sqf"))
+            && path.parent().filename().eq_ignore_ascii_case("tests"))
+}
+
+fn display(path: &WorkspacePath) -> String {
+    path.as_str().trim_start_matches('/').to_string()
+}
+
+fn classes<'a>(properties: &'a [Property], name: &str) -> Vec<&'a Class> {
+    properties
+        .iter()
+        .filter_map(|property| match property {
+            Property::Class(class @ Class::Local { name: found, .. })
+                if found.as_str().eq_ignore_ascii_case(name) =>
+            {
+                Some(children(class))
+            }
+            _ => None,
+        })
+        .flatten()
+        .collect()
+}
+
+fn children(class: &Class) -> Vec<&Class> {
+    match class {
+        Class::Local { properties, .. } => properties
+            .iter()
+            .filter_map(|property| match property {
+                Property::Class(class) => Some(class),
+                _ => None,
+            })
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+fn string(class: &Class, name: &str) -> Option<String> {
+    let Class::Local { properties, .. } = class else {
+        return None;
+    };
+    properties.iter().find_map(|property| match property {
+        Property::Entry {
+            name: found,
+            value: Value::Str(value),
+            ..
+        } if found.as_str().eq_ignore_ascii_case(name) => Some(value.value().to_string()),
+        _ => None,
+    })
+}
+
+pub struct RequiredMissing {
+    from: String,
+    to: String,
+    addon: String,
+    required: String,
+    patch: Option<String>,
+}
+
+impl Code for RequiredMissing {
+    fn ident(&self) -> &'static str {
+        // binary, utils, addons, warning 1
+        "BUAW1"
+    }
+
+    fn severity(&self) -> Severity {
+        Severity::Warning
+    }
+
+    fn message(&self) -> String {
+        format!(
+            "`{}` uses `{}`, but `{}` does not require `{}`",
+            self.from, self.to, self.addon, self.required
+        )
+    }
+
+    fn help(&self) -> Option<String> {
+        Some(self.patch.as_ref().map_or_else(
+            || {
+                format!(
+                    "add `{}` to the `requiredAddons` of `{}`",
+                    self.required, self.addon
+                )
+            },
+            |patch| format!("add `{patch}` to the `requiredAddons` of `{}`", self.addon),
+        ))
+    }
+
+    fn diagnostic(&self) -> Option<Diagnostic> {
+        Some(Diagnostic::from_code(self))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use sealed_test::prelude::*;
+
+    use super::{Kind, Usage};
+    use crate::{
+        context::{Context, PreservePrevious},
+        report::{Report, WithIncludes},
+        utils::config::database,
+    };
+
+    fn usage() -> (Usage, Report) {
+        std::env::set_current_dir(format!("{}/tests/delta", env!("CARGO_MANIFEST_DIR"))).unwrap();
+        let ctx = Context::new(Some("check"), PreservePrevious::Keep, false).unwrap();
+        let (database, mut report) = database(&ctx).unwrap();
+        let usage = Usage::build(&ctx, &database, &mut report).unwrap();
+        for code in usage.crossings(&database) {
+            report.push(code);
+        }
+        (usage, report)
+    }
+
+    fn reference(usage: &Usage, from: &str, to: &str) -> Option<Kind> {
+        usage.references.iter().find_map(|(f, t, kind)| {
+            (super::display(&usage.files[*f].path) == from
+                && super::display(&usage.files[*t].path) == to)
+                .then_some(*kind)
+        })
+    }
+
+    #[sealed_test]
+    fn unused() {
+        let (usage, _) = usage();
+        let mut unused = usage.unused();
+        unused.sort();
+        assert_eq!(
+            unused,
+            vec![
+                "addons/main/helper.sqf",
+                "addons/main/unused.sqf",
+                "addons/other/data/orphan.paa",
+            ]
+        );
+    }
+
+    #[sealed_test]
+    fn roots() {
+        let (usage, _) = usage();
+        for root in [
+            "addons/main/config.cpp",
+            "addons/main/$PBOPREFIX$",
+            "addons/main/tests/test_main.sqf",
+            "addons/other/config.cpp",
+        ] {
+            let index = usage
+                .files
+                .iter()
+                .position(|file| super::display(&file.path) == root)
+                .unwrap();
+            assert!(usage.used.contains(&index), "{root} is not a root");
+        }
+    }
+
+    #[sealed_test]
+    fn references() {
+        let (usage, _) = usage();
+        assert_eq!(
+            reference(
+                &usage,
+                "addons/main/config.cpp",
+                "addons/main/functions/fn_used.sqf"
+            ),
+            Some(Kind::Function)
+        );
+        assert_eq!(
+            reference(
+                &usage,
+                "addons/main/functions/fn_used.sqf",
+                "addons/main/script_component.hpp"
+            ),
+            Some(Kind::Include)
+        );
+        assert_eq!(
+            reference(
+                &usage,
+                "addons/main/tests/test_main.sqf",
+                "addons/main/tests/test_macros.hpp"
+            ),
+            Some(Kind::Include)
+        );
+        assert_eq!(
+            reference(
+                &usage,
+                "addons/other/config.cpp",
+                "addons/main/data/icon.paa"
+            ),
+            Some(Kind::Path)
+        );
+        // Only files reachable from the roots are scanned
+        assert_eq!(
+            reference(&usage, "addons/main/unused.sqf", "addons/main/helper.sqf"),
+            None
+        );
+    }
+
+    #[sealed_test]
+    fn required_missing() {
+        let (_, report) = usage();
+        assert!(report.errors().is_empty());
+        let codes = report
+            .warnings(WithIncludes::Yes)
+            .iter()
+            .filter(|code| code.ident() == "BUAW1")
+            .map(|code| code.message())
+            .collect::<Vec<_>>();
+        // `other` requires `main`, but `main` does not require `other`
+        assert_eq!(
+            codes,
+            vec![
+                "`addons/main/functions/fn_used.sqf` uses `addons/other/data/shared.paa`, but `main` does not require `other`"
+            ]
+        );
+    }
+}
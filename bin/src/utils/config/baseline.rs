@@ -0,0 +1,178 @@
+use std::{
+    io::{BufReader, Cursor, Read},
+    path::{Path, PathBuf},
+};
+
+use hemtt_config::{
+    Config,
+    database::{AddonConfig, read_baseline},
+    rapify::{Derapify, Rapify},
+};
+use hemtt_pbo::ReadablePbo;
+
+use crate::{
+    Error,
+    context::{Context, PreservePrevious},
+    report::Report,
+};
+
+#[derive(clap::Parser)]
+#[command(arg_required_else_help = true)]
+/// Configs from the game or other mods, used to check the project against
+pub struct Command {
+    #[command(subcommand)]
+    commands: Subcommands,
+}
+
+#[derive(clap::Subcommand)]
+enum Subcommands {
+    /// Import configs into the baseline
+    Import(ImportArgs),
+    /// List the addons in the baseline
+    List,
+    /// Remove every addon from the baseline
+    Clear,
+}
+
+#[derive(clap::Args)]
+#[allow(clippy::module_name_repetitions)]
+pub struct ImportArgs {
+    /// PBOs, rapified `config.bin` files, config dumps, or folders containing PBOs
+    #[arg(required = true)]
+    paths: Vec<String>,
+}
+
+/// Execute the baseline command
+///
+/// # Errors
+/// [`Error`] if the project could not be loaded, or a config could not be read
+pub fn execute(cmd: &Command) -> Result<Report, Error> {
+    let ctx = Context::new(None, PreservePrevious::Keep, false)?;
+    let folder = ctx.baseline_folder();
+    match &cmd.commands {
+        Subcommands::Import(args) => return import(&folder, args),
+        Subcommands::List => list(&folder)?,
+        Subcommands::Clear => {
+            if folder.exists() {
+                std::fs::remove_dir_all(&folder)?;
+            }
+            info!("Cleared the baseline");
+        }
+    }
+    Ok(Report::new())
+}
+
+fn import(folder: &Path, args: &ImportArgs) -> Result<Report, Error> {
+    let mut report = Report::new();
+    let mut configs = Vec::new();
+    for path in &args.paths {
+        let path = PathBuf::from(path);
+        if path.is_dir() {
+            for entry in walkdir::WalkDir::new(&path).sort_by_file_name() {
+                let entry = entry?;
+                if entry
+                    .path()
+                    .extension()
+                    .is_some_and(|ext| ext.eq_ignore_ascii_case("pbo"))
+                {
+                    configs.extend(from_pbo(entry.path())?);
+                }
+            }
+            continue;
+        }
+        let extension = path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .map(str::to_lowercase);
+        match extension.as_deref() {
+            Some("pbo") => configs.extend(from_pbo(&path)?),
+            Some("bin") => {
+                let mut file = BufReader::new(std::fs::File::open(&path)?);
+                configs.push((name_of(&path), Config::derapify(&mut file)?));
+            }
+            _ if !path.is_file() => {
+                return Err(std::io::Error::new(
+                    std::io::ErrorKind::NotFound,
+                    format!("{} was not found", path.display()),
+                )
+                .into());
+            }
+            _ => match super::inspect::get_report(&path)? {
+                Ok(parsed) => configs.push((name_of(&path), parsed.into_config())),
+                Err(codes) => report.extend(codes),
+            },
+        }
+    }
+    std::fs::create_dir_all(folder)?;
+    for (name, config) in configs {
+        let mut output = Vec::with_capacity(config.rapified_length());
+        config.rapify(&mut output, 0)?;
+        std::fs::write(folder.join(format!("{name}.bin")), output)?;
+        let patches = AddonConfig::baseline(&name, config).patches().len();
+        info!("Imported `{name}` into the baseline, with {patches} patches");
+    }
+    Ok(report)
+}
+
+fn list(folder: &Path) -> Result<(), Error> {
+    let baseline = read_baseline(folder)?;
+    if baseline.is_empty() {
+        info!("The baseline is empty");
+    }
+    for config in baseline {
+        let patches = config.patches();
+        println!("{} ({} patches)", config.addon(), patches.len());
+        for patch in patches {
+            println!("  {}", patch.as_str());
+        }
+    }
+    Ok(())
+}
+
+/// The `config.bin` files of a PBO, named after the PBO
+pub(super) fn from_pbo(path: &Path) -> Result<Vec<(String, Config)>, Error> {
+    let mut pbo = ReadablePbo::from(std::fs::File::open(path)?)?;
+    let name = name_of(path);
+    let configs = pbo
+        .files()
+        .into_iter()
+        .filter(|header| {
+            header
+                .filename()
+                .to_lowercase()
+                .rsplit('\\')
+                .next()
+                .is_some_and(|file| file == "config.bin")
+        })
+        .map(|header| header.filename().to_string())
+        .collect::<Vec<_>>();
+    let mut found = Vec::with_capacity(configs.len());
+    for (index, file) in configs.iter().enumerate() {
+        let Some(mut reader) = pbo.file(file)? else {
+            continue;
+        };
+        let mut buffer = Vec::new();
+        reader.read_to_end(&mut buffer)?;
+        let config = Config::derapify(&mut Cursor::new(buffer))?;
+        // Nested configs are loaded by the game as well
+        if index == 0 {
+            found.push((name.clone(), config));
+        } else {
+            found.push((format!("{name}_{index}"), config));
+        }
+    }
+    Ok(found)
+}
+
+/// The addon name for a file, `config.bin` files are named after their folder
+fn name_of(path: &Path) -> String {
+    let stem = path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+    path.parent()
+        .and_then(|parent| parent.file_name())
+        .filter(|_| stem == "config")
+        .map(|folder| folder.to_string_lossy().to_lowercase())
+        .unwrap_or(stem)
+}
@@ -0,0 +1,114 @@
+use std::{
+    io::BufReader,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use hemtt_config::{Config, compare::compare, rapify::Derapify};
+use hemtt_workspace::reporting::{Code, Diagnostic};
+
+use crate::{Error, report::Report};
+
+#[derive(clap::Args)]
+#[allow(clippy::module_name_repetitions)]
+pub struct CompareArgs {
+    /// First config, a `config.cpp`, `config.bin`, or PBO
+    pub(crate) left: String,
+    /// Second config, a `config.cpp`, `config.bin`, or PBO
+    pub(crate) right: String,
+}
+
+/// Prints the structural differences between two configs
+///
+/// Formatting and how numbers are encoded are ignored, so a rapified config
+/// can be compared with its source, or with one built by another tool.
+///
+/// # Errors
+/// [`Error`] if either config could not be read
+pub fn execute(args: &CompareArgs) -> Result<Report, Error> {
+    let mut report = Report::new();
+    let (Some(left), Some(right)) = (
+        load(Path::new(&args.left), &mut report)?,
+        load(Path::new(&args.right), &mut report)?,
+    ) else {
+        return Ok(report);
+    };
+    let differences = compare(&left, &right);
+    for difference in &differences {
+        println!("{}", difference.describe(&args.left, &args.right));
+    }
+    if differences.is_empty() {
+        info!("The configs are the same");
+    } else {
+        report.push(ConfigsDiffer::code(differences.len()));
+    }
+    Ok(report)
+}
+
+/// Load a config, rapified, from the root of a PBO, or from source
+fn load(path: &Path, report: &mut Report) -> Result<Option<Config>, Error> {
+    if !path.is_file() {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::NotFound,
+            format!("{} was not found", path.display()),
+        )
+        .into());
+    }
+    let extension = path
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .map(str::to_lowercase);
+    match extension.as_deref() {
+        Some("pbo") => {
+            let Some((_, config)) = super::baseline::from_pbo(path)?.into_iter().next() else {
+                return Err(std::io::Error::new(
+                    std::io::ErrorKind::NotFound,
+                    format!("{} does not contain a config.bin", path.display()),
+                )
+                .into());
+            };
+            Ok(Some(config))
+        }
+        Some("bin") => {
+            let mut file = BufReader::new(std::fs::File::open(path)?);
+            Ok(Some(Config::derapify(&mut file)?))
+        }
+        _ => match super::inspect::get_report(&PathBuf::from(path))? {
+            Ok(parsed) => Ok(Some(parsed.into_config())),
+            Err(codes) => {
+                report.extend(codes);
+                Ok(None)
+            }
+        },
+    }
+}
+
+pub struct ConfigsDiffer {
+    count: usize,
+}
+
+impl ConfigsDiffer {
+    #[must_use]
+    pub fn code(count: usize) -> Arc<dyn Code> {
+        Arc::new(Self { count })
+    }
+}
+
+impl Code for ConfigsDiffer {
+    fn ident(&self) -> &'static str {
+        // binary, utils, config, error 2
+        "BUCE2"
+    }
+
+    fn message(&self) -> String {
+        format!(
+            "the configs have {} difference{}",
+            self.count,
+            if self.count == 1 { "" } else { "s" }
+        )
+    }
+
+    fn diagnostic(&self) -> Option<Diagnostic> {
+        Some(Diagnostic::from_code(self))
+    }
+}
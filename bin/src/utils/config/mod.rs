@@ -1,11 +1,15 @@
 use std::path::PathBuf;
 
-use crate::Error;
+use crate::{Error, report::Report};
 
+mod baseline;
+mod compare;
 mod derapify;
 mod inspect;
+mod query;
 
 pub use inspect::inspect;
+pub use query::{addon_config, database};
 
 #[derive(clap::Parser)]
 #[command(arg_required_else_help = true)]
@@ -17,10 +21,16 @@ pub struct Command {
 
 #[derive(clap::Subcommand)]
 enum Subcommands {
+    /// Configs from the game or other mods, used to check the project against
+    Baseline(baseline::Command),
+    /// Compare two configs, ignoring formatting
+    Compare(compare::CompareArgs),
     /// Derapify a config file
     Derapify(derapify::DerapifyArgs),
     /// Inspect a config file
     Inspect(inspect::InspectArgs),
+    /// Query the project's config, after every addon has been merged
+    Query(query::QueryArgs),
 }
 
 /// Execute the config command
@@ -30,11 +40,15 @@ enum Subcommands {
 ///
 /// # Panics
 /// If the args are not present from clap
-pub fn execute(cmd: &Command) -> Result<(), Error> {
+pub fn execute(cmd: &Command) -> Result<Report, Error> {
     match &cmd.commands {
+        Subcommands::Baseline(cmd) => return baseline::execute(cmd),
+        Subcommands::Compare(args) => return compare::execute(args),
         Subcommands::Derapify(args) => {
-            derapify::derapify(&PathBuf::from(&args.file), args.output.as_deref())
+            derapify::derapify(&PathBuf::from(&args.file), args.output.as_deref())?;
         }
-        Subcommands::Inspect(args) => inspect::inspect(&PathBuf::from(&args.config)),
+        Subcommands::Inspect(args) => inspect::inspect(&PathBuf::from(&args.config))?,
+        Subcommands::Query(args) => return query::query(args),
     }
+    Ok(Report::new())
 }
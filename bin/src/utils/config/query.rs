@@ -0,0 +1,180 @@
+use std::sync::Arc;
+
+use hemtt_config::{
+    Value,
+    database::{AddonConfig, ClassRef, Database, Found, PropertyRef, read_baseline},
+};
+use hemtt_preprocessor::Processor;
+use hemtt_workspace::{
+    addons::Addon,
+    reporting::{Code, Diagnostic},
+};
+
+use crate::{
+    Error,
+    context::{Context, PreservePrevious},
+    report::Report,
+};
+
+#[derive(clap::Args)]
+#[allow(clippy::module_name_repetitions)]
+pub struct QueryArgs {
+    /// Path of the class or property, `CfgVehicles >> MyTruck >> maxSpeed`
+    pub(crate) query: String,
+}
+
+/// Prints the effective value of a class or property, after every addon has been merged
+///
+/// # Errors
+/// [`Error`] if the project could not be loaded
+pub fn query(args: &QueryArgs) -> Result<Report, Error> {
+    let ctx = Context::new(Some("check"), PreservePrevious::Keep, false)?;
+    let (database, mut report) = database(&ctx)?;
+    match database.query(&args.query) {
+        Some(Found::Class(class)) => print_class(&class),
+        Some(Found::Property(property)) => print_property(&property),
+        None => report.push(NotFound::code(args.query.clone())),
+    }
+    Ok(report)
+}
+
+/// Merge the `config.cpp` of every addon in the project, on top of the baseline
+///
+/// Addons that fail to preprocess or parse are left out, with their errors in the report
+///
+/// # Errors
+/// [`Error`] if a config could not be read
+pub fn database(ctx: &Context) -> Result<(Database, Report), Error> {
+    let mut report = Report::new();
+    let mut configs = read_baseline(&ctx.baseline_folder())?;
+    for addon in ctx.addons() {
+        configs.extend(addon_config(ctx, addon, &mut report)?);
+    }
+    Ok((Database::new(configs), report))
+}
+
+/// The parsed `config.cpp` of an addon, `None` if it has none or it fails to preprocess or parse
+///
+/// # Errors
+/// [`Error`] if the config could not be read
+pub fn addon_config(
+    ctx: &Context,
+    addon: &Addon,
+    report: &mut Report,
+) -> Result<Option<AddonConfig>, Error> {
+    let path = ctx
+        .workspace_path()
+        .join(addon.folder())?
+        .join("config.cpp")?;
+    if !path.exists()? {
+        return Ok(None);
+    }
+    let processed = match Processor::run(&path) {
+        Ok(processed) => processed,
+        Err((_, hemtt_preprocessor::Error::Code(code))) => {
+            report.push(code);
+            return Ok(None);
+        }
+        Err((_, e)) => return Err(e.into()),
+    };
+    match hemtt_config::parse(Some(ctx.config()), &processed) {
+        Ok(parsed) if parsed.errors().is_empty() => Ok(Some(AddonConfig::new(
+            addon.name(),
+            parsed.into_config(),
+            Some(&processed),
+        ))),
+        Ok(parsed) => {
+            report.extend(parsed.errors().into_iter().cloned().collect());
+            Ok(None)
+        }
+        Err(codes) => {
+            report.extend(codes);
+            Ok(None)
+        }
+    }
+}
+
+fn print_class(class: &ClassRef) {
+    match class.parent_name() {
+        Some(parent) => println!("class {}: {parent}", class.path()),
+        None => println!("class {}", class.path()),
+    }
+    if class.is_external() {
+        println!("  declared, but not defined by the project or the baseline");
+    }
+    for definition in class.definitions() {
+        println!("  defined in {definition}");
+    }
+    let mut parent = class.parent();
+    while let Some(current) = parent {
+        println!("  inherits {}", current.path());
+        parent = current.parent();
+    }
+    for property in class.properties() {
+        println!(
+            "  {} // {}",
+            assignment(&property),
+            origin(class, &property)
+        );
+    }
+    for child in class.classes() {
+        match child.parent_name() {
+            Some(parent) => println!("  class {}: {parent}", child.name()),
+            None => println!("  class {}", child.name()),
+        }
+    }
+}
+
+fn print_property(property: &PropertyRef) {
+    println!("{} >> {}", property.owner().path(), assignment(property));
+    println!("  defined in {}", property.definition());
+}
+
+fn assignment(property: &PropertyRef) -> String {
+    match property.value() {
+        Value::Array(_) => format!("{}[] = {};", property.name(), property.value()),
+        value => format!("{} = {value};", property.name()),
+    }
+}
+
+fn origin(class: &ClassRef, property: &PropertyRef) -> String {
+    if property.owner().path() == class.path() {
+        property.definition().to_string()
+    } else {
+        format!(
+            "inherited from {}, {}",
+            property.owner().path(),
+            property.definition()
+        )
+    }
+}
+
+pub struct NotFound {
+    query: String,
+}
+
+impl NotFound {
+    #[must_use]
+    pub fn code(query: String) -> Arc<dyn Code> {
+        Arc::new(Self { query })
+    }
+}
+
+impl Code for NotFound {
+    fn ident(&self) -> &'static str {
+        // binary, utils, config, error 1
+        "BUCE1"
+    }
+
+    fn message(&self) -> String {
+        format!("`{}` was not found in the project's config", self.query)
+    }
+
+    fn help(&self) -> Option<String> {
+        Some("classes are separated by `>>`, `CfgVehicles >> MyTruck >> maxSpeed`".to_string())
+    }
+
+    fn diagnostic(&self) -> Option<Diagnostic> {
+        Some(Diagnostic::from_code(self))
+    }
+}
@@ -1,3 +1,4 @@
+pub mod addons;
 pub mod audio;
 pub mod bom;
 pub mod config;
@@ -7,8 +8,10 @@ pub mod p3d;
 pub mod paa;
 pub mod pbo;
 pub mod photoshoot;
+pub mod rpt;
 pub mod sqf;
 pub mod verify;
+pub mod verify_mod;
 
 #[must_use]
 #[allow(clippy::while_float)]
@@ -22,6 +22,7 @@ pub struct PboInspectArgs {
 pub struct FileInfo {
     filename: String,
     mime: String,
+    #[tabled(rename = "packed")]
     size: u32,
     original: u32,
     timestamp: u32,
@@ -54,13 +55,31 @@ pub fn inspect(file: File, format: &TableFormat) -> Result<(), Error> {
         println!("  - Sorted: false !!!");
     }
     println!("  - Count: {}", files.len());
+    let compressed = files
+        .iter()
+        .filter(|file| file.is_compressed())
+        .collect::<Vec<_>>();
+    if !compressed.is_empty() {
+        println!(
+            "  - Compressed: {} ({} bytes packed, {} bytes original)",
+            compressed.len(),
+            compressed
+                .iter()
+                .map(|file| u64::from(file.size()))
+                .sum::<u64>(),
+            compressed
+                .iter()
+                .map(|file| u64::from(file.original()))
+                .sum::<u64>()
+        );
+    }
     let data = files
         .iter()
         .map(|file| FileInfo {
             filename: file.filename().to_string(),
             mime: file.mime().to_string(),
             size: file.size(),
-            original: file.original(),
+            original: file.unpacked_size(),
             timestamp: file.timestamp(),
         })
         .collect::<Vec<_>>();
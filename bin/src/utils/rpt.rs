@@ -0,0 +1,424 @@
+use std::{
+    ops::Range,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use hemtt_common::arma::rpt::{self, Entry, Group, Level};
+use hemtt_common::config::PDriveOption;
+use hemtt_preprocessor::Processor;
+use hemtt_workspace::{
+    Workspace, WorkspacePath,
+    addons::Addon,
+    reporting::{Code, Diagnostic, Label, Severity},
+};
+
+use crate::{Error, report::Report};
+
+#[derive(clap::Parser)]
+#[command(arg_required_else_help = true)]
+/// Map errors in an RPT file back to the project source
+///
+/// Parses an Arma 3 `.rpt` file, groups identical script errors and warnings,
+/// and points them at the file, line and macros in the project that produced them.
+/// Files are read from the most recent build in `.hemttout`, as the game ran them.
+pub struct Command {
+    /// RPT file, or a folder to use the newest RPT from
+    rpt: String,
+}
+
+/// Execute the rpt command
+///
+/// # Errors
+/// [`Error`] depending on the modules
+pub fn execute(cmd: &Command) -> Result<Report, Error> {
+    let path = find_rpt(Path::new(&cmd.rpt))?;
+    debug!("Reading RPT: {:?}", &path);
+    let content = String::from_utf8_lossy(&std::fs::read(&path)?).to_string();
+    let groups = rpt::group(rpt::parse(&content));
+
+    let build = if Path::new(".hemtt").join("project.toml").exists() {
+        let build = Build::last(Path::new(".hemttout"))?;
+        if build.is_none() {
+            warn!("No build found in .hemttout, files will not be mapped to their source");
+        }
+        build
+    } else {
+        warn!("Not in a HEMTT project, files will not be mapped to their source");
+        None
+    };
+
+    let errors = groups
+        .iter()
+        .filter(|g| g.entry().level() == Level::Error)
+        .count();
+    info!(
+        "{} unique errors and {} unique warnings in {}",
+        errors,
+        groups.len() - errors,
+        path.display()
+    );
+
+    let mut report = Report::new();
+    for group in groups {
+        let Some(build) = &build else {
+            report.push(RptEntry::code(None, group));
+            continue;
+        };
+        let mut entry = RptEntry::new(Some(&build.workspace), group);
+        if let Some(location) = &mut entry.location {
+            location.source = build.source(&location.file);
+        }
+        report.push(Arc::new(entry));
+    }
+    Ok(report)
+}
+
+/// The PBOs of the most recent build, the files as the game ran them
+struct Build {
+    folder: PathBuf,
+    workspace: WorkspacePath,
+    addons: Vec<Addon>,
+}
+
+impl Build {
+    /// The build folder in `.hemttout` with the newest PBO
+    fn last(out: &Path) -> Result<Option<Self>, Error> {
+        if !out.is_dir() {
+            return Ok(None);
+        }
+        let mut newest: Option<(std::time::SystemTime, PathBuf)> = None;
+        for entry in std::fs::read_dir(out)? {
+            let folder = entry?.path();
+            let modified = walkdir::WalkDir::new(&folder)
+                .into_iter()
+                .filter_map(Result::ok)
+                .filter(|entry| {
+                    entry
+                        .path()
+                        .extension()
+                        .is_some_and(|ext| ext.eq_ignore_ascii_case("pbo"))
+                })
+                .filter_map(|entry| entry.metadata().ok()?.modified().ok())
+                .max();
+            let Some(modified) = modified else {
+                continue;
+            };
+            if newest.as_ref().is_none_or(|(time, _)| modified > *time) {
+                newest = Some((modified, folder));
+            }
+        }
+        let Some((_, folder)) = newest else {
+            return Ok(None);
+        };
+        debug!("Using the build in {}", folder.display());
+        let workspace =
+            Workspace::builder()
+                .pbos(&folder)?
+                .finish(None, false, &PDriveOption::Disallow)?;
+        Ok(Some(Self {
+            folder,
+            workspace,
+            addons: Addon::scan(Path::new("."))?,
+        }))
+    }
+
+    /// The project file a built file was packed from
+    fn source(&self, file: &WorkspacePath) -> Option<String> {
+        let path = file.as_str().trim_start_matches('/').replace('/', "\\");
+        let addon = self.addons.iter().find(|addon| {
+            path.to_lowercase()
+                .starts_with(&format!("{}\\", addon.prefix().to_string().to_lowercase()))
+        })?;
+        Some(format!(
+            "built from `{}/{}` in {}",
+            addon.folder(),
+            path[addon.prefix().to_string().len() + 1..].replace('\\', "/"),
+            self.folder.display()
+        ))
+    }
+}
+
+/// Use the file, or the newest `.rpt` in the folder
+fn find_rpt(path: &Path) -> Result<PathBuf, Error> {
+    if !path.is_dir() {
+        return Ok(path.to_path_buf());
+    }
+    let mut newest: Option<(std::time::SystemTime, PathBuf)> = None;
+    for entry in std::fs::read_dir(path)? {
+        let entry = entry?.path();
+        if entry
+            .extension()
+            .is_none_or(|ext| !ext.eq_ignore_ascii_case("rpt"))
+        {
+            continue;
+        }
+        let modified = entry.metadata()?.modified()?;
+        if newest.as_ref().is_none_or(|(time, _)| modified > *time) {
+            newest = Some((modified, entry));
+        }
+    }
+    newest.map(|(_, path)| path).ok_or_else(|| {
+        std::io::Error::new(
+            std::io::ErrorKind::NotFound,
+            format!("No RPT files found in {}", path.display()),
+        )
+        .into()
+    })
+}
+
+/// An RPT entry, mapped to the project source when possible
+pub struct RptEntry {
+    group: Group,
+    location: Option<Location>,
+}
+
+struct Location {
+    file: WorkspacePath,
+    span: Range<usize>,
+    macros: Vec<String>,
+    /// The project file, when the entry is located in a build
+    source: Option<String>,
+}
+
+impl RptEntry {
+    #[must_use]
+    pub fn code(workspace: Option<&WorkspacePath>, group: Group) -> Arc<dyn Code> {
+        Arc::new(Self::new(workspace, group))
+    }
+
+    fn new(workspace: Option<&WorkspacePath>, group: Group) -> Self {
+        let location = workspace.and_then(|workspace| locate(workspace, group.entry()));
+        Self { group, location }
+    }
+}
+
+impl Code for RptEntry {
+    fn ident(&self) -> &'static str {
+        match self.group.entry().level() {
+            // binary, utils, rpt, error 1
+            Level::Error => "BURE1",
+            // binary, utils, rpt, warning 1
+            Level::Warning => "BURW1",
+        }
+    }
+
+    fn severity(&self) -> Severity {
+        match self.group.entry().level() {
+            Level::Error => Severity::Error,
+            Level::Warning => Severity::Warning,
+        }
+    }
+
+    fn message(&self) -> String {
+        let message = self.group.entry().message();
+        if message.is_empty() {
+            "script error".to_string()
+        } else {
+            message.to_string()
+        }
+    }
+
+    fn label_message(&self) -> String {
+        self.group
+            .entry()
+            .position()
+            .and_then(|p| p.lines().next())
+            .map_or_else(
+                || "error occurred here".to_string(),
+                |p| format!("`{}`", truncate(p)),
+            )
+    }
+
+    fn note(&self) -> Option<String> {
+        let entry = self.group.entry();
+        let mut notes = Vec::new();
+        if self.location.is_none() {
+            if let (Some(file), Some(line)) = (entry.file(), entry.line()) {
+                notes.push(format!("in {file}, line {line}"));
+            } else if let Some(expression) = entry.expression() {
+                notes.push(format!("in expression `{}`", truncate(expression)));
+            }
+        }
+        if self.group.count() > 1 {
+            notes.push(format!(
+                "occurred {} times, from {} to {}",
+                self.group.count(),
+                self.group.first().unwrap_or("?"),
+                self.group.last().unwrap_or("?")
+            ));
+        }
+        if notes.is_empty() {
+            None
+        } else {
+            Some(notes.join("\n"))
+        }
+    }
+
+    fn diagnostic(&self) -> Option<Diagnostic> {
+        let mut diag = Diagnostic::from_code(self);
+        if let Some(location) = &self.location {
+            diag = diag
+                .with_label(
+                    Label::primary(location.file.clone(), location.span.clone())
+                        .with_message(self.label_message()),
+                )
+                .with_notes(
+                    location
+                        .source
+                        .iter()
+                        .chain(&location.macros)
+                        .cloned()
+                        .collect(),
+                );
+        }
+        Some(diag)
+    }
+}
+
+/// Locate the entry in the project, expanding any macros used on the line
+fn locate(workspace: &WorkspacePath, entry: &Entry) -> Option<Location> {
+    let line = entry.line()?;
+    let file = workspace.locate(entry.file()?).ok()??.path;
+    let content = file.read_to_string().ok()?;
+    let line_start = content
+        .split_inclusive('\n')
+        .take(line.checked_sub(1)?)
+        .map(str::len)
+        .sum::<usize>();
+    let line_text = content.get(line_start..)?.lines().next()?;
+    let indent = line_text.len() - line_text.trim_start().len();
+    let mut span = line_start + indent..line_start + line_text.trim_end().len();
+
+    let needle = entry
+        .position()
+        .and_then(|p| p.split_whitespace().next())
+        .map(|p| p.trim_end_matches(';'))
+        .filter(|p| !p.is_empty());
+    let found = needle.and_then(|needle| line_text.find(needle).map(|i| (i, needle.len())));
+    if let Some((offset, len)) = found {
+        span = line_start + offset..line_start + offset + len;
+    }
+
+    let mut macros = Vec::new();
+    let processed = if file.extension().as_deref() == Some("sqf") {
+        Processor::run(&file).ok()
+    } else {
+        None
+    };
+    if let Some(processed) = processed {
+        for mapping in processed
+            .mappings_on_line(&file, line)
+            .into_iter()
+            .filter(|m| m.was_macro())
+        {
+            let expanded = processed
+                .extract(mapping.processed_start().offset()..mapping.processed_end().offset());
+            if found.is_none() && needle.is_some_and(|n| expanded.contains(n)) {
+                span = mapping.original_start()..mapping.original_end();
+            }
+            macros.push(format!(
+                "`{}` expands to `{}`",
+                mapping.token().to_source(),
+                truncate(&expanded)
+            ));
+        }
+    }
+
+    Some(Location {
+        file,
+        span,
+        macros,
+        source: None,
+    })
+}
+
+fn truncate(code: &str) -> String {
+    let code = code.split_whitespace().collect::<Vec<_>>().join(" ");
+    if code.chars().count() > 80 {
+        format!("{}...", code.chars().take(77).collect::<String>())
+    } else {
+        code
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use std::{fs::File, io::Cursor, path::Path};
+
+    use hemtt_pbo::WritablePbo;
+    use hemtt_workspace::reporting::WorkspaceFiles;
+    use sealed_test::prelude::*;
+
+    use super::{Command, execute};
+
+    const RPT: &str = r" 8:12:19 Error in expression <_b = _z + 1;>
+ 8:12:19   Error position: <_z + 1;>
+ 8:12:19   Error Undefined variable in expression: _z
+ 8:12:19 File \x\abe\addons\main\functions\fnc_test.sqf..., line 2
+";
+
+    fn project() {
+        std::fs::create_dir_all(".hemtt").unwrap();
+        std::fs::write(".hemtt/project.toml", "name = \"Test\"\nprefix = \"abe\"\n").unwrap();
+        std::fs::create_dir_all("addons/main/functions").unwrap();
+        std::fs::write("addons/main/$PBOPREFIX$", "x\\abe\\addons\\main").unwrap();
+        // changed since the build
+        std::fs::write(
+            "addons/main/functions/fnc_test.sqf",
+            "params [\"_a\"];\n\n\nprivate _b = _a + 1;\n",
+        )
+        .unwrap();
+        std::fs::write("abe.rpt", RPT).unwrap();
+    }
+
+    fn rendered() -> String {
+        let report = execute(&Command {
+            rpt: "abe.rpt".to_string(),
+        })
+        .unwrap();
+        let errors = report.errors();
+        assert_eq!(errors.len(), 1);
+        errors[0]
+            .diagnostic()
+            .unwrap()
+            .to_string(&WorkspaceFiles::new())
+    }
+
+    #[sealed_test]
+    fn last_build() {
+        project();
+        std::fs::create_dir_all(".hemttout/build/addons").unwrap();
+        let mut pbo = WritablePbo::new();
+        pbo.add_property("prefix", "x\\abe\\addons\\main");
+        pbo.add_file(
+            "functions\\fnc_test.sqf",
+            Cursor::new(b"params [\"_a\"];\n_b = _z + 1;\n".to_vec()),
+        )
+        .unwrap();
+        pbo.write(
+            &mut File::create(".hemttout/build/addons/abe_main.pbo").unwrap(),
+            true,
+        )
+        .unwrap();
+
+        let rendered = rendered();
+        // the line as it was built, not as it is now
+        assert!(
+            rendered.contains("x/abe/addons/main/functions/fnc_test.sqf:2:6"),
+            "{rendered}"
+        );
+        assert!(rendered.contains("built from `addons/main/functions/fnc_test.sqf`"));
+        assert!(!Path::new(".hemttout/dev").exists());
+    }
+
+    #[sealed_test]
+    fn no_build() {
+        project();
+        let rendered = rendered();
+        assert!(rendered.contains(r"in \x\abe\addons\main\functions\fnc_test.sqf, line 2"));
+        assert!(!Path::new(".hemttout").exists());
+    }
+}
@@ -0,0 +1,630 @@
+use std::{
+    fs::File,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use hemtt_common::BISignVersion;
+use hemtt_pbo::ReadablePbo;
+use hemtt_signing::{BIPublicKey, BISign};
+use hemtt_workspace::reporting::{Code, Diagnostic, Severity};
+use serde::Serialize;
+
+use crate::{Error, report::Report};
+
+#[derive(clap::Parser)]
+#[command(arg_required_else_help = true)]
+/// Verify every signed PBO in a mod folder
+///
+/// Checks the signatures of every PBO in the mod against the keys
+/// a server accepts, for deploying mods to a server
+pub struct Command {
+    /// Mod folder to verify
+    folder: String,
+
+    #[arg(long)]
+    /// Folder of `.bikey` files to verify against, defaults to the `keys` folder of the mod
+    keys: Option<String>,
+
+    #[arg(long, default_value = "text")]
+    /// Output format
+    format: VerifyFormat,
+}
+
+#[derive(clap::ValueEnum, Clone, Copy, Debug)]
+pub enum VerifyFormat {
+    /// A line for each signature, ideal for humans
+    Text,
+    /// json, ideal for machines
+    Json,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+enum Status {
+    /// The signature matches the PBO
+    Valid,
+    /// No key has the authority of the signature
+    UnknownAuthority,
+    /// The signature is for different contents, usually an older version of the PBO
+    Stale,
+    /// A key has the authority of the signature, but none is the key that made it
+    KeyMismatch,
+    /// The signature could not be read or verified
+    Invalid,
+}
+
+#[derive(Serialize)]
+struct Signature {
+    authority: String,
+    version: Option<BISignVersion>,
+    status: Status,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    reason: Option<String>,
+}
+
+#[derive(Serialize)]
+struct Pbo {
+    path: String,
+    signatures: Vec<Signature>,
+}
+
+#[derive(Serialize)]
+struct Key {
+    authority: String,
+    used: bool,
+}
+
+#[derive(Serialize)]
+struct Output {
+    valid: bool,
+    pbos: Vec<Pbo>,
+    keys: Vec<Key>,
+}
+
+/// Execute the verify-mod command
+///
+/// # Errors
+/// [`Error`] if the folder or a key could not be read
+pub fn execute(cmd: &Command) -> Result<Report, Error> {
+    let folder = PathBuf::from(&cmd.folder);
+    if !folder.is_dir() {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::NotFound,
+            format!("{} is not a folder", folder.display()),
+        )
+        .into());
+    }
+    let keys_folder = cmd
+        .keys
+        .as_ref()
+        .map_or_else(|| folder.join("keys"), PathBuf::from);
+    let keys = read_keys(&keys_folder)?;
+    let mut used = vec![false; keys.len()];
+
+    let mut report = Report::new();
+    let mut pbos = Vec::new();
+    for path in find_pbos(&folder)? {
+        let display = path
+            .strip_prefix(&folder)
+            .unwrap_or(&path)
+            .display()
+            .to_string()
+            .replace('\\', "/");
+        let signatures = verify_pbo(&path, &keys, &mut used)?;
+        if signatures.is_empty() {
+            report.push(Issue::code(IssueKind::Unsigned, display.clone()));
+        }
+        let signed = signatures
+            .iter()
+            .any(|signature| signature.status == Status::Valid);
+        for signature in &signatures {
+            let authority = signature.authority.clone();
+            let kind = match signature.status {
+                Status::Valid => continue,
+                Status::UnknownAuthority => IssueKind::UnknownAuthority { authority, signed },
+                Status::Stale => IssueKind::Stale { authority },
+                Status::KeyMismatch => IssueKind::KeyMismatch { authority },
+                Status::Invalid => IssueKind::Invalid {
+                    authority,
+                    reason: signature.reason.clone().unwrap_or_default(),
+                },
+            };
+            report.push(Issue::code(kind, display.clone()));
+        }
+        pbos.push(Pbo {
+            path: display,
+            signatures,
+        });
+    }
+    let keys = keys
+        .iter()
+        .zip(used)
+        .map(|(key, used)| Key {
+            authority: key.authority().to_string(),
+            used,
+        })
+        .collect::<Vec<_>>();
+    for key in keys.iter().filter(|key| !key.used) {
+        report.push(Issue::code(IssueKind::ExtraKey, key.authority.clone()));
+    }
+
+    let output = Output {
+        valid: report.errors().is_empty(),
+        pbos,
+        keys,
+    };
+    match cmd.format {
+        VerifyFormat::Text => print_text(&output),
+        VerifyFormat::Json => println!("{}", serde_json::to_string_pretty(&output)?),
+    }
+    Ok(report)
+}
+
+/// Verify each signature of the PBO, marking the keys that are used
+fn verify_pbo(
+    path: &Path,
+    keys: &[BIPublicKey],
+    used: &mut [bool],
+) -> Result<Vec<Signature>, Error> {
+    let mut signatures = Vec::new();
+    for sig_path in find_signatures(path)? {
+        let signature = match BISign::read(&mut File::open(&sig_path)?) {
+            Ok(signature) => signature,
+            Err(e) => {
+                signatures.push(Signature {
+                    authority: authority_from_name(path, &sig_path),
+                    version: None,
+                    status: Status::Invalid,
+                    reason: Some(e.to_string()),
+                });
+                continue;
+            }
+        };
+        let authority = signature.authority().to_string();
+        // Several keys can share an authority, such as a new key made with the same name
+        let candidates = keys
+            .iter()
+            .enumerate()
+            .filter(|(_, key)| key.authority() == signature.authority())
+            .map(|(index, _)| index)
+            .collect::<Vec<_>>();
+        // Only a key with the modulus of the signature can have made it
+        let matching = candidates
+            .iter()
+            .copied()
+            .filter(|index| keys[*index].modulus() == signature.modulus())
+            .collect::<Vec<_>>();
+        let (status, reason) = if candidates.is_empty() {
+            (Status::UnknownAuthority, None)
+        } else if matching.is_empty() {
+            for index in candidates {
+                used[index] = true;
+            }
+            (Status::KeyMismatch, None)
+        } else {
+            let mut result = (Status::Invalid, None);
+            for index in &matching {
+                let mut pbo = match ReadablePbo::from(File::open(path)?) {
+                    Ok(pbo) => pbo,
+                    Err(e) => {
+                        result = (Status::Invalid, Some(e.to_string()));
+                        break;
+                    }
+                };
+                match keys[*index].verify(&mut pbo, &signature) {
+                    Ok(()) => {
+                        result = (Status::Valid, None);
+                        // Only the key that verified the signature is used
+                        used[*index] = true;
+                        break;
+                    }
+                    Err(hemtt_signing::Error::HashMismatch { .. }) => {
+                        result = (Status::Stale, None);
+                    }
+                    Err(e) if result.0 != Status::Stale => {
+                        result = (Status::Invalid, Some(e.to_string()));
+                    }
+                    Err(_) => {}
+                }
+            }
+            if result.0 != Status::Valid {
+                for index in candidates {
+                    used[index] = true;
+                }
+            }
+            result
+        };
+        signatures.push(Signature {
+            authority,
+            version: Some(signature.version()),
+            status,
+            reason,
+        });
+    }
+    Ok(signatures)
+}
+
+fn print_text(output: &Output) {
+    for pbo in &output.pbos {
+        for signature in &pbo.signatures {
+            println!(
+                "{}: `{}` {} {}",
+                pbo.path,
+                signature.authority,
+                signature
+                    .version
+                    .map_or_else(|| "?".to_string(), |version| version.to_string()),
+                match signature.status {
+                    Status::Valid => "valid",
+                    Status::UnknownAuthority => "unknown authority",
+                    Status::Stale => "stale",
+                    Status::KeyMismatch => "key mismatch",
+                    Status::Invalid => "invalid",
+                }
+            );
+        }
+    }
+    if output.valid {
+        info!(
+            "Verified {} PBOs against {} keys",
+            output.pbos.len(),
+            output.keys.len()
+        );
+    }
+}
+
+fn read_keys(folder: &Path) -> Result<Vec<BIPublicKey>, Error> {
+    let mut keys = Vec::new();
+    if !folder.is_dir() {
+        return Ok(keys);
+    }
+    let mut paths = std::fs::read_dir(folder)?
+        .map(|entry| entry.map(|entry| entry.path()))
+        .collect::<Result<Vec<_>, _>>()?;
+    paths.sort();
+    for path in paths {
+        if has_extension(&path, "bikey") {
+            debug!("Reading BIKey: {:?}", &path);
+            keys.push(BIPublicKey::read(&mut File::open(&path)?)?);
+        }
+    }
+    Ok(keys)
+}
+
+/// Every PBO in the mod, in any folder
+fn find_pbos(folder: &Path) -> Result<Vec<PathBuf>, Error> {
+    let mut pbos = Vec::new();
+    for entry in walkdir::WalkDir::new(folder).sort_by_file_name() {
+        let entry = entry?;
+        if entry.file_type().is_file() && has_extension(entry.path(), "pbo") {
+            pbos.push(entry.into_path());
+        }
+    }
+    Ok(pbos)
+}
+
+/// The `{pbo}.{authority}.bisign` files next to the PBO
+fn find_signatures(pbo: &Path) -> Result<Vec<PathBuf>, Error> {
+    let Some(parent) = pbo.parent() else {
+        return Ok(Vec::new());
+    };
+    let prefix =
+        format!("{}.", pbo.file_name().unwrap_or_default().to_string_lossy()).to_lowercase();
+    let mut signatures = Vec::new();
+    for entry in std::fs::read_dir(parent)? {
+        let path = entry?.path();
+        let name = path
+            .file_name()
+            .unwrap_or_default()
+            .to_string_lossy()
+            .to_lowercase();
+        if name.starts_with(&prefix) && has_extension(&path, "bisign") {
+            signatures.push(path);
+        }
+    }
+    signatures.sort();
+    Ok(signatures)
+}
+
+/// The authority in the name of a signature that could not be read
+fn authority_from_name(pbo: &Path, signature: &Path) -> String {
+    let pbo = pbo.file_name().unwrap_or_default().to_string_lossy();
+    let signature = signature.file_name().unwrap_or_default().to_string_lossy();
+    signature
+        .get(pbo.len() + 1..signature.len() - ".bisign".len())
+        .unwrap_or_default()
+        .to_string()
+}
+
+fn has_extension(path: &Path, extension: &str) -> bool {
+    path.extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case(extension))
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum IssueKind {
+    Unsigned,
+    /// `signed` is true if another signature of the PBO is valid
+    UnknownAuthority {
+        authority: String,
+        signed: bool,
+    },
+    Stale {
+        authority: String,
+    },
+    KeyMismatch {
+        authority: String,
+    },
+    Invalid {
+        authority: String,
+        reason: String,
+    },
+    ExtraKey,
+}
+
+pub struct Issue {
+    kind: IssueKind,
+    /// The PBO, or the authority of an extra key
+    target: String,
+}
+
+impl Issue {
+    #[must_use]
+    pub fn code(kind: IssueKind, target: String) -> Arc<dyn Code> {
+        Arc::new(Self { kind, target })
+    }
+}
+
+impl Code for Issue {
+    fn ident(&self) -> &'static str {
+        // binary, utils, signing
+        match self.kind {
+            IssueKind::Unsigned => "BUSE1",
+            IssueKind::UnknownAuthority { .. } => "BUSE2",
+            IssueKind::Stale { .. } => "BUSE3",
+            IssueKind::Invalid { .. } => "BUSE4",
+            IssueKind::KeyMismatch { .. } => "BUSE5",
+            IssueKind::ExtraKey => "BUSW1",
+        }
+    }
+
+    fn severity(&self) -> Severity {
+        match self.kind {
+            IssueKind::UnknownAuthority { signed: true, .. } | IssueKind::ExtraKey => {
+                Severity::Warning
+            }
+            _ => Severity::Error,
+        }
+    }
+
+    fn message(&self) -> String {
+        match &self.kind {
+            IssueKind::Unsigned => format!("`{}` is not signed", self.target),
+            IssueKind::UnknownAuthority { authority, .. } => format!(
+                "`{}` is signed by `{authority}`, which has no key",
+                self.target
+            ),
+            IssueKind::Stale { authority } => {
+                format!("`{}` has a stale signature from `{authority}`", self.target)
+            }
+            IssueKind::Invalid { authority, .. } => format!(
+                "`{}` has an invalid signature from `{authority}`",
+                self.target
+            ),
+            IssueKind::KeyMismatch { authority } => format!(
+                "`{}` is signed by a different `{authority}` key than the one verified against",
+                self.target
+            ),
+            IssueKind::ExtraKey => format!("key `{}` does not sign any PBO", self.target),
+        }
+    }
+
+    fn note(&self) -> Option<String> {
+        match &self.kind {
+            IssueKind::Stale { .. } => Some(
+                "the PBO has changed since it was signed, the signature is likely from an older version"
+                    .to_string(),
+            ),
+            IssueKind::Invalid { reason, .. } => Some(reason.clone()),
+            IssueKind::KeyMismatch { .. } => Some(
+                "the keys share an authority, but the signature was made with another key".to_string(),
+            ),
+            _ => None,
+        }
+    }
+
+    fn help(&self) -> Option<String> {
+        match self.kind {
+            IssueKind::UnknownAuthority { signed: false, .. } => {
+                Some("add the mod's `.bikey` to the keys being verified against".to_string())
+            }
+            IssueKind::KeyMismatch { .. } => {
+                Some("publish the key that signed the PBO, or sign it again".to_string())
+            }
+            IssueKind::ExtraKey => Some("keys that are no longer used can be removed".to_string()),
+            _ => None,
+        }
+    }
+
+    fn diagnostic(&self) -> Option<Diagnostic> {
+        Some(Diagnostic::from_code(self))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use std::{fs::File, io::Cursor, path::Path};
+
+    use hemtt_common::BISignVersion;
+    use hemtt_pbo::{ReadablePbo, WritablePbo};
+    use hemtt_signing::BIPrivateKey;
+    use sealed_test::prelude::*;
+
+    use super::{Command, VerifyFormat, execute};
+    use crate::report::{Report, WithIncludes};
+
+    fn pbo(path: &Path, content: &str) {
+        let mut pbo = WritablePbo::new();
+        pbo.add_property("prefix", "z\\abe\\addons\\main");
+        pbo.add_file("config.cpp", Cursor::new(content.as_bytes().to_vec()))
+            .unwrap();
+        pbo.write(&mut File::create(path).unwrap(), true).unwrap();
+    }
+
+    fn sign(path: &Path, key: &BIPrivateKey) {
+        sign_version(path, key, BISignVersion::V3);
+    }
+
+    fn sign_version(path: &Path, key: &BIPrivateKey, version: BISignVersion) {
+        let mut pbo = ReadablePbo::from(File::open(path).unwrap()).unwrap();
+        let signature = key.sign(&mut pbo, version).unwrap();
+        let name = format!(
+            "{}.{}.bisign",
+            path.file_name().unwrap().to_string_lossy(),
+            key.authority()
+        );
+        signature
+            .write(&mut File::create(path.with_file_name(name)).unwrap())
+            .unwrap();
+    }
+
+    fn publish(key: &BIPrivateKey, name: &str) {
+        std::fs::create_dir_all("mod/keys").unwrap();
+        key.to_public_key()
+            .write(&mut File::create(format!("mod/keys/{name}.bikey")).unwrap())
+            .unwrap();
+    }
+
+    fn verify() -> Report {
+        execute(&Command {
+            folder: "mod".to_string(),
+            keys: None,
+            format: VerifyFormat::Text,
+        })
+        .unwrap()
+    }
+
+    fn idents(report: &Report) -> Vec<(&'static str, String)> {
+        let mut codes = report.errors();
+        codes.extend(report.warnings(WithIncludes::Yes));
+        codes
+            .iter()
+            .map(|code| (code.ident(), code.message()))
+            .collect()
+    }
+
+    #[sealed_test]
+    fn signatures() {
+        std::fs::create_dir_all("mod/addons").unwrap();
+        let key = BIPrivateKey::generate(1024, "abe").unwrap();
+        let other = BIPrivateKey::generate(1024, "other").unwrap();
+        publish(&key, "abe");
+
+        let good = Path::new("mod/addons/abe_good.pbo");
+        pbo(good, "good");
+        sign(good, &key);
+
+        let stale = Path::new("mod/addons/abe_stale.pbo");
+        pbo(stale, "old");
+        sign(stale, &key);
+        pbo(stale, "new");
+
+        pbo(Path::new("mod/addons/abe_missing.pbo"), "missing");
+
+        let unknown = Path::new("mod/addons/abe_unknown.pbo");
+        pbo(unknown, "unknown");
+        sign(unknown, &other);
+
+        pbo(Path::new("mod/addons/abe_invalid.pbo"), "invalid");
+        std::fs::write("mod/addons/abe_invalid.pbo.abe.bisign", b"not a signature").unwrap();
+
+        assert_eq!(
+            idents(&verify()),
+            vec![
+                (
+                    "BUSE4",
+                    "`addons/abe_invalid.pbo` has an invalid signature from `abe`".to_string()
+                ),
+                (
+                    "BUSE1",
+                    "`addons/abe_missing.pbo` is not signed".to_string()
+                ),
+                (
+                    "BUSE3",
+                    "`addons/abe_stale.pbo` has a stale signature from `abe`".to_string()
+                ),
+                (
+                    "BUSE2",
+                    "`addons/abe_unknown.pbo` is signed by `other`, which has no key".to_string()
+                ),
+            ]
+        );
+    }
+
+    #[sealed_test]
+    fn duplicate_authority() {
+        std::fs::create_dir_all("mod/addons").unwrap();
+        let old = BIPrivateKey::generate(1024, "abe").unwrap();
+        let new = BIPrivateKey::generate(1024, "abe").unwrap();
+        // The key that signed the PBO is read second
+        publish(&old, "abe_1");
+        publish(&new, "abe_2");
+
+        let path = Path::new("mod/addons/abe_main.pbo");
+        pbo(path, "main");
+        sign(path, &new);
+
+        assert_eq!(
+            idents(&verify()),
+            vec![("BUSW1", "key `abe` does not sign any PBO".to_string())]
+        );
+    }
+
+    #[sealed_test]
+    fn version_2() {
+        std::fs::create_dir_all("mod/addons").unwrap();
+        let key = BIPrivateKey::generate(1024, "abe").unwrap();
+        publish(&key, "abe");
+
+        let good = Path::new("mod/addons/abe_good.pbo");
+        pbo(good, "good");
+        sign_version(good, &key, BISignVersion::V2);
+
+        let stale = Path::new("mod/addons/abe_stale.pbo");
+        pbo(stale, "old");
+        sign_version(stale, &key, BISignVersion::V2);
+        pbo(stale, "new");
+
+        assert_eq!(
+            idents(&verify()),
+            vec![(
+                "BUSE3",
+                "`addons/abe_stale.pbo` has a stale signature from `abe`".to_string()
+            )]
+        );
+    }
+
+    #[sealed_test]
+    fn key_mismatch() {
+        std::fs::create_dir_all("mod/addons").unwrap();
+        let published = BIPrivateKey::generate(1024, "abe").unwrap();
+        let signer = BIPrivateKey::generate(1024, "abe").unwrap();
+        publish(&published, "abe");
+
+        let path = Path::new("mod/addons/abe_main.pbo");
+        pbo(path, "main");
+        sign(path, &signer);
+
+        assert_eq!(
+            idents(&verify()),
+            vec![(
+                "BUSE5",
+                "`addons/abe_main.pbo` is signed by a different `abe` key than the one verified against"
+                    .to_string()
+            )]
+        );
+    }
+}
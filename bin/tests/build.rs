@@ -18,3 +18,9 @@ fn build_bravo() {
     hemtt::execute(&Cli::parse_from(vec!["hemtt", "script", "test"])).unwrap();
     hemtt::execute(&Cli::parse_from(vec!["hemtt", "release", "--in-test"])).unwrap();
 }
+
+#[sealed_test]
+fn build_foxtrot() {
+    std::env::set_current_dir(format!("{}/tests/foxtrot", env!("CARGO_MANIFEST_DIR"))).unwrap();
+    hemtt::execute(&Cli::parse_from(vec!["hemtt", "build", "--in-test"])).unwrap();
+}
@@ -0,0 +1,6 @@
+class CfgPatches {
+    class MyMod {
+        name = "yes it is my mod";
+        requiredVersion = 2.00;
+    };
+};
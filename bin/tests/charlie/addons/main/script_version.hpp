@@ -0,0 +1,4 @@
+#define MAJOR 3
+#define MINOR 15
+#define PATCHLVL 2
+#define BUILD 69
@@ -0,0 +1,17 @@
+class CfgPatches {
+    class delta_main {
+        units[] = {};
+        weapons[] = {};
+        requiredVersion = 2.00;
+        requiredAddons[] = {};
+    };
+};
+
+class CfgFunctions {
+    class delta {
+        class main {
+            file = "\z\delta\addons\main\functions";
+            class used {};
+        };
+    };
+};
@@ -0,0 +1 @@
+#define TEST_TRUE private _passed = true
@@ -0,0 +1,14 @@
+class CfgPatches {
+    class delta_other {
+        units[] = {};
+        weapons[] = {};
+        requiredVersion = 2.00;
+        requiredAddons[] = {"delta_main"};
+    };
+};
+
+class CfgVehicles {
+    class delta_thing {
+        icon = "\z\delta\addons\main\data\icon.paa";
+    };
+};
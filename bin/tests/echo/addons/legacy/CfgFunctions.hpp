@@ -0,0 +1,9 @@
+class CfgFunctions {
+    class echo_legacy {
+        class legacy {
+            class old {
+                file = "\z\echo\addons\legacy\functions\fnc_old.sqf";
+            };
+        };
+    };
+};
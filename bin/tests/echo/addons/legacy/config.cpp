@@ -0,0 +1,10 @@
+class CfgPatches {
+    class echo_legacy {
+        units[] = {};
+        weapons[] = {};
+        requiredVersion = 2.00;
+        requiredAddons[] = {};
+    };
+};
+
+#include "CfgFunctions.hpp"
@@ -0,0 +1,8 @@
+class CfgPatches {
+    class echo_main {
+        units[] = {};
+        weapons[] = {};
+        requiredVersion = 2.00;
+        requiredAddons[] = {};
+    };
+};
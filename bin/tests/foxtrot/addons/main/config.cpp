@@ -0,0 +1,14 @@
+class CfgPatches {
+    class fox_main {
+        units[] = {"fox_truck"};
+        weapons[] = {};
+        requiredVersion = 2.14;
+        requiredAddons[] = {};
+    };
+};
+
+class CfgVehicles {
+    class fox_truck {
+        maxSpeed = 120;
+    };
+};
@@ -0,0 +1,15 @@
+class CfgPatches {
+    class fox_extra {
+        units[] = {};
+        weapons[] = {};
+        requiredVersion = 2.14;
+        requiredAddons[] = {"fox_main"};
+    };
+};
+
+class CfgVehicles {
+    class fox_truck;
+    class fox_fast_truck: fox_truck {
+        maxSpeed = 160;
+    };
+};
@@ -0,0 +1,79 @@
+#![allow(clippy::unwrap_used)]
+#![cfg(unix)]
+
+use std::os::unix::fs::PermissionsExt;
+
+use clap::Parser;
+use sealed_test::prelude::*;
+
+use hemtt::Cli;
+
+/// A `steamcmd` that records its arguments and the VDF, and creates the item
+const FAKE_STEAMCMD: &str = r#"#!/bin/sh
+echo "$@" > "$(dirname "$0")/args.txt"
+while [ "$#" -gt 0 ]; do
+    if [ "$1" = "+workshop_build_item" ]; then
+        cp "$2" "$(dirname "$0")/item.vdf"
+        sed 's/"publishedfileid"\t\t"0"/"publishedfileid"\t\t"1234"/' "$2" > "$2.tmp" && mv "$2.tmp" "$2"
+    fi
+    shift
+done
+echo "Success."
+"#;
+
+#[sealed_test]
+fn release_workshop() {
+    let project = format!("{}/tests/charlie", env!("CARGO_MANIFEST_DIR"));
+    let steamcmd_folder = std::env::temp_dir().join("hemtt-fake-steamcmd");
+    std::fs::create_dir_all(&steamcmd_folder).unwrap();
+    let steamcmd = steamcmd_folder.join("steamcmd");
+    std::fs::write(&steamcmd, FAKE_STEAMCMD).unwrap();
+    std::fs::set_permissions(&steamcmd, std::fs::Permissions::from_mode(0o755)).unwrap();
+    // SAFETY: the test runs in its own process
+    unsafe {
+        std::env::set_var("HEMTT_STEAMCMD", &steamcmd);
+        std::env::remove_var("HEMTT_STEAM_USERNAME");
+        std::env::remove_var("HEMTT_STEAM_PASSWORD");
+    }
+
+    std::env::set_current_dir(&project).unwrap();
+    hemtt::execute(&Cli::parse_from(vec![
+        "hemtt",
+        "release",
+        "--in-test",
+        "--no-archive",
+        "--workshop",
+    ]))
+    .unwrap();
+
+    let workshop = std::path::absolute(".hemttout/workshop").unwrap();
+    let args = std::fs::read_to_string(steamcmd_folder.join("args.txt")).unwrap();
+    assert_eq!(
+        args.trim(),
+        format!(
+            "+login tester +workshop_build_item {} +quit",
+            workshop.join("item.vdf").display()
+        )
+    );
+
+    let vdf = std::fs::read_to_string(steamcmd_folder.join("item.vdf")).unwrap();
+    assert!(vdf.starts_with("\"workshopitem\"\n{\n"));
+    assert!(vdf.contains("\t\"appid\"\t\t\"107410\"\n"));
+    assert!(vdf.contains("\t\"publishedfileid\"\t\t\"0\"\n"));
+    assert!(vdf.contains(&format!(
+        "\t\"contentfolder\"\t\t\"{}\"\n",
+        workshop.join("content").display()
+    )));
+    assert!(vdf.contains("\t\"visibility\"\t\t\"3\"\n"));
+    assert!(vdf.contains("\t\"title\"\t\t\"charlie 3.15.2.69\"\n"));
+    assert!(vdf.contains("\t\"description\"\t\t\"The \\\"charlie\\\" mod\"\n"));
+    assert!(vdf.contains("\t\"changenote\"\t\t\"Version 3.15.2\"\n"));
+    assert!(!vdf.contains("previewfile"));
+
+    assert!(workshop.join("content/addons/charlie_main.pbo").is_file());
+    assert!(
+        workshop
+            .join("content/keys/charlie_3.15.2.69.bikey")
+            .is_file()
+    );
+}
@@ -9,22 +9,46 @@ pub fn run(chapter: &mut Chapter) {
         ("launch", hemtt::commands::launch::Command::command()),
         ("build", hemtt::commands::build::Command::command()),
         ("release", hemtt::commands::release::Command::command()),
+        ("changelog", hemtt::commands::changelog::Command::command()),
         ("script", hemtt::commands::script::Command::command()),
+        ("test", hemtt::commands::test::Command::command()),
+        ("fmt", hemtt::commands::fmt::Command::command()),
+        ("docs", hemtt::commands::docs::Command::command()),
     ];
 
-    let nested = [(
-        "localization",
-        vec![
-            (
-                "coverage",
-                hemtt::commands::localization::coverage::Command::command(),
-            ),
-            (
-                "sort",
-                hemtt::commands::localization::sort::Command::command(),
-            ),
-        ],
-    )];
+    let nested = [
+        (
+            "localization",
+            vec![
+                (
+                    "coverage",
+                    hemtt::commands::localization::coverage::Command::command(),
+                ),
+                (
+                    "sort",
+                    hemtt::commands::localization::sort::Command::command(),
+                ),
+            ],
+        ),
+        (
+            "keys",
+            vec![
+                (
+                    "generate",
+                    hemtt::commands::keys::generate::Command::command(),
+                ),
+                ("show", hemtt::commands::keys::show::Command::command()),
+                (
+                    "export-public",
+                    hemtt::commands::keys::export_public::Command::command(),
+                ),
+            ],
+        ),
+        (
+            "version",
+            vec![("bump", hemtt::commands::version::bump::Command::command())],
+        ),
+    ];
 
     for item in &mut chapter.sub_items {
         if let mdbook::BookItem::Chapter(chapter) = item {
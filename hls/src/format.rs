@@ -0,0 +1,39 @@
+use std::path::Path;
+
+use hemtt_format::{Language, Options};
+use tower_lsp::jsonrpc::Result;
+use tower_lsp::lsp_types::{DocumentFormattingParams, Position, Range, TextEdit};
+
+use crate::files::FileCache;
+
+pub async fn formatting(params: DocumentFormattingParams) -> Result<Option<Vec<TextEdit>>> {
+    let url = params.text_document.uri;
+    let Some(language) = Language::from_path(Path::new(url.path())) else {
+        return Ok(None);
+    };
+    let Some(text) = FileCache::get().text(&url) else {
+        return Ok(None);
+    };
+    let options = Options::new(
+        params.options.tab_size as usize,
+        params.options.insert_spaces,
+        Options::default().width(),
+    );
+    let formatted = hemtt_format::format(&text, language, &options);
+    if formatted == text {
+        return Ok(Some(vec![]));
+    }
+    // Replace the whole document, the end is the last line and its length in UTF-16
+    let last = text.rsplit('\n').next().unwrap_or_default();
+    let end = Position {
+        line: text.matches('\n').count() as u32,
+        character: last.encode_utf16().count() as u32,
+    };
+    Ok(Some(vec![TextEdit {
+        range: Range {
+            start: Position::default(),
+            end,
+        },
+        new_text: formatted,
+    }]))
+}
@@ -18,6 +18,7 @@ mod color;
 mod config;
 mod diag_manager;
 mod files;
+mod format;
 mod p3d;
 mod paa;
 mod positions;
@@ -60,6 +61,7 @@ impl LanguageServer for Backend {
                     },
                 }),
                 definition_provider: Some(OneOf::Left(true)),
+                document_formatting_provider: Some(OneOf::Left(true)),
                 color_provider: Some(ColorProviderCapability::Options(
                     StaticTextDocumentColorProviderOptions {
                         document_selector: Some(vec![
@@ -198,6 +200,10 @@ impl LanguageServer for Backend {
         Ok(PreprocessorAnalyzer::get().goto_definition(&params).await)
     }
 
+    async fn formatting(&self, params: DocumentFormattingParams) -> Result<Option<Vec<TextEdit>>> {
+        format::formatting(params).await
+    }
+
     async fn document_color(&self, params: DocumentColorParams) -> Result<Vec<ColorInformation>> {
         color::info(params.text_document.uri).await
     }
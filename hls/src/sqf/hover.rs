@@ -1,12 +1,12 @@
 use arma3_wiki::model::{Command, Locality, Since, Syntax};
-use hemtt_sqf::parser::database::Database;
-use hemtt_workspace::reporting::Symbol;
+use hemtt_sqf::{header::Header, parser::database::Database};
+use hemtt_workspace::{addons::Addon, reporting::Symbol};
 use regex::Regex;
 use tower_lsp::lsp_types::{Hover, HoverContents, MarkedString, Position};
 use tracing::warn;
 use url::Url;
 
-use crate::workspace::EditorWorkspaces;
+use crate::workspace::{EditorWorkspace, EditorWorkspaces};
 
 use super::SqfAnalyzer;
 
@@ -38,11 +38,41 @@ impl SqfAnalyzer {
         let Symbol::Word(word) = token.symbol() else {
             return None;
         };
-        database.wiki().commands().get(word)?;
+        if database.wiki().commands().get(word).is_none() {
+            return function_hover(word, &workspace);
+        }
         Some(hover(word, &database))
     }
 }
 
+/// The header of one of the project's functions, `abe_main_fnc_play`
+fn function_hover(word: &str, workspace: &EditorWorkspace) -> Option<Hover> {
+    let lower = word.to_lowercase();
+    let (tag, name) = lower.split_once("_fnc_")?;
+    let config = workspace.config()?;
+    let functions = config.functions();
+    let addon = Addon::scan(workspace.root_disk())
+        .ok()?
+        .into_iter()
+        .find(|addon| functions.tag(config.prefix(), addon.name()).to_lowercase() == tag)?;
+    let folder = workspace
+        .root()
+        .join(addon.folder())
+        .and_then(|root| root.join(functions.folder()))
+        .ok()?;
+    let filename = format!("fnc_{name}.sqf");
+    let path = folder
+        .walk_dir()
+        .ok()?
+        .into_iter()
+        .find(|path| path.filename().to_lowercase() == filename)?;
+    let header = Header::parse(&path.read_to_string().ok()?)?;
+    Some(Hover {
+        contents: HoverContents::Scalar(MarkedString::String(header.markdown(word))),
+        range: None,
+    })
+}
+
 fn hover(command: &str, database: &Database) -> Hover {
     database.wiki().commands().get(command).map_or_else(
         || Hover {
@@ -137,6 +137,18 @@ impl EditorWorkspace {
             if include.is_dir() {
                 builder = builder.physical(&include, LayerType::Include);
             }
+            if let Ok(config) = ProjectConfig::from_file(&root.join(".hemtt").join("project.toml"))
+            {
+                for pbos in config.hemtt().include_pbos() {
+                    builder = match builder.pbos(&root.join(pbos)) {
+                        Ok(builder) => builder,
+                        Err(e) => {
+                            debug!("failed to mount pbos from {pbos}: {e:?}");
+                            return None;
+                        }
+                    };
+                }
+            }
             let Ok(workspace) = builder.finish(None, true, &PDriveOption::Disallow) else {
                 return None;
             };
@@ -1,4 +1,88 @@
 //! Messages to control Arma from HEMTT
+//!
+//! Messages are sent as JSON, prefixed with their length as a little endian `u32`
+
+use std::io::{Read, Write};
+
+use serde::{Serialize, de::DeserializeOwned};
+
+/// Write a message to a stream
+///
+/// # Errors
+/// [`std::io::Error`] if the message could not be serialized or written
+pub fn write<W: Write, M: Serialize>(writer: &mut W, message: &M) -> std::io::Result<()> {
+    let message = serde_json::to_string(message)?;
+    let len = u32::try_from(message.len()).map_err(|_| {
+        std::io::Error::new(std::io::ErrorKind::InvalidInput, "message is too large")
+    })?;
+    let mut buf = len.to_le_bytes().to_vec();
+    buf.extend(message.as_bytes());
+    let mut written = 0;
+    while written < buf.len() {
+        match writer.write(&buf[written..]) {
+            Ok(0) => return Err(std::io::ErrorKind::WriteZero.into()),
+            Ok(wrote) => written += wrote,
+            Err(e) if retry(&e) => std::thread::sleep(std::time::Duration::from_millis(1)),
+            Err(e) => return Err(e),
+        }
+    }
+    writer.flush()
+}
+
+/// Read a message from a stream
+///
+/// Returns `None` if the stream is non-blocking and no message is waiting
+///
+/// # Errors
+/// [`std::io::Error`] if the stream was closed, or the message could not be deserialized
+pub fn read<R: Read, M: DeserializeOwned>(reader: &mut R) -> std::io::Result<Option<M>> {
+    let mut len_buf = [0u8; 4];
+    if !fill(reader, &mut len_buf, true)? {
+        return Ok(None);
+    }
+    let mut buf = vec![0u8; u32::from_le_bytes(len_buf) as usize];
+    fill(reader, &mut buf, false)?;
+    Ok(Some(serde_json::from_slice(&buf)?))
+}
+
+#[must_use]
+/// If the error is from the other side closing the stream, rather than a bad message
+pub fn closed(error: &std::io::Error) -> bool {
+    matches!(
+        error.kind(),
+        std::io::ErrorKind::UnexpectedEof
+            | std::io::ErrorKind::BrokenPipe
+            | std::io::ErrorKind::ConnectionReset
+            | std::io::ErrorKind::ConnectionAborted
+    )
+}
+
+/// Fill the buffer from the stream, waiting for the rest once any of it has been read
+///
+/// Returns `false` if `none_waiting` is set and nothing could be read without blocking
+fn fill<R: Read>(reader: &mut R, buf: &mut [u8], none_waiting: bool) -> std::io::Result<bool> {
+    let mut filled = 0;
+    while filled < buf.len() {
+        match reader.read(&mut buf[filled..]) {
+            Ok(0) => return Err(std::io::ErrorKind::UnexpectedEof.into()),
+            Ok(read) => filled += read,
+            Err(e) if none_waiting && filled == 0 && e.kind() == std::io::ErrorKind::WouldBlock => {
+                return Ok(false);
+            }
+            Err(e) if retry(&e) => std::thread::sleep(std::time::Duration::from_millis(1)),
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(true)
+}
+
+/// Once a message has started, a non-blocking stream waits for the rest of it
+fn retry(error: &std::io::Error) -> bool {
+    matches!(
+        error.kind(),
+        std::io::ErrorKind::WouldBlock | std::io::ErrorKind::Interrupted
+    )
+}
 
 pub mod toarma {
     use serde::{Deserialize, Serialize};
@@ -7,6 +91,17 @@ pub mod toarma {
     pub enum Message {
         Control(Control),
         Photoshoot(Photoshoot),
+        /// Run SQF code in the mission namespace
+        Execute(String),
+        /// Replace a function with new code, (function, code)
+        Reload(String, String),
+        Test(Test),
+    }
+
+    #[derive(Debug, Serialize, Deserialize)]
+    pub enum Test {
+        /// Run the test files at the given paths, in order
+        Run(Vec<String>),
     }
 
     #[derive(Debug, Serialize, Deserialize)]
@@ -41,6 +136,31 @@ pub mod fromarma {
         Control(Control),
         Photoshoot(Photoshoot),
         Log(Level, String),
+        /// The outcome of an `Execute` or `Reload`
+        Outcome(Outcome),
+        Test(Test),
+    }
+
+    #[derive(Debug, Serialize, Deserialize)]
+    pub enum Test {
+        /// The test mission is ready to run tests
+        Ready,
+        /// (path, seconds)
+        Passed(String, f64),
+        /// (path, reason, seconds)
+        Failed(String, String, f64),
+        /// All tests have been run
+        Done,
+    }
+
+    #[derive(Debug, Serialize, Deserialize)]
+    pub enum Outcome {
+        /// The value returned by executed code
+        Returned(String),
+        /// The function that was reloaded
+        Reloaded(String),
+        /// Why the code could not be executed or the function reloaded
+        Failed(String),
     }
 
     #[derive(Debug, Serialize, Deserialize)]
@@ -60,3 +180,268 @@ pub mod fromarma {
         PreviewsDone,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::net::{TcpListener, TcpStream};
+
+    use super::{closed, fromarma, read, toarma, write};
+
+    #[test]
+    fn round_trip() {
+        let mut buffer = Vec::new();
+        write(
+            &mut buffer,
+            &toarma::Message::Control(toarma::Control::Exit),
+        )
+        .expect("failed to write");
+        assert_eq!(&buffer[..4], &18u32.to_le_bytes());
+        let message: toarma::Message = read(&mut buffer.as_slice())
+            .expect("failed to read")
+            .expect("no message");
+        assert!(matches!(
+            message,
+            toarma::Message::Control(toarma::Control::Exit)
+        ));
+    }
+
+    #[test]
+    fn closed_or_invalid() {
+        let error = read::<_, toarma::Message>(&mut [].as_slice()).expect_err("read nothing");
+        assert!(closed(&error));
+        // the stream ended part way through a message
+        let mut buffer = Vec::new();
+        write(
+            &mut buffer,
+            &toarma::Message::Control(toarma::Control::Exit),
+        )
+        .expect("failed to write");
+        let error = read::<_, toarma::Message>(&mut &buffer[..10]).expect_err("read part");
+        assert!(closed(&error));
+        let mut buffer = 2u32.to_le_bytes().to_vec();
+        buffer.extend(b"{}");
+        let error = read::<_, toarma::Message>(&mut buffer.as_slice()).expect_err("read {}");
+        assert!(!closed(&error));
+    }
+
+    /// Hands out the bytes in chunks, blocking between each
+    struct Trickle {
+        chunks: Vec<Vec<u8>>,
+        blocked: bool,
+    }
+
+    impl std::io::Read for Trickle {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            if !self.blocked {
+                self.blocked = true;
+                return Err(std::io::ErrorKind::WouldBlock.into());
+            }
+            self.blocked = false;
+            let Some(chunk) = self.chunks.first_mut() else {
+                return Err(std::io::ErrorKind::WouldBlock.into());
+            };
+            let len = chunk.len().min(buf.len());
+            buf[..len].copy_from_slice(&chunk[..len]);
+            chunk.drain(..len);
+            if chunk.is_empty() {
+                self.chunks.remove(0);
+            }
+            Ok(len)
+        }
+    }
+
+    #[test]
+    fn partial_header() {
+        let mut buffer = Vec::new();
+        write(
+            &mut buffer,
+            &toarma::Message::Control(toarma::Control::Exit),
+        )
+        .expect("failed to write");
+        let mut reader = Trickle {
+            chunks: vec![
+                buffer[..1].to_vec(),
+                buffer[1..3].to_vec(),
+                buffer[3..10].to_vec(),
+                buffer[10..].to_vec(),
+            ],
+            blocked: false,
+        };
+        let nothing: Option<toarma::Message> = read(&mut reader).expect("failed to read");
+        assert!(nothing.is_none());
+        let message: Option<toarma::Message> = read(&mut reader).expect("failed to read");
+        assert!(matches!(
+            message,
+            Some(toarma::Message::Control(toarma::Control::Exit))
+        ));
+        let nothing: Option<toarma::Message> = read(&mut reader).expect("failed to read");
+        assert!(nothing.is_none());
+    }
+
+    #[test]
+    fn fake_client() {
+        let listener = TcpListener::bind("127.0.0.1:0").expect("failed to bind");
+        let addr = listener.local_addr().expect("no address");
+        let client = std::thread::spawn(move || {
+            let mut stream = TcpStream::connect(addr).expect("failed to connect");
+            write(
+                &mut stream,
+                &fromarma::Message::Log(fromarma::Level::Warn, "careful".to_string()),
+            )
+            .expect("failed to write");
+            write(
+                &mut stream,
+                &fromarma::Message::Control(fromarma::Control::Mission("test.VR".to_string())),
+            )
+            .expect("failed to write");
+            let reply: Option<toarma::Message> = read(&mut stream).expect("failed to read");
+            assert!(matches!(
+                reply,
+                Some(toarma::Message::Control(toarma::Control::Exit))
+            ));
+        });
+        let (mut stream, _) = listener.accept().expect("failed to accept");
+        let log: Option<fromarma::Message> = read(&mut stream).expect("failed to read");
+        assert!(matches!(
+            log,
+            Some(fromarma::Message::Log(fromarma::Level::Warn, ref text)) if text == "careful"
+        ));
+        let mission: Option<fromarma::Message> = read(&mut stream).expect("failed to read");
+        assert!(matches!(
+            mission,
+            Some(fromarma::Message::Control(fromarma::Control::Mission(ref m))) if m == "test.VR"
+        ));
+        stream
+            .set_nonblocking(true)
+            .expect("failed to set non-blocking");
+        let nothing: Option<fromarma::Message> = read(&mut stream).expect("failed to read");
+        assert!(nothing.is_none());
+        stream
+            .set_nonblocking(false)
+            .expect("failed to set blocking");
+        write(
+            &mut stream,
+            &toarma::Message::Control(toarma::Control::Exit),
+        )
+        .expect("failed to write");
+        client.join().expect("client panicked");
+    }
+
+    #[test]
+    fn execute_reload() {
+        let listener = TcpListener::bind("127.0.0.1:0").expect("failed to bind");
+        let addr = listener.local_addr().expect("no address");
+        // Stands in for the extension, answering each request
+        let client = std::thread::spawn(move || {
+            let mut stream = TcpStream::connect(addr).expect("failed to connect");
+            for _ in 0..2 {
+                let request: toarma::Message = read(&mut stream)
+                    .expect("failed to read")
+                    .expect("no message");
+                let outcome = match request {
+                    toarma::Message::Execute(code) => {
+                        fromarma::Outcome::Returned(code.len().to_string())
+                    }
+                    toarma::Message::Reload(function, _) => fromarma::Outcome::Reloaded(function),
+                    _ => fromarma::Outcome::Failed("unexpected".to_string()),
+                };
+                write(&mut stream, &fromarma::Message::Outcome(outcome)).expect("failed to write");
+            }
+        });
+        let (mut stream, _) = listener.accept().expect("failed to accept");
+        stream
+            .set_nonblocking(true)
+            .expect("failed to set non-blocking");
+        let code = "x".repeat(1024 * 1024);
+        write(&mut stream, &toarma::Message::Execute(code)).expect("failed to write");
+        write(
+            &mut stream,
+            &toarma::Message::Reload("abe_main_fnc_test".to_string(), "true".to_string()),
+        )
+        .expect("failed to write");
+        let mut outcomes = Vec::new();
+        while outcomes.len() < 2 {
+            if let Some(fromarma::Message::Outcome(outcome)) =
+                read(&mut stream).expect("failed to read")
+            {
+                outcomes.push(outcome);
+            }
+        }
+        assert!(matches!(
+            &outcomes[0],
+            fromarma::Outcome::Returned(len) if len == "1048576"
+        ));
+        assert!(matches!(
+            &outcomes[1],
+            fromarma::Outcome::Reloaded(function) if function == "abe_main_fnc_test"
+        ));
+        client.join().expect("client panicked");
+    }
+
+    #[test]
+    fn test_run() {
+        let listener = TcpListener::bind("127.0.0.1:0").expect("failed to bind");
+        let addr = listener.local_addr().expect("no address");
+        // Stands in for the extension, reporting on each test it is asked to run
+        let client = std::thread::spawn(move || {
+            let mut stream = TcpStream::connect(addr).expect("failed to connect");
+            write(&mut stream, &fromarma::Message::Test(fromarma::Test::Ready))
+                .expect("failed to write");
+            let request: toarma::Message = read(&mut stream)
+                .expect("failed to read")
+                .expect("no message");
+            let toarma::Message::Test(toarma::Test::Run(paths)) = request else {
+                panic!("unexpected message: {request:?}");
+            };
+            for (i, path) in paths.into_iter().enumerate() {
+                let result = if i == 0 {
+                    fromarma::Test::Passed(path, 0.5)
+                } else {
+                    fromarma::Test::Failed(path, "assertion failed".to_string(), 1.25)
+                };
+                write(&mut stream, &fromarma::Message::Test(result)).expect("failed to write");
+            }
+            write(&mut stream, &fromarma::Message::Test(fromarma::Test::Done))
+                .expect("failed to write");
+        });
+        let (mut stream, _) = listener.accept().expect("failed to accept");
+        let ready: Option<fromarma::Message> = read(&mut stream).expect("failed to read");
+        assert!(matches!(
+            ready,
+            Some(fromarma::Message::Test(fromarma::Test::Ready))
+        ));
+        write(
+            &mut stream,
+            &toarma::Message::Test(toarma::Test::Run(vec![
+                "tests/test_math.sqf".to_string(),
+                "tests/test_strings.sqf".to_string(),
+            ])),
+        )
+        .expect("failed to write");
+        let mut results = Vec::new();
+        loop {
+            let message: fromarma::Message = read(&mut stream)
+                .expect("failed to read")
+                .expect("no message");
+            match message {
+                fromarma::Message::Test(fromarma::Test::Done) => break,
+                fromarma::Message::Test(result) => results.push(result),
+                _ => panic!("unexpected message: {message:?}"),
+            }
+        }
+        assert_eq!(results.len(), 2);
+        assert!(matches!(
+            &results[0],
+            fromarma::Test::Passed(path, seconds)
+                if path == "tests/test_math.sqf" && (*seconds - 0.5).abs() < f64::EPSILON
+        ));
+        assert!(matches!(
+            &results[1],
+            fromarma::Test::Failed(path, reason, seconds)
+                if path == "tests/test_strings.sqf"
+                    && reason == "assertion failed"
+                    && (*seconds - 1.25).abs() < f64::EPSILON
+        ));
+        client.join().expect("client panicked");
+    }
+}
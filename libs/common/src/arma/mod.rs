@@ -2,3 +2,6 @@
 
 pub mod control;
 pub mod dlc;
+pub mod rpt;
+pub mod server;
+pub mod testing;
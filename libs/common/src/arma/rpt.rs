@@ -0,0 +1,354 @@
+//! Parsing of Arma 3 report (`.rpt`) files
+
+use serde::Serialize;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize)]
+/// Severity of an RPT entry
+pub enum Level {
+    #[default]
+    /// A script error, `Error in expression`
+    Error,
+    /// A warning, `Warning Message:`
+    Warning,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
+/// A single error or warning from an RPT file
+pub struct Entry {
+    level: Level,
+    timestamp: Option<String>,
+    message: String,
+    expression: Option<String>,
+    position: Option<String>,
+    file: Option<String>,
+    line: Option<usize>,
+}
+
+impl Entry {
+    #[must_use]
+    /// The severity of the entry
+    pub const fn level(&self) -> Level {
+        self.level
+    }
+
+    #[must_use]
+    /// The timestamp of the entry, as printed by the game
+    pub fn timestamp(&self) -> Option<&str> {
+        self.timestamp.as_deref()
+    }
+
+    #[must_use]
+    /// The message, without the leading `Error` or `Warning Message:`
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    #[must_use]
+    /// The code surrounding the error, from `Error in expression <...>`
+    pub fn expression(&self) -> Option<&str> {
+        self.expression.as_deref()
+    }
+
+    #[must_use]
+    /// The code at the error, from `Error position: <...>`
+    pub fn position(&self) -> Option<&str> {
+        self.position.as_deref()
+    }
+
+    #[must_use]
+    /// The virtual path of the file, from `File \x\...\fnc_foo.sqf..., line 42`
+    pub fn file(&self) -> Option<&str> {
+        self.file.as_deref()
+    }
+
+    #[must_use]
+    /// The line in the file
+    pub const fn line(&self) -> Option<usize> {
+        self.line
+    }
+
+    fn key(&self) -> (Level, &str, Option<&str>, Option<usize>) {
+        (self.level(), &self.message, self.file(), self.line)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+/// Identical entries, grouped together
+pub struct Group {
+    entry: Entry,
+    count: usize,
+    first: Option<String>,
+    last: Option<String>,
+}
+
+impl Group {
+    #[must_use]
+    /// The first entry of the group
+    pub const fn entry(&self) -> &Entry {
+        &self.entry
+    }
+
+    #[must_use]
+    /// The number of times the entry occurred
+    pub const fn count(&self) -> usize {
+        self.count
+    }
+
+    #[must_use]
+    /// The timestamp of the first occurrence
+    pub fn first(&self) -> Option<&str> {
+        self.first.as_deref()
+    }
+
+    #[must_use]
+    /// The timestamp of the last occurrence
+    pub fn last(&self) -> Option<&str> {
+        self.last.as_deref()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Snippet {
+    Expression,
+    Position,
+}
+
+#[derive(Debug, Default)]
+/// A line based RPT parser
+///
+/// Lines can be pushed as they are written to the file,
+/// allowing an RPT to be followed while the game is running
+pub struct Parser {
+    current: Option<Entry>,
+    snippet: Option<(Snippet, String)>,
+}
+
+impl Parser {
+    #[must_use]
+    /// Create a new parser
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Push a line from the RPT, returns any entries that were completed by it
+    pub fn push(&mut self, line: &str) -> Vec<Entry> {
+        let line = line.trim_end_matches(['\r', '\n']);
+        if let Some((kind, mut snippet)) = self.snippet.take() {
+            snippet.push('\n');
+            if let Some(end) = line.strip_suffix('>') {
+                snippet.push_str(end);
+                self.finish_snippet(kind, &snippet);
+            } else {
+                snippet.push_str(line);
+                self.snippet = Some((kind, snippet));
+            }
+            return Vec::new();
+        }
+        let (timestamp, content) = split_timestamp(line);
+        let content = content.trim();
+        let mut done = Vec::new();
+        if let Some(expression) = content.strip_prefix("Error in expression <") {
+            done.extend(self.current.take());
+            self.current = Some(Entry {
+                level: Level::Error,
+                timestamp: timestamp.map(ToString::to_string),
+                ..Default::default()
+            });
+            self.start_snippet(Snippet::Expression, expression);
+        } else if let Some(position) = content.strip_prefix("Error position: <") {
+            if self.current.is_none() {
+                self.current = Some(Entry {
+                    level: Level::Error,
+                    timestamp: timestamp.map(ToString::to_string),
+                    ..Default::default()
+                });
+            }
+            self.start_snippet(Snippet::Position, position);
+        } else if let Some(file) = content.strip_prefix("File ") {
+            if let (Some((file, line)), Some(mut current)) =
+                (parse_file_line(file), self.current.take())
+            {
+                current.file = Some(file);
+                current.line = Some(line);
+                done.push(current);
+            }
+        } else if let Some(message) = content.strip_prefix("Warning Message: ") {
+            done.extend(self.current.take());
+            done.push(Entry {
+                level: Level::Warning,
+                timestamp: timestamp.map(ToString::to_string),
+                message: message.to_string(),
+                ..Default::default()
+            });
+        } else if let Some(message) = content.strip_prefix("Error ") {
+            if let Some(current) = self.current.as_mut().filter(|c| c.message.is_empty()) {
+                current.message = message.to_string();
+            }
+        } else if !content.is_empty() {
+            // Unrelated output, the current entry has no file
+            done.extend(self.current.take().filter(|c| !c.message.is_empty()));
+        }
+        done
+    }
+
+    /// Finish parsing, returns the last entry if it was incomplete
+    pub fn finish(&mut self) -> Option<Entry> {
+        self.snippet = None;
+        self.current.take().filter(|c| !c.message.is_empty())
+    }
+
+    fn start_snippet(&mut self, kind: Snippet, content: &str) {
+        if let Some(end) = content.strip_suffix('>') {
+            self.finish_snippet(kind, end);
+        } else {
+            self.snippet = Some((kind, content.to_string()));
+        }
+    }
+
+    fn finish_snippet(&mut self, kind: Snippet, snippet: &str) {
+        let Some(current) = &mut self.current else {
+            return;
+        };
+        let snippet = snippet.trim_end().to_string();
+        match kind {
+            Snippet::Expression => current.expression = Some(snippet),
+            Snippet::Position => current.position = Some(snippet),
+        }
+    }
+}
+
+#[must_use]
+/// Parse an entire RPT, returning each entry in order
+pub fn parse(content: &str) -> Vec<Entry> {
+    let mut parser = Parser::new();
+    let mut entries = Vec::new();
+    for line in content.lines() {
+        entries.extend(parser.push(line));
+    }
+    entries.extend(parser.finish());
+    entries
+}
+
+#[must_use]
+/// Group identical entries, in order of their first occurrence
+pub fn group(entries: Vec<Entry>) -> Vec<Group> {
+    let mut groups: Vec<Group> = Vec::new();
+    for entry in entries {
+        if let Some(group) = groups.iter_mut().find(|g| g.entry.key() == entry.key()) {
+            group.count += 1;
+            group.last.clone_from(&entry.timestamp);
+        } else {
+            groups.push(Group {
+                first: entry.timestamp.clone(),
+                last: entry.timestamp.clone(),
+                entry,
+                count: 1,
+            });
+        }
+    }
+    groups
+}
+
+/// Split a leading ` 8:12:17` or `18:12:17.123` timestamp from a line
+fn split_timestamp(line: &str) -> (Option<&str>, &str) {
+    let trimmed = line.trim_start();
+    let Some((stamp, rest)) = trimmed.split_once(' ') else {
+        return (None, line);
+    };
+    let mut parts = stamp.split(':');
+    let valid = parts.clone().count() == 3
+        && parts.all(|p| {
+            !p.is_empty() && p.chars().all(|c| c.is_ascii_digit() || c == '.') && p.len() <= 6
+        });
+    if valid {
+        (Some(stamp), rest)
+    } else {
+        (None, line)
+    }
+}
+
+/// Parse `\x\abe\addons\main\fnc_foo.sqf..., line 42`
+fn parse_file_line(content: &str) -> Option<(String, usize)> {
+    let (file, line) = content.rsplit_once(", line ")?;
+    let file = file.trim_end_matches("...").trim();
+    let line = line.trim().parse().ok()?;
+    if file.is_empty() {
+        return None;
+    }
+    Some((file.to_string(), line))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Level, group, parse};
+
+    const RPT: &str = r" 8:12:17 Error in expression <private _a = 1;
+_b = _z + 1;
+>
+ 8:12:17   Error position: <_z + 1;
+>
+ 8:12:17   Error Undefined variable in expression: _z
+ 8:12:17 File \x\abe\addons\main\functions\fnc_test.sqf..., line 12
+ 8:12:18 Warning Message: No entry 'bin\config.bin/CfgWeapons/abe_rifle.scope'.
+ 8:12:19 Error in expression <_b = _z + 1;>
+ 8:12:19   Error position: <_z + 1;>
+ 8:12:19   Error Undefined variable in expression: _z
+ 8:12:19 File \x\abe\addons\main\functions\fnc_test.sqf..., line 12
+ 8:12:20 Error in expression <call abe_main_fnc_missing>
+ 8:12:20   Error position: <abe_main_fnc_missing>
+ 8:12:20   Error Undefined variable in expression: abe_main_fnc_missing
+ 8:12:21 Mission id: 123
+";
+
+    #[test]
+    fn entries() {
+        let entries = parse(RPT);
+        assert_eq!(entries.len(), 4);
+        let first = &entries[0];
+        assert_eq!(first.level(), Level::Error);
+        assert_eq!(first.timestamp(), Some("8:12:17"));
+        assert_eq!(first.message(), "Undefined variable in expression: _z");
+        assert_eq!(first.expression(), Some("private _a = 1;\n_b = _z + 1;"));
+        assert_eq!(first.position(), Some("_z + 1;"));
+        assert_eq!(
+            first.file(),
+            Some(r"\x\abe\addons\main\functions\fnc_test.sqf")
+        );
+        assert_eq!(first.line(), Some(12));
+        let warning = &entries[1];
+        assert_eq!(warning.level(), Level::Warning);
+        assert_eq!(
+            warning.message(),
+            r"No entry 'bin\config.bin/CfgWeapons/abe_rifle.scope'."
+        );
+        assert_eq!(entries[2].position(), Some("_z + 1;"));
+        let no_file = &entries[3];
+        assert_eq!(no_file.file(), None);
+        assert_eq!(
+            no_file.message(),
+            "Undefined variable in expression: abe_main_fnc_missing"
+        );
+    }
+
+    #[test]
+    fn grouped() {
+        let groups = group(parse(RPT));
+        assert_eq!(groups.len(), 3);
+        assert_eq!(groups[0].count(), 2);
+        assert_eq!(groups[0].first(), Some("8:12:17"));
+        assert_eq!(groups[0].last(), Some("8:12:19"));
+        assert_eq!(groups[1].count(), 1);
+    }
+
+    #[test]
+    fn timestamps() {
+        assert_eq!(
+            super::split_timestamp("18:12:17.123 Warning Message: x"),
+            (Some("18:12:17.123"), "Warning Message: x")
+        );
+        assert_eq!(
+            super::split_timestamp("Error in expression <x>"),
+            (None, "Error in expression <x>")
+        );
+    }
+}
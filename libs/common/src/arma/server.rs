@@ -0,0 +1,102 @@
+//! Configuration files for a local dedicated server
+
+use std::fmt::Write;
+
+use crate::config::ServerOptions;
+
+#[must_use]
+/// Generate a `server.cfg` hosting `mission`, the name of a folder in `MPMissions`
+///
+/// Headless clients and file patching are allowed from the local machine only
+pub fn server_cfg(options: &ServerOptions, mission: Option<&str>) -> String {
+    let mut cfg = format!(
+        r#"hostname = "HEMTT";
+password = "{}";
+passwordAdmin = "hemtt";
+serverCommandPassword = "hemtt";
+maxPlayers = 64;
+persistent = 1;
+battlEye = 0;
+verifySignatures = 0;
+allowedFilePatching = 2;
+headlessClients[] = {{"127.0.0.1"}};
+localClient[] = {{"127.0.0.1"}};
+"#,
+        escape(options.password())
+    );
+    if let Some(mission) = mission {
+        let _ = write!(
+            cfg,
+            r#"class Missions {{
+    class hemtt {{
+        template = "{}";
+        difficulty = "Custom";
+    }};
+}};
+"#,
+            escape(mission.trim_end_matches(".pbo"))
+        );
+    }
+    cfg
+}
+
+#[must_use]
+/// Generate a `basic.cfg`, tuned for clients on the same machine
+pub fn basic_cfg() -> String {
+    r"MaxMsgSend = 2048;
+MaxSizeGuaranteed = 512;
+MaxSizeNonguaranteed = 256;
+MinBandwidth = 131072000;
+MaxBandwidth = 10000000000;
+MinErrorToSend = 0.001;
+MinErrorToSendNear = 0.01;
+MaxCustomFileSize = 0;
+"
+    .to_string()
+}
+
+/// Quotes are doubled inside config strings
+fn escape(value: &str) -> String {
+    value.replace('"', "\"\"")
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::config::ServerOptions;
+
+    #[test]
+    fn server_cfg() {
+        let options: ServerOptions = toml::from_str(
+            r#"
+mission = "test.VR"
+password = "say \"hi\""
+"#,
+        )
+        .expect("failed to deserialize");
+        let cfg = super::server_cfg(&options, options.mission());
+        assert!(cfg.contains(r#"password = "say ""hi""";"#));
+        assert!(cfg.contains(r#"headlessClients[] = {"127.0.0.1"};"#));
+        assert!(cfg.contains(
+            r#"class Missions {
+    class hemtt {
+        template = "test.VR";
+        difficulty = "Custom";
+    };
+};"#
+        ));
+    }
+
+    #[test]
+    fn server_cfg_no_mission() {
+        let cfg = super::server_cfg(&ServerOptions::default(), None);
+        assert!(cfg.contains(r#"password = "";"#));
+        assert!(!cfg.contains("class Missions"));
+    }
+
+    #[test]
+    fn basic_cfg() {
+        let cfg = super::basic_cfg();
+        assert!(cfg.contains("MaxMsgSend = 2048;"));
+        assert!(cfg.lines().all(|line| line.ends_with(';')));
+    }
+}
@@ -0,0 +1,327 @@
+//! Running SQF tests in game
+//!
+//! A [`Session`] answers the test mission over the [`control`](super::control) socket,
+//! and collects the results of each test file
+
+use std::fmt::Write;
+
+use super::control::{fromarma, toarma};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// A test file to run in game
+pub struct Test {
+    suite: String,
+    name: String,
+    path: String,
+}
+
+impl Test {
+    #[must_use]
+    /// Create a new test
+    ///
+    /// `path` is the path the game will load the file from, `\x\abe\addons\main\tests\foo.sqf`
+    pub const fn new(suite: String, name: String, path: String) -> Self {
+        Self { suite, name, path }
+    }
+
+    #[must_use]
+    /// The suite the test belongs to, usually the addon
+    pub fn suite(&self) -> &str {
+        &self.suite
+    }
+
+    #[must_use]
+    /// The name of the test, usually the file name
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    #[must_use]
+    /// The path the game will load the test from
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+/// The result of a single test
+pub struct TestResult {
+    test: Test,
+    failure: Option<String>,
+    seconds: f64,
+}
+
+impl TestResult {
+    #[must_use]
+    /// The test that was run
+    pub const fn test(&self) -> &Test {
+        &self.test
+    }
+
+    #[must_use]
+    /// Why the test failed, `None` if it passed
+    pub fn failure(&self) -> Option<&str> {
+        self.failure.as_deref()
+    }
+
+    #[must_use]
+    /// If the test passed
+    pub const fn passed(&self) -> bool {
+        self.failure.is_none()
+    }
+
+    #[must_use]
+    /// How long the test took to run
+    pub const fn seconds(&self) -> f64 {
+        self.seconds
+    }
+}
+
+#[derive(Debug, Default)]
+/// Runs a set of tests against the test mission
+pub struct Session {
+    tests: Vec<Test>,
+    results: Vec<TestResult>,
+    done: bool,
+}
+
+impl Session {
+    #[must_use]
+    /// Create a new session for the given tests
+    pub const fn new(tests: Vec<Test>) -> Self {
+        Self {
+            tests,
+            results: Vec::new(),
+            done: false,
+        }
+    }
+
+    /// Handle a message from the test mission, returning the messages to send back
+    pub fn incoming(&mut self, message: fromarma::Test) -> Vec<toarma::Message> {
+        match message {
+            fromarma::Test::Ready => {
+                vec![toarma::Message::Test(toarma::Test::Run(
+                    self.tests.iter().map(|t| t.path.clone()).collect(),
+                ))]
+            }
+            fromarma::Test::Passed(path, seconds) => {
+                self.record(&path, None, seconds);
+                Vec::new()
+            }
+            fromarma::Test::Failed(path, reason, seconds) => {
+                self.record(&path, Some(reason), seconds);
+                Vec::new()
+            }
+            fromarma::Test::Done => {
+                for test in &self.tests {
+                    if !self.results.iter().any(|r| r.test.path == test.path) {
+                        self.results.push(TestResult {
+                            test: test.clone(),
+                            failure: Some("the test did not run".to_string()),
+                            seconds: 0.0,
+                        });
+                    }
+                }
+                self.done = true;
+                vec![toarma::Message::Control(toarma::Control::Exit)]
+            }
+        }
+    }
+
+    fn record(&mut self, path: &str, failure: Option<String>, seconds: f64) {
+        let Some(test) = self.tests.iter().find(|t| t.path == path) else {
+            return;
+        };
+        self.results.push(TestResult {
+            test: test.clone(),
+            failure,
+            seconds,
+        });
+    }
+
+    #[must_use]
+    /// The tests in this session
+    pub fn tests(&self) -> &[Test] {
+        &self.tests
+    }
+
+    #[must_use]
+    /// The results received so far
+    pub fn results(&self) -> &[TestResult] {
+        &self.results
+    }
+
+    #[must_use]
+    /// If the test mission has finished running the tests
+    pub const fn done(&self) -> bool {
+        self.done
+    }
+
+    #[must_use]
+    /// The results as a `JUnit` XML report
+    pub fn junit(&self) -> String {
+        let mut suites: Vec<(&str, Vec<&TestResult>)> = Vec::new();
+        for result in &self.results {
+            if let Some((_, results)) = suites.iter_mut().find(|(s, _)| *s == result.test.suite) {
+                results.push(result);
+            } else {
+                suites.push((&result.test.suite, vec![result]));
+            }
+        }
+        let mut out = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        let _ = writeln!(
+            out,
+            "<testsuites name=\"hemtt\" tests=\"{}\" failures=\"{}\" time=\"{:.3}\">",
+            self.results.len(),
+            self.results.iter().filter(|r| !r.passed()).count(),
+            self.results.iter().map(|r| r.seconds).sum::<f64>(),
+        );
+        for (suite, results) in suites {
+            let _ = writeln!(
+                out,
+                "  <testsuite name=\"{}\" tests=\"{}\" failures=\"{}\" time=\"{:.3}\">",
+                escape(suite),
+                results.len(),
+                results.iter().filter(|r| !r.passed()).count(),
+                results.iter().map(|r| r.seconds).sum::<f64>(),
+            );
+            for result in results {
+                let _ = write!(
+                    out,
+                    "    <testcase name=\"{}\" classname=\"{}\" time=\"{:.3}\"",
+                    escape(&result.test.name),
+                    escape(suite),
+                    result.seconds,
+                );
+                if let Some(failure) = &result.failure {
+                    let _ = writeln!(
+                        out,
+                        ">\n      <failure message=\"{}\"/>\n    </testcase>",
+                        escape(failure)
+                    );
+                } else {
+                    out.push_str("/>\n");
+                }
+            }
+            out.push_str("  </testsuite>\n");
+        }
+        out.push_str("</testsuites>\n");
+        out
+    }
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::{TcpListener, TcpStream};
+
+    use crate::arma::control::{fromarma, read, toarma, write};
+
+    use super::{Session, Test};
+
+    fn tests() -> Vec<Test> {
+        ["ok", "bad", "slow"]
+            .into_iter()
+            .map(|name| {
+                Test::new(
+                    "main".to_string(),
+                    name.to_string(),
+                    format!(r"\x\abe\addons\main\tests\{name}.sqf"),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn session() {
+        let listener = TcpListener::bind("127.0.0.1:0").expect("failed to bind");
+        let addr = listener.local_addr().expect("no address");
+        // Stands in for the extension and test mission
+        let mission = std::thread::spawn(move || {
+            let mut stream = TcpStream::connect(addr).expect("failed to connect");
+            write(&mut stream, &fromarma::Message::Test(fromarma::Test::Ready))
+                .expect("failed to write");
+            let Some(toarma::Message::Test(toarma::Test::Run(paths))) =
+                read(&mut stream).expect("failed to read")
+            else {
+                panic!("expected tests to run");
+            };
+            assert_eq!(paths.len(), 3);
+            write(
+                &mut stream,
+                &fromarma::Message::Test(fromarma::Test::Passed(paths[0].clone(), 0.25)),
+            )
+            .expect("failed to write");
+            write(
+                &mut stream,
+                &fromarma::Message::Test(fromarma::Test::Failed(
+                    paths[1].clone(),
+                    "returned false".to_string(),
+                    0.5,
+                )),
+            )
+            .expect("failed to write");
+            write(&mut stream, &fromarma::Message::Test(fromarma::Test::Done))
+                .expect("failed to write");
+            let exit: Option<toarma::Message> = read(&mut stream).expect("failed to read");
+            assert!(matches!(
+                exit,
+                Some(toarma::Message::Control(toarma::Control::Exit))
+            ));
+        });
+        let (mut stream, _) = listener.accept().expect("failed to accept");
+        let mut session = Session::new(tests());
+        while !session.done() {
+            let Some(fromarma::Message::Test(message)) = read(&mut stream).expect("failed to read")
+            else {
+                continue;
+            };
+            for reply in session.incoming(message) {
+                write(&mut stream, &reply).expect("failed to write");
+            }
+        }
+        mission.join().expect("mission panicked");
+
+        let results = session.results();
+        assert_eq!(results.len(), 3);
+        assert!(results[0].passed());
+        assert_eq!(results[1].failure(), Some("returned false"));
+        assert_eq!(results[2].test().name(), "slow");
+        assert_eq!(results[2].failure(), Some("the test did not run"));
+    }
+
+    #[test]
+    fn junit() {
+        let mut session = Session::new(tests());
+        session.incoming(fromarma::Test::Passed(tests()[0].path().to_string(), 0.25));
+        session.incoming(fromarma::Test::Failed(
+            tests()[1].path().to_string(),
+            "expected <1> & got \"2\"".to_string(),
+            0.5,
+        ));
+        session.incoming(fromarma::Test::Done);
+        assert_eq!(
+            session.junit(),
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<testsuites name="hemtt" tests="3" failures="2" time="0.750">
+  <testsuite name="main" tests="3" failures="2" time="0.750">
+    <testcase name="ok" classname="main" time="0.250"/>
+    <testcase name="bad" classname="main" time="0.500">
+      <failure message="expected &lt;1&gt; &amp; got &quot;2&quot;"/>
+    </testcase>
+    <testcase name="slow" classname="main" time="0.000">
+      <failure message="the test did not run"/>
+    </testcase>
+  </testsuite>
+</testsuites>
+"#
+        );
+    }
+}
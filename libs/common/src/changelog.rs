@@ -0,0 +1,389 @@
+//! Release notes from the conventional commits since the last tag
+
+use std::{collections::HashMap, fmt::Write, path::Path};
+
+use git2::{Oid, Repository};
+
+use crate::{error::Error, version::Version};
+
+/// The sections of the release notes, by the kind of commit
+const SECTIONS: &[(&str, &str)] = &[
+    ("feat", "Features"),
+    ("fix", "Fixes"),
+    ("perf", "Performance"),
+];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// A commit, parsed as a conventional commit
+pub struct Commit {
+    kind: Option<String>,
+    scope: Option<String>,
+    breaking: bool,
+    description: String,
+    hash: String,
+}
+
+impl Commit {
+    #[must_use]
+    /// Parse a commit message, messages that are not conventional commits have no kind
+    pub fn parse(message: &str, hash: &str) -> Self {
+        let summary = message.lines().next().unwrap_or_default().trim();
+        let footer = message.lines().skip(1).any(|line| {
+            line.starts_with("BREAKING CHANGE:") || line.starts_with("BREAKING-CHANGE:")
+        });
+        let hash = hash.chars().take(7).collect();
+        let Some((header, description)) = summary
+            .split_once(':')
+            .filter(|(header, description)| is_header(header) && description.starts_with(' '))
+        else {
+            return Self {
+                kind: None,
+                scope: None,
+                breaking: footer,
+                description: summary.to_string(),
+                hash,
+            };
+        };
+        let (header, breaking) = header
+            .strip_suffix('!')
+            .map_or((header, false), |header| (header, true));
+        let (kind, scope) = header
+            .split_once('(')
+            .map_or((header, None), |(kind, scope)| {
+                (kind, Some(scope.trim_end_matches(')').to_string()))
+            });
+        Self {
+            kind: Some(kind.to_lowercase()),
+            scope: scope.filter(|scope| !scope.is_empty()),
+            breaking: breaking || footer,
+            description: description.trim().to_string(),
+            hash,
+        }
+    }
+
+    #[must_use]
+    /// The kind of the commit, like `feat` or `fix`
+    pub fn kind(&self) -> Option<&str> {
+        self.kind.as_deref()
+    }
+
+    #[must_use]
+    /// The scope of the commit
+    pub fn scope(&self) -> Option<&str> {
+        self.scope.as_deref()
+    }
+
+    #[must_use]
+    /// Is the commit a breaking change
+    pub const fn breaking(&self) -> bool {
+        self.breaking
+    }
+
+    #[must_use]
+    /// The description of the commit
+    pub fn description(&self) -> &str {
+        &self.description
+    }
+
+    #[must_use]
+    /// The short hash of the commit
+    pub fn hash(&self) -> &str {
+        &self.hash
+    }
+}
+
+/// Is the text before the `:` a conventional commit header, `kind(scope)!`
+fn is_header(header: &str) -> bool {
+    let header = header.strip_suffix('!').unwrap_or(header);
+    let (kind, scope) = header
+        .split_once('(')
+        .map_or((header, None), |(kind, scope)| (kind, Some(scope)));
+    !kind.is_empty()
+        && kind.chars().all(|c| c.is_ascii_alphabetic())
+        && scope.is_none_or(|scope| {
+            scope.ends_with(')') && !scope[..scope.len() - 1].contains(['(', ')'])
+        })
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// The commits since the last tag
+pub struct Changelog {
+    since: Option<String>,
+    commits: Vec<Commit>,
+}
+
+impl Changelog {
+    #[must_use]
+    /// Create release notes from commits, newest first
+    pub const fn new(since: Option<String>, commits: Vec<Commit>) -> Self {
+        Self { since, commits }
+    }
+
+    /// The commits between the last tag and `HEAD` of the repository containing the path
+    ///
+    /// If `HEAD` is tagged, the commits since the tag before it are used, so the notes of a tagged
+    /// release contain its changes. Merge commits are skipped.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the path is not in a git repository
+    pub fn discover(path: &Path) -> Result<Self, Error> {
+        let repo = Repository::discover(path)?;
+        let head = repo.head()?.peel_to_commit()?.id();
+        let mut tags: HashMap<Oid, String> = HashMap::new();
+        for name in repo.tag_names(None)?.iter().flatten() {
+            if let Ok(commit) = repo
+                .revparse_single(&format!("refs/tags/{name}"))
+                .and_then(|object| object.peel_to_commit())
+            {
+                tags.insert(commit.id(), name.to_string());
+            }
+        }
+
+        let mut walk = repo.revwalk()?;
+        walk.set_sorting(git2::Sort::TOPOLOGICAL | git2::Sort::TIME)?;
+        walk.push(head)?;
+        let mut since = None;
+        for oid in walk {
+            let oid = oid?;
+            if oid == head {
+                continue;
+            }
+            if let Some(tag) = tags.get(&oid) {
+                since = Some((oid, tag.clone()));
+                break;
+            }
+        }
+
+        let mut walk = repo.revwalk()?;
+        walk.set_sorting(git2::Sort::TOPOLOGICAL | git2::Sort::TIME)?;
+        walk.push(head)?;
+        if let Some((oid, _)) = &since {
+            walk.hide(*oid)?;
+        }
+        let mut commits = Vec::new();
+        for oid in walk {
+            let commit = repo.find_commit(oid?)?;
+            if commit.parent_count() > 1 {
+                continue;
+            }
+            commits.push(Commit::parse(
+                &String::from_utf8_lossy(commit.message_bytes()),
+                &commit.id().to_string(),
+            ));
+        }
+        Ok(Self::new(since.map(|(_, tag)| tag), commits))
+    }
+
+    #[must_use]
+    /// The tag the changes are since, `None` if there is no earlier tag
+    pub fn since(&self) -> Option<&str> {
+        self.since.as_deref()
+    }
+
+    #[must_use]
+    /// The commits, newest first
+    pub fn commits(&self) -> &[Commit] {
+        &self.commits
+    }
+
+    #[must_use]
+    /// The release notes of a version as markdown, grouped into sections by the kind of commit
+    ///
+    /// Breaking changes are listed first, commits that are not features, fixes, or performance
+    /// improvements are listed last
+    pub fn to_markdown(&self, version: &Version) -> String {
+        // The git hash is not part of the title
+        let title = Version::new(
+            version.major(),
+            version.minor(),
+            version.patch(),
+            version.build(),
+        );
+        let mut out = format!("## {title}\n");
+        let mut sections: Vec<(&str, Vec<&Commit>)> = vec![(
+            "Breaking Changes",
+            self.commits
+                .iter()
+                .filter(|commit| commit.breaking)
+                .collect(),
+        )];
+        for (kind, section) in SECTIONS {
+            sections.push((
+                section,
+                self.commits
+                    .iter()
+                    .filter(|commit| !commit.breaking && commit.kind() == Some(kind))
+                    .collect(),
+            ));
+        }
+        sections.push((
+            "Other Changes",
+            self.commits
+                .iter()
+                .filter(|commit| {
+                    !commit.breaking
+                        && !SECTIONS.iter().any(|(kind, _)| commit.kind() == Some(kind))
+                })
+                .collect(),
+        ));
+        if sections.iter().all(|(_, commits)| commits.is_empty()) {
+            out.push_str("\nNo changes\n");
+            return out;
+        }
+        for (section, commits) in sections {
+            if commits.is_empty() {
+                continue;
+            }
+            let _ = write!(out, "\n### {section}\n\n");
+            for commit in commits {
+                out.push_str("- ");
+                if let Some(scope) = commit.scope() {
+                    let _ = write!(out, "**{scope}:** ");
+                }
+                let _ = writeln!(out, "{} ({})", commit.description, commit.hash);
+            }
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse() {
+        let commit = Commit::parse("feat(medical): add splints\n\nbody", "1a2b3c4d5e6f");
+        assert_eq!(commit.kind(), Some("feat"));
+        assert_eq!(commit.scope(), Some("medical"));
+        assert!(!commit.breaking());
+        assert_eq!(commit.description(), "add splints");
+        assert_eq!(commit.hash(), "1a2b3c4");
+
+        let commit = Commit::parse("Fix!: remove the old API", "1a2b3c4");
+        assert_eq!(commit.kind(), Some("fix"));
+        assert_eq!(commit.scope(), None);
+        assert!(commit.breaking());
+
+        let commit = Commit::parse(
+            "refactor: rename functions\n\nBREAKING CHANGE: functions are renamed",
+            "1a2b3c4",
+        );
+        assert_eq!(commit.kind(), Some("refactor"));
+        assert!(commit.breaking());
+    }
+
+    #[test]
+    fn parse_not_conventional() {
+        for message in [
+            "Update README.md",
+            "Merge branch 'main': conflicts",
+            "feat:missing space",
+            "feat(scope: unclosed",
+        ] {
+            let commit = Commit::parse(message, "1a2b3c4");
+            assert_eq!(commit.kind(), None, "{message}");
+            assert_eq!(commit.description(), message);
+        }
+    }
+
+    #[test]
+    fn markdown() {
+        let changelog = Changelog::new(
+            Some("v1.0.0".to_string()),
+            vec![
+                Commit::parse("fix(ui): align buttons", "aaaaaaa"),
+                Commit::parse("feat: add vehicles", "bbbbbbb"),
+                Commit::parse("Update README.md", "ccccccc"),
+                Commit::parse("feat!: remove vehicles", "ddddddd"),
+            ],
+        );
+        assert_eq!(changelog.since(), Some("v1.0.0"));
+        assert_eq!(
+            changelog.to_markdown(&Version::try_from("1.1.0-1a2b3c4d").unwrap()),
+            "## 1.1.0
+
+### Breaking Changes
+
+- remove vehicles (ddddddd)
+
+### Features
+
+- add vehicles (bbbbbbb)
+
+### Fixes
+
+- **ui:** align buttons (aaaaaaa)
+
+### Other Changes
+
+- Update README.md (ccccccc)
+"
+        );
+        assert_eq!(
+            Changelog::new(None, Vec::new()).to_markdown(&Version::new(1, 1, 0, Some(5))),
+            "## 1.1.0.5\n\nNo changes\n"
+        );
+    }
+
+    #[test]
+    fn repository() {
+        let path = std::env::temp_dir().join(format!("hemtt-changelog-{}", std::process::id()));
+        if path.exists() {
+            std::fs::remove_dir_all(&path).unwrap();
+        }
+        let repo = Repository::init(&path).unwrap();
+        let signature = git2::Signature::now("HEMTT", "hemtt@example.com").unwrap();
+        let commit = |message: &str| {
+            let tree = repo
+                .find_tree(repo.index().unwrap().write_tree().unwrap())
+                .unwrap();
+            let parents = repo
+                .head()
+                .ok()
+                .map(|head| head.peel_to_commit().unwrap())
+                .into_iter()
+                .collect::<Vec<_>>();
+            let parents = parents.iter().collect::<Vec<_>>();
+            repo.commit(
+                Some("HEAD"),
+                &signature,
+                &signature,
+                message,
+                &tree,
+                &parents,
+            )
+            .unwrap()
+        };
+        let tag = |name: &str, oid: Oid| {
+            repo.tag_lightweight(name, &repo.find_object(oid, None).unwrap(), false)
+                .unwrap();
+        };
+
+        commit("feat: first");
+        assert_eq!(Changelog::discover(&path).unwrap().commits().len(), 1);
+        let first = commit("fix: released");
+        tag("v1.0.0", first);
+        commit("feat: second");
+        let head = commit("fix: third");
+        let changelog = Changelog::discover(&path).unwrap();
+        assert_eq!(changelog.since(), Some("v1.0.0"));
+        assert_eq!(
+            changelog
+                .commits()
+                .iter()
+                .map(Commit::description)
+                .collect::<Vec<_>>(),
+            vec!["third", "second"]
+        );
+
+        // The notes of a tagged release
+        tag("v1.1.0", head);
+        let changelog = Changelog::discover(&path).unwrap();
+        assert_eq!(changelog.since(), Some("v1.0.0"));
+        assert_eq!(changelog.commits().len(), 2);
+        std::fs::remove_dir_all(&path).unwrap();
+    }
+}
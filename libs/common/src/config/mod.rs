@@ -12,8 +12,14 @@ pub use addon::AddonConfig;
 pub use pdrive::PDriveOption;
 pub use project::{
     ProjectConfig,
-    hemtt::{RuntimeArguments, launch::LaunchOptions},
+    functions::FunctionsConfig,
+    hemtt::{
+        RuntimeArguments,
+        launch::{LaunchOptions, ServerOptions},
+        release::{ArchiveFormat, ArchiveTarget},
+    },
     lint::{LintConfig, LintConfigOverride, LintEnabled},
+    workshop::Visibility,
 };
 
 fn deprecated(file: &str, key: &str, replacement: &str, info: Option<&str>) {
@@ -0,0 +1,125 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+#[allow(clippy::module_name_repetitions)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// Configuration for generating `CfgFunctions`
+pub struct FunctionsConfig {
+    enabled: bool,
+
+    tag: String,
+
+    folder: String,
+
+    categories: HashMap<String, String>,
+}
+
+impl FunctionsConfig {
+    #[must_use]
+    /// Should `CfgFunctions` be generated from the functions folder of each addon?
+    pub const fn enabled(&self) -> bool {
+        self.enabled
+    }
+
+    #[must_use]
+    #[allow(clippy::literal_string_with_formatting_args)]
+    /// The tag of an addon's functions, `{prefix}` and `{addon}` are replaced
+    pub fn tag(&self, prefix: &str, addon: &str) -> String {
+        self.tag
+            .replace("{prefix}", prefix)
+            .replace("{addon}", addon)
+    }
+
+    #[must_use]
+    /// The folder inside each addon that functions are read from
+    pub fn folder(&self) -> &str {
+        &self.folder
+    }
+
+    #[must_use]
+    /// The category of functions in a subfolder of an addon's functions folder
+    ///
+    /// Functions directly in the folder use the addon name, and functions in a subfolder use
+    /// the subfolder's name, unless either is mapped to another category.
+    /// Nested subfolders are joined with `_`, `a/b` is the category `a_b`
+    pub fn category(&self, addon: &str, subfolder: Option<&str>) -> String {
+        let (key, default) = subfolder.map_or_else(
+            || (addon.to_string(), addon.to_string()),
+            |subfolder| (format!("{addon}/{subfolder}"), subfolder.replace('/', "_")),
+        );
+        self.categories.get(&key).cloned().unwrap_or(default)
+    }
+}
+
+#[allow(clippy::module_name_repetitions)]
+#[derive(PartialEq, Eq, Debug, Default, Clone, Serialize, Deserialize)]
+pub struct FunctionsSectionFile {
+    #[serde(default)]
+    enabled: Option<bool>,
+
+    #[serde(default)]
+    tag: Option<String>,
+
+    #[serde(default)]
+    folder: Option<String>,
+
+    #[serde(default)]
+    categories: HashMap<String, String>,
+}
+
+impl From<FunctionsSectionFile> for FunctionsConfig {
+    #[allow(clippy::literal_string_with_formatting_args)]
+    fn from(file: FunctionsSectionFile) -> Self {
+        Self {
+            enabled: file.enabled.unwrap_or_default(),
+            tag: file.tag.unwrap_or_else(|| "{prefix}_{addon}".to_string()),
+            folder: file.folder.unwrap_or_else(|| "functions".to_string()),
+            categories: file.categories,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fully_defined() {
+        let toml = r#"
+enabled = true
+tag = "{prefix}"
+folder = "fnc"
+
+[categories]
+main = "Core"
+"main/vehicles" = "Transport"
+"main/vehicles/air" = "Aircraft"
+"#;
+        let file: FunctionsSectionFile = toml::from_str(toml).expect("failed to deserialize");
+        let config = FunctionsConfig::from(file);
+        assert!(config.enabled());
+        assert_eq!(config.tag("abe", "main"), "abe");
+        assert_eq!(config.folder(), "fnc");
+        assert_eq!(config.category("main", None), "Core");
+        assert_eq!(config.category("main", Some("vehicles")), "Transport");
+        assert_eq!(config.category("main", Some("ui")), "ui");
+        assert_eq!(config.category("main", Some("vehicles/air")), "Aircraft");
+        assert_eq!(
+            config.category("main", Some("vehicles/land")),
+            "vehicles_land"
+        );
+        assert_eq!(config.category("other", None), "other");
+    }
+
+    #[test]
+    fn default() {
+        let toml = "";
+        let file: FunctionsSectionFile = toml::from_str(toml).expect("failed to deserialize");
+        let config = FunctionsConfig::from(file);
+        assert!(!config.enabled());
+        assert_eq!(config.tag("abe", "main"), "abe_main");
+        assert_eq!(config.folder(), "functions");
+        assert_eq!(config.category("main", None), "main");
+    }
+}
@@ -41,6 +41,9 @@ pub struct LaunchOptions {
 
     // Should HEMTT rapify
     rapify: Option<bool>,
+
+    /// Dedicated server to launch, with clients connecting to it
+    server: Option<ServerOptions>,
 }
 
 impl LaunchOptions {
@@ -136,6 +139,12 @@ impl LaunchOptions {
         self.rapify.unwrap_or(true)
     }
 
+    #[must_use]
+    /// Dedicated server to launch, with clients connecting to it
+    pub const fn server(&self) -> Option<&ServerOptions> {
+        self.server.as_ref()
+    }
+
     #[must_use]
     /// Overlay two launch options
     /// Other will take precedence
@@ -167,6 +176,104 @@ impl LaunchOptions {
         if let Some(rapify) = other.rapify {
             base.rapify = Some(rapify);
         }
+        base.server = match (base.server, other.server) {
+            (Some(base), Some(other)) => Some(base.overlay(other)),
+            (base, other) => other.or(base),
+        };
+        base
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+/// A dedicated server for `hemtt launch`
+pub struct ServerOptions {
+    #[serde(default)]
+    /// Mission to host, copied to `MPMissions`
+    mission: Option<String>,
+
+    #[serde(default)]
+    /// Number of headless clients to connect
+    headless_clients: Option<u8>,
+
+    #[serde(default)]
+    /// Port to host on
+    port: Option<u16>,
+
+    #[serde(default)]
+    /// Password required to join
+    password: Option<String>,
+
+    #[serde(default)]
+    /// Server binary to launch
+    executable: Option<String>,
+
+    #[serde(default)]
+    /// Extra server launch parameters
+    parameters: Vec<String>,
+}
+
+impl ServerOptions {
+    #[must_use]
+    /// Mission to host, copied to `MPMissions`
+    pub fn mission(&self) -> Option<&str> {
+        self.mission.as_deref()
+    }
+
+    #[must_use]
+    /// Number of headless clients to connect
+    /// Defaults to `0`
+    pub fn headless_clients(&self) -> u8 {
+        self.headless_clients.unwrap_or(0)
+    }
+
+    #[must_use]
+    /// Port to host on
+    /// Defaults to `2302`
+    pub fn port(&self) -> u16 {
+        self.port.unwrap_or(2302)
+    }
+
+    #[must_use]
+    /// Password required to join
+    /// Defaults to no password
+    pub fn password(&self) -> &str {
+        self.password.as_deref().unwrap_or_default()
+    }
+
+    #[must_use]
+    /// Server binary to launch, without an extension
+    /// Defaults to `arma3server_x64`
+    pub fn executable(&self) -> &str {
+        self.executable.as_deref().unwrap_or("arma3server_x64")
+    }
+
+    #[must_use]
+    /// Extra server launch parameters
+    pub fn parameters(&self) -> &[String] {
+        &self.parameters
+    }
+
+    #[must_use]
+    /// Overlay two server options
+    /// Other will take precedence
+    pub fn overlay(self, other: Self) -> Self {
+        let mut base = self;
+        base.parameters.extend(other.parameters);
+        if let Some(mission) = other.mission {
+            base.mission = Some(mission);
+        }
+        if let Some(headless_clients) = other.headless_clients {
+            base.headless_clients = Some(headless_clients);
+        }
+        if let Some(port) = other.port {
+            base.port = Some(port);
+        }
+        if let Some(password) = other.password {
+            base.password = Some(password);
+        }
+        if let Some(executable) = other.executable {
+            base.executable = Some(executable);
+        }
         base
     }
 }
@@ -213,6 +320,9 @@ pub struct LaunchOptionsFile {
 
     #[serde(default)]
     rapify: Option<bool>,
+
+    #[serde(default)]
+    server: Option<ServerOptions>,
 }
 
 impl LaunchOptionsFile {
@@ -244,6 +354,10 @@ impl LaunchOptionsFile {
         if let Some(rapify) = self.rapify {
             other.rapify = Some(rapify);
         }
+        other.server = match (other.server, self.server) {
+            (Some(base), Some(server)) => Some(base.overlay(server)),
+            (base, server) => server.or(base),
+        };
         other
     }
 
@@ -276,6 +390,7 @@ impl From<LaunchOptionsFile> for LaunchOptions {
             file_patching: file.file_patching,
             instances: file.instances,
             rapify: file.rapify,
+            server: file.server,
         }
     }
 }
@@ -330,5 +445,35 @@ rapify = false
         assert!(config.file_patching());
         assert_eq!(config.instances(), 1);
         assert!(config.rapify());
+        assert!(config.server().is_none());
+    }
+
+    #[test]
+    fn server() {
+        let base: LaunchOptionsFile = toml::from_str(
+            r#"
+[server]
+mission = "test.VR"
+parameters = ["-loadMissionToMemory"]
+"#,
+        )
+        .expect("failed to deserialize");
+        let extended: LaunchOptionsFile = toml::from_str(
+            r#"
+[server]
+headless_clients = 2
+password = "hemtt"
+parameters = ["-autoInit"]
+"#,
+        )
+        .expect("failed to deserialize");
+        let config = LaunchOptions::from(extended.extend(base));
+        let server = config.server().expect("server is set");
+        assert_eq!(server.mission(), Some("test.VR"));
+        assert_eq!(server.headless_clients(), 2);
+        assert_eq!(server.password(), "hemtt");
+        assert_eq!(server.port(), 2302);
+        assert_eq!(server.executable(), "arma3server_x64");
+        assert_eq!(server.parameters(), &["-loadMissionToMemory", "-autoInit"]);
     }
 }
@@ -24,6 +24,8 @@ pub struct HemttConfig {
     build: build::BuildOptions,
 
     release: release::ReleaseOptions,
+
+    include_pbos: Vec<String>,
 }
 
 impl HemttConfig {
@@ -51,6 +53,11 @@ impl HemttConfig {
     pub const fn release(&self) -> &release::ReleaseOptions {
         &self.release
     }
+
+    /// Get the folders of PBOs from other mods that includes can be resolved from
+    pub fn include_pbos(&self) -> &[String] {
+        &self.include_pbos
+    }
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, Default)]
@@ -60,6 +67,8 @@ pub struct RuntimeArguments {
     is_just: bool,
 
     explicit_lints: Arc<[String]>,
+
+    source_date_epoch: Option<u32>,
 }
 
 impl RuntimeArguments {
@@ -108,6 +117,20 @@ impl RuntimeArguments {
             ..self
         }
     }
+
+    #[must_use]
+    /// The time to use instead of the current time, for reproducible output
+    pub const fn source_date_epoch(&self) -> Option<u32> {
+        self.source_date_epoch
+    }
+
+    #[must_use]
+    pub fn with_source_date_epoch(self, source_date_epoch: Option<u32>) -> Self {
+        Self {
+            source_date_epoch,
+            ..self
+        }
+    }
 }
 
 #[allow(clippy::module_name_repetitions)]
@@ -128,6 +151,10 @@ pub struct HemttSectionFile {
 
     #[serde(default)]
     release: release::ReleaseOptionsFile,
+
+    #[serde(default)]
+    /// Folders of PBOs from other mods, relative to the project
+    include_pbos: Vec<String>,
 }
 
 impl HemttSectionFile {
@@ -195,6 +222,7 @@ impl HemttSectionFile {
             },
             build: self.build.into(),
             release: self.release.into_config(prefix),
+            include_pbos: self.include_pbos,
         })
     }
 }
@@ -275,4 +303,21 @@ mission = "test"
         let config = file.into_config(Path::new("."), "test");
         assert!(config.is_err());
     }
+
+    #[test]
+    fn include_pbos() {
+        let toml = r#"
+include_pbos = ["../@CBA_A3", "mods"]
+"#;
+        let file: HemttSectionFile = toml::from_str(toml).expect("failed to deserialize");
+        let config = file
+            .into_config(Path::new("."), "test")
+            .expect("failed to convert");
+        assert_eq!(config.include_pbos(), &["../@CBA_A3", "mods"]);
+        let file: HemttSectionFile = toml::from_str("").expect("failed to deserialize");
+        let config = file
+            .into_config(Path::new("."), "test")
+            .expect("failed to convert");
+        assert!(config.include_pbos().is_empty());
+    }
 }
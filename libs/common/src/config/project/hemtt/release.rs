@@ -7,6 +7,8 @@ pub struct ReleaseOptions {
     folder: String,
     sign: bool,
     archive: bool,
+    archives: Vec<ArchiveTarget>,
+    changelog: Option<String>,
 }
 
 impl ReleaseOptions {
@@ -27,6 +29,138 @@ impl ReleaseOptions {
     pub const fn archive(&self) -> bool {
         self.archive
     }
+
+    /// Archives to create of the release
+    /// Defaults to a zip named `{prefix}-latest` and a zip named `{prefix}-{version}`
+    pub fn archives(&self) -> &[ArchiveTarget] {
+        &self.archives
+    }
+
+    #[must_use]
+    /// Name of the release notes written to `releases`, without the extension
+    /// A template, see [`ArchiveTarget::folder`]
+    pub fn changelog(&self) -> Option<&str> {
+        self.changelog.as_deref()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+/// The format of a release archive
+pub enum ArchiveFormat {
+    #[serde(rename = "zip")]
+    Zip,
+    #[serde(rename = "tar.zst")]
+    TarZst,
+    #[serde(rename = "7z")]
+    SevenZ,
+    #[serde(rename = "folder")]
+    /// A plain folder, not an archive
+    Folder,
+    #[serde(rename = "swifty")]
+    /// A Swifty repository, a folder with a `repo.json` and a `mod.srf` for each mod
+    Swifty,
+}
+
+impl ArchiveFormat {
+    #[must_use]
+    /// The file extension of the archive, none for a folder
+    pub const fn extension(self) -> Option<&'static str> {
+        match self {
+            Self::Zip => Some("zip"),
+            Self::TarZst => Some("tar.zst"),
+            Self::SevenZ => Some("7z"),
+            Self::Folder | Self::Swifty => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// An archive to create of the release
+pub struct ArchiveTarget {
+    format: ArchiveFormat,
+    name: String,
+    folder: String,
+    optionals: bool,
+    keys: bool,
+    previous: Option<String>,
+}
+
+impl ArchiveTarget {
+    #[must_use]
+    /// The format of the archive
+    pub const fn format(&self) -> ArchiveFormat {
+        self.format
+    }
+
+    #[must_use]
+    /// Name of the archive, without the extension
+    /// A template, see [`ArchiveTarget::folder`]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    #[must_use]
+    /// Folder in the archive that contains the release, empty for the root of the archive
+    /// A template that can use `{prefix}`, `{folder}`, `{version}`, `{major}`, `{minor}`, `{patch}`, `{build}` and `{hash}`
+    /// Defaults to `@{folder}`
+    pub fn folder(&self) -> &str {
+        &self.folder
+    }
+
+    #[must_use]
+    /// Include the optionals folder
+    /// Defaults to true
+    pub const fn optionals(&self) -> bool {
+        self.optionals
+    }
+
+    #[must_use]
+    /// Include the keys
+    /// Defaults to true
+    pub const fn keys(&self) -> bool {
+        self.keys
+    }
+
+    #[must_use]
+    /// The previous repository, relative to the project, for incremental updates of a Swifty repository
+    /// Defaults to the repository created by the last release
+    pub fn previous(&self) -> Option<&str> {
+        self.previous.as_deref()
+    }
+}
+
+#[derive(PartialEq, Eq, Debug, Clone, Serialize, Deserialize)]
+/// An archive to create of the release
+pub struct ArchiveTargetFile {
+    #[serde(default)]
+    format: Option<ArchiveFormat>,
+
+    name: String,
+
+    #[serde(default)]
+    folder: Option<String>,
+
+    #[serde(default)]
+    optionals: Option<bool>,
+
+    #[serde(default)]
+    keys: Option<bool>,
+
+    #[serde(default)]
+    previous: Option<String>,
+}
+
+impl From<ArchiveTargetFile> for ArchiveTarget {
+    fn from(file: ArchiveTargetFile) -> Self {
+        Self {
+            format: file.format.unwrap_or(ArchiveFormat::Zip),
+            name: file.name,
+            folder: file.folder.unwrap_or_else(|| "@{folder}".to_string()),
+            optionals: file.optionals.unwrap_or(true),
+            keys: file.keys.unwrap_or(true),
+            previous: file.previous,
+        }
+    }
 }
 
 #[allow(clippy::module_name_repetitions)]
@@ -41,6 +175,12 @@ pub struct ReleaseOptionsFile {
 
     #[serde(default)]
     archive: Option<bool>,
+
+    #[serde(default)]
+    archives: Option<Vec<ArchiveTargetFile>>,
+
+    #[serde(default)]
+    changelog: Option<String>,
 }
 
 impl ReleaseOptionsFile {
@@ -49,6 +189,23 @@ impl ReleaseOptionsFile {
             folder: self.folder.unwrap_or_else(|| prefix.to_string()),
             sign: self.sign.unwrap_or(true),
             archive: self.archive.unwrap_or(true),
+            archives: self.archives.map_or_else(
+                || {
+                    ["{prefix}-latest", "{prefix}-{version}"]
+                        .into_iter()
+                        .map(|name| ArchiveTarget {
+                            format: ArchiveFormat::Zip,
+                            name: name.to_string(),
+                            folder: "@{folder}".to_string(),
+                            optionals: true,
+                            keys: true,
+                            previous: None,
+                        })
+                        .collect()
+                },
+                |archives| archives.into_iter().map(Into::into).collect(),
+            ),
+            changelog: self.changelog,
         }
     }
 }
@@ -63,12 +220,14 @@ mod tests {
 folder = "test"
 sign = false
 archive = false
+changelog = "{prefix}-{version}"
 "#;
         let file: ReleaseOptionsFile = toml::from_str(toml).expect("failed to deserialize");
         let config = file.into_config("test");
         assert_eq!(config.folder(), "test");
         assert!(!config.sign());
         assert!(!config.archive());
+        assert_eq!(config.changelog(), Some("{prefix}-{version}"));
     }
 
     #[test]
@@ -79,5 +238,82 @@ archive = false
         assert_eq!(config.folder(), "test");
         assert!(config.sign());
         assert!(config.archive());
+        assert!(config.changelog().is_none());
+        assert_eq!(config.archives().len(), 2);
+        assert_eq!(config.archives()[0].name(), "{prefix}-latest");
+        assert_eq!(config.archives()[1].name(), "{prefix}-{version}");
+        assert!(
+            config
+                .archives()
+                .iter()
+                .all(|archive| archive.format() == ArchiveFormat::Zip
+                    && archive.folder() == "@{folder}"
+                    && archive.optionals()
+                    && archive.keys())
+        );
+    }
+
+    #[test]
+    fn archives() {
+        let toml = r#"
+[[archives]]
+name = "{prefix}-{major}.{minor}"
+
+[[archives]]
+format = "tar.zst"
+name = "{prefix}-mirror"
+folder = ""
+optionals = false
+keys = false
+
+[[archives]]
+format = "7z"
+name = "{prefix}"
+
+[[archives]]
+format = "folder"
+name = "workshop"
+"#;
+        let file: ReleaseOptionsFile = toml::from_str(toml).expect("failed to deserialize");
+        let config = file.into_config("test");
+        let archives = config.archives();
+        assert_eq!(archives.len(), 4);
+        assert_eq!(archives[0].format(), ArchiveFormat::Zip);
+        assert_eq!(archives[0].name(), "{prefix}-{major}.{minor}");
+        assert_eq!(archives[0].folder(), "@{folder}");
+        assert_eq!(archives[1].format(), ArchiveFormat::TarZst);
+        assert_eq!(archives[1].folder(), "");
+        assert!(!archives[1].optionals());
+        assert!(!archives[1].keys());
+        assert_eq!(archives[2].format().extension(), Some("7z"));
+        assert_eq!(archives[3].format(), ArchiveFormat::Folder);
+        assert_eq!(archives[3].format().extension(), None);
+        assert!(archives.iter().all(|archive| archive.previous().is_none()));
+    }
+
+    #[test]
+    fn swifty() {
+        let toml = r#"
+[[archives]]
+format = "swifty"
+name = "repo"
+previous = "mirror/repo"
+"#;
+        let file: ReleaseOptionsFile = toml::from_str(toml).expect("failed to deserialize");
+        let config = file.into_config("test");
+        let archives = config.archives();
+        assert_eq!(archives[0].format(), ArchiveFormat::Swifty);
+        assert_eq!(archives[0].format().extension(), None);
+        assert_eq!(archives[0].previous(), Some("mirror/repo"));
+    }
+
+    #[test]
+    fn invalid_format() {
+        let toml = r#"
+[[archives]]
+format = "rar"
+name = "test"
+"#;
+        assert!(toml::from_str::<ReleaseOptionsFile>(toml).is_err());
     }
 }
@@ -11,10 +11,12 @@ use crate::error::Error;
 use super::deprecated;
 
 pub mod files;
+pub mod functions;
 pub mod hemtt;
 pub mod lint;
 pub mod signing;
 pub mod version;
+pub mod workshop;
 
 #[allow(clippy::module_name_repetitions)]
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -38,6 +40,9 @@ pub struct ProjectConfig {
     /// Files to be included in the root of the project, supports glob patterns
     files: files::FilesConfig,
 
+    /// Generation of `CfgFunctions`
+    functions: functions::FunctionsConfig,
+
     /// Configuration for lints
     lints: lint::LintGroupConfig,
 
@@ -47,6 +52,9 @@ pub struct ProjectConfig {
     /// Signing specific configuration
     signing: signing::SigningConfig,
 
+    /// Steam Workshop specific configuration
+    workshop: workshop::WorkshopConfig,
+
     /// Runtime specific arguments
     runtime: hemtt::RuntimeArguments,
 }
@@ -94,6 +102,12 @@ impl ProjectConfig {
         &self.files
     }
 
+    #[must_use]
+    /// Generation of `CfgFunctions`
+    pub const fn functions(&self) -> &functions::FunctionsConfig {
+        &self.functions
+    }
+
     #[must_use]
     /// Configuration for lints
     pub const fn lints(&self) -> &lint::LintGroupConfig {
@@ -112,6 +126,12 @@ impl ProjectConfig {
         &self.signing
     }
 
+    #[must_use]
+    /// Steam Workshop specific configuration
+    pub const fn workshop(&self) -> &workshop::WorkshopConfig {
+        &self.workshop
+    }
+
     #[must_use]
     /// HEMTT specific configuration
     pub const fn runtime(&self) -> &hemtt::RuntimeArguments {
@@ -159,6 +179,10 @@ pub struct ProjectFile {
     /// Files to be included in the root of the project, supports glob patterns
     files: files::FilesSectionFile,
 
+    #[serde(default)]
+    /// Generation of `CfgFunctions`
+    functions: functions::FunctionsSectionFile,
+
     #[serde(default)]
     /// Lint configuration
     lints: lint::LintSectionFile,
@@ -169,6 +193,9 @@ pub struct ProjectFile {
     #[serde(default)]
     signing: signing::SigningSectionFile,
 
+    #[serde(default)]
+    workshop: workshop::WorkshopSectionFile,
+
     #[serde(skip)]
     meta_path: PathBuf,
 }
@@ -221,8 +248,10 @@ impl TryFrom<ProjectFile> for ProjectConfig {
             version: file.version.try_into()?,
             properties: file.properties,
             files: file.files.into(),
+            functions: file.functions.into(),
             lints: file.lints.into(),
             signing: file.signing.into(),
+            workshop: file.workshop.into(),
             runtime: RuntimeArguments::default(),
         };
 
@@ -254,7 +283,7 @@ impl TryFrom<ProjectFile> for ProjectConfig {
 mod test_helper {
     use std::collections::HashMap;
 
-    use super::{files, hemtt, lint, signing, version};
+    use super::{files, functions, hemtt, lint, signing, version, workshop};
 
     impl super::ProjectConfig {
         #[must_use]
@@ -271,9 +300,11 @@ mod test_helper {
                 version: version::VersionSectionFile::default(),
                 properties: HashMap::default(),
                 files: files::FilesSectionFile::default(),
+                functions: functions::FunctionsSectionFile::default(),
                 lints: lint::LintSectionFile::default(),
                 hemtt: hemtt::HemttSectionFile::default(),
                 signing: signing::SigningSectionFile::default(),
+                workshop: workshop::WorkshopSectionFile::default(),
                 meta_path: std::path::PathBuf::default(),
             }
             .try_into()
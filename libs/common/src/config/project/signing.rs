@@ -8,6 +8,10 @@ pub struct SigningConfig {
     version: BISignVersion,
 
     authority: Option<String>,
+
+    private_key: Option<String>,
+
+    private_key_env: Option<String>,
 }
 
 impl SigningConfig {
@@ -18,6 +22,19 @@ impl SigningConfig {
     pub fn authority(&self) -> Option<&str> {
         self.authority.as_deref()
     }
+
+    pub fn private_key(&self) -> Option<&str> {
+        self.private_key.as_deref()
+    }
+
+    pub fn private_key_env(&self) -> Option<&str> {
+        self.private_key_env.as_deref()
+    }
+
+    /// Whether a persistent key is configured, instead of generating a new key for each release
+    pub const fn is_persistent(&self) -> bool {
+        self.private_key.is_some() || self.private_key_env.is_some()
+    }
 }
 
 #[allow(clippy::module_name_repetitions)]
@@ -27,6 +44,12 @@ pub struct SigningSectionFile {
 
     #[serde(default)]
     authority: Option<String>,
+
+    #[serde(default)]
+    private_key: Option<String>,
+
+    #[serde(default)]
+    private_key_env: Option<String>,
 }
 
 impl From<SigningSectionFile> for SigningConfig {
@@ -34,6 +57,8 @@ impl From<SigningSectionFile> for SigningConfig {
         Self {
             version: file.version.unwrap_or_default(),
             authority: file.authority,
+            private_key: file.private_key,
+            private_key_env: file.private_key_env,
         }
     }
 }
@@ -52,6 +77,20 @@ authority = "test"
         let config = SigningConfig::from(file);
         assert_eq!(config.version(), BISignVersion::V2);
         assert_eq!(config.authority(), Some("test"));
+        assert!(!config.is_persistent());
+    }
+
+    #[test]
+    fn persistent() {
+        let toml = r#"
+private_key = "keys/test.biprivatekey"
+private_key_env = "TEST_PRIVATE_KEY"
+"#;
+        let file: SigningSectionFile = toml::from_str(toml).expect("failed to deserialize");
+        let config = SigningConfig::from(file);
+        assert_eq!(config.private_key(), Some("keys/test.biprivatekey"));
+        assert_eq!(config.private_key_env(), Some("TEST_PRIVATE_KEY"));
+        assert!(config.is_persistent());
     }
 
     #[test]
@@ -61,5 +100,8 @@ authority = "test"
         let config = SigningConfig::from(file);
         assert_eq!(config.version(), BISignVersion::V3);
         assert!(config.authority().is_none());
+        assert!(config.private_key().is_none());
+        assert!(config.private_key_env().is_none());
+        assert!(!config.is_persistent());
     }
 }
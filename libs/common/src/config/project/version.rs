@@ -1,11 +1,14 @@
-use std::{mem::MaybeUninit, sync::RwLock};
+use std::{mem::MaybeUninit, path::Path, sync::RwLock};
 
 use git2::Repository;
 use serde::{Deserialize, Serialize};
 use tracing::trace;
 use vfs::VfsPath;
 
-use crate::{error::Error, version::Version};
+use crate::{
+    error::Error,
+    version::{Component, Version, join_lines, replace_value},
+};
 
 #[allow(clippy::module_name_repetitions)]
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -83,6 +86,33 @@ impl VersionConfig {
         Err(crate::version::Error::UnknownVersion.into())
     }
 
+    /// Bump a component of the version, in `project.toml` if it is defined there, otherwise in the version macro file
+    ///
+    /// Returns the current and the new version
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the version can not be read or written
+    pub fn bump(&self, project: &Path, component: Component) -> Result<(Version, Version), Error> {
+        if let Some((major, minor, patch, build)) = self.defined {
+            let current = Version::new(major, minor, patch, build);
+            let next = current.bump(component);
+            let path = project.join(".hemtt").join("project.toml");
+            let content = std::fs::read_to_string(&path)?;
+            std::fs::write(&path, update_project_toml(&content, &next))?;
+            return Ok((current, next));
+        }
+        let path = project.join(&self.path);
+        if !path.exists() {
+            return Err(crate::version::Error::UnknownVersion.into());
+        }
+        let content = std::fs::read_to_string(&path)?;
+        let current = Version::try_from_script_version(&content)?;
+        let next = current.bump(component);
+        std::fs::write(&path, next.update_script_version(&content)?)?;
+        Ok((current, next))
+    }
+
     pub fn path(&self) -> &str {
         &self.path
     }
@@ -100,6 +130,50 @@ impl VersionConfig {
     }
 }
 
+/// Write the version to the `[version]` table of a `project.toml`, keeping the rest of the file
+///
+/// A `build` key is added after `patch` if the version has a build number
+fn update_project_toml(content: &str, version: &Version) -> String {
+    let mut lines = content.lines().map(ToString::to_string).collect::<Vec<_>>();
+    let mut table = String::new();
+    let mut patch = None;
+    let mut build = false;
+    for (index, line) in lines.iter_mut().enumerate() {
+        let trimmed = line.trim();
+        if trimmed.starts_with('[') {
+            table = trimmed.trim_matches(['[', ']']).trim().to_string();
+            continue;
+        }
+        if table != "version" {
+            continue;
+        }
+        let Some((key, _)) = trimmed.split_once('=') else {
+            continue;
+        };
+        let value = match key.trim() {
+            "major" => version.major(),
+            "minor" => version.minor(),
+            "patch" => {
+                patch = Some(index);
+                version.patch()
+            }
+            "build" => {
+                build = true;
+                let Some(value) = version.build() else {
+                    continue;
+                };
+                value
+            }
+            _ => continue,
+        };
+        *line = replace_value(line, "#", value);
+    }
+    if let (Some(value), Some(patch), false) = (version.build(), patch, build) {
+        lines.insert(patch + 1, format!("build = {value}"));
+    }
+    join_lines(&lines, content)
+}
+
 #[allow(clippy::module_name_repetitions)]
 #[derive(PartialEq, Eq, Debug, Default, Clone, Serialize, Deserialize)]
 pub struct VersionSectionFile {
@@ -236,4 +310,34 @@ patch = 3
         let config = VersionConfig::try_from(file);
         assert!(config.is_err());
     }
+
+    #[test]
+    fn update_toml() {
+        let toml = r#"name = "Test"
+major = 9
+
+[version]
+major = 1 # major
+minor = 2
+patch = 3
+git_hash = 0
+
+[files]
+patch = 7
+"#;
+        assert_eq!(
+            update_project_toml(toml, &Version::new(1, 3, 0, None)),
+            toml.replace("minor = 2", "minor = 3")
+                .replace("patch = 3", "patch = 0")
+        );
+        assert_eq!(
+            update_project_toml(toml, &Version::new(1, 2, 3, Some(1))),
+            toml.replace("patch = 3\n", "patch = 3\nbuild = 1\n")
+        );
+        let toml = "[version]\r\nmajor=1\r\nminor=2\r\npatch=3\r\nbuild=4";
+        assert_eq!(
+            update_project_toml(toml, &Version::new(2, 0, 0, Some(0))),
+            "[version]\r\nmajor=2\r\nminor=0\r\npatch=0\r\nbuild=0"
+        );
+    }
 }
@@ -0,0 +1,193 @@
+use serde::{Deserialize, Serialize};
+
+#[allow(clippy::module_name_repetitions)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// Configuration for uploading to the Steam Workshop
+pub struct WorkshopConfig {
+    item_id: Option<u64>,
+
+    title: Option<String>,
+
+    visibility: Option<Visibility>,
+
+    preview: Option<String>,
+
+    description: Option<String>,
+
+    changelog: Option<String>,
+
+    username: Option<String>,
+
+    optionals: bool,
+}
+
+impl WorkshopConfig {
+    #[must_use]
+    /// The id of the workshop item, a new item is created if not set
+    pub const fn item_id(&self) -> Option<u64> {
+        self.item_id
+    }
+
+    #[must_use]
+    /// The title of the workshop item, a template
+    pub fn title(&self) -> Option<&str> {
+        self.title.as_deref()
+    }
+
+    #[must_use]
+    /// The visibility of the workshop item
+    pub const fn visibility(&self) -> Option<Visibility> {
+        self.visibility
+    }
+
+    #[must_use]
+    /// Path to the preview image, relative to the project
+    pub fn preview(&self) -> Option<&str> {
+        self.preview.as_deref()
+    }
+
+    #[must_use]
+    /// Path to the template of the description, relative to the project
+    pub fn description(&self) -> Option<&str> {
+        self.description.as_deref()
+    }
+
+    #[must_use]
+    /// Path to the template of the change notes, relative to the project
+    pub fn changelog(&self) -> Option<&str> {
+        self.changelog.as_deref()
+    }
+
+    #[must_use]
+    /// The Steam account to upload with
+    pub fn username(&self) -> Option<&str> {
+        self.username.as_deref()
+    }
+
+    #[must_use]
+    /// Include the optionals folder
+    /// Defaults to true
+    pub const fn optionals(&self) -> bool {
+        self.optionals
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+/// Who can see the workshop item
+pub enum Visibility {
+    Public,
+    Friends,
+    Private,
+    Unlisted,
+}
+
+impl Visibility {
+    #[must_use]
+    /// The value used by `steamcmd`
+    pub const fn steam_value(self) -> u8 {
+        match self {
+            Self::Public => 0,
+            Self::Friends => 1,
+            Self::Private => 2,
+            Self::Unlisted => 3,
+        }
+    }
+}
+
+#[allow(clippy::module_name_repetitions)]
+#[derive(PartialEq, Eq, Debug, Default, Clone, Serialize, Deserialize)]
+pub struct WorkshopSectionFile {
+    #[serde(default)]
+    item_id: Option<u64>,
+
+    #[serde(default)]
+    title: Option<String>,
+
+    #[serde(default)]
+    visibility: Option<Visibility>,
+
+    #[serde(default)]
+    preview: Option<String>,
+
+    #[serde(default)]
+    description: Option<String>,
+
+    #[serde(default)]
+    changelog: Option<String>,
+
+    #[serde(default)]
+    username: Option<String>,
+
+    #[serde(default)]
+    optionals: Option<bool>,
+}
+
+impl From<WorkshopSectionFile> for WorkshopConfig {
+    fn from(file: WorkshopSectionFile) -> Self {
+        Self {
+            item_id: file.item_id,
+            title: file.title,
+            visibility: file.visibility,
+            preview: file.preview,
+            description: file.description,
+            changelog: file.changelog,
+            username: file.username,
+            optionals: file.optionals.unwrap_or(true),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fully_defined() {
+        let toml = r#"
+item_id = 1234567890
+title = "{prefix} {version}"
+visibility = "unlisted"
+preview = ".hemtt/workshop/preview.png"
+description = ".hemtt/workshop/description.txt"
+changelog = ".hemtt/workshop/changelog.txt"
+username = "builder"
+optionals = false
+"#;
+        let file: WorkshopSectionFile = toml::from_str(toml).expect("failed to deserialize");
+        let config = WorkshopConfig::from(file);
+        assert_eq!(config.item_id(), Some(1_234_567_890));
+        assert_eq!(config.title(), Some("{prefix} {version}"));
+        assert_eq!(config.visibility(), Some(Visibility::Unlisted));
+        assert_eq!(config.visibility().map(Visibility::steam_value), Some(3));
+        assert_eq!(config.preview(), Some(".hemtt/workshop/preview.png"));
+        assert_eq!(
+            config.description(),
+            Some(".hemtt/workshop/description.txt")
+        );
+        assert_eq!(config.changelog(), Some(".hemtt/workshop/changelog.txt"));
+        assert_eq!(config.username(), Some("builder"));
+        assert!(!config.optionals());
+    }
+
+    #[test]
+    fn default() {
+        let toml = "";
+        let file: WorkshopSectionFile = toml::from_str(toml).expect("failed to deserialize");
+        let config = WorkshopConfig::from(file);
+        assert!(config.item_id().is_none());
+        assert!(config.title().is_none());
+        assert!(config.visibility().is_none());
+        assert!(config.preview().is_none());
+        assert!(config.description().is_none());
+        assert!(config.changelog().is_none());
+        assert!(config.username().is_none());
+        assert!(config.optionals());
+    }
+
+    #[test]
+    fn invalid_visibility() {
+        let toml = r#"visibility = "hidden""#;
+        assert!(toml::from_str::<WorkshopSectionFile>(toml).is_err());
+    }
+}
@@ -1,6 +1,7 @@
 //! HEMTT - Common Library
 
 pub mod arma;
+pub mod changelog;
 pub mod config;
 pub mod error;
 pub mod io;
@@ -11,3 +11,26 @@ pub fn find_app(app_id: u32) -> Option<PathBuf> {
     let dir = library.resolve_app_dir(&app);
     Some(dir)
 }
+
+#[must_use]
+/// Find `steamcmd`, on the `PATH`, next to the Steam client, or in a `steamcmd` folder in the home directory
+pub fn find_steamcmd() -> Option<PathBuf> {
+    let names: &[&str] = if cfg!(windows) {
+        &["steamcmd.exe"]
+    } else {
+        &["steamcmd", "steamcmd.sh"]
+    };
+    let mut folders = std::env::var_os("PATH")
+        .map(|path| std::env::split_paths(&path).collect::<Vec<_>>())
+        .unwrap_or_default();
+    if let Ok(steam) = SteamDir::locate() {
+        folders.push(steam.path().join("steamcmd"));
+    }
+    if let Some(home) = std::env::var_os(if cfg!(windows) { "USERPROFILE" } else { "HOME" }) {
+        folders.push(PathBuf::from(home).join("steamcmd"));
+    }
+    folders
+        .iter()
+        .flat_map(|folder| names.iter().map(move |name| folder.join(name)))
+        .find(|path| path.is_file())
+}
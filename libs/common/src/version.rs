@@ -57,6 +57,54 @@ impl Version {
         })
     }
 
+    /// The next version, the lower components are reset to 0
+    ///
+    /// The build number is only reset if it is set
+    #[must_use]
+    pub fn bump(&self, component: Component) -> Self {
+        let build = self.build.map(|_| 0);
+        match component {
+            Component::Major => Self::new(self.major + 1, 0, 0, build),
+            Component::Minor => Self::new(self.major, self.minor + 1, 0, build),
+            Component::Patch => Self::new(self.major, self.minor, self.patch + 1, build),
+            Component::Build => Self::new(
+                self.major,
+                self.minor,
+                self.patch,
+                Some(self.build.unwrap_or_default() + 1),
+            ),
+        }
+    }
+
+    /// Write the version to the macros of a `script_version.hpp` file, keeping the rest of the file
+    ///
+    /// A `BUILD` macro is added after the patch macro if the version has a build number
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file does not contain the major, minor, and patch macros
+    pub fn update_script_version(&self, content: &str) -> Result<String, Error> {
+        let mut lines = content.lines().map(ToString::to_string).collect::<Vec<_>>();
+        let mut replace = |component: &str, value: u32| {
+            let line = lines
+                .iter_mut()
+                .find(|line| line.trim().starts_with(&format!("#define {component}")))?;
+            *line = replace_value(line, "//", value);
+            Some(())
+        };
+        replace("MAJOR", self.major).ok_or(Error::ExpectedMajor)?;
+        replace("MINOR", self.minor).ok_or(Error::ExpectedMinor)?;
+        replace("PATCH", self.patch).ok_or(Error::ExpectedPatch)?;
+        if let Some((build, None)) = self.build.map(|build| (build, replace("BUILD", build))) {
+            let patch = lines
+                .iter()
+                .position(|line| line.trim().starts_with("#define PATCH"))
+                .ok_or(Error::ExpectedPatch)?;
+            lines.insert(patch + 1, format!("#define BUILD {build}"));
+        }
+        Ok(join_lines(&lines, content))
+    }
+
     /// Set the build number
     pub fn set_build(&mut self, build: impl Into<String>) {
         self.hash = Some(build.into());
@@ -119,6 +167,43 @@ impl Version {
     }
 }
 
+/// Replace the value at the end of a line, before any comment
+pub(crate) fn replace_value(line: &str, comment: &str, value: u32) -> String {
+    let code = line.split_once(comment).map_or(line, |(code, _)| code);
+    let trimmed = code.trim_end();
+    let start = trimmed
+        .rfind(|c: char| c.is_whitespace() || c == '=')
+        .map_or(0, |index| index + 1);
+    format!("{}{value}{}", &trimmed[..start], &line[trimmed.len()..])
+}
+
+/// Join lines with the line endings of the original content
+pub(crate) fn join_lines(lines: &[String], content: &str) -> String {
+    let newline = if content.contains("\r\n") {
+        "\r\n"
+    } else {
+        "\n"
+    };
+    let mut joined = lines.join(newline);
+    if content.ends_with('\n') {
+        joined.push_str(newline);
+    }
+    joined
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+/// A component of a version
+pub enum Component {
+    /// The major version
+    Major,
+    /// The minor version
+    Minor,
+    /// The patch version
+    Patch,
+    /// The build number
+    Build,
+}
+
 impl TryFrom<&str> for Version {
     type Error = Error;
 
@@ -341,6 +426,46 @@ mod tests {
         assert_eq!(version.hash, Some("1a2b3c4d".to_string()));
     }
 
+    #[test]
+    fn version_bump() {
+        let version = Version::try_from("1.2.3").unwrap();
+        assert_eq!(version.bump(Component::Major).to_string(), "2.0.0");
+        assert_eq!(version.bump(Component::Minor).to_string(), "1.3.0");
+        assert_eq!(version.bump(Component::Patch).to_string(), "1.2.4");
+        assert_eq!(version.bump(Component::Build).to_string(), "1.2.3.1");
+        let version = Version::try_from("1.2.3.45-1a2b3c4d").unwrap();
+        assert_eq!(version.bump(Component::Minor).to_string(), "1.3.0.0");
+        assert_eq!(version.bump(Component::Build).to_string(), "1.2.3.46");
+    }
+
+    #[test]
+    fn version_update_script_version() {
+        let content = "#define MAJOR 3\n#define MINOR 15 // minor\n#define PATCHLVL 2\n    #define BUILD 69\n";
+        let version = Version::try_from_script_version(content)
+            .unwrap()
+            .bump(Component::Minor);
+        assert_eq!(
+            version.update_script_version(content).unwrap(),
+            "#define MAJOR 3\n#define MINOR 16 // minor\n#define PATCHLVL 0\n    #define BUILD 0\n"
+        );
+    }
+
+    #[test]
+    fn version_update_script_version_add_build() {
+        let content = "#define MAJOR 1\r\n#define MINOR 0\r\n#define PATCH 0";
+        let version = Version::try_from_script_version(content)
+            .unwrap()
+            .bump(Component::Build);
+        assert_eq!(
+            version.update_script_version(content).unwrap(),
+            "#define MAJOR 1\r\n#define MINOR 0\r\n#define PATCH 0\r\n#define BUILD 1"
+        );
+        assert_eq!(
+            Version::new(1, 0, 0, None).update_script_version("#define MAJOR 1"),
+            Err(Error::ExpectedMinor)
+        );
+    }
+
     #[test]
     fn version_getters() {
         let version = Version::try_from("1.2.42-1a2b3c4d").unwrap();
@@ -0,0 +1,195 @@
+use std::sync::Arc;
+
+use hemtt_common::config::{LintConfig, ProjectConfig};
+use hemtt_workspace::{
+    lint::{AnyLintRunner, Lint, LintRunner},
+    position::Position,
+    reporting::{Code, Codes, Diagnostic, Label, Processed, Severity},
+};
+
+use crate::{analyze::LintData, database::Database};
+
+crate::analyze::lint!(LintC15RequiredAddonsUnknown);
+
+impl Lint<LintData> for LintC15RequiredAddonsUnknown {
+    fn ident(&self) -> &'static str {
+        "required_addons_unknown"
+    }
+
+    fn sort(&self) -> u32 {
+        150
+    }
+
+    fn description(&self) -> &'static str {
+        "Reports on `requiredAddons` entries that are not a patch in the project, the baseline, or a known external patch"
+    }
+
+    fn documentation(&self) -> &'static str {
+        r#"### Configuration
+
+- **known**: Patches from outside the project that may be required. `*` matches any characters, and matching is case-insensitive.
+
+Patches in the [baseline](../utilities/config/baseline.md) are always known.
+
+**default values shown below**
+
+```toml
+[lints.config.required_addons_unknown]
+options.known = ["A3_*", "cba_*", "ace_*"]
+```
+
+### Example
+
+**Incorrect**
+```hpp
+class CfgPatches {
+    class abe_main {
+        requiredAddons[] = {"abe_commn"};
+    };
+};
+```
+
+**Correct**
+```hpp
+class CfgPatches {
+    class abe_main {
+        requiredAddons[] = {"abe_common"};
+    };
+};
+```
+
+### Explanation
+
+The game skips addons whose `requiredAddons` can not be found, usually with only a line in the RPT. A typo, or a renamed patch, stops the addon from loading entirely.
+"#
+    }
+
+    fn default_config(&self) -> LintConfig {
+        LintConfig::warning()
+    }
+
+    fn runners(&self) -> Vec<Box<dyn AnyLintRunner<LintData>>> {
+        vec![Box::new(Runner)]
+    }
+}
+
+struct Runner;
+impl LintRunner<LintData> for Runner {
+    type Target = Database;
+    fn run(
+        &self,
+        _project: Option<&ProjectConfig>,
+        config: &LintConfig,
+        _processed: Option<&Processed>,
+        _runtime: &hemtt_common::config::RuntimeArguments,
+        target: &Database,
+        _data: &LintData,
+    ) -> Codes {
+        let known = if let Some(toml::Value::Array(known)) = config.option("known") {
+            known
+                .iter()
+                .filter_map(|v| v.as_str())
+                .map(str::to_lowercase)
+                .collect::<Vec<_>>()
+        } else {
+            vec!["a3_*".to_string(), "cba_*".to_string(), "ace_*".to_string()]
+        };
+        let mut codes: Codes = Vec::new();
+        for addon in target.addons() {
+            for (patch, required) in addon.required_addons() {
+                for required in required {
+                    if target.patch_addon(required.value()).is_some()
+                        || target.baseline_patch_addon(required.value()).is_some()
+                    {
+                        continue;
+                    }
+                    let name = required.value().to_lowercase();
+                    if known.iter().any(|pattern| wildcard(pattern, &name)) {
+                        continue;
+                    }
+                    codes.push(Arc::new(CodeC15RequiredAddonsUnknown {
+                        required: required.value().to_string(),
+                        patch: patch.as_str().to_string(),
+                        position: addon.position_of_str(required).cloned(),
+                        severity: config.severity(),
+                    }));
+                }
+            }
+        }
+        codes
+    }
+}
+
+/// Match `value` against a pattern where `*` matches any characters
+fn wildcard(pattern: &str, value: &str) -> bool {
+    let mut parts = pattern.split('*');
+    let Some(first) = parts.next() else {
+        return true;
+    };
+    let Some(mut rest) = value.strip_prefix(first) else {
+        return false;
+    };
+    let parts = parts.collect::<Vec<_>>();
+    let Some((last, middle)) = parts.split_last() else {
+        return rest.is_empty();
+    };
+    for part in middle {
+        let Some(index) = rest.find(part) else {
+            return false;
+        };
+        rest = &rest[index + part.len()..];
+    }
+    rest.ends_with(last)
+}
+
+#[allow(clippy::module_name_repetitions)]
+pub struct CodeC15RequiredAddonsUnknown {
+    required: String,
+    patch: String,
+    position: Option<Position>,
+    severity: Severity,
+}
+
+impl Code for CodeC15RequiredAddonsUnknown {
+    fn ident(&self) -> &'static str {
+        "L-C15"
+    }
+
+    fn link(&self) -> Option<&str> {
+        Some("/analysis/config.html#required_addons_unknown")
+    }
+
+    fn severity(&self) -> Severity {
+        self.severity
+    }
+
+    fn message(&self) -> String {
+        format!(
+            "`{}` requires `{}`, which is not a known patch",
+            self.patch, self.required
+        )
+    }
+
+    fn label_message(&self) -> String {
+        "unknown patch".to_string()
+    }
+
+    fn help(&self) -> Option<String> {
+        Some(
+            "add external patches to `options.known` of `lints.config.required_addons_unknown`"
+                .to_string(),
+        )
+    }
+
+    fn diagnostic(&self) -> Option<Diagnostic> {
+        let diag = Diagnostic::from_code(self);
+        Some(match &self.position {
+            Some(position) => diag.with_label(
+                Label::primary(position.path().clone(), position.span())
+                    .with_message(self.label_message()),
+            ),
+            None => diag,
+        })
+    }
+}
+
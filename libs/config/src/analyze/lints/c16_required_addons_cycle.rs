@@ -0,0 +1,172 @@
+use std::sync::Arc;
+
+use hemtt_common::config::{LintConfig, ProjectConfig};
+use hemtt_workspace::{
+    lint::{AnyLintRunner, Lint, LintRunner},
+    position::Position,
+    reporting::{Code, Codes, Diagnostic, Label, Processed, Severity},
+};
+
+use crate::{analyze::LintData, database::Database};
+
+crate::analyze::lint!(LintC16RequiredAddonsCycle);
+
+impl Lint<LintData> for LintC16RequiredAddonsCycle {
+    fn ident(&self) -> &'static str {
+        "required_addons_cycle"
+    }
+
+    fn sort(&self) -> u32 {
+        160
+    }
+
+    fn description(&self) -> &'static str {
+        "Reports on addons that require each other through `requiredAddons`"
+    }
+
+    fn documentation(&self) -> &'static str {
+        r#"### Example
+
+**Incorrect**
+```hpp
+// addons/main/config.cpp
+class CfgPatches {
+    class abe_main {
+        requiredAddons[] = {"abe_common"};
+    };
+};
+// addons/common/config.cpp
+class CfgPatches {
+    class abe_common {
+        requiredAddons[] = {"abe_main"};
+    };
+};
+```
+
+**Correct**
+```hpp
+// addons/main/config.cpp
+class CfgPatches {
+    class abe_main {
+        requiredAddons[] = {"abe_common"};
+    };
+};
+// addons/common/config.cpp
+class CfgPatches {
+    class abe_common {
+        requiredAddons[] = {};
+    };
+};
+```
+
+### Explanation
+
+When addons require each other there is no order the game can load them in, and which one loads first is left to chance. Classes that one addon expects the other to have defined may not exist yet.
+"#
+    }
+
+    fn default_config(&self) -> LintConfig {
+        LintConfig::error()
+    }
+
+    fn runners(&self) -> Vec<Box<dyn AnyLintRunner<LintData>>> {
+        vec![Box::new(Runner)]
+    }
+}
+
+struct Runner;
+impl LintRunner<LintData> for Runner {
+    type Target = Database;
+    fn run(
+        &self,
+        _project: Option<&ProjectConfig>,
+        config: &LintConfig,
+        _processed: Option<&Processed>,
+        _runtime: &hemtt_common::config::RuntimeArguments,
+        target: &Database,
+        _data: &LintData,
+    ) -> Codes {
+        let mut codes: Codes = Vec::new();
+        for cycle in target.cycles() {
+            let Some(first) = cycle.first() else {
+                continue;
+            };
+            // Point at the first requirement that leads back into the cycle
+            let position = target
+                .addons()
+                .iter()
+                .filter(|addon| addon.addon() == *first)
+                .flat_map(|addon| {
+                    addon
+                        .required_addons()
+                        .into_iter()
+                        .flat_map(|(_, required)| required)
+                        .filter(|required| {
+                            target
+                                .patch_addon(required.value())
+                                .is_some_and(|found| found != *first && cycle.contains(&found))
+                        })
+                        .filter_map(|required| addon.position_of_str(required).cloned())
+                        .collect::<Vec<_>>()
+                })
+                .next();
+            codes.push(Arc::new(CodeC16RequiredAddonsCycle {
+                addons: cycle.iter().map(ToString::to_string).collect(),
+                position,
+                severity: config.severity(),
+            }));
+        }
+        codes
+    }
+}
+
+#[allow(clippy::module_name_repetitions)]
+pub struct CodeC16RequiredAddonsCycle {
+    addons: Vec<String>,
+    position: Option<Position>,
+    severity: Severity,
+}
+
+impl Code for CodeC16RequiredAddonsCycle {
+    fn ident(&self) -> &'static str {
+        "L-C16"
+    }
+
+    fn link(&self) -> Option<&str> {
+        Some("/analysis/config.html#required_addons_cycle")
+    }
+
+    fn severity(&self) -> Severity {
+        self.severity
+    }
+
+    fn message(&self) -> String {
+        format!(
+            "addons require each other: {}",
+            self.addons
+                .iter()
+                .map(|addon| format!("`{addon}`"))
+                .collect::<Vec<_>>()
+                .join(", ")
+        )
+    }
+
+    fn label_message(&self) -> String {
+        "leads back to this addon".to_string()
+    }
+
+    fn help(&self) -> Option<String> {
+        Some("move what the addons share into an addon that both can require".to_string())
+    }
+
+    fn diagnostic(&self) -> Option<Diagnostic> {
+        let diag = Diagnostic::from_code(self);
+        Some(match &self.position {
+            Some(position) => diag.with_label(
+                Label::primary(position.path().clone(), position.span())
+                    .with_message(self.label_message()),
+            ),
+            None => diag,
+        })
+    }
+}
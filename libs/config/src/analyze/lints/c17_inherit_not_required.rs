@@ -0,0 +1,178 @@
+use std::sync::Arc;
+
+use hemtt_common::config::{LintConfig, ProjectConfig};
+use hemtt_workspace::{
+    lint::{AnyLintRunner, Lint, LintRunner},
+    position::Position,
+    reporting::{Code, Codes, Diagnostic, Label, Processed, Severity},
+};
+
+use crate::{
+    analyze::LintData,
+    database::{ClassRef, Database},
+};
+
+crate::analyze::lint!(LintC17InheritNotRequired);
+
+impl Lint<LintData> for LintC17InheritNotRequired {
+    fn ident(&self) -> &'static str {
+        "inherit_not_required"
+    }
+
+    fn sort(&self) -> u32 {
+        170
+    }
+
+    fn description(&self) -> &'static str {
+        "Reports on classes that inherit from a class defined in an addon that is not required"
+    }
+
+    fn documentation(&self) -> &'static str {
+        r#"### Example
+
+**Incorrect**
+```hpp
+// addons/vehicles/config.cpp
+class CfgPatches {
+    class abe_vehicles {
+        requiredAddons[] = {"A3_Soft_F"};
+    };
+};
+class CfgVehicles {
+    class abe_truck_base;
+    class abe_truck: abe_truck_base {};
+};
+// addons/common/config.cpp
+class CfgVehicles {
+    class Car;
+    class abe_truck_base: Car {};
+};
+```
+
+**Correct**
+```hpp
+// addons/vehicles/config.cpp
+class CfgPatches {
+    class abe_vehicles {
+        requiredAddons[] = {"A3_Soft_F", "abe_common"};
+    };
+};
+```
+
+### Explanation
+
+The game only guarantees that an addon loads after the addons it requires. Inheriting from a class in any other addon depends on the load order happening to be correct, and breaks when it is not.
+"#
+    }
+
+    fn default_config(&self) -> LintConfig {
+        LintConfig::warning()
+    }
+
+    fn runners(&self) -> Vec<Box<dyn AnyLintRunner<LintData>>> {
+        vec![Box::new(Runner)]
+    }
+}
+
+struct Runner;
+impl LintRunner<LintData> for Runner {
+    type Target = Database;
+    fn run(
+        &self,
+        _project: Option<&ProjectConfig>,
+        config: &LintConfig,
+        _processed: Option<&Processed>,
+        _runtime: &hemtt_common::config::RuntimeArguments,
+        target: &Database,
+        _data: &LintData,
+    ) -> Codes {
+        let mut codes: Codes = Vec::new();
+        check(target, &target.root(), config, &mut codes);
+        codes
+    }
+}
+
+fn check(database: &Database, class: &ClassRef, config: &LintConfig, codes: &mut Codes) {
+    for child in class.classes() {
+        let parent = child.parent().filter(|parent| !parent.is_external());
+        // Classes from the baseline are not loaded by the project's addons
+        let owner = parent
+            .as_ref()
+            .and_then(|parent| parent.definitions().first())
+            .filter(|owner| !owner.is_baseline());
+        if let (Some(parent), Some(owner)) = (&parent, owner) {
+            for definition in child.definitions() {
+                if definition.is_baseline()
+                    || definition.addon() == owner.addon()
+                    || database
+                        .dependencies(definition.addon())
+                        .contains(owner.addon())
+                {
+                    continue;
+                }
+                codes.push(Arc::new(CodeC17InheritNotRequired {
+                    class: child.path(),
+                    parent: parent.path(),
+                    addon: definition.addon().to_string(),
+                    owner: owner.addon().to_string(),
+                    position: definition.position().cloned(),
+                    severity: config.severity(),
+                }));
+            }
+        }
+        check(database, &child, config, codes);
+    }
+}
+
+#[allow(clippy::module_name_repetitions)]
+pub struct CodeC17InheritNotRequired {
+    class: String,
+    parent: String,
+    addon: String,
+    owner: String,
+    position: Option<Position>,
+    severity: Severity,
+}
+
+impl Code for CodeC17InheritNotRequired {
+    fn ident(&self) -> &'static str {
+        "L-C17"
+    }
+
+    fn link(&self) -> Option<&str> {
+        Some("/analysis/config.html#inherit_not_required")
+    }
+
+    fn severity(&self) -> Severity {
+        self.severity
+    }
+
+    fn message(&self) -> String {
+        format!(
+            "`{}` inherits from `{}`, but `{}` does not require `{}`",
+            self.class, self.parent, self.addon, self.owner
+        )
+    }
+
+    fn label_message(&self) -> String {
+        format!("parent defined in `{}`", self.owner)
+    }
+
+    fn help(&self) -> Option<String> {
+        Some(format!(
+            "add a patch from `{}` to the `requiredAddons` of `{}`",
+            self.owner, self.addon
+        ))
+    }
+
+    fn diagnostic(&self) -> Option<Diagnostic> {
+        let diag = Diagnostic::from_code(self);
+        Some(match &self.position {
+            Some(position) => diag.with_label(
+                Label::primary(position.path().clone(), position.span())
+                    .with_message(self.label_message()),
+            ),
+            None => diag,
+        })
+    }
+}
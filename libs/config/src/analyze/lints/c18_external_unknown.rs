@@ -0,0 +1,170 @@
+use std::sync::Arc;
+
+use hemtt_common::config::{LintConfig, ProjectConfig};
+use hemtt_workspace::{
+    lint::{AnyLintRunner, Lint, LintRunner},
+    position::Position,
+    reporting::{Code, Codes, Diagnostic, Label, Processed, Severity},
+};
+
+use crate::{
+    analyze::LintData,
+    database::{ClassRef, Database},
+};
+
+crate::analyze::lint!(LintC18ExternalUnknown);
+
+impl Lint<LintData> for LintC18ExternalUnknown {
+    fn ident(&self) -> &'static str {
+        "external_unknown"
+    }
+
+    fn sort(&self) -> u32 {
+        180
+    }
+
+    fn description(&self) -> &'static str {
+        "Reports on external classes that are not defined by the baseline"
+    }
+
+    fn documentation(&self) -> &'static str {
+        r"### Example
+
+**Incorrect**
+```hpp
+class CfgVehicles {
+    class Truck_01_bse_F;
+    class abe_truck: Truck_01_bse_F {};
+};
+```
+
+**Correct**
+```hpp
+class CfgVehicles {
+    class Truck_01_base_F;
+    class abe_truck: Truck_01_base_F {};
+};
+```
+
+### Explanation
+
+Only checked when a [baseline](../utilities/config/baseline.md) has been imported, and only for addons whose `requiredAddons` are all in the project or the baseline.
+
+A declared class, `class Truck_01_base_F;`, that no loaded addon defines is usually a typo, or a class from a mod that is not required. The game creates an empty class instead, and everything inheriting from it loses its values.
+"
+    }
+
+    fn default_config(&self) -> LintConfig {
+        LintConfig::warning()
+    }
+
+    fn runners(&self) -> Vec<Box<dyn AnyLintRunner<LintData>>> {
+        vec![Box::new(Runner)]
+    }
+}
+
+struct Runner;
+impl LintRunner<LintData> for Runner {
+    type Target = Database;
+    fn run(
+        &self,
+        _project: Option<&ProjectConfig>,
+        config: &LintConfig,
+        _processed: Option<&Processed>,
+        _runtime: &hemtt_common::config::RuntimeArguments,
+        target: &Database,
+        _data: &LintData,
+    ) -> Codes {
+        if !target.has_baseline() {
+            return Vec::new();
+        }
+        let mut codes: Codes = Vec::new();
+        check(target, &target.root(), config, &mut codes);
+        codes
+    }
+}
+
+fn check(database: &Database, scope: &ClassRef, config: &LintConfig, codes: &mut Codes) {
+    // `class Turrets;` inside a class refers to the class inherited by the scope
+    let inherited = match (scope.parent_name(), scope.parent()) {
+        (None, _) => None,
+        (Some(_), Some(parent)) => Some(parent),
+        // The scope's parent is missing, which is reported on its own
+        (Some(_), None) => return,
+    };
+    for child in scope.classes() {
+        if child.is_external() {
+            let found = inherited
+                .as_ref()
+                .and_then(|parent| parent.class(child.name()))
+                .is_some_and(|class| !class.is_external());
+            if found {
+                continue;
+            }
+            for declaration in child.declarations() {
+                if declaration.is_baseline() || !database.covers(declaration.addon()) {
+                    continue;
+                }
+                codes.push(Arc::new(CodeC18ExternalUnknown {
+                    class: child.path(),
+                    addon: declaration.addon().to_string(),
+                    position: declaration.position().cloned(),
+                    severity: config.severity(),
+                }));
+            }
+        } else {
+            check(database, &child, config, codes);
+        }
+    }
+}
+
+#[allow(clippy::module_name_repetitions)]
+pub struct CodeC18ExternalUnknown {
+    class: String,
+    addon: String,
+    position: Option<Position>,
+    severity: Severity,
+}
+
+impl Code for CodeC18ExternalUnknown {
+    fn ident(&self) -> &'static str {
+        "L-C18"
+    }
+
+    fn link(&self) -> Option<&str> {
+        Some("/analysis/config.html#external_unknown")
+    }
+
+    fn severity(&self) -> Severity {
+        self.severity
+    }
+
+    fn message(&self) -> String {
+        format!(
+            "`{}` is declared in `{}`, but is not defined by the baseline",
+            self.class, self.addon
+        )
+    }
+
+    fn label_message(&self) -> String {
+        "unknown class".to_string()
+    }
+
+    fn help(&self) -> Option<String> {
+        Some(
+            "check the spelling, or import the mod that defines the class into the baseline"
+                .to_string(),
+        )
+    }
+
+    fn diagnostic(&self) -> Option<Diagnostic> {
+        let diag = Diagnostic::from_code(self);
+        Some(match &self.position {
+            Some(position) => diag.with_label(
+                Label::primary(position.path().clone(), position.span())
+                    .with_message(self.label_message()),
+            ),
+            None => diag,
+        })
+    }
+}
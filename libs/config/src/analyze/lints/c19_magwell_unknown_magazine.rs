@@ -0,0 +1,208 @@
+use std::sync::Arc;
+
+use hemtt_common::config::{LintConfig, ProjectConfig};
+use hemtt_workspace::{
+    lint::{AnyLintRunner, Lint, LintRunner},
+    position::Position,
+    reporting::{Code, Codes, Diagnostic, Label, Processed, Severity},
+};
+
+use crate::{
+    Class, Item, Property, Value,
+    analyze::LintData,
+    database::{Database, Found},
+};
+
+crate::analyze::lint!(LintC19MagwellUnknownMagazine);
+
+impl Lint<LintData> for LintC19MagwellUnknownMagazine {
+    fn ident(&self) -> &'static str {
+        "magwell_unknown_magazine"
+    }
+
+    fn sort(&self) -> u32 {
+        190
+    }
+
+    fn description(&self) -> &'static str {
+        "Reports on magazines in CfgMagazineWells that are not defined by the project or the baseline"
+    }
+
+    fn documentation(&self) -> &'static str {
+        r#"### Example
+
+**Incorrect**
+```hpp
+class CfgMagazineWells {
+    class CBA_762x51_STANAG {
+        abe_main[] = {
+            "20Rnd_762x51_Mag",
+            "20Rnd_762x15_Mag"
+        };
+    };
+};
+```
+
+**Correct**
+```hpp
+class CfgMagazineWells {
+    class CBA_762x51_STANAG {
+        abe_main[] = {
+            "20Rnd_762x51_Mag"
+        };
+    };
+};
+```
+
+### Explanation
+
+Only checked when a [baseline](../utilities/config/baseline.md) has been imported, and only for addons whose `requiredAddons` are all in the project or the baseline. Magazines using the project's prefix are checked by [magwell_missing_magazine](#magwell_missing_magazine) instead.
+
+Magazine wells often list magazines from the game or other mods. A magazine that does not exist is silently ignored by the game, so a typo only shows up as a magazine that can not be loaded.
+"#
+    }
+
+    fn default_config(&self) -> LintConfig {
+        LintConfig::warning()
+    }
+
+    fn runners(&self) -> Vec<Box<dyn AnyLintRunner<LintData>>> {
+        vec![Box::new(Runner)]
+    }
+}
+
+struct Runner;
+impl LintRunner<LintData> for Runner {
+    type Target = Database;
+    fn run(
+        &self,
+        project: Option<&ProjectConfig>,
+        config: &LintConfig,
+        _processed: Option<&Processed>,
+        _runtime: &hemtt_common::config::RuntimeArguments,
+        target: &Database,
+        _data: &LintData,
+    ) -> Codes {
+        if !target.has_baseline() {
+            return Vec::new();
+        }
+        let prefix = project.map(|project| project.prefix().to_lowercase());
+        let mut codes: Codes = Vec::new();
+        for addon in target.addons() {
+            if !target.covers(addon.addon()) {
+                continue;
+            }
+            let magwells = addon
+                .config()
+                .0
+                .iter()
+                .filter(|property| {
+                    property
+                        .name()
+                        .as_str()
+                        .eq_ignore_ascii_case("cfgmagazinewells")
+                })
+                .filter_map(|property| match property {
+                    Property::Class(Class::Local { properties, .. }) => Some(properties),
+                    _ => None,
+                })
+                .flatten();
+            for magwell in magwells {
+                let Property::Class(Class::Local {
+                    name: magwell,
+                    properties,
+                    ..
+                }) = magwell
+                else {
+                    continue;
+                };
+                for property in properties {
+                    let Property::Entry {
+                        value: Value::Array(magazines),
+                        ..
+                    } = property
+                    else {
+                        continue;
+                    };
+                    for item in &magazines.items {
+                        let Item::Str(magazine) = item else {
+                            continue;
+                        };
+                        let name = magazine.value();
+                        if prefix
+                            .as_ref()
+                            .is_some_and(|prefix| name.to_lowercase().starts_with(prefix))
+                        {
+                            continue;
+                        }
+                        let query = format!("CfgMagazines >> \"{name}\"");
+                        if matches!(
+                            target.query(&query),
+                            Some(Found::Class(class)) if !class.is_external()
+                        ) {
+                            continue;
+                        }
+                        codes.push(Arc::new(CodeC19MagwellUnknownMagazine {
+                            magazine: name.to_string(),
+                            magwell: magwell.as_str().to_string(),
+                            position: addon.position_of_str(magazine).cloned(),
+                            severity: config.severity(),
+                        }));
+                    }
+                }
+            }
+        }
+        codes
+    }
+}
+
+#[allow(clippy::module_name_repetitions)]
+pub struct CodeC19MagwellUnknownMagazine {
+    magazine: String,
+    magwell: String,
+    position: Option<Position>,
+    severity: Severity,
+}
+
+impl Code for CodeC19MagwellUnknownMagazine {
+    fn ident(&self) -> &'static str {
+        "L-C19"
+    }
+
+    fn link(&self) -> Option<&str> {
+        Some("/analysis/config.html#magwell_unknown_magazine")
+    }
+
+    fn severity(&self) -> Severity {
+        self.severity
+    }
+
+    fn message(&self) -> String {
+        format!(
+            "`{}` in magazine well `{}` is not defined by the project or the baseline",
+            self.magazine, self.magwell
+        )
+    }
+
+    fn label_message(&self) -> String {
+        "unknown magazine".to_string()
+    }
+
+    fn help(&self) -> Option<String> {
+        Some(
+            "check the spelling, or import the mod that defines the magazine into the baseline"
+                .to_string(),
+        )
+    }
+
+    fn diagnostic(&self) -> Option<Diagnostic> {
+        let diag = Diagnostic::from_code(self);
+        Some(match &self.position {
+            Some(position) => diag.with_label(
+                Label::primary(position.path().clone(), position.span())
+                    .with_message(self.label_message()),
+            ),
+            None => diag,
+        })
+    }
+}
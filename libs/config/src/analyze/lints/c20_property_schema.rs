@@ -0,0 +1,261 @@
+use std::sync::Arc;
+
+use hemtt_common::config::{LintConfig, ProjectConfig};
+use hemtt_workspace::{
+    lint::{AnyLintRunner, Lint, LintRunner},
+    position::Position,
+    reporting::{Code, Codes, Diagnostic, Label, Processed, Severity},
+};
+
+use crate::{
+    Class, Property,
+    analyze::LintData,
+    database::{AddonConfig, ClassRef, Database},
+    schema::{Problem, Schema},
+};
+
+crate::analyze::lint!(LintC20PropertySchema);
+
+impl Lint<LintData> for LintC20PropertySchema {
+    fn ident(&self) -> &'static str {
+        "property_schema"
+    }
+
+    fn sort(&self) -> u32 {
+        200
+    }
+
+    fn description(&self) -> &'static str {
+        "Reports on properties of well-known classes that do not match their schema"
+    }
+
+    fn documentation(&self) -> &'static str {
+        r#"### Example
+
+**Incorrect**
+```hpp
+class CfgMagazines {
+    class abe_mag {
+        count = 30.5;
+        scope = 3;
+        dispalyName = "Banana Magazine";
+    };
+};
+```
+
+**Correct**
+```hpp
+class CfgMagazines {
+    class abe_mag {
+        count = 30;
+        scope = 2;
+        displayName = "Banana Magazine";
+    };
+};
+```
+
+### Explanation
+
+HEMTT ships a schema of the expected properties of classes in `CfgAmmo`, `CfgFunctions`, `CfgMagazines`, `CfgPatches`, `CfgVehicles` and `CfgWeapons`. Values of the wrong type, numbers out of range, and property names that look like a typo of a known property are reported.
+
+A schema applies to a class when its path matches the class, or any class it inherits from. `*` matches any class.
+
+### Project schemas
+
+The schema can be extended with `.toml` or `.json` files in `.hemtt/schemas`. Properties replace the shipped schema for the same path.
+
+```toml
+# .hemtt/schemas/bananas.toml
+["CfgVehicles >> abe_banana_base"]
+closed = true # report every property not in the schema
+
+["CfgVehicles >> abe_banana_base".properties]
+ripeness = { type = "number", min = 0, max = 1 }
+peeled = "bool"
+variety = { type = "string", values = ["cavendish", "plantain"] }
+stickers = { type = "array", items = "string" }
+```
+
+Types are `string`, `number`, `integer`, `bool`, `array`, and `any`.
+"#
+    }
+
+    fn default_config(&self) -> LintConfig {
+        LintConfig::warning()
+    }
+
+    fn runners(&self) -> Vec<Box<dyn AnyLintRunner<LintData>>> {
+        vec![Box::new(Runner)]
+    }
+}
+
+struct Runner;
+impl LintRunner<LintData> for Runner {
+    type Target = Database;
+    fn run(
+        &self,
+        _project: Option<&ProjectConfig>,
+        config: &LintConfig,
+        _processed: Option<&Processed>,
+        _runtime: &hemtt_common::config::RuntimeArguments,
+        target: &Database,
+        data: &LintData,
+    ) -> Codes {
+        let mut codes: Codes = Vec::new();
+        for addon in target.addons() {
+            check(
+                &data.schema,
+                addon,
+                &target.root(),
+                &addon.config().0,
+                config,
+                &mut codes,
+            );
+        }
+        codes
+    }
+}
+
+fn check(
+    schema: &Schema,
+    addon: &AddonConfig,
+    class: &ClassRef,
+    properties: &[Property],
+    config: &LintConfig,
+    codes: &mut Codes,
+) {
+    let applied = schema.applied(class);
+    for property in properties {
+        match property {
+            Property::Entry { name, value, .. } if !applied.is_empty() => {
+                let problem = applied.property(name.as_str()).map_or_else(
+                    || {
+                        let similar = applied.similar(name.as_str());
+                        (applied.is_closed() || !similar.is_empty())
+                            .then_some(Issue::Unknown(similar))
+                    },
+                    |expected| expected.check(value).map(Issue::Problem),
+                );
+                if let Some(issue) = problem {
+                    codes.push(Arc::new(CodeC20PropertySchema {
+                        class: class.path(),
+                        property: name.as_str().to_string(),
+                        issue,
+                        position: addon.position(name).cloned(),
+                        severity: config.severity(),
+                    }));
+                }
+            }
+            Property::Class(Class::Local {
+                name, properties, ..
+            }) => {
+                if let Some(child) = class.class(name.as_str()) {
+                    check(schema, addon, &child, properties, config, codes);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+enum Issue {
+    Problem(Problem),
+    Unknown(Vec<String>),
+}
+
+fn describe(problem: &Problem) -> String {
+    match problem {
+        Problem::Type(kind) => format!("should be {kind}"),
+        Problem::Range { value, min, max } => match (min, max) {
+            (Some(min), Some(max)) => format!("is {value}, but should be from {min} to {max}"),
+            (Some(min), None) => format!("is {value}, but should be at least {min}"),
+            (None, Some(max)) => format!("is {value}, but should be at most {max}"),
+            (None, None) => format!("is {value}"),
+        },
+        Problem::Value(values) => format!("should be one of `{}`", values.join("`, `")),
+        Problem::Item(problem) => describe(problem),
+    }
+}
+
+fn label(problem: &Problem) -> String {
+    match problem {
+        Problem::Type(kind) => format!("expected {kind}"),
+        Problem::Range { .. } => "out of range".to_string(),
+        Problem::Value(_) => "unexpected value".to_string(),
+        Problem::Item(problem) => label(problem),
+    }
+}
+
+#[allow(clippy::module_name_repetitions)]
+pub struct CodeC20PropertySchema {
+    class: String,
+    property: String,
+    issue: Issue,
+    position: Option<Position>,
+    severity: Severity,
+}
+
+impl Code for CodeC20PropertySchema {
+    fn ident(&self) -> &'static str {
+        "L-C20"
+    }
+
+    fn link(&self) -> Option<&str> {
+        Some("/analysis/config.html#property_schema")
+    }
+
+    fn severity(&self) -> Severity {
+        self.severity
+    }
+
+    fn message(&self) -> String {
+        match &self.issue {
+            Issue::Problem(Problem::Item(problem)) => format!(
+                "an item of `{}` in `{}` {}",
+                self.property,
+                self.class,
+                describe(problem)
+            ),
+            Issue::Problem(problem) => format!(
+                "`{}` in `{}` {}",
+                self.property,
+                self.class,
+                describe(problem)
+            ),
+            Issue::Unknown(_) => format!(
+                "`{}` is not a known property of `{}`",
+                self.property, self.class
+            ),
+        }
+    }
+
+    fn label_message(&self) -> String {
+        match &self.issue {
+            Issue::Problem(problem) => label(problem),
+            Issue::Unknown(_) => "unknown property".to_string(),
+        }
+    }
+
+    fn help(&self) -> Option<String> {
+        match &self.issue {
+            Issue::Unknown(similar) if !similar.is_empty() => {
+                Some(format!("did you mean `{}`?", similar.join("`, `")))
+            }
+            Issue::Unknown(_) => {
+                Some("add the property to a schema in `.hemtt/schemas`".to_string())
+            }
+            Issue::Problem(_) => None,
+        }
+    }
+
+    fn diagnostic(&self) -> Option<Diagnostic> {
+        let diag = Diagnostic::from_code(self);
+        Some(match &self.position {
+            Some(position) => diag.with_label(
+                Label::primary(position.path().clone(), position.span())
+                    .with_message(self.label_message()),
+            ),
+            None => diag,
+        })
+    }
+}
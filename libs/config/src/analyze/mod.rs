@@ -24,6 +24,7 @@ pub struct LintData {
     pub(crate) localizations: Arc<Mutex<Vec<(String, Position)>>>,
     pub(crate) functions_defined: Arc<Mutex<DefinedFunctions>>,
     pub(crate) magazine_well_info: Arc<Mutex<MagazineWellInfo>>,
+    pub(crate) schema: Arc<Schema>,
 }
 
 lint_manager!(config, vec![]);
@@ -31,7 +32,10 @@ lint_manager!(config, vec![]);
 pub use cfgpatch::CfgPatch;
 pub use chumsky::ChumskyCode;
 
-use crate::{Array, Class, Config, Expression, Item, Number, Property, Str, Value};
+use crate::{
+    Array, Class, Config, Expression, Item, Number, Property, Str, Value, database::Database,
+    schema::Schema,
+};
 
 /// Trait for rapifying objects
 pub trait Analyze: Sized + 'static {
@@ -93,6 +97,7 @@ impl Analyze for Class {
                     localizations: data.localizations.clone(),
                     functions_defined: data.functions_defined.clone(),
                     magazine_well_info: data.magazine_well_info.clone(),
+                    schema: data.schema.clone(),
                 };
                 properties
                     .iter()
@@ -121,6 +126,7 @@ impl Analyze for Property {
                     localizations: data.localizations.clone(),
                     functions_defined: data.functions_defined.clone(),
                     magazine_well_info: data.magazine_well_info.clone(),
+                    schema: data.schema.clone(),
                 };
                 value.analyze(&data, project, processed, manager)
             }
@@ -220,9 +226,42 @@ pub fn lint_all(project: Option<&ProjectConfig>, addons: &Vec<Addon>) -> Codes {
             localizations: Arc::new(Mutex::new(vec![])),
             functions_defined: Arc::new(Mutex::new(HashSet::new())),
             magazine_well_info: Arc::new(Mutex::new((Vec::new(), Vec::new()))),
+            schema: Schema::builtin(),
         },
         project,
         None,
         addons,
     )
 }
+
+#[must_use]
+/// Run the lints that need the merged config of every addon
+pub fn lint_database(
+    project: Option<&ProjectConfig>,
+    database: &Database,
+    schema: Arc<Schema>,
+) -> Codes {
+    let mut manager = LintManager::new(
+        project.map_or_else(Default::default, |project| project.lints().config().clone()),
+        project.map_or_else(RuntimeArguments::default, |p| p.runtime().clone()),
+    );
+    let _e = manager.extend(
+        crate::analyze::CONFIG_LINTS
+            .iter()
+            .map(|l| (**l).clone())
+            .collect::<Vec<_>>(),
+    );
+
+    manager.run(
+        &LintData {
+            path: String::new(),
+            localizations: Arc::new(Mutex::new(vec![])),
+            functions_defined: Arc::new(Mutex::new(HashSet::new())),
+            magazine_well_info: Arc::new(Mutex::new((Vec::new(), Vec::new()))),
+            schema,
+        },
+        project,
+        None,
+        database,
+    )
+}
@@ -0,0 +1,344 @@
+//! Structural comparison of two configs
+//!
+//! Formatting and how numbers are encoded are ignored, `1` as an `int32`, `int64`, or `float32` is
+//! the same value. Names are compared case-insensitively, the same as the game.
+
+use indexmap::IndexMap;
+
+use crate::{Array, Class, Config, Expression, Ident, Item, Number, Property, Value};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// One of the two configs being compared
+pub enum Side {
+    /// The first config
+    Left,
+    /// The second config
+    Right,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// A difference between two configs
+pub enum Difference {
+    /// A class only exists in one config
+    Class { path: String, side: Side },
+    /// A class is defined in one config, but only declared, `class Name;`, in the other
+    External { path: String, defined: Side },
+    /// A class inherits from a different parent
+    Parent {
+        path: String,
+        left: Option<String>,
+        right: Option<String>,
+    },
+    /// A property only exists in one config
+    Property {
+        path: String,
+        side: Side,
+        value: String,
+    },
+    /// A property has a different value
+    Value {
+        path: String,
+        left: String,
+        right: String,
+    },
+    /// A class is deleted in only one config
+    Delete { path: String, side: Side },
+    /// The classes that are in both configs are in a different order
+    ///
+    /// The order of classes is used by the game, such as for the index of turrets and the order
+    /// controls are drawn in
+    Order {
+        path: String,
+        left: Vec<String>,
+        right: Vec<String>,
+    },
+}
+
+impl Difference {
+    #[must_use]
+    /// The path of the class or property that is different
+    pub fn path(&self) -> &str {
+        match self {
+            Self::Class { path, .. }
+            | Self::External { path, .. }
+            | Self::Parent { path, .. }
+            | Self::Property { path, .. }
+            | Self::Value { path, .. }
+            | Self::Delete { path, .. }
+            | Self::Order { path, .. } => path,
+        }
+    }
+
+    #[must_use]
+    /// Describe the difference, naming the configs `left` and `right`
+    pub fn describe(&self, left: &str, right: &str) -> String {
+        let name = |side: &Side| match side {
+            Side::Left => left,
+            Side::Right => right,
+        };
+        let parent = |parent: &Option<String>| {
+            parent
+                .as_ref()
+                .map_or_else(|| "nothing".to_string(), |parent| format!("`{parent}`"))
+        };
+        match self {
+            Self::Class { path, side } => format!("{path}: class only in {}", name(side)),
+            Self::External { path, defined } => format!(
+                "{path}: defined in {}, only declared in {}",
+                name(defined),
+                name(&other(*defined))
+            ),
+            Self::Parent {
+                path,
+                left: left_parent,
+                right: right_parent,
+            } => format!(
+                "{path}: inherits {} in {left}, {} in {right}",
+                parent(left_parent),
+                parent(right_parent)
+            ),
+            Self::Property { path, side, value } => {
+                format!("{path}: only in {}, `{value}`", name(side))
+            }
+            Self::Value {
+                path,
+                left: left_value,
+                right: right_value,
+            } => format!("{path}: `{left_value}` in {left}, `{right_value}` in {right}"),
+            Self::Delete { path, side } => format!("{path}: deleted only in {}", name(side)),
+            Self::Order {
+                path,
+                left: left_order,
+                right: right_order,
+            } => format!(
+                "{path}: classes are ordered `{}` in {left}, `{}` in {right}",
+                left_order.join(", "),
+                right_order.join(", ")
+            ),
+        }
+    }
+}
+
+const fn other(side: Side) -> Side {
+    match side {
+        Side::Left => Side::Right,
+        Side::Right => Side::Left,
+    }
+}
+
+#[must_use]
+/// Compare two configs, returning every difference between them
+pub fn compare(left: &Config, right: &Config) -> Vec<Difference> {
+    let mut differences = Vec::new();
+    properties("", &left.0, &right.0, &mut differences);
+    differences
+}
+
+fn properties(path: &str, left: &[Property], right: &[Property], out: &mut Vec<Difference>) {
+    let (left, right) = (index(left), index(right));
+    let join = |name: &str| {
+        if path.is_empty() {
+            name.to_string()
+        } else {
+            format!("{path} >> {name}")
+        }
+    };
+    for (key, property) in &left {
+        let path = join(property.name().as_str());
+        match right.get(key) {
+            Some(other) => pair(&path, property, other, out),
+            None => out.push(only(path, property, Side::Left)),
+        }
+    }
+    for (key, property) in &right {
+        if !left.contains_key(key) {
+            out.push(only(join(property.name().as_str()), property, Side::Right));
+        }
+    }
+    let order = |index: &IndexMap<String, &Property>, other: &IndexMap<String, &Property>| {
+        index
+            .iter()
+            .filter(|(key, property)| {
+                property.is_class() && other.get(*key).is_some_and(|other| other.is_class())
+            })
+            .map(|(_, property)| property.name().as_str().to_string())
+            .collect::<Vec<_>>()
+    };
+    let (left_order, right_order) = (order(&left, &right), order(&right, &left));
+    let same = left_order
+        .iter()
+        .zip(&right_order)
+        .all(|(left, right)| left.eq_ignore_ascii_case(right));
+    if !same {
+        out.push(Difference::Order {
+            path: if path.is_empty() {
+                "root".to_string()
+            } else {
+                path.to_string()
+            },
+            left: left_order,
+            right: right_order,
+        });
+    }
+}
+
+/// Properties by their lowercase name, the first definition wins
+fn index(properties: &[Property]) -> IndexMap<String, &Property> {
+    let mut index = IndexMap::new();
+    for property in properties {
+        if !matches!(property, Property::MissingSemicolon(..)) {
+            index
+                .entry(property.name().as_str().to_lowercase())
+                .or_insert(property);
+        }
+    }
+    index
+}
+
+fn only(path: String, property: &Property, side: Side) -> Difference {
+    match property {
+        Property::Class(_) => Difference::Class { path, side },
+        Property::Delete(_) => Difference::Delete { path, side },
+        Property::Entry { value, .. } => Difference::Property {
+            path,
+            side,
+            value: display(value),
+        },
+        Property::MissingSemicolon(..) => unreachable!("not indexed"),
+    }
+}
+
+fn pair(path: &str, left: &Property, right: &Property, out: &mut Vec<Difference>) {
+    match (left, right) {
+        (Property::Class(left), Property::Class(right)) => class(path, left, right, out),
+        (Property::Entry { value: left, .. }, Property::Entry { value: right, .. }) => {
+            if !value(left, right) {
+                out.push(Difference::Value {
+                    path: path.to_string(),
+                    left: display(left),
+                    right: display(right),
+                });
+            }
+        }
+        (Property::Delete(_), Property::Delete(_)) => {}
+        (left, right) => out.push(Difference::Value {
+            path: path.to_string(),
+            left: describe(left),
+            right: describe(right),
+        }),
+    }
+}
+
+fn class(path: &str, left: &Class, right: &Class, out: &mut Vec<Difference>) {
+    match (left, right) {
+        (
+            Class::Local {
+                parent: left_parent,
+                properties: left,
+                ..
+            },
+            Class::Local {
+                parent: right_parent,
+                properties: right,
+                ..
+            },
+        ) => {
+            let left_parent = left_parent.as_ref().map(Ident::as_str);
+            let right_parent = right_parent.as_ref().map(Ident::as_str);
+            let same = match (left_parent, right_parent) {
+                (Some(left), Some(right)) => left.eq_ignore_ascii_case(right),
+                (left, right) => left == right,
+            };
+            if !same {
+                out.push(Difference::Parent {
+                    path: path.to_string(),
+                    left: left_parent.map(ToString::to_string),
+                    right: right_parent.map(ToString::to_string),
+                });
+            }
+            properties(path, left, right, out);
+        }
+        (Class::Local { .. }, Class::External { .. }) => out.push(Difference::External {
+            path: path.to_string(),
+            defined: Side::Left,
+        }),
+        (Class::External { .. }, Class::Local { .. }) => out.push(Difference::External {
+            path: path.to_string(),
+            defined: Side::Right,
+        }),
+        _ => {}
+    }
+}
+
+fn describe(property: &Property) -> String {
+    match property {
+        Property::Class(_) => "class".to_string(),
+        Property::Delete(_) => "delete".to_string(),
+        Property::Entry { value, .. } => display(value),
+        Property::MissingSemicolon(..) => unreachable!("not indexed"),
+    }
+}
+
+/// A value as written, with `+=` for arrays that extend the parent's array
+fn display(value: &Value) -> String {
+    match value {
+        Value::Array(array) | Value::UnexpectedArray(array) if array.expand => {
+            format!("+= {value}")
+        }
+        _ => value.to_string(),
+    }
+}
+
+fn value(left: &Value, right: &Value) -> bool {
+    match (left, right) {
+        (Value::Str(left), Value::Str(right)) => left.value() == right.value(),
+        (Value::Number(left), Value::Number(right)) => number(left, right),
+        (Value::Expression(left), Value::Expression(right)) => {
+            left.value == right.value
+                || evaluate(left)
+                    .is_some_and(|left| evaluate(right).is_some_and(|right| number(&left, &right)))
+        }
+        // Expressions are evaluated when rapified
+        (Value::Expression(expression), Value::Number(other))
+        | (Value::Number(other), Value::Expression(expression)) => {
+            evaluate(expression).is_some_and(|evaluated| number(&evaluated, other))
+        }
+        (
+            Value::Array(left) | Value::UnexpectedArray(left),
+            Value::Array(right) | Value::UnexpectedArray(right),
+        ) => array(left, right),
+        _ => false,
+    }
+}
+
+fn evaluate(expression: &Expression) -> Option<Number> {
+    Number::try_evaulation(&expression.value, expression.span.clone())
+}
+
+fn array(left: &Array, right: &Array) -> bool {
+    left.expand == right.expand && items(&left.items, &right.items)
+}
+
+fn items(left: &[Item], right: &[Item]) -> bool {
+    left.len() == right.len()
+        && left.iter().zip(right).all(|pair| match pair {
+            (Item::Str(left), Item::Str(right)) => left.value() == right.value(),
+            (Item::Number(left), Item::Number(right)) => number(left, right),
+            (Item::Array(left), Item::Array(right)) => items(left, right),
+            _ => false,
+        })
+}
+
+/// Numbers are equal if they have the same value, however they are encoded
+fn number(left: &Number, right: &Number) -> bool {
+    let integer = |number: &Number| match number {
+        Number::Int32 { value, .. } => Some(i64::from(*value)),
+        Number::Int64 { value, .. } => Some(*value),
+        Number::Float32 { .. } => None,
+    };
+    match (integer(left), integer(right)) {
+        (Some(left), Some(right)) => left == right,
+        // Floats are stored as 32 bits, compare at that precision
+        _ => (left.as_f64() as f32).to_bits() == (right.as_f64() as f32).to_bits(),
+    }
+}
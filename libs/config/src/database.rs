@@ -0,0 +1,810 @@
+//! The effective config of a project, after every addon's config has been merged
+//!
+//! Addons are loaded in the order of their `CfgPatches` `requiredAddons`,
+//! classes are merged as the game would, and values are resolved through inheritance.
+//!
+//! A baseline, configs from the game or other mods, can be loaded beneath the project
+//! so that external classes and patches can be resolved.
+
+use std::collections::{HashMap, HashSet};
+
+use hemtt_workspace::{position::Position, reporting::Processed};
+use indexmap::IndexMap;
+
+use crate::{Class, Config, Ident, Item, Property, Value, rapify::Derapify};
+
+/// Inheritance deeper than this is assumed to be a cycle
+const MAX_DEPTH: usize = 64;
+
+#[derive(Debug, Clone)]
+/// The config of a single addon, ready to be merged into a [`Database`]
+pub struct AddonConfig {
+    addon: String,
+    config: Config,
+    positions: HashMap<usize, Position>,
+    baseline: bool,
+}
+
+impl AddonConfig {
+    #[must_use]
+    /// Create a new addon config
+    ///
+    /// When `processed` is provided, definitions are mapped back to their source files
+    pub fn new(addon: impl Into<String>, config: Config, processed: Option<&Processed>) -> Self {
+        let mut positions = HashMap::new();
+        if let Some(processed) = processed {
+            locate(&config.0, processed, &mut positions);
+        }
+        Self {
+            addon: addon.into(),
+            config,
+            positions,
+            baseline: false,
+        }
+    }
+
+    #[must_use]
+    /// Create a config that is not part of the project, from the game or another mod
+    pub fn baseline(addon: impl Into<String>, config: Config) -> Self {
+        Self {
+            addon: addon.into(),
+            config,
+            positions: HashMap::new(),
+            baseline: true,
+        }
+    }
+
+    #[must_use]
+    /// The name of the addon
+    pub fn addon(&self) -> &str {
+        &self.addon
+    }
+
+    #[must_use]
+    /// The config is part of the baseline, not the project
+    pub const fn is_baseline(&self) -> bool {
+        self.baseline
+    }
+
+    #[must_use]
+    /// The parsed config
+    pub const fn config(&self) -> &Config {
+        &self.config
+    }
+
+    #[must_use]
+    /// The classes defined in `CfgPatches`
+    pub fn patches(&self) -> Vec<&Ident> {
+        self.cfgpatches().filter_map(|patch| patch.name()).collect()
+    }
+
+    #[must_use]
+    /// The `requiredAddons` of every class in `CfgPatches`
+    pub fn required_addons(&self) -> Vec<(&Ident, Vec<&crate::Str>)> {
+        self.cfgpatches()
+            .filter_map(|patch| {
+                let required = patch.properties().iter().find_map(|property| {
+                    let Property::Entry {
+                        name,
+                        value: Value::Array(array),
+                        ..
+                    } = property
+                    else {
+                        return None;
+                    };
+                    if !name.as_str().eq_ignore_ascii_case("requiredaddons") {
+                        return None;
+                    }
+                    Some(
+                        array
+                            .items
+                            .iter()
+                            .filter_map(|item| match item {
+                                Item::Str(value) => Some(value),
+                                _ => None,
+                            })
+                            .collect::<Vec<_>>(),
+                    )
+                });
+                Some((patch.name()?, required.unwrap_or_default()))
+            })
+            .collect()
+    }
+
+    #[must_use]
+    /// Where an identifier in this config was defined
+    pub fn position(&self, ident: &Ident) -> Option<&Position> {
+        self.positions.get(&ident.span.start)
+    }
+
+    #[must_use]
+    /// Where a string in an array in this config was defined
+    pub fn position_of_str(&self, value: &crate::Str) -> Option<&Position> {
+        self.positions.get(&value.span.start)
+    }
+
+    fn cfgpatches(&self) -> impl Iterator<Item = &Class> {
+        self.config
+            .0
+            .iter()
+            .filter_map(|property| match property {
+                Property::Class(class @ Class::Local { name, .. })
+                    if name.as_str().eq_ignore_ascii_case("cfgpatches") =>
+                {
+                    Some(class.properties())
+                }
+                _ => None,
+            })
+            .flatten()
+            .filter_map(|property| match property {
+                Property::Class(class @ Class::Local { .. }) => Some(class),
+                _ => None,
+            })
+    }
+}
+
+/// Read the baseline configs from a folder of rapified configs, one per addon
+///
+/// Files are read in the order of their names, a missing folder is an empty baseline
+///
+/// # Errors
+/// [`std::io::Error`] if a file can not be read, or is not a rapified config
+pub fn read_baseline(folder: &std::path::Path) -> Result<Vec<AddonConfig>, std::io::Error> {
+    if !folder.is_dir() {
+        return Ok(Vec::new());
+    }
+    let mut paths = std::fs::read_dir(folder)?
+        .map(|entry| entry.map(|entry| entry.path()))
+        .collect::<Result<Vec<_>, _>>()?;
+    paths.retain(|path| path.extension().is_some_and(|ext| ext == "bin"));
+    paths.sort();
+    let mut configs = Vec::with_capacity(paths.len());
+    for path in paths {
+        let Some(name) = path.file_stem().and_then(|stem| stem.to_str()) else {
+            continue;
+        };
+        let mut file = std::io::BufReader::new(std::fs::File::open(&path)?);
+        configs.push(AddonConfig::baseline(name, Config::derapify(&mut file)?));
+    }
+    Ok(configs)
+}
+
+fn locate(
+    properties: &[Property],
+    processed: &Processed,
+    positions: &mut HashMap<usize, Position>,
+) {
+    for property in properties {
+        if matches!(property, Property::Class(Class::Root { .. })) {
+            continue;
+        }
+        let name = property.name();
+        if let Some(position) = original(processed, &name.span) {
+            positions.insert(name.span.start, position);
+        }
+        match property {
+            Property::Class(class) => locate(class.properties(), processed, positions),
+            Property::Entry {
+                value: Value::Array(array),
+                ..
+            } => locate_items(&array.items, processed, positions),
+            _ => {}
+        }
+    }
+}
+
+fn locate_items(items: &[Item], processed: &Processed, positions: &mut HashMap<usize, Position>) {
+    for item in items {
+        match item {
+            Item::Str(value) => {
+                if let Some(position) = original(processed, &value.span) {
+                    positions.insert(value.span.start, position);
+                }
+            }
+            Item::Array(items) => locate_items(items, processed, positions),
+            _ => {}
+        }
+    }
+}
+
+/// The position of a span of the processed output in its source file
+fn original(processed: &Processed, span: &std::ops::Range<usize>) -> Option<Position> {
+    let start = processed.mapping(span.start)?.original();
+    let from = start.start();
+    let end = processed
+        .mapping(span.end.saturating_sub(1).max(span.start))
+        .map(hemtt_workspace::reporting::Mapping::original)
+        .filter(|end| end.path() == start.path() && end.end() >= from);
+    Some(end.map_or_else(|| start.clone(), |end| start.clone_with_end(*end.end())))
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// Where a class or property was defined
+pub struct Definition {
+    addon: String,
+    position: Option<Position>,
+    baseline: bool,
+}
+
+impl Definition {
+    #[must_use]
+    /// The addon that made the definition
+    pub fn addon(&self) -> &str {
+        &self.addon
+    }
+
+    #[must_use]
+    /// The position in the source, if known
+    pub const fn position(&self) -> Option<&Position> {
+        self.position.as_ref()
+    }
+
+    #[must_use]
+    /// The definition was made by the baseline, not the project
+    pub const fn is_baseline(&self) -> bool {
+        self.baseline
+    }
+}
+
+impl std::fmt::Display for Definition {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.position {
+            Some(position) => write!(
+                f,
+                "{}:{} ({})",
+                position.path().as_str().trim_start_matches('/'),
+                position.start().line(),
+                self.addon
+            ),
+            None if self.baseline => write!(f, "{} (baseline)", self.addon),
+            None => write!(f, "{}", self.addon),
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct Node {
+    name: String,
+    parent: Option<String>,
+    external: bool,
+    classes: IndexMap<String, Self>,
+    properties: IndexMap<String, Entry>,
+    definitions: Vec<Definition>,
+    declarations: Vec<Definition>,
+}
+
+#[derive(Debug)]
+struct Entry {
+    name: String,
+    value: Value,
+    definition: Definition,
+}
+
+#[derive(Debug, Default)]
+/// The merged config of a set of addons
+pub struct Database {
+    addons: Vec<AddonConfig>,
+    baseline: Vec<AddonConfig>,
+    patches: HashMap<String, String>,
+    baseline_patches: HashMap<String, String>,
+    root: Node,
+}
+
+impl Database {
+    #[must_use]
+    /// Merge the configs, in the order of their `requiredAddons`
+    ///
+    /// Baseline configs are loaded before the project's configs,
+    /// addons are otherwise loaded in the order they are provided
+    pub fn new(configs: Vec<AddonConfig>) -> Self {
+        let mut database = Self::default();
+        let (baseline, configs): (Vec<_>, Vec<_>) =
+            configs.into_iter().partition(AddonConfig::is_baseline);
+        for config in &baseline {
+            for patch in config.patches() {
+                database
+                    .baseline_patches
+                    .insert(patch.as_str().to_lowercase(), config.addon.clone());
+            }
+        }
+        for config in &configs {
+            for patch in config.patches() {
+                database
+                    .patches
+                    .insert(patch.as_str().to_lowercase(), config.addon.clone());
+            }
+        }
+        for config in sort(baseline, &database.baseline_patches) {
+            merge(&mut database.root, &config.config.0, &config);
+            database.baseline.push(config);
+        }
+        for config in sort(configs, &database.patches) {
+            merge(&mut database.root, &config.config.0, &config);
+            database.addons.push(config);
+        }
+        database
+    }
+
+    #[must_use]
+    /// The baseline configs, in the order they were loaded
+    pub fn baseline(&self) -> &[AddonConfig] {
+        &self.baseline
+    }
+
+    #[must_use]
+    /// A baseline was loaded, external classes and patches can be checked against it
+    pub const fn has_baseline(&self) -> bool {
+        !self.baseline.is_empty()
+    }
+
+    #[must_use]
+    /// The project addons, in the order they were loaded
+    pub fn load_order(&self) -> Vec<&str> {
+        self.addons.iter().map(AddonConfig::addon).collect()
+    }
+
+    #[must_use]
+    /// The config of each project addon, in the order they were loaded
+    pub fn addons(&self) -> &[AddonConfig] {
+        &self.addons
+    }
+
+    #[must_use]
+    /// The project addons directly required by an addon
+    pub fn requires(&self, addon: &str) -> Vec<&str> {
+        let mut requires = Vec::new();
+        for config in self.addons.iter().filter(|config| config.addon == addon) {
+            for (_, required) in config.required_addons() {
+                for required in required {
+                    if let Some(found) = self
+                        .patch_addon(required.value())
+                        .filter(|found| *found != addon && !requires.contains(found))
+                    {
+                        requires.push(found);
+                    }
+                }
+            }
+        }
+        requires
+    }
+
+    #[must_use]
+    /// The project addons required by an addon, directly or through other addons
+    pub fn dependencies(&self, addon: &str) -> HashSet<&str> {
+        let mut found = HashSet::new();
+        let mut queue = self.requires(addon);
+        while let Some(next) = queue.pop() {
+            if found.insert(next) {
+                queue.extend(self.requires(next));
+            }
+        }
+        found
+    }
+
+    #[must_use]
+    /// Groups of addons that require each other, in load order
+    pub fn cycles(&self) -> Vec<Vec<&str>> {
+        let mut cycles: Vec<Vec<&str>> = Vec::new();
+        for addon in self.load_order() {
+            if cycles.iter().any(|cycle| cycle.contains(&addon)) {
+                continue;
+            }
+            let dependencies = self.dependencies(addon);
+            if !dependencies.contains(addon) {
+                continue;
+            }
+            cycles.push(
+                self.load_order()
+                    .into_iter()
+                    .filter(|other| {
+                        dependencies.contains(other) && self.dependencies(other).contains(addon)
+                    })
+                    .collect(),
+            );
+        }
+        cycles
+    }
+
+    #[must_use]
+    /// The addon that defines a `CfgPatches` class
+    pub fn patch_addon(&self, patch: &str) -> Option<&str> {
+        self.patches.get(&patch.to_lowercase()).map(String::as_str)
+    }
+
+    #[must_use]
+    /// The baseline addon that defines a `CfgPatches` class
+    pub fn baseline_patch_addon(&self, patch: &str) -> Option<&str> {
+        self.baseline_patches
+            .get(&patch.to_lowercase())
+            .map(String::as_str)
+    }
+
+    #[must_use]
+    /// Every patch required by the addon is in the project or the baseline
+    ///
+    /// Classes from the addon's requirements can only be checked when this is true
+    pub fn covers(&self, addon: &str) -> bool {
+        self.addons
+            .iter()
+            .filter(|config| config.addon == addon)
+            .flat_map(AddonConfig::required_addons)
+            .flat_map(|(_, required)| required)
+            .all(|required| {
+                self.patch_addon(required.value()).is_some()
+                    || self.baseline_patch_addon(required.value()).is_some()
+            })
+    }
+
+    #[must_use]
+    /// The root of the config
+    pub const fn root(&self) -> ClassRef<'_> {
+        ClassRef {
+            database: self,
+            path: Vec::new(),
+        }
+    }
+
+    #[must_use]
+    /// Find a class or property by its path
+    ///
+    /// ```text
+    /// CfgVehicles >> MyTruck >> maxSpeed
+    /// configFile >> "CfgVehicles" >> "MyTruck"
+    /// ```
+    pub fn query(&self, query: &str) -> Option<Found<'_>> {
+        let mut segments = query
+            .split(">>")
+            .map(|segment| segment.trim().trim_matches('"'))
+            .collect::<Vec<_>>();
+        if segments
+            .first()
+            .is_some_and(|first| first.eq_ignore_ascii_case("configfile"))
+        {
+            segments.remove(0);
+        }
+        let last = segments.pop()?;
+        let mut class = self.root();
+        for segment in segments {
+            class = class.class(segment)?;
+        }
+        if last.is_empty() {
+            return Some(Found::Class(class));
+        }
+        class
+            .class(last)
+            .map(Found::Class)
+            .or_else(|| class.property(last).map(Found::Property))
+    }
+
+    fn node(&self, path: &[String]) -> Option<&Node> {
+        let mut node = &self.root;
+        for segment in path {
+            node = node.classes.get(segment)?;
+        }
+        Some(node)
+    }
+
+    /// The path of a class's parent
+    fn parent_of(&self, path: &[String], depth: usize) -> Option<Vec<String>> {
+        let (name, scope) = path.split_last()?;
+        let parent = self.node(path)?.parent.as_ref()?.to_lowercase();
+        if depth > MAX_DEPTH {
+            return None;
+        }
+        // `class Turrets: Turrets` refers to the class inherited by the scope
+        if &parent != name && self.node(scope)?.classes.contains_key(&parent) {
+            let mut found = scope.to_vec();
+            found.push(parent);
+            return Some(found);
+        }
+        if let Some(found) = self
+            .parent_of(scope, depth + 1)
+            .and_then(|scope_parent| self.member(&scope_parent, &parent, depth + 1))
+        {
+            return Some(found);
+        }
+        // Then each enclosing class in turn, out to the root
+        (0..scope.len())
+            .rev()
+            .find_map(|end| self.member(&scope[..end], &parent, depth + 1))
+    }
+
+    /// The path of a class, defined in or inherited by the class at `path`
+    fn member(&self, path: &[String], name: &str, depth: usize) -> Option<Vec<String>> {
+        if depth > MAX_DEPTH {
+            return None;
+        }
+        if self.node(path)?.classes.contains_key(name) {
+            let mut found = path.to_vec();
+            found.push(name.to_string());
+            return Some(found);
+        }
+        let parent = self.parent_of(path, depth + 1)?;
+        self.member(&parent, name, depth + 1)
+    }
+}
+
+fn sort(mut remaining: Vec<AddonConfig>, patches: &HashMap<String, String>) -> Vec<AddonConfig> {
+    let mut sorted: Vec<AddonConfig> = Vec::with_capacity(remaining.len());
+    while !remaining.is_empty() {
+        let next = remaining
+            .iter()
+            .position(|config| {
+                config.required_addons().iter().all(|(_, required)| {
+                    required.iter().all(|required| {
+                        patches
+                            .get(&required.value().to_lowercase())
+                            .is_none_or(|addon| {
+                                addon == &config.addon || sorted.iter().any(|s| &s.addon == addon)
+                            })
+                    })
+                })
+            })
+            // A cycle, load the rest in the order they were provided
+            .unwrap_or(0);
+        sorted.push(remaining.remove(next));
+    }
+    sorted
+}
+
+fn merge(node: &mut Node, properties: &[Property], config: &AddonConfig) {
+    let definition = |ident: &Ident| Definition {
+        addon: config.addon.clone(),
+        position: config.position(ident).cloned(),
+        baseline: config.baseline,
+    };
+    for property in properties {
+        match property {
+            Property::Entry { name, value, .. } => {
+                let key = name.as_str().to_lowercase();
+                let mut value = value.clone();
+                // `+=` on a class that already has the value appends to it
+                let existing = node
+                    .properties
+                    .get(&key)
+                    .and_then(|entry| match &entry.value {
+                        Value::Array(existing) => Some(existing),
+                        _ => None,
+                    });
+                match (&mut value, existing) {
+                    (Value::Array(array), Some(existing)) if array.expand => {
+                        let mut items = existing.items.clone();
+                        items.append(&mut array.items);
+                        array.items = items;
+                        array.expand = existing.expand;
+                    }
+                    _ => {}
+                }
+                node.properties.insert(
+                    key,
+                    Entry {
+                        name: name.as_str().to_string(),
+                        value,
+                        definition: definition(name),
+                    },
+                );
+            }
+            Property::Class(Class::Local {
+                name,
+                parent,
+                properties,
+                ..
+            }) => {
+                let class = node
+                    .classes
+                    .entry(name.as_str().to_lowercase())
+                    .or_default();
+                class.name = name.as_str().to_string();
+                class.parent = parent.as_ref().map(|parent| parent.as_str().to_string());
+                class.external = false;
+                class.definitions.push(definition(name));
+                merge(class, properties, config);
+            }
+            Property::Class(Class::External { name }) => {
+                node.classes
+                    .entry(name.as_str().to_lowercase())
+                    .or_insert_with(|| Node {
+                        name: name.as_str().to_string(),
+                        external: true,
+                        ..Default::default()
+                    })
+                    .declarations
+                    .push(definition(name));
+            }
+            Property::Delete(name) => {
+                node.classes.shift_remove(&name.as_str().to_lowercase());
+            }
+            Property::Class(Class::Root { .. }) | Property::MissingSemicolon(..) => {}
+        }
+    }
+}
+
+/// The result of a [`Database::query`]
+pub enum Found<'a> {
+    Class(ClassRef<'a>),
+    Property(PropertyRef<'a>),
+}
+
+#[derive(Clone)]
+/// A class in the [`Database`]
+pub struct ClassRef<'a> {
+    database: &'a Database,
+    path: Vec<String>,
+}
+
+impl<'a> ClassRef<'a> {
+    fn node(&self) -> &'a Node {
+        self.database
+            .node(&self.path)
+            .expect("class refs are only created for existing classes")
+    }
+
+    #[must_use]
+    /// The name of the class, empty for the root
+    pub fn name(&self) -> &'a str {
+        &self.node().name
+    }
+
+    #[must_use]
+    /// The full path of the class, `CfgVehicles >> Car`
+    pub fn path(&self) -> String {
+        let mut node = &self.database.root;
+        let mut names = Vec::new();
+        for segment in &self.path {
+            node = &node.classes[segment];
+            names.push(node.name.as_str());
+        }
+        names.join(" >> ")
+    }
+
+    #[must_use]
+    /// The name of the parent, as written
+    pub fn parent_name(&self) -> Option<&'a str> {
+        self.node().parent.as_deref()
+    }
+
+    #[must_use]
+    /// The class this class inherits from, if it can be found
+    pub fn parent(&self) -> Option<Self> {
+        self.database.parent_of(&self.path, 0).map(|path| Self {
+            database: self.database,
+            path,
+        })
+    }
+
+    #[must_use]
+    /// The class was only declared, `class Car;`, and never defined
+    pub fn is_external(&self) -> bool {
+        self.node().external
+    }
+
+    #[must_use]
+    /// Every definition of the class, in load order
+    pub fn definitions(&self) -> &'a [Definition] {
+        &self.node().definitions
+    }
+
+    #[must_use]
+    /// Every declaration of the class, `class Car;`, in load order
+    pub fn declarations(&self) -> &'a [Definition] {
+        &self.node().declarations
+    }
+
+    #[must_use]
+    /// A class defined in or inherited by this class
+    pub fn class(&self, name: &str) -> Option<Self> {
+        self.database
+            .member(&self.path, &name.to_lowercase(), 0)
+            .map(|path| Self {
+                database: self.database,
+                path,
+            })
+    }
+
+    #[must_use]
+    /// The classes defined in this class, not including inherited classes
+    pub fn classes(&self) -> Vec<Self> {
+        self.node()
+            .classes
+            .keys()
+            .map(|key| {
+                let mut path = self.path.clone();
+                path.push(key.clone());
+                Self {
+                    database: self.database,
+                    path,
+                }
+            })
+            .collect()
+    }
+
+    #[must_use]
+    /// A property defined in or inherited by this class
+    pub fn property(&self, name: &str) -> Option<PropertyRef<'a>> {
+        let key = name.to_lowercase();
+        let mut class = self.clone();
+        for _ in 0..MAX_DEPTH {
+            if let Some(entry) = class.node().properties.get(&key) {
+                return Some(PropertyRef::new(entry, class));
+            }
+            class = class.parent()?;
+        }
+        None
+    }
+
+    #[must_use]
+    /// The effective properties of this class, including inherited properties
+    pub fn properties(&self) -> Vec<PropertyRef<'a>> {
+        let mut found: IndexMap<&str, PropertyRef<'a>> = IndexMap::new();
+        let mut class = Some(self.clone());
+        let mut depth = 0;
+        while let Some(current) = class {
+            if depth > MAX_DEPTH {
+                break;
+            }
+            for (key, entry) in &current.node().properties {
+                found
+                    .entry(key.as_str())
+                    .or_insert_with(|| PropertyRef::new(entry, current.clone()));
+            }
+            class = current.parent();
+            depth += 1;
+        }
+        found.into_values().collect()
+    }
+}
+
+/// A property in the [`Database`]
+pub struct PropertyRef<'a> {
+    entry: &'a Entry,
+    owner: ClassRef<'a>,
+    value: Value,
+}
+
+impl<'a> PropertyRef<'a> {
+    /// Arrays using `+=` are appended to the inherited value
+    fn new(entry: &'a Entry, owner: ClassRef<'a>) -> Self {
+        let mut value = entry.value.clone();
+        let inherited = match &value {
+            Value::Array(array) if array.expand => owner
+                .parent()
+                .and_then(|parent| parent.property(&entry.name))
+                .map(|inherited| inherited.value),
+            _ => None,
+        };
+        if let (Value::Array(array), Some(Value::Array(inherited))) = (&mut value, inherited) {
+            let mut items = inherited.items;
+            items.append(&mut array.items);
+            array.items = items;
+        }
+        Self {
+            entry,
+            owner,
+            value,
+        }
+    }
+
+    #[must_use]
+    /// The name of the property, as written
+    pub fn name(&self) -> &'a str {
+        &self.entry.name
+    }
+
+    #[must_use]
+    /// The effective value
+    pub const fn value(&self) -> &Value {
+        &self.value
+    }
+
+    #[must_use]
+    /// Where the effective value was defined
+    pub const fn definition(&self) -> &'a Definition {
+        &self.entry.definition
+    }
+
+    #[must_use]
+    /// The class the value was defined in, differs from the queried class when inherited
+    pub const fn owner(&self) -> &ClassRef<'a> {
+        &self.owner
+    }
+}
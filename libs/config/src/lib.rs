@@ -10,10 +10,13 @@ use std::{
 };
 
 pub mod analyze;
+pub mod compare;
+pub mod database;
 pub mod display;
 mod model;
 pub mod parse;
 pub mod rapify;
+pub mod schema;
 
 pub use model::*;
 
@@ -71,6 +74,7 @@ pub fn parse(
                     localizations: localizations.clone(),
                     functions_defined: functions_defined.clone(),
                     magazine_well_info: magazine_well_info.clone(),
+                    schema: schema::Schema::builtin(),
                 },
                 project,
                 processed,
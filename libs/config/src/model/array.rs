@@ -10,6 +10,20 @@ pub struct Array {
     pub(crate) span: Range<usize>,
 }
 
+impl Array {
+    #[must_use]
+    /// The items of the array
+    pub fn items(&self) -> &[Item] {
+        &self.items
+    }
+
+    #[must_use]
+    /// The array is appended to the inherited value, `+=`
+    pub const fn expand(&self) -> bool {
+        self.expand
+    }
+}
+
 #[derive(Debug, Clone, PartialEq)]
 /// An array value
 pub enum Item {
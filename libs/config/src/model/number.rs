@@ -69,6 +69,26 @@ impl Number {
         }
     }
 
+    #[must_use]
+    /// Get the value as a float
+    pub fn as_f64(&self) -> f64 {
+        match self {
+            Self::Int32 { value, .. } => f64::from(*value),
+            #[allow(clippy::cast_precision_loss)]
+            Self::Int64 { value, .. } => *value as f64,
+            Self::Float32 { value, .. } => f64::from(*value),
+        }
+    }
+
+    #[must_use]
+    /// The number has no fractional part
+    pub fn is_integer(&self) -> bool {
+        match self {
+            Self::Int32 { .. } | Self::Int64 { .. } => true,
+            Self::Float32 { value, .. } => value.fract() == 0.0,
+        }
+    }
+
     #[must_use]
     /// Get the range of the number
     pub fn span(&self) -> Range<usize> {
@@ -0,0 +1,465 @@
+//! Expected properties and value types of well-known classes
+//!
+//! A schema maps class paths to the properties their classes may define.
+//! Paths are matched against a class and every class it inherits from,
+//! so `CfgVehicles >> Man` applies to every unit, and `*` matches any class.
+//!
+//! ```toml
+//! ["CfgMagazines >> *".properties]
+//! count = { type = "integer", min = 1 }
+//! displayName = "string"
+//! ```
+
+use std::{
+    path::Path,
+    sync::{Arc, LazyLock},
+};
+
+use indexmap::IndexMap;
+use serde::Deserialize;
+
+use crate::{Item, Number, Value, database::ClassRef};
+
+static BUILTIN: LazyLock<Arc<Schema>> = LazyLock::new(|| {
+    let mut schema = Schema::default();
+    for (file, source) in [
+        ("cfgammo.toml", include_str!("cfgammo.toml")),
+        ("cfgfunctions.toml", include_str!("cfgfunctions.toml")),
+        ("cfgmagazines.toml", include_str!("cfgmagazines.toml")),
+        ("cfgpatches.toml", include_str!("cfgpatches.toml")),
+        ("cfgvehicles.toml", include_str!("cfgvehicles.toml")),
+        ("cfgweapons.toml", include_str!("cfgweapons.toml")),
+    ] {
+        schema.extend(
+            Schema::from_toml(source)
+                .unwrap_or_else(|e| panic!("builtin schema {file} is invalid: {e}")),
+        );
+    }
+    Arc::new(schema)
+});
+
+#[derive(Debug, Clone, Default)]
+/// Expected properties of classes, by path
+pub struct Schema {
+    classes: Vec<(Vec<String>, ClassSchema)>,
+}
+
+impl Schema {
+    #[must_use]
+    /// The schema shipped with HEMTT
+    pub fn builtin() -> Arc<Self> {
+        BUILTIN.clone()
+    }
+
+    /// Parse a schema from TOML
+    ///
+    /// # Errors
+    /// [`toml::de::Error`] if the schema is invalid
+    pub fn from_toml(source: &str) -> Result<Self, toml::de::Error> {
+        toml::from_str::<IndexMap<String, ClassSchema>>(source).map(Self::from_map)
+    }
+
+    /// Parse a schema from JSON
+    ///
+    /// # Errors
+    /// [`serde_json::Error`] if the schema is invalid
+    pub fn from_json(source: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str::<IndexMap<String, ClassSchema>>(source).map(Self::from_map)
+    }
+
+    fn from_map(map: IndexMap<String, ClassSchema>) -> Self {
+        Self {
+            classes: map
+                .into_iter()
+                .map(|(path, class)| (segments(&path), class))
+                .collect(),
+        }
+    }
+
+    /// The builtin schema, extended by every `.toml` and `.json` schema in a folder
+    ///
+    /// Files are read in the order of their names, a missing folder adds nothing
+    ///
+    /// # Errors
+    /// [`std::io::Error`] if a file can not be read, or is not a valid schema
+    pub fn with_folder(folder: &Path) -> Result<Self, std::io::Error> {
+        let mut schema = (*Self::builtin()).clone();
+        if !folder.is_dir() {
+            return Ok(schema);
+        }
+        let mut paths = std::fs::read_dir(folder)?
+            .map(|entry| entry.map(|entry| entry.path()))
+            .collect::<Result<Vec<_>, _>>()?;
+        paths.sort();
+        for path in paths {
+            let invalid = |e: &dyn std::fmt::Display| {
+                std::io::Error::new(
+                    std::io::ErrorKind::InvalidData,
+                    format!("invalid schema {}: {e}", path.display()),
+                )
+            };
+            let extension = path
+                .extension()
+                .and_then(|ext| ext.to_str())
+                .map(str::to_lowercase);
+            let parsed =
+                match extension.as_deref() {
+                    Some("toml") => Self::from_toml(&std::fs::read_to_string(&path)?)
+                        .map_err(|e| invalid(&e))?,
+                    Some("json") => Self::from_json(&std::fs::read_to_string(&path)?)
+                        .map_err(|e| invalid(&e))?,
+                    _ => continue,
+                };
+            schema.extend(parsed);
+        }
+        Ok(schema)
+    }
+
+    /// Add the classes of another schema, its properties replace existing ones for the same path
+    pub fn extend(&mut self, other: Self) {
+        for (path, class) in other.classes {
+            if let Some((_, existing)) = self.classes.iter_mut().find(|(p, _)| *p == path) {
+                existing.closed |= class.closed;
+                existing.properties.extend(class.properties);
+            } else {
+                self.classes.push((path, class));
+            }
+        }
+    }
+
+    #[must_use]
+    /// The properties expected for a class, from every path that matches it or a class it inherits from
+    pub fn applied(&self, class: &ClassRef) -> Applied<'_> {
+        let mut applied = Applied::default();
+        let mut current = Some(class.clone());
+        let mut depth = 0;
+        while let Some(class) = current {
+            if depth > 64 {
+                break;
+            }
+            let path = segments(&class.path());
+            for (pattern, schema) in &self.classes {
+                if !matches(pattern, &path) {
+                    continue;
+                }
+                applied.closed |= schema.closed;
+                for (name, property) in &schema.properties {
+                    applied
+                        .properties
+                        .entry(name.to_lowercase())
+                        .or_insert((name.as_str(), property));
+                }
+            }
+            current = class.parent();
+            depth += 1;
+        }
+        applied
+    }
+}
+
+fn segments(path: &str) -> Vec<String> {
+    path.split(">>")
+        .map(|segment| segment.trim().to_lowercase())
+        .filter(|segment| !segment.is_empty())
+        .collect()
+}
+
+fn matches(pattern: &[String], path: &[String]) -> bool {
+    pattern.len() == path.len()
+        && pattern
+            .iter()
+            .zip(path)
+            .all(|(pattern, segment)| pattern == "*" || pattern == segment)
+}
+
+#[derive(Debug, Default)]
+/// The schemas that apply to a class, merged
+pub struct Applied<'a> {
+    closed: bool,
+    properties: IndexMap<String, (&'a str, &'a PropertySchema)>,
+}
+
+impl Applied<'_> {
+    #[must_use]
+    /// No schema applies to the class
+    pub fn is_empty(&self) -> bool {
+        self.properties.is_empty() && !self.closed
+    }
+
+    #[must_use]
+    /// Properties not in the schema are always reported
+    pub const fn is_closed(&self) -> bool {
+        self.closed
+    }
+
+    #[must_use]
+    /// The schema of a property, case-insensitive
+    pub fn property(&self, name: &str) -> Option<&PropertySchema> {
+        self.properties
+            .get(&name.to_lowercase())
+            .map(|(_, property)| *property)
+    }
+
+    #[must_use]
+    /// Known property names that look like a typo of `name`
+    ///
+    /// Names under 8 characters may differ by 1 edit, longer names by 2
+    pub fn similar(&self, name: &str) -> Vec<String> {
+        let name = name.to_lowercase();
+        let allowed = if name.len() < 8 { 1 } else { 2 };
+        self.properties
+            .iter()
+            .filter(|(key, _)| strsim::levenshtein(key, &name) <= allowed)
+            .map(|(_, (name, _))| (*name).to_string())
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ClassSchema {
+    #[serde(default)]
+    closed: bool,
+    #[serde(default)]
+    properties: IndexMap<String, PropertySchema>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+/// The type of a value
+pub enum Kind {
+    String,
+    Number,
+    Integer,
+    Bool,
+    Array,
+    Any,
+}
+
+impl std::fmt::Display for Kind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::String => "a string",
+            Self::Number => "a number",
+            Self::Integer => "a whole number",
+            Self::Bool => "0 or 1",
+            Self::Array => "an array",
+            Self::Any => "any value",
+        })
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+enum RawProperty {
+    Kind(Kind),
+    Full {
+        #[serde(rename = "type")]
+        kind: Kind,
+        #[serde(default)]
+        min: Option<f64>,
+        #[serde(default)]
+        max: Option<f64>,
+        #[serde(default)]
+        items: Option<Kind>,
+        #[serde(default)]
+        values: Option<Vec<String>>,
+    },
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(from = "RawProperty")]
+/// The expected value of a property
+pub struct PropertySchema {
+    kind: Kind,
+    min: Option<f64>,
+    max: Option<f64>,
+    items: Option<Kind>,
+    values: Option<Vec<String>>,
+}
+
+impl From<RawProperty> for PropertySchema {
+    fn from(raw: RawProperty) -> Self {
+        match raw {
+            RawProperty::Kind(kind) => Self {
+                kind,
+                min: None,
+                max: None,
+                items: None,
+                values: None,
+            },
+            RawProperty::Full {
+                kind,
+                min,
+                max,
+                items,
+                values,
+            } => Self {
+                kind,
+                min,
+                max,
+                items,
+                values,
+            },
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+/// A value that does not match its [`PropertySchema`]
+pub enum Problem {
+    /// The value is of the wrong type
+    Type(Kind),
+    /// The number is outside of the allowed range
+    Range {
+        value: f64,
+        min: Option<f64>,
+        max: Option<f64>,
+    },
+    /// The string is not one of the allowed values
+    Value(Vec<String>),
+    /// An item of the array has a problem
+    Item(Box<Self>),
+}
+
+impl PropertySchema {
+    #[must_use]
+    /// The first problem with a value, if any
+    pub fn check(&self, value: &Value) -> Option<Problem> {
+        match (self.kind, value) {
+            (Kind::Any, _) | (_, Value::Invalid(_)) => None,
+            (Kind::Array, Value::Array(array) | Value::UnexpectedArray(array)) => {
+                let items = self.items?;
+                array.items.iter().find_map(|item| {
+                    let value = match item {
+                        Item::Str(value) => Value::Str(value.clone()),
+                        Item::Number(value) => Value::Number(value.clone()),
+                        Item::Array(_) | Item::Invalid(_) => return None,
+                    };
+                    Self {
+                        kind: items,
+                        items: None,
+                        ..self.clone()
+                    }
+                    .check(&value)
+                    .map(|problem| Problem::Item(Box::new(problem)))
+                })
+            }
+            (Kind::Array, _) | (_, Value::Array(_) | Value::UnexpectedArray(_)) => {
+                Some(Problem::Type(self.kind))
+            }
+            // Evaluated by the game, the result can not be checked
+            (_, Value::Expression(_)) => None,
+            (Kind::String, Value::Str(value)) => self.values.as_ref().and_then(|values| {
+                (!values
+                    .iter()
+                    .any(|allowed| allowed.eq_ignore_ascii_case(value.value())))
+                .then(|| Problem::Value(values.clone()))
+            }),
+            (Kind::String, _) => Some(Problem::Type(Kind::String)),
+            (_, Value::Number(number)) => self.check_number(number),
+            // Quoted numbers are converted by the game
+            (_, Value::Str(value)) => value.value().trim().parse::<f64>().map_or(
+                Some(Problem::Type(self.kind)),
+                |number| {
+                    self.check_number(&Number::Float32 {
+                        #[allow(clippy::cast_possible_truncation)]
+                        value: number as f32,
+                        span: 0..0,
+                    })
+                },
+            ),
+        }
+    }
+
+    fn check_number(&self, number: &Number) -> Option<Problem> {
+        let value = number.as_f64();
+        match self.kind {
+            Kind::Integer if !number.is_integer() => return Some(Problem::Type(Kind::Integer)),
+            Kind::Bool if value != 0.0 && (value - 1.0).abs() > f64::EPSILON => {
+                return Some(Problem::Type(Kind::Bool));
+            }
+            _ => {}
+        }
+        if self.min.is_some_and(|min| value < min) || self.max.is_some_and(|max| value > max) {
+            return Some(Problem::Range {
+                value,
+                min: self.min,
+                max: self.max,
+            });
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Number, Str, Value};
+
+    use super::{Kind, Problem, Schema};
+
+    fn number(value: f32) -> Value {
+        Value::Number(Number::Float32 { value, span: 0..0 })
+    }
+
+    fn string(value: &str) -> Value {
+        Value::Str(Str {
+            value: value.to_string(),
+            span: 0..0,
+        })
+    }
+
+    #[test]
+    fn builtin() {
+        let schema = Schema::builtin();
+        assert!(!schema.classes.is_empty());
+    }
+
+    #[test]
+    fn check() {
+        let schema = Schema::from_toml(
+            r#"
+["CfgThings >> *"]
+closed = true
+
+["CfgThings >> *".properties]
+name = "string"
+count = { type = "integer", min = 1, max = 10 }
+enabled = "bool"
+kind = { type = "string", values = ["a", "b"] }
+"#,
+        )
+        .expect("valid schema");
+        let (_, class) = &schema.classes[0];
+        assert!(class.closed);
+        let property = |name: &str| &class.properties[name];
+        assert_eq!(property("name").check(&string("hi")), None);
+        assert_eq!(
+            property("name").check(&number(1.0)),
+            Some(Problem::Type(Kind::String))
+        );
+        assert_eq!(property("count").check(&number(5.0)), None);
+        assert_eq!(property("count").check(&string("5")), None);
+        assert_eq!(
+            property("count").check(&number(1.5)),
+            Some(Problem::Type(Kind::Integer))
+        );
+        assert_eq!(
+            property("count").check(&number(11.0)),
+            Some(Problem::Range {
+                value: 11.0,
+                min: Some(1.0),
+                max: Some(10.0)
+            })
+        );
+        assert_eq!(
+            property("enabled").check(&number(2.0)),
+            Some(Problem::Type(Kind::Bool))
+        );
+        assert_eq!(property("kind").check(&string("B")), None);
+        assert_eq!(
+            property("kind").check(&string("c")),
+            Some(Problem::Value(vec!["a".to_string(), "b".to_string()]))
+        );
+    }
+}
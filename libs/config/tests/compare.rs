@@ -0,0 +1,35 @@
+#![allow(clippy::unwrap_used)]
+
+use hemtt_config::{Config, compare::compare, rapify::Derapify};
+use hemtt_preprocessor::Processor;
+use hemtt_workspace::LayerType;
+
+fn parse(folder: &str, file: &str) -> Config {
+    let workspace = hemtt_workspace::Workspace::builder()
+        .physical(&std::path::PathBuf::from(folder), LayerType::Source)
+        .finish(None, false, &hemtt_common::config::PDriveOption::Disallow)
+        .unwrap();
+    let source = workspace.join(file).unwrap();
+    let processed = Processor::run(&source).unwrap();
+    hemtt_config::parse(None, &processed).unwrap().into_config()
+}
+
+#[test]
+fn differences() {
+    let left = parse("tests/compare/", "left.hpp");
+    let right = parse("tests/compare/", "right.hpp");
+    let differences = compare(&left, &right)
+        .iter()
+        .map(|difference| difference.describe("left.hpp", "right.hpp"))
+        .collect::<Vec<_>>();
+    insta::assert_snapshot!(differences.join("\n"));
+    assert!(compare(&left, &left).is_empty());
+}
+
+#[test]
+fn derapified() {
+    let parsed = parse("tests/rapify/ace_main/", "source.hpp");
+    let mut expected = std::fs::File::open("tests/rapify/ace_main/expected.bin").unwrap();
+    let derapified = Config::derapify(&mut expected).unwrap();
+    assert_eq!(compare(&parsed, &derapified), Vec::new());
+}
@@ -0,0 +1,27 @@
+class CfgPatches {
+    class abe_main {
+        units[] = {};
+        weapons[] = {};
+        requiredVersion = 2.14;
+        requiredAddons[] = {"cba_main"};
+    };
+};
+class CfgVehicles {
+    class Car;
+    class Truck: Car {
+        maxSpeed = 100;
+        fuelCapacity = __EVAL(20 * 3);
+        transportSoldier = __EVAL(2 + 2);
+        displayName = "Truck";
+        class Turrets {
+            class MainTurret {};
+            class CommanderTurret {};
+        };
+    };
+    class Van: Car {
+        armor = 40;
+        textures[] = {"a.paa", {1, 2}};
+    };
+    class Bike;
+    delete Plane;
+};
@@ -0,0 +1,27 @@
+class CfgPatches {
+    class ABE_Main {
+        units[]={};
+        weapons[]={};
+        requiredVersion=2.1400001;
+        requiredAddons[]={"cba_main"};
+    };
+};
+class CfgVehicles {
+    class Car;
+    class LandVehicle;
+    class Truck: LandVehicle {
+        maxSpeed = 100.0;
+        fuelCapacity = 60;
+        transportSoldier = 5;
+        displayName = "Big Truck";
+        class Turrets {
+            class CommanderTurret {};
+            class MainTurret {};
+        };
+    };
+    class Van: Car {
+        textures[] += {"a.paa", {1, 3}};
+        fuel = 20;
+    };
+    class Bike {};
+};
@@ -0,0 +1,228 @@
+#![allow(clippy::unwrap_used)]
+
+use hemtt_common::config::ProjectConfig;
+use hemtt_config::{
+    analyze::lint_database,
+    database::{AddonConfig, Database, Found},
+    schema::Schema,
+};
+use hemtt_preprocessor::Processor;
+use hemtt_workspace::{
+    LayerType,
+    reporting::{Codes, WorkspaceFiles},
+};
+
+const ROOT: &str = "tests/database/";
+
+fn addon(name: &str) -> AddonConfig {
+    addon_in(ROOT, name)
+}
+
+fn addon_in(folder: &str, name: &str) -> AddonConfig {
+    let workspace = hemtt_workspace::Workspace::builder()
+        .physical(&std::path::PathBuf::from(folder), LayerType::Source)
+        .finish(
+            Some(ProjectConfig::test_project()),
+            false,
+            &hemtt_common::config::PDriveOption::Disallow,
+        )
+        .unwrap();
+    let source = workspace.join(format!("{name}.hpp")).unwrap();
+    let processed = Processor::run(&source).unwrap();
+    let report = hemtt_config::parse(None, &processed).unwrap();
+    AddonConfig::new(name, report.into_config(), Some(&processed))
+}
+
+fn database() -> Database {
+    // Provided out of order, sorted by requiredAddons
+    Database::new(vec![addon("main"), addon("extra"), addon("base")])
+}
+
+/// Lints run in parallel, sorted for a stable snapshot
+fn diagnostics(codes: &Codes, workspacefiles: &WorkspaceFiles) -> String {
+    let mut rendered = codes
+        .iter()
+        .map(|code| code.diagnostic().unwrap().to_string(workspacefiles))
+        .collect::<Vec<_>>();
+    rendered.sort();
+    rendered.join("\n").replace('\r', "")
+}
+
+fn value(database: &Database, query: &str) -> (String, String, String) {
+    let Some(Found::Property(property)) = database.query(query) else {
+        panic!("{query} is not a property");
+    };
+    (
+        property.value().to_string(),
+        property.owner().path(),
+        property.definition().to_string(),
+    )
+}
+
+#[test]
+fn load_order() {
+    let database = database();
+    assert_eq!(database.load_order(), ["base", "main", "extra"]);
+    assert_eq!(database.patch_addon("ABE_BASE"), Some("base"));
+    assert_eq!(database.patch_addon("A3_Data_F"), None);
+}
+
+#[test]
+fn overridden() {
+    let database = database();
+    assert_eq!(
+        value(&database, "CfgVehicles >> MyTruck >> maxSpeed"),
+        (
+            "130".to_string(),
+            "CfgVehicles >> MyTruck".to_string(),
+            "extra.hpp:9 (extra)".to_string()
+        )
+    );
+    let Some(Found::Class(truck)) = database.query("cfgvehicles >> mytruck") else {
+        panic!("MyTruck is not a class");
+    };
+    assert_eq!(truck.name(), "MyTruck");
+    assert_eq!(truck.definitions().len(), 2);
+    assert_eq!(truck.parent().unwrap().path(), "CfgVehicles >> Car");
+}
+
+#[test]
+fn inherited() {
+    let database = database();
+    assert_eq!(
+        value(&database, "CfgVehicles >> MyTruck >> displayName"),
+        (
+            "\"Car\"".to_string(),
+            "CfgVehicles >> Car".to_string(),
+            "base.hpp:9 (base)".to_string()
+        )
+    );
+    assert_eq!(
+        value(
+            &database,
+            r#"configFile >> "CfgVehicles" >> "MyTruck" >> "Turrets" >> "MainTurret" >> "gunner""#
+        )
+        .1,
+        "CfgVehicles >> Car >> Turrets >> MainTurret"
+    );
+    assert_eq!(
+        value(
+            &database,
+            "CfgVehicles >> MyTruck >> Turrets >> MainTurret >> weapons"
+        )
+        .0,
+        "{\"horn\", \"gun\"}"
+    );
+    let Some(Found::Class(truck)) = database.query("CfgVehicles >> MyTruck") else {
+        panic!("MyTruck is not a class");
+    };
+    let properties = truck
+        .properties()
+        .iter()
+        .map(|p| p.name().to_string())
+        .collect::<Vec<_>>();
+    assert_eq!(properties, ["maxSpeed", "displayName"]);
+}
+
+#[test]
+fn enclosing() {
+    let folder = format!("{ROOT}scope/");
+    let database = Database::new(vec![addon_in(&folder, "items")]);
+    let Some(Found::Class(info)) = database.query("CfgWeapons >> abe_item >> ItemInfo") else {
+        panic!("ItemInfo is not a class");
+    };
+    assert_eq!(
+        info.parent().unwrap().path(),
+        "CfgWeapons >> InventoryItem_Base_F"
+    );
+    assert_eq!(
+        value(
+            &database,
+            "CfgWeapons >> abe_item >> ItemInfo >> allowedSlots"
+        ),
+        (
+            "{901}".to_string(),
+            "CfgWeapons >> InventoryItem_Base_F".to_string(),
+            "items.hpp:14 (items)".to_string()
+        )
+    );
+}
+
+#[test]
+fn deleted() {
+    let database = database();
+    assert!(database.query("CfgVehicles >> Old").is_none());
+    assert!(
+        database
+            .query("CfgVehicles >> Missing >> maxSpeed")
+            .is_none()
+    );
+}
+
+#[test]
+fn required_addons() {
+    let folder = format!("{ROOT}required/");
+    let database = Database::new(vec![
+        addon_in(&folder, "alpha"),
+        addon_in(&folder, "beta"),
+        addon_in(&folder, "gamma"),
+    ]);
+    assert_eq!(database.requires("alpha"), ["beta"]);
+    assert_eq!(database.cycles(), [["alpha", "beta"]]);
+    assert!(database.dependencies("gamma").is_empty());
+
+    let workspacefiles = WorkspaceFiles::new();
+    let codes = lint_database(None, &database, Schema::builtin());
+    insta::assert_snapshot!(diagnostics(&codes, &workspacefiles));
+}
+
+#[test]
+fn baseline() {
+    let folder = format!("{ROOT}baseline/");
+    let vanilla = addon_in(&folder, "vanilla");
+    let database = Database::new(vec![
+        addon_in(&folder, "compat"),
+        addon_in(&folder, "vehicles"),
+        AddonConfig::baseline("vanilla", vanilla.config().clone()),
+    ]);
+    assert!(database.has_baseline());
+    assert_eq!(database.load_order(), ["vehicles", "compat"]);
+    assert_eq!(database.baseline_patch_addon("a3_data_f"), Some("vanilla"));
+    assert!(database.covers("vehicles"));
+    assert!(!database.covers("compat"));
+    assert_eq!(
+        value(&database, "CfgVehicles >> abe_car >> maxSpeed"),
+        (
+            "100".to_string(),
+            "CfgVehicles >> Car_F".to_string(),
+            "vanilla (baseline)".to_string()
+        )
+    );
+
+    let workspacefiles = WorkspaceFiles::new();
+    let codes = lint_database(None, &database, Schema::builtin());
+    insta::assert_snapshot!(diagnostics(&codes, &workspacefiles));
+}
+
+#[test]
+fn schema() {
+    let folder = format!("{ROOT}schema/");
+    let database = Database::new(vec![addon_in(&folder, "items")]);
+    let mut schema = (*Schema::builtin()).clone();
+    schema.extend(
+        Schema::from_toml(
+            r#"
+["CfgVehicles >> abe_banana"]
+closed = true
+
+["CfgVehicles >> abe_banana".properties]
+ripeness = { type = "number", min = 0, max = 1 }
+"#,
+        )
+        .unwrap(),
+    );
+
+    let workspacefiles = WorkspaceFiles::new();
+    let codes = lint_database(None, &database, std::sync::Arc::new(schema));
+    insta::assert_snapshot!(diagnostics(&codes, &workspacefiles));
+}
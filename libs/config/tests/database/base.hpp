@@ -0,0 +1,18 @@
+class CfgPatches {
+    class abe_base {
+        requiredAddons[] = {"A3_Data_F"};
+    };
+};
+class CfgVehicles {
+    class Car {
+        maxSpeed = 100;
+        displayName = "Car";
+        class Turrets {
+            class MainTurret {
+                gunner = "base";
+                weapons[] = {"horn"};
+            };
+        };
+    };
+    class Old {};
+};
@@ -0,0 +1,9 @@
+class CfgPatches {
+    class abe_compat {
+        requiredAddons[] = {"abe_vehicles", "cba_main"};
+    };
+};
+class CfgVehicles {
+    class CBA_Truck;
+    class abe_cba_truck: CBA_Truck {};
+};
@@ -0,0 +1,14 @@
+class CfgPatches {
+    class A3_Data_F {
+        requiredAddons[] = {};
+    };
+};
+class CfgVehicles {
+    class Car;
+    class Car_F: Car {
+        maxSpeed = 100;
+    };
+};
+class CfgMagazines {
+    class 20Rnd_762x51_Mag {};
+};
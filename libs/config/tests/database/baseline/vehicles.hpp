@@ -0,0 +1,16 @@
+class CfgPatches {
+    class abe_vehicles {
+        requiredAddons[] = {"A3_Data_F"};
+    };
+};
+class CfgVehicles {
+    class Car_F;
+    class Truck_01_bse_F;
+    class abe_car: Car_F {};
+    class abe_truck: Truck_01_bse_F {};
+};
+class CfgMagazineWells {
+    class abe_762x51 {
+        abe_vehicles[] = {"20Rnd_762x51_Mag", "20Rnd_762x15_Mag"};
+    };
+};
@@ -0,0 +1,11 @@
+class CfgPatches {
+    class abe_extra {
+        requiredAddons[] = {"abe_main"};
+    };
+};
+class CfgVehicles {
+    class Car;
+    class MyTruck: Car {
+        maxSpeed = 130;
+    };
+};
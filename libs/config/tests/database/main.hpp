@@ -0,0 +1,17 @@
+class CfgPatches {
+    class abe_main {
+        requiredAddons[] = {"abe_base"};
+    };
+};
+class CfgVehicles {
+    class Car;
+    class MyTruck: Car {
+        maxSpeed = 120;
+        class Turrets: Turrets {
+            class MainTurret: MainTurret {
+                weapons[] += {"gun"};
+            };
+        };
+    };
+    delete Old;
+};
@@ -0,0 +1,9 @@
+class CfgPatches {
+    class abe_alpha {
+        requiredAddons[] = {"abe_beta", "cba_main", "A3_Data_F", "abe_missing"};
+    };
+};
+class CfgVehicles {
+    class Truck;
+    class AlphaTruck: Truck {};
+};
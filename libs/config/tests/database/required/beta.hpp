@@ -0,0 +1,5 @@
+class CfgPatches {
+    class abe_beta {
+        requiredAddons[] = {"abe_alpha"};
+    };
+};
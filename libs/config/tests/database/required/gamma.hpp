@@ -0,0 +1,9 @@
+class CfgPatches {
+    class abe_gamma {
+        requiredAddons[] = {"A3_Soft_F"};
+    };
+};
+class CfgVehicles {
+    class Car;
+    class Truck: Car {};
+};
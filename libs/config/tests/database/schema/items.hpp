@@ -0,0 +1,36 @@
+class CfgPatches {
+    class abe_items {
+        requiredAddons[] = {};
+        requiredVersion = "2.18";
+    };
+};
+class CfgMagazines {
+    class abe_mag {
+        count = 30.5;
+        scope = 3;
+        dispalyName = "Banana Magazine";
+        mass = "8";
+        magazineGroup[] = {"abe_bananas", 1};
+    };
+};
+class CfgVehicles {
+    class Man;
+    class abe_unit: Man {
+        uniformClas = "abe_uniform";
+        camouflage = -1;
+    };
+    class abe_banana: abe_unit {
+        ripeness = 2;
+        colour = "yellow";
+    };
+};
+class CfgFunctions {
+    class abe {
+        class common {
+            class peel {
+                ext = ".sqs";
+                preInit = 2;
+            };
+        };
+    };
+};
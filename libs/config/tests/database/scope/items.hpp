@@ -0,0 +1,21 @@
+class CfgPatches {
+    class abe_items {
+        requiredAddons[] = {};
+        requiredVersion = "2.18";
+    };
+};
+class CfgWeapons {
+    class ItemCore {
+        class ItemInfo {
+            mass = 1;
+        };
+    };
+    class InventoryItem_Base_F {
+        allowedSlots[] = {901};
+    };
+    class abe_item: ItemCore {
+        class ItemInfo: InventoryItem_Base_F {
+            mass = 4;
+        };
+    };
+};
@@ -1,7 +1,13 @@
 #![allow(clippy::unwrap_used)]
 
 use hemtt_common::config::ProjectConfig;
-use hemtt_config::ConfigReport;
+use hemtt_config::{
+    ConfigReport,
+    analyze::lint_database,
+    database::{AddonConfig, Database, read_baseline},
+    rapify::Rapify,
+    schema::Schema,
+};
 use hemtt_preprocessor::Processor;
 use hemtt_workspace::{LayerType, reporting::WorkspaceFiles};
 
@@ -36,6 +42,25 @@ lint!(c12_math_could_be_unquoted);
 lint!(c13_config_this_call);
 lint!(c14_unused_external);
 
+/// Lints that check the addons of the project together, each addon is a file in the folder
+macro_rules! database_lint {
+    ($dir:ident, $code:literal) => {
+        paste::paste! {
+            #[test]
+            fn [<config_error_ $dir>]() {
+                insta::assert_snapshot!(database_lint(stringify!($dir), $code));
+            }
+        }
+    };
+}
+
+database_lint!(c15_required_addons_unknown, "L-C15");
+database_lint!(c16_required_addons_cycle, "L-C16");
+database_lint!(c17_inherit_not_required, "L-C17");
+database_lint!(c18_external_unknown, "L-C18");
+database_lint!(c19_magwell_unknown_magazine, "L-C19");
+database_lint!(c20_property_schema, "L-C20");
+
 fn lint(file: &str) -> (String, ConfigReport) {
     let folder = std::path::PathBuf::from(ROOT);
     let workspace = hemtt_workspace::Workspace::builder()
@@ -73,6 +98,73 @@ fn lint(file: &str) -> (String, ConfigReport) {
     }
 }
 
+fn database_lint(dir: &str, code: &str) -> String {
+    let folder = std::path::PathBuf::from(ROOT).join(dir);
+    let config_path_full = std::path::PathBuf::from(ROOT).join("project_tests.toml");
+    let test_config = ProjectConfig::from_file(&config_path_full).unwrap();
+    let mut addons = addons(&folder, Some(&test_config));
+    addons.extend(baseline(dir, &folder.join("baseline")));
+    let database = Database::new(addons);
+    let workspacefiles = WorkspaceFiles::new();
+    // Lints run in parallel, sorted for a stable snapshot
+    let schema = Schema::with_folder(&folder.join("schemas")).unwrap();
+    let mut rendered = lint_database(Some(&test_config), &database, std::sync::Arc::new(schema))
+        .iter()
+        .filter(|c| c.ident() == code)
+        .map(|c| c.diagnostic().unwrap().to_string(&workspacefiles))
+        .collect::<Vec<_>>();
+    rendered.sort();
+    rendered.join("\n").replace('\r', "")
+}
+
+/// Every `.hpp` file in the folder as an addon, named after the file
+fn addons(folder: &std::path::Path, project: Option<&ProjectConfig>) -> Vec<AddonConfig> {
+    if !folder.is_dir() {
+        return Vec::new();
+    }
+    let workspace = hemtt_workspace::Workspace::builder()
+        .physical(&folder.to_path_buf(), LayerType::Source)
+        .finish(
+            Some(ProjectConfig::test_project()),
+            false,
+            &hemtt_common::config::PDriveOption::Disallow,
+        )
+        .unwrap();
+    let mut names = std::fs::read_dir(folder)
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "hpp"))
+        .map(|path| path.file_stem().unwrap().to_str().unwrap().to_string())
+        .collect::<Vec<_>>();
+    names.sort();
+    names
+        .iter()
+        .map(|name| {
+            let source = workspace.join(format!("{name}.hpp")).unwrap();
+            let processed = Processor::run(&source).unwrap();
+            let report = hemtt_config::parse(project, &processed).unwrap();
+            AddonConfig::new(name, report.into_config(), Some(&processed))
+        })
+        .collect()
+}
+
+/// The addons in the `baseline` folder, rapified and read back as an imported baseline
+fn baseline(dir: &str, folder: &std::path::Path) -> Vec<AddonConfig> {
+    let imported = std::env::temp_dir().join(format!("hemtt-config-lints-{dir}"));
+    if imported.exists() {
+        std::fs::remove_dir_all(&imported).unwrap();
+    }
+    std::fs::create_dir_all(&imported).unwrap();
+    for addon in addons(folder, None) {
+        let mut file =
+            std::fs::File::create(imported.join(format!("{}.bin", addon.addon()))).unwrap();
+        addon.config().rapify(&mut file, 0).unwrap();
+    }
+    let configs = read_baseline(&imported).unwrap();
+    std::fs::remove_dir_all(&imported).unwrap();
+    configs
+}
+
 #[test]
 /// Test `C09_gwell_missing_magazine` - maChecking results from the report (will not create errors directly)
 fn test_c09_magwell_missing_magazine() {
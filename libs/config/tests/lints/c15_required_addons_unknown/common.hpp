@@ -0,0 +1,5 @@
+class CfgPatches {
+    class abe_common {
+        requiredAddons[] = {"cba_common"};
+    };
+};
@@ -0,0 +1,12 @@
+class CfgPatches {
+    class abe_main {
+        requiredAddons[] = {
+            "abe_common",
+            "A3_Data_F",
+            "cba_main",
+            "ACE_Common",
+            "abe_commn",
+            "rhs_main"
+        };
+    };
+};
@@ -0,0 +1,5 @@
+class CfgPatches {
+    class abe_extra {
+        requiredAddons[] = {"abe_main"};
+    };
+};
@@ -0,0 +1,5 @@
+class CfgPatches {
+    class abe_main {
+        requiredAddons[] = {"abe_common"};
+    };
+};
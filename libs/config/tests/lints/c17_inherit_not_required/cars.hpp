@@ -0,0 +1,9 @@
+class CfgPatches {
+    class abe_cars {
+        requiredAddons[] = {"abe_common"};
+    };
+};
+class CfgVehicles {
+    class abe_car_base;
+    class abe_car: abe_car_base {};
+};
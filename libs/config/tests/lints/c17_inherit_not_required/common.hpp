@@ -0,0 +1,10 @@
+class CfgPatches {
+    class abe_common {
+        requiredAddons[] = {"A3_Soft_F"};
+    };
+};
+class CfgVehicles {
+    class Car;
+    class abe_truck_base: Car {};
+    class abe_car_base: Car {};
+};
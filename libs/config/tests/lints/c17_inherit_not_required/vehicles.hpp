@@ -0,0 +1,9 @@
+class CfgPatches {
+    class abe_vehicles {
+        requiredAddons[] = {"A3_Soft_F"};
+    };
+};
+class CfgVehicles {
+    class abe_truck_base;
+    class abe_truck: abe_truck_base {};
+};
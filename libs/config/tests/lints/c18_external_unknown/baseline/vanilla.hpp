@@ -0,0 +1,11 @@
+class CfgPatches {
+    class A3_Soft_F {
+        requiredAddons[] = {};
+    };
+};
+class CfgVehicles {
+    class Car;
+    class Car_F: Car {};
+    class Truck_F: Car_F {};
+    class Truck_01_base_F: Truck_F {};
+};
@@ -0,0 +1,11 @@
+class CfgPatches {
+    class abe_vehicles {
+        requiredAddons[] = {"A3_Soft_F"};
+    };
+};
+class CfgVehicles {
+    class Car_F;
+    class Truck_01_bse_F;
+    class abe_car: Car_F {};
+    class abe_truck: Truck_01_bse_F {};
+};
@@ -0,0 +1,9 @@
+class CfgPatches {
+    class A3_Weapons_F {
+        requiredAddons[] = {};
+    };
+};
+class CfgMagazines {
+    class CA_Magazine;
+    class 20Rnd_762x51_Mag: CA_Magazine {};
+};
@@ -0,0 +1,14 @@
+class CfgPatches {
+    class abe_main {
+        requiredAddons[] = {"A3_Weapons_F"};
+    };
+};
+class CfgMagazineWells {
+    class CBA_762x51_STANAG {
+        abe_main[] = {
+            "20Rnd_762x51_Mag",
+            "20Rnd_762x15_Mag",
+            "abe_762x51_mag"
+        };
+    };
+};
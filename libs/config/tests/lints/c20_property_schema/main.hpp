@@ -0,0 +1,27 @@
+class CfgPatches {
+    class abe_main {
+        requiredAddons[] = {};
+    };
+};
+class CfgMagazines {
+    class abe_mag {
+        count = 30.5;
+        scope = 3;
+        dispalyName = "Banana Magazine";
+        magazineGroup[] = {"abe_bananas", 1};
+    };
+    class abe_mag_short: abe_mag {
+        count = 10;
+        scope = 2;
+        displayName = "Short Banana Magazine";
+    };
+};
+class CfgVehicles {
+    class abe_banana_base {
+        ripeness = 0.5;
+    };
+    class abe_banana: abe_banana_base {
+        ripeness = 2;
+        peel = 1;
+    };
+};
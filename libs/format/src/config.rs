@@ -0,0 +1,189 @@
+//! Normalizations that only apply to configs
+
+/// Normalize a single line of config code, without any trailing comment
+///
+/// Returns the lines to replace it with, and how far each is indented past the original
+pub fn normalize(code: &str, width: usize) -> Vec<(usize, String)> {
+    if let Some(class) = class(code) {
+        return vec![(0, class)];
+    }
+    let Some((name, array, op, value)) = assignment(code) else {
+        return vec![(0, code.to_string())];
+    };
+    let name = format!("{name}{} {op}", if array { "[]" } else { "" });
+    let items = if array {
+        value
+            .strip_suffix(';')
+            .and_then(|value| self::array(value.trim_end()))
+    } else {
+        None
+    };
+    let Some(items) = items else {
+        return vec![(0, format!("{name} {value}"))];
+    };
+    let line = format!("{name} {{{}}};", items.join(", "));
+    if line.chars().count() <= width || items.len() < 2 {
+        return vec![(0, line)];
+    }
+    let mut lines = vec![(0, format!("{name} {{"))];
+    let last = items.len() - 1;
+    for (i, item) in items.into_iter().enumerate() {
+        lines.push((1, if i == last { item } else { format!("{item},") }));
+    }
+    lines.push((0, "};".to_string()));
+    lines
+}
+
+/// `class Name: Parent {`, with a single space before the brace and after the colon
+fn class(code: &str) -> Option<String> {
+    let body = code.strip_prefix("class")?;
+    if !body.starts_with(char::is_whitespace) || body.contains('"') {
+        return None;
+    }
+    let body = body.trim_start();
+    let mut out = String::from("class ");
+    let mut depth = 0usize;
+    let mut space = false;
+    for (i, c) in body.char_indices() {
+        if depth == 0 {
+            match c {
+                c if c.is_whitespace() => {
+                    space = true;
+                    continue;
+                }
+                ':' => {
+                    out.push_str(": ");
+                    space = false;
+                    continue;
+                }
+                '{' => {
+                    out.push_str(" {");
+                    out.push_str(body[i + 1..].trim_end());
+                    return Some(out);
+                }
+                _ => {}
+            }
+        }
+        match c {
+            '(' => depth += 1,
+            ')' => depth = depth.saturating_sub(1),
+            _ => {}
+        }
+        if space && !out.ends_with(' ') && c != ';' {
+            out.push(' ');
+        }
+        space = false;
+        out.push(c);
+    }
+    Some(out)
+}
+
+/// `name[] = value`, split into its parts
+fn assignment(code: &str) -> Option<(&str, bool, &str, &str)> {
+    let end = code.find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))?;
+    if end == 0 {
+        return None;
+    }
+    let (name, rest) = code.split_at(end);
+    let rest = rest.trim_start();
+    let (array, rest) = rest
+        .strip_prefix("[]")
+        .map_or((false, rest), |rest| (true, rest.trim_start()));
+    let op = if rest.starts_with("+=") {
+        "+="
+    } else if rest.starts_with('=') {
+        "="
+    } else {
+        return None;
+    };
+    Some((name, array, op, rest[op.len()..].trim_start()))
+}
+
+/// The items of an array written on a single line, with nested arrays normalized
+fn array(value: &str) -> Option<Vec<String>> {
+    let inner = value.strip_prefix('{')?.strip_suffix('}')?;
+    let mut items = Vec::new();
+    let mut depth = 0usize;
+    let mut quoted = false;
+    let mut start = 0;
+    for (i, c) in inner.char_indices() {
+        match c {
+            '"' => quoted = !quoted,
+            _ if quoted => {}
+            '{' | '(' | '[' => depth += 1,
+            '}' | ')' | ']' => depth = depth.checked_sub(1)?,
+            ',' if depth == 0 => {
+                items.push(&inner[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    if quoted || depth != 0 {
+        return None;
+    }
+    items.push(&inner[start..]);
+    if items.len() == 1 && items[0].trim().is_empty() {
+        return Some(Vec::new());
+    }
+    items
+        .into_iter()
+        .map(|item| {
+            let item = item.trim();
+            if item.is_empty() {
+                None
+            } else if item.starts_with('{') {
+                array(item).map(|items| format!("{{{}}}", items.join(", ")))
+            } else {
+                Some(item.to_string())
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn classes() {
+        assert_eq!(class("class A:B{"), Some("class A: B {".to_string()));
+        assert_eq!(
+            class("class A  :  B {};"),
+            Some("class A: B {};".to_string())
+        );
+        assert_eq!(class("class A;"), Some("class A;".to_string()));
+        assert_eq!(
+            class("class DOUBLES(PREFIX, x) :B{"),
+            Some("class DOUBLES(PREFIX, x): B {".to_string())
+        );
+        assert_eq!(class("classes = 1;"), None);
+    }
+
+    #[test]
+    fn assignments() {
+        assert_eq!(normalize("a=1;", 80), vec![(0, "a = 1;".to_string())]);
+        assert_eq!(
+            normalize("a [] += {1,2 , {3,4}};", 80),
+            vec![(0, "a[] += {1, 2, {3, 4}};".to_string())]
+        );
+        assert_eq!(
+            normalize("a[]={\"x,y\",\"z\"};", 80),
+            vec![(0, "a[] = {\"x,y\", \"z\"};".to_string())]
+        );
+        assert_eq!(normalize("a[]={};", 80), vec![(0, "a[] = {};".to_string())]);
+        assert_eq!(
+            normalize("a[]={1,,2};", 80),
+            vec![(0, "a[] = {1,,2};".to_string())]
+        );
+        assert_eq!(
+            normalize("a[] = {\"one\", \"two\"};", 10),
+            vec![
+                (0, "a[] = {".to_string()),
+                (1, "\"one\",".to_string()),
+                (1, "\"two\"".to_string()),
+                (0, "};".to_string()),
+            ]
+        );
+    }
+}
@@ -0,0 +1,362 @@
+//! Formatting of configs and SQF
+//!
+//! The formatter works on the source as written, before preprocessing, so comments, macros, and
+//! preprocessor directives are kept as they are. Only whitespace outside of strings and comments
+//! is changed.
+
+use std::path::Path;
+
+mod config;
+mod scan;
+
+use scan::{Line, State};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// The language of a file to format
+pub enum Language {
+    /// `config.cpp`, `.hpp`, and `.ext` files
+    Config,
+    /// `.sqf` files
+    Sqf,
+}
+
+impl Language {
+    #[must_use]
+    /// The language of a file, if it can be formatted
+    pub fn from_path(path: &Path) -> Option<Self> {
+        let extension = path.extension()?.to_str()?.to_lowercase();
+        match extension.as_str() {
+            "cpp" | "hpp" | "ext" => Some(Self::Config),
+            "sqf" => Some(Self::Sqf),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// Options for the formatter
+pub struct Options {
+    indent: String,
+    width: usize,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            indent: "    ".to_string(),
+            width: 100,
+        }
+    }
+}
+
+impl Options {
+    #[must_use]
+    /// Indent with the given number of spaces, or with tabs
+    pub fn new(tab_size: usize, spaces: bool, width: usize) -> Self {
+        Self {
+            indent: if spaces {
+                " ".repeat(tab_size)
+            } else {
+                "\t".to_string()
+            },
+            width,
+        }
+    }
+
+    #[must_use]
+    /// The string used for one level of indentation
+    pub fn indent(&self) -> &str {
+        &self.indent
+    }
+
+    #[must_use]
+    /// The width that config arrays are wrapped at
+    pub const fn width(&self) -> usize {
+        self.width
+    }
+
+    fn indent_width(&self, level: usize) -> usize {
+        level
+            * self
+                .indent
+                .chars()
+                .map(|c| if c == '\t' { 4 } else { 1 })
+                .sum::<usize>()
+    }
+}
+
+#[must_use]
+/// Format the source of a file
+pub fn format(source: &str, language: Language, options: &Options) -> String {
+    let (bom, source) = source
+        .strip_prefix('\u{feff}')
+        .map_or(("", source), |source| ("\u{feff}", source));
+    let newline = if source.contains("\r\n") {
+        "\r\n"
+    } else {
+        "\n"
+    };
+    let mut output = Output::default();
+    let mut indent = Indent::default();
+    // The indentation at each `#if`, and at the end of its first branch
+    let mut conditionals: Vec<(Indent, Option<Indent>)> = Vec::new();
+    for line in scan::lines(source, language) {
+        if line.directive {
+            match directive(line.text) {
+                Some("if" | "ifdef" | "ifndef") => conditionals.push((indent.clone(), None)),
+                Some("else" | "elif") => {
+                    if let Some((start, first)) = conditionals.last_mut() {
+                        if first.is_none() {
+                            *first = Some(indent.clone());
+                        }
+                        indent = start.clone();
+                    }
+                }
+                Some("endif") => {
+                    if let Some((_, Some(first))) = conditionals.pop() {
+                        indent = first;
+                    }
+                }
+                _ => {}
+            }
+            let trimmed = line.text.trim_end();
+            // Trailing whitespace after a `\` would otherwise become a continuation
+            let text = if trimmed.ends_with('\\') && !line.text.ends_with('\\') {
+                line.text
+            } else {
+                trimmed
+            };
+            output.push(Item::Verbatim(text.to_string()));
+            continue;
+        }
+        let level = indent.line(&line);
+        if line.starts_in != State::Code {
+            output.push(Item::Verbatim(if line.ends_in == State::Code {
+                line.text.trim_end().to_string()
+            } else {
+                line.text.to_string()
+            }));
+            continue;
+        }
+        if line.is_blank() {
+            output.push(Item::Blank);
+            continue;
+        }
+        let text = if matches!(line.ends_in, State::Str(_)) {
+            line.text.trim_start()
+        } else {
+            line.text.trim()
+        };
+        let offset = line.text.len() - line.text.trim_start().len();
+        let (code, comment) = line
+            .comment_at
+            .map_or((text, ""), |at| text.split_at(at - offset));
+        let normalized = if language == Language::Config
+            && line.ends_in == State::Code
+            && comment.is_empty()
+        {
+            config::normalize(
+                code,
+                options.width.saturating_sub(options.indent_width(level)),
+            )
+        } else if language == Language::Config && line.ends_in == State::Code {
+            let trimmed = code.trim_end();
+            config::normalize(trimmed, usize::MAX)
+                .into_iter()
+                .map(|(extra, text)| (extra, format!("{text}{}{comment}", &code[trimmed.len()..])))
+                .collect()
+        } else {
+            vec![(0, text.to_string())]
+        };
+        let joinable = comment.is_empty() && line.ends_in == State::Code;
+        for (extra, text) in normalized {
+            output.push(Item::Code {
+                level: level + extra,
+                text,
+                joinable,
+            });
+        }
+    }
+    let mut formatted = String::from(bom);
+    for item in output.items {
+        match item {
+            Item::Code { level, text, .. } => {
+                formatted.push_str(&options.indent.repeat(level));
+                formatted.push_str(&text);
+            }
+            Item::Verbatim(text) => formatted.push_str(&text),
+            Item::Blank => {}
+        }
+        formatted.push_str(newline);
+    }
+    formatted
+}
+
+/// The name of a preprocessor directive
+fn directive(text: &str) -> Option<&str> {
+    text.trim_start()
+        .strip_prefix('#')?
+        .trim_start()
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .next()
+}
+
+#[derive(Debug, Clone, Default)]
+/// Indentation, one level for each line that leaves brackets open
+struct Indent {
+    depth: usize,
+    /// The depth at the start of each level
+    levels: Vec<usize>,
+}
+
+impl Indent {
+    /// Process the brackets on a line, returning the level it is indented at
+    fn line(&mut self, line: &Line) -> usize {
+        let leading = line.leading_closers();
+        for _ in 0..leading {
+            self.close();
+        }
+        let level = self.levels.len();
+        let mut lowest = self.depth;
+        for open in line.brackets.iter().skip(leading) {
+            if *open {
+                self.depth += 1;
+            } else {
+                self.close();
+                lowest = lowest.min(self.depth);
+            }
+        }
+        if self.depth > lowest {
+            self.levels.push(lowest);
+        }
+        level
+    }
+
+    fn close(&mut self) {
+        self.depth = self.depth.saturating_sub(1);
+        while self.levels.last().is_some_and(|start| *start >= self.depth) {
+            self.levels.pop();
+        }
+    }
+}
+
+#[derive(Debug)]
+enum Item {
+    Code {
+        level: usize,
+        text: String,
+        /// Nothing after the code would stop a following `{` from being moved onto the line
+        joinable: bool,
+    },
+    Verbatim(String),
+    Blank,
+}
+
+#[derive(Debug, Default)]
+struct Output {
+    items: Vec<Item>,
+    blank: bool,
+}
+
+impl Output {
+    fn push(&mut self, item: Item) {
+        let Item::Code { text, .. } = &item else {
+            if matches!(item, Item::Blank) {
+                self.blank = true;
+            } else {
+                self.flush_blank(false);
+                self.items.push(item);
+            }
+            return;
+        };
+        // Opening braces on their own line are moved onto the previous line
+        if let Some(Item::Code { text: previous, .. }) = self.items.last_mut().filter(|previous| {
+            text == "{"
+                && matches!(previous, Item::Code { text, joinable: true, .. }
+                        if !text.ends_with(['{', '}', '[', '(', ';', ',', '/']))
+        }) {
+            previous.push_str(" {");
+            self.blank = false;
+            return;
+        }
+        self.flush_blank(text.starts_with('}'));
+        self.items.push(item);
+    }
+
+    /// Keep a single blank line, unless it would follow an opening brace or the start of the file
+    fn flush_blank(&mut self, closing: bool) {
+        if std::mem::take(&mut self.blank)
+            && !closing
+            && self.items.last().is_some_and(|item| match item {
+                Item::Code { text, .. } => !text.ends_with('{'),
+                Item::Verbatim(_) => true,
+                Item::Blank => false,
+            })
+        {
+            self.items.push(Item::Blank);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(source: &str) -> String {
+        format(source, Language::Config, &Options::default())
+    }
+
+    fn sqf(source: &str) -> String {
+        format(source, Language::Sqf, &Options::default())
+    }
+
+    #[test]
+    fn indent() {
+        assert_eq!(
+            config("class A{\nclass B\n{\nx=1;\n};\n};"),
+            "class A {\n    class B {\n        x = 1;\n    };\n};\n"
+        );
+        assert_eq!(
+            sqf("[_a, {\nhint 'x';\n}] call f;\nif (a) then {\n} else {\nb;\n};\n"),
+            "[_a, {\n    hint 'x';\n}] call f;\nif (a) then {\n} else {\n    b;\n};\n"
+        );
+    }
+
+    #[test]
+    fn blank_lines() {
+        assert_eq!(
+            sqf("\n\na;\n\n\n\nb;\nif (a) then {\n\n  c;\n\n};\n\n\n"),
+            "a;\n\nb;\nif (a) then {\n    c;\n};\n"
+        );
+    }
+
+    #[test]
+    fn preserved() {
+        let source = "#define X(a) class a {\\\n    x = 1;  \\\n}\n/*\n  {\n*/\nclass A {\n    text = \"  {  \n  \";\n  // {\n};\n";
+        assert_eq!(
+            config(source),
+            "#define X(a) class a {\\\n    x = 1;  \\\n}\n/*\n  {\n*/\nclass A {\n    text = \"  {  \n  \";\n    // {\n};\n"
+        );
+    }
+
+    #[test]
+    fn conditionals() {
+        assert_eq!(
+            config("#ifdef A\nclass B: C {\n#else\nclass B: D {\n#endif\nx = 1;\n};\n"),
+            "#ifdef A\nclass B: C {\n#else\nclass B: D {\n#endif\n    x = 1;\n};\n"
+        );
+    }
+
+    #[test]
+    fn comments() {
+        assert_eq!(
+            config("class A // the class\n{\nx=1;   // one\n};"),
+            "class A // the class\n{\n    x = 1;   // one\n};\n"
+        );
+    }
+
+    #[test]
+    fn line_endings() {
+        assert_eq!(sqf("\u{feff}a;\r\n  b;\r\n"), "\u{feff}a;\r\nb;\r\n");
+    }
+}
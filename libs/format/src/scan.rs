@@ -0,0 +1,151 @@
+use crate::Language;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// What the scanner is inside of
+pub enum State {
+    Code,
+    Comment,
+    Str(char),
+}
+
+#[derive(Debug)]
+/// A physical line of the source, with what is needed to format it
+pub struct Line<'a> {
+    /// The line, without the line ending
+    pub text: &'a str,
+    pub starts_in: State,
+    pub ends_in: State,
+    /// The line is a preprocessor directive, or continues one
+    pub directive: bool,
+    /// The byte offset of the first comment outside of a string
+    pub comment_at: Option<usize>,
+    /// Brackets outside of strings and comments, `true` for openers
+    pub brackets: Vec<bool>,
+}
+
+impl Line<'_> {
+    /// The number of closing brackets before anything else on the line
+    pub fn leading_closers(&self) -> usize {
+        if self.starts_in != State::Code || self.directive {
+            return 0;
+        }
+        self.text
+            .chars()
+            .take_while(|c| c.is_whitespace() || is_closer(*c))
+            .filter(|c| is_closer(*c))
+            .count()
+    }
+
+    /// The line is a blank line of code
+    pub fn is_blank(&self) -> bool {
+        self.starts_in == State::Code && !self.directive && self.text.trim().is_empty()
+    }
+}
+
+const fn is_closer(c: char) -> bool {
+    matches!(c, '}' | ']' | ')')
+}
+
+/// Split the source into lines, tracking strings, comments, and directives across them
+pub fn lines(source: &str, language: Language) -> Vec<Line<'_>> {
+    let mut state = State::Code;
+    let mut continues = false;
+    let mut lines = Vec::new();
+    for text in source.lines() {
+        let starts_in = state;
+        let directive =
+            continues || (starts_in == State::Code && text.trim_start().starts_with('#'));
+        let mut comment_at = None;
+        let mut brackets = Vec::new();
+        let mut chars = text.char_indices().peekable();
+        while let Some((i, c)) = chars.next() {
+            match state {
+                State::Comment => {
+                    if c == '*' && chars.peek().is_some_and(|(_, next)| *next == '/') {
+                        chars.next();
+                        state = State::Code;
+                    }
+                }
+                State::Str(quote) => {
+                    if c == quote {
+                        // Quotes are escaped by doubling them
+                        if chars.peek().is_some_and(|(_, next)| *next == quote) {
+                            chars.next();
+                        } else {
+                            state = State::Code;
+                        }
+                    }
+                }
+                State::Code => match c {
+                    '/' if chars.peek().is_some_and(|(_, next)| *next == '/') => {
+                        comment_at.get_or_insert(i);
+                        break;
+                    }
+                    '/' if chars.peek().is_some_and(|(_, next)| *next == '*') => {
+                        chars.next();
+                        comment_at.get_or_insert(i);
+                        state = State::Comment;
+                    }
+                    '"' => state = State::Str(c),
+                    '\'' if language == Language::Sqf => state = State::Str(c),
+                    '{' | '[' | '(' => brackets.push(true),
+                    '}' | ']' | ')' => brackets.push(false),
+                    _ => {}
+                },
+            }
+        }
+        continues = directive && text.ends_with('\\');
+        if directive && !continues && matches!(state, State::Str(_)) {
+            // Strings do not continue past the end of a directive
+            state = State::Code;
+        }
+        lines.push(Line {
+            text,
+            starts_in,
+            ends_in: state,
+            directive,
+            comment_at,
+            brackets: if directive { Vec::new() } else { brackets },
+        });
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn state() {
+        let lines = lines(
+            "a = \"{ // \"\"\"; // {\n/* {\n} */ b[] = {\n#define X {\\\n}\nc = 'x",
+            Language::Config,
+        );
+        assert_eq!(lines[0].brackets, Vec::<bool>::new());
+        assert_eq!(lines[0].comment_at, Some(15));
+        assert_eq!(lines[1].ends_in, State::Comment);
+        assert_eq!(lines[2].starts_in, State::Comment);
+        assert_eq!(lines[2].brackets, vec![true, false, true]);
+        assert!(lines[3].directive);
+        assert!(lines[4].directive);
+        assert!(lines[4].brackets.is_empty());
+        assert_eq!(lines[5].ends_in, State::Code);
+    }
+
+    #[test]
+    fn sqf_strings() {
+        let lines = lines("_a = 'it''s {';\n_b = \"multi\nline {\";", Language::Sqf);
+        assert!(lines[0].brackets.is_empty());
+        assert_eq!(lines[1].ends_in, State::Str('"'));
+        assert_eq!(lines[2].starts_in, State::Str('"'));
+        assert!(lines[2].brackets.is_empty());
+    }
+
+    #[test]
+    fn leading_closers() {
+        let lines = lines("}];\n}, {\n  a }", Language::Sqf);
+        assert_eq!(lines[0].leading_closers(), 2);
+        assert_eq!(lines[1].leading_closers(), 1);
+        assert_eq!(lines[2].leading_closers(), 0);
+    }
+}
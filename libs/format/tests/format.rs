@@ -0,0 +1,33 @@
+use hemtt_format::{Language, Options, format};
+
+macro_rules! format_test {
+    ($name:ident, $file:literal, $language:expr) => {
+        #[test]
+        fn $name() {
+            let source = std::fs::read_to_string(concat!("tests/format/", $file))
+                .expect("fixture should exist");
+            let formatted = format(&source, $language, &Options::default());
+            assert_eq!(
+                formatted,
+                format(&formatted, $language, &Options::default()),
+                "formatting should be stable"
+            );
+            insta::assert_snapshot!(formatted);
+        }
+    };
+}
+
+format_test!(config, "config.hpp", Language::Config);
+format_test!(function, "function.sqf", Language::Sqf);
+
+#[test]
+fn tabs() {
+    assert_eq!(
+        format(
+            "class A {\nx = 1;\n};\n",
+            Language::Config,
+            &Options::new(4, false, 100)
+        ),
+        "class A {\n\tx = 1;\n};\n"
+    );
+}
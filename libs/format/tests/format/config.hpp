@@ -0,0 +1,42 @@
+#include "script_component.hpp"
+
+class CfgPatches
+{
+  class ADDON
+  {
+    name=COMPONENT_NAME;
+    units[]={};
+    weapons[] = {  };
+    requiredVersion = REQUIRED_VERSION;
+    requiredAddons[]={"cba_main","ace_common","ace_interaction","ace_interact_menu"};   // every dependency
+    author = "ABE";
+    VERSION_CONFIG;
+  };
+};
+
+
+class CfgVehicles {
+    class Car;
+    class abe_truck :Car{
+        /*
+           A comment, kept as is {
+        */
+        displayName = CSTRING(Truck);
+        hiddenSelectionsTextures[] = {QPATHTOF(data\truck_co.paa), QPATHTOF(data\truck_logo_co.paa)};
+        class UserActions {
+
+#ifdef ABE_DEBUG
+            class Debug {
+#else
+            class Release {
+#endif
+                condition = "alive this && {speed this < 1}";
+            };
+
+        };
+    };
+};
+
+#define TURRET(name) class name: MainTurret { \
+    gunnerName = QUOTE(name); \
+}
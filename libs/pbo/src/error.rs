@@ -14,6 +14,9 @@ pub enum Error {
     #[error("File is too large for PBO format")]
     /// File is too large for PBO format
     FileTooLarge,
+    #[error("Failed to decompress file: {0}")]
+    /// A compressed file could not be decompressed
+    Decompression(String),
     #[error("HEMTT does not support signing PBOs with no files")]
     /// HEMTT does not support signing PBOs with no files
     NoFiles,
@@ -1,33 +1,58 @@
 //! File abstraction for reading from a PBO file.
 
-use std::io::Read;
+use std::io::{Cursor, Read};
 
 use crate::model::Header;
 
 /// A file in a PBO
 pub struct File<'a, I: Read> {
-    size: u32,
-    read: u32,
-    input: &'a mut I,
+    inner: Inner<'a, I>,
+}
+
+enum Inner<'a, I: Read> {
+    /// Read directly from the PBO
+    Stored {
+        size: u32,
+        read: u32,
+        input: &'a mut I,
+    },
+    /// Decompressed into memory
+    Decompressed(Cursor<Vec<u8>>),
 }
 
 impl<'a, I: Read> File<'a, I> {
     /// Create a new file from a header and a reader
+    ///
+    /// The file's data is read as it is stored, compressed files are not decompressed
     pub fn new(header: &Header, input: &'a mut I) -> Self {
         Self {
-            size: header.size(),
-            read: 0,
-            input,
+            inner: Inner::Stored {
+                size: header.size(),
+                read: 0,
+                input,
+            },
+        }
+    }
+
+    /// Create a new file from data that has been decompressed
+    pub(crate) fn decompressed(data: Vec<u8>) -> Self {
+        Self {
+            inner: Inner::Decompressed(Cursor::new(data)),
         }
     }
 }
 
 impl<I: Read> Read for File<'_, I> {
     fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
-        // read up to the size of the file
-        let size = std::cmp::min(self.size - self.read, buf.len() as u32);
-        let read = self.input.read(&mut buf[..size as usize])?;
-        self.read += read as u32;
-        Ok(read)
+        match &mut self.inner {
+            Inner::Stored { size, read, input } => {
+                // read up to the size of the file
+                let size = std::cmp::min(*size - *read, buf.len() as u32);
+                let count = input.read(&mut buf[..size as usize])?;
+                *read += count as u32;
+                Ok(count)
+            }
+            Inner::Decompressed(cursor) => cursor.read(buf),
+        }
     }
 }
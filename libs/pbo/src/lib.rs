@@ -13,6 +13,7 @@ use std::io::{Read, Write};
 
 mod error;
 pub mod file;
+pub mod lzss;
 mod model;
 mod read;
 pub mod tests;
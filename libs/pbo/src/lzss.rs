@@ -0,0 +1,215 @@
+//! BI's LZSS compression, used by `Cprs` entries
+//!
+//! The data is a series of packets, each a flag byte followed by up to 8 items. A set bit is a
+//! byte copied as is, a clear bit is a 2 byte pointer to up to 18 bytes earlier in the output.
+//! A 32 bit sum of the uncompressed bytes follows the packets.
+
+use std::collections::HashMap;
+
+use crate::error::Error;
+
+const WINDOW: usize = 4095;
+const MIN_MATCH: usize = 3;
+const MAX_MATCH: usize = 18;
+/// How many earlier positions are checked for each match
+const MAX_CANDIDATES: usize = 256;
+
+/// Decompress data, `original` is the size of the uncompressed data
+///
+/// # Errors
+/// [`Error::Decompression`] if the data ends early, or the checksum does not match
+pub fn decompress(input: &[u8], original: usize) -> Result<Vec<u8>, Error> {
+    let mut output = Vec::with_capacity(original);
+    let mut read = 0;
+    let mut next = || {
+        let byte = input
+            .get(read)
+            .copied()
+            .ok_or_else(|| Error::Decompression("unexpected end of data".to_string()));
+        read += 1;
+        byte
+    };
+    while output.len() < original {
+        let flags = next()?;
+        for bit in 0..8 {
+            if output.len() >= original {
+                break;
+            }
+            if flags & (1 << bit) != 0 {
+                output.push(next()?);
+                continue;
+            }
+            let low = usize::from(next()?);
+            let high = usize::from(next()?);
+            let offset = low | ((high & 0xF0) << 4);
+            if offset == 0 {
+                return Err(Error::Decompression("invalid pointer".to_string()));
+            }
+            let length = ((high & 0x0F) + MIN_MATCH).min(original - output.len());
+            let start = output.len().saturating_sub(offset);
+            // Pointers before the start of the data are spaces
+            let spaces = offset.saturating_sub(output.len()).min(length);
+            output.extend(std::iter::repeat_n(b' ', spaces));
+            for index in start..start + length - spaces {
+                output.push(output[index]);
+            }
+        }
+    }
+    let stored = input
+        .get(read..read + 4)
+        .ok_or_else(|| Error::Decompression("missing checksum".to_string()))?;
+    let stored = u32::from_le_bytes([stored[0], stored[1], stored[2], stored[3]]);
+    if stored != checksum(&output) {
+        return Err(Error::Decompression("checksum does not match".to_string()));
+    }
+    Ok(output)
+}
+
+#[must_use]
+/// Compress data, including the trailing checksum
+pub fn compress(input: &[u8]) -> Vec<u8> {
+    let mut output = Vec::with_capacity(input.len() / 2 + 4);
+    // The most recent position of each 3 byte sequence, and the position before each position
+    // with the same sequence
+    let mut head: HashMap<[u8; 3], usize> = HashMap::new();
+    let mut previous = vec![usize::MAX; input.len()];
+    let mut position = 0;
+    while position < input.len() {
+        let flags_at = output.len();
+        output.push(0);
+        for bit in 0..8 {
+            if position >= input.len() {
+                break;
+            }
+            let (offset, length) = longest(input, position, &head, &previous);
+            if length >= MIN_MATCH {
+                output.push((offset & 0xFF) as u8);
+                output.push((((offset >> 4) & 0xF0) | (length - MIN_MATCH)) as u8);
+                for skipped in position..position + length {
+                    insert(input, skipped, &mut head, &mut previous);
+                }
+                position += length;
+            } else {
+                output[flags_at] |= 1 << bit;
+                output.push(input[position]);
+                insert(input, position, &mut head, &mut previous);
+                position += 1;
+            }
+        }
+    }
+    output.extend_from_slice(&checksum(input).to_le_bytes());
+    output
+}
+
+fn insert(
+    input: &[u8],
+    position: usize,
+    head: &mut HashMap<[u8; 3], usize>,
+    previous: &mut [usize],
+) {
+    if let Some(last) = key(input, position).and_then(|key| head.insert(key, position)) {
+        previous[position] = last;
+    }
+}
+
+fn key(input: &[u8], position: usize) -> Option<[u8; 3]> {
+    input
+        .get(position..position + MIN_MATCH)
+        .map(|bytes| [bytes[0], bytes[1], bytes[2]])
+}
+
+/// The longest earlier match for the data at the position, as an offset and length
+fn longest(
+    input: &[u8],
+    position: usize,
+    head: &HashMap<[u8; 3], usize>,
+    previous: &[usize],
+) -> (usize, usize) {
+    let Some(mut candidate) = key(input, position).and_then(|key| head.get(&key).copied()) else {
+        return (0, 0);
+    };
+    let limit = MAX_MATCH.min(input.len() - position);
+    let mut best = (0, 0);
+    for _ in 0..MAX_CANDIDATES {
+        let offset = position - candidate;
+        if offset > WINDOW {
+            break;
+        }
+        // Matches may overlap the data being compressed, it is copied one byte at a time
+        let length = (0..limit)
+            .take_while(|index| input[candidate + index] == input[position + index])
+            .count();
+        if length > best.1 {
+            best = (offset, length);
+            if length == limit {
+                break;
+            }
+        }
+        candidate = previous[candidate];
+        if candidate == usize::MAX {
+            break;
+        }
+    }
+    best
+}
+
+/// The sum of every byte, stored after the compressed data
+fn checksum(data: &[u8]) -> u32 {
+    data.iter()
+        .fold(0u32, |sum, byte| sum.wrapping_add(u32::from(*byte)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn roundtrip(data: &[u8]) {
+        let compressed = compress(data);
+        assert_eq!(
+            decompress(&compressed, data.len()).expect("decompress"),
+            data
+        );
+    }
+
+    #[test]
+    fn roundtrips() {
+        roundtrip(b"");
+        roundtrip(b"a");
+        roundtrip(b"aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa");
+        roundtrip(b"class CfgPatches { class abe_main { units[] = {}; weapons[] = {}; }; };");
+        let mut large = Vec::new();
+        for i in 0..20_000u32 {
+            large.extend_from_slice(format!("private _var{} = {};\n", i % 97, i).as_bytes());
+        }
+        roundtrip(&large);
+        let noise = (0..10_000u32)
+            .map(|i| (i.wrapping_mul(2_654_435_761) >> 13) as u8)
+            .collect::<Vec<_>>();
+        roundtrip(&noise);
+    }
+
+    #[test]
+    fn smaller() {
+        let data = b"class Item0 {}; class Item1 {}; class Item2 {}; class Item3 {};".repeat(20);
+        assert!(compress(&data).len() < data.len() / 4);
+    }
+
+    #[test]
+    fn known() {
+        // Two literals and a pointer one byte back, repeating `b` 3 times
+        let data = [0b0000_0011, b'a', b'b', 0x01, 0x00, 0xE9, 0x01, 0x00, 0x00];
+        assert_eq!(decompress(&data, 5).expect("decompress"), b"abbbb");
+        // Pointers before the start are spaces
+        let data = [0b0000_0000, 0x05, 0x00, 0x60, 0x00, 0x00, 0x00];
+        assert_eq!(decompress(&data, 3).expect("decompress"), b"   ");
+    }
+
+    #[test]
+    fn invalid() {
+        let mut compressed = compress(b"class CfgPatches {};");
+        assert!(decompress(&compressed, 30).is_err());
+        let last = compressed.len() - 1;
+        compressed[last] ^= 0xFF;
+        assert!(decompress(&compressed, 20).is_err());
+    }
+}
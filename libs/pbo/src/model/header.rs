@@ -28,6 +28,40 @@ impl Header {
         }
     }
 
+    #[must_use]
+    /// The header for the file once it has been compressed with [`crate::lzss`]
+    pub(crate) fn compressed(self, original: u32, size: u32) -> Self {
+        Self {
+            mime: Mime::Cprs,
+            original,
+            size,
+            ..self
+        }
+    }
+
+    #[must_use]
+    /// The header with a different timestamp
+    pub(crate) fn with_timestamp(self, timestamp: u32) -> Self {
+        Self { timestamp, ..self }
+    }
+
+    #[must_use]
+    /// Is the file compressed
+    pub fn is_compressed(&self) -> bool {
+        self.mime == Mime::Cprs
+    }
+
+    #[must_use]
+    /// Get the size of the file once it has been read
+    ///
+    /// The original size for compressed files, otherwise the stored size
+    pub const fn unpacked_size(&self) -> u32 {
+        match self.mime {
+            Mime::Cprs => self.original,
+            _ => self.size,
+        }
+    }
+
     #[must_use]
     /// Create a new header for an property
     pub fn property() -> Self {
@@ -108,9 +108,35 @@ impl<I: Seek + Read> ReadablePbo<I> {
 
     /// Read a file from the PBO
     ///
+    /// Compressed files are decompressed
+    ///
+    /// # Errors
+    /// if the file cannot be read, or a compressed file cannot be decompressed
+    pub fn file(&mut self, name: &str) -> Result<Option<File<'_, I>>, Error> {
+        let name = name.replace('/', "\\").to_lowercase();
+        let Some(header) = self
+            .headers
+            .iter()
+            .find(|header| header.filename().to_lowercase() == name && header.is_compressed())
+        else {
+            return self.file_raw(&name);
+        };
+        let original = header.original() as usize;
+        let mut data = Vec::with_capacity(header.size() as usize);
+        let Some(mut file) = self.file_raw(&name)? else {
+            return Ok(None);
+        };
+        file.read_to_end(&mut data)?;
+        Ok(Some(File::decompressed(crate::lzss::decompress(
+            &data, original,
+        )?)))
+    }
+
+    /// Read a file from the PBO as it is stored, without decompressing it
+    ///
     /// # Errors
     /// if the file cannot be read
-    pub fn file(&mut self, name: &str) -> Result<Option<File<I>>, Error> {
+    pub fn file_raw(&mut self, name: &str) -> Result<Option<File<'_, I>>, Error> {
         self.input.seek(SeekFrom::Start(self.blob_start))?;
         for header in &self.headers {
             if header.filename().to_lowercase() == name.replace('/', "\\").to_lowercase() {
@@ -206,7 +232,7 @@ impl<I: Seek + Read> ReadablePbo<I> {
 
         for header in &self.files_sorted() {
             let mut file = self
-                .file(header.filename())?
+                .file_raw(header.filename())?
                 .expect("file with header should exist");
             std::io::copy(&mut file, &mut hasher)?;
         }
@@ -228,7 +254,7 @@ impl<I: Seek + Read> ReadablePbo<I> {
 
         for header in &self.files_sorted() {
             // Skip empty files
-            let Some(mut file) = self.file(header.filename())? else {
+            let Some(mut file) = self.file_raw(header.filename())? else {
                 continue;
             };
             if file.read_u8().is_err() {
@@ -254,7 +280,7 @@ impl<I: Seek + Read> ReadablePbo<I> {
                 continue;
             }
             nothing = false;
-            let Some(mut file) = self.file(header.filename())? else {
+            let Some(mut file) = self.file_raw(header.filename())? else {
                 continue;
             };
             std::io::copy(&mut file, &mut hasher)?;
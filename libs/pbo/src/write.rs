@@ -7,13 +7,18 @@ use hemtt_common::io::WriteExt;
 use indexmap::IndexMap;
 use sha1::{Digest, Sha1};
 
-use crate::{Checksum, WritePbo, error::Error, model::Header};
+use crate::{Checksum, WritePbo, error::Error, lzss, model::Header};
+
+/// Files that are already compressed, and are never smaller with LZSS
+const INCOMPRESSIBLE: [&str; 7] = ["paa", "pac", "ogg", "wss", "jpg", "jpeg", "png"];
 
 #[derive(Default)]
 /// A PBO file that can be written to
 pub struct WritablePbo<I: Seek + Read> {
     properties: IndexMap<String, String>,
     files: HashMap<String, (I, Header)>,
+    compress: bool,
+    timestamp: Option<u32>,
 }
 
 impl<I: Seek + Read> WritablePbo<I> {
@@ -23,9 +28,25 @@ impl<I: Seek + Read> WritablePbo<I> {
         Self {
             properties: IndexMap::new(),
             files: HashMap::new(),
+            compress: false,
+            timestamp: None,
         }
     }
 
+    /// Compress files with LZSS when the PBO is written
+    ///
+    /// The game only reads compressed files from mission PBOs. Files are only stored compressed
+    /// when it makes them smaller.
+    pub const fn compress(&mut self, compress: bool) {
+        self.compress = compress;
+    }
+
+    /// Set the timestamp of every file when the PBO is written, instead of the timestamp in its
+    /// header
+    pub const fn timestamp(&mut self, timestamp: u32) {
+        self.timestamp = Some(timestamp);
+    }
+
     /// Add files to the PBO
     ///
     /// # Errors
@@ -87,10 +108,13 @@ impl<I: Seek + Read> WritablePbo<I> {
     #[must_use]
     pub fn files_sorted(&self) -> Vec<Header> {
         let mut sorted = self.files();
+        // Files that only differ by case are sorted by name, so the order does not depend on the
+        // order they were added
         sorted.sort_by(|a, b| {
             a.filename()
                 .to_lowercase()
                 .cmp(&b.filename().to_lowercase())
+                .then_with(|| a.filename().cmp(b.filename()))
         });
         sorted
     }
@@ -154,7 +178,51 @@ impl<I: Seek + Read> WritablePbo<I> {
             headers.write_all(&[0])?;
         }
 
-        let files_sorted = self.files_sorted();
+        let mut compressed = HashMap::new();
+        if self.compress {
+            for header in self.files_sorted() {
+                if header
+                    .filename()
+                    .rsplit_once('.')
+                    .is_some_and(|(_, extension)| {
+                        INCOMPRESSIBLE
+                            .iter()
+                            .any(|skip| extension.eq_ignore_ascii_case(skip))
+                    })
+                {
+                    continue;
+                }
+                let file = self
+                    .file(header.filename())?
+                    .expect("file with header should exist");
+                let mut buffer = Vec::with_capacity(header.size() as usize);
+                file.read_to_end(&mut buffer)?;
+                let packed = lzss::compress(&buffer);
+                if packed.len() < buffer.len() {
+                    compressed.insert(
+                        header.filename().to_string(),
+                        (
+                            header.compressed(buffer.len() as u32, packed.len() as u32),
+                            packed,
+                        ),
+                    );
+                }
+            }
+        }
+
+        let files_sorted = self
+            .files_sorted()
+            .into_iter()
+            .map(|header| {
+                let header = compressed
+                    .get(header.filename())
+                    .map_or(header, |(header, _)| header.clone());
+                match self.timestamp {
+                    Some(timestamp) => header.with_timestamp(timestamp),
+                    None => header,
+                }
+            })
+            .collect::<Vec<_>>();
 
         for header in &files_sorted {
             header.write_pbo(&mut headers)?;
@@ -170,11 +238,16 @@ impl<I: Seek + Read> WritablePbo<I> {
         hasher.update(headers.get_ref());
 
         for header in &files_sorted {
-            let file = self
-                .file(header.filename())?
-                .expect("file with header should exist");
-            let mut buffer = Vec::with_capacity(header.size() as usize);
-            file.read_to_end(&mut buffer)?;
+            let buffer = if let Some((_, packed)) = compressed.remove(header.filename()) {
+                packed
+            } else {
+                let file = self
+                    .file(header.filename())?
+                    .expect("file with header should exist");
+                let mut buffer = Vec::with_capacity(header.size() as usize);
+                file.read_to_end(&mut buffer)?;
+                buffer
+            };
 
             if header.size() > 1_000_000 {
                 // pay the paralellization cost for large files
@@ -0,0 +1,63 @@
+#![allow(clippy::unwrap_used)]
+
+use std::io::{Cursor, Read};
+
+use hemtt_pbo::{Mime, ReadablePbo, WritablePbo};
+
+const SQM: &str = "version=54;\nclass Mission\n{\n\tclass Entities\n\t{\n\t\titems=0;\n\t};\n};\n";
+
+#[test]
+fn roundtrip() {
+    let sqm = SQM.repeat(20);
+    let mut pbo = WritablePbo::new();
+    pbo.compress(true);
+    pbo.add_file("mission.sqm", Cursor::new(sqm.as_bytes().to_vec()))
+        .unwrap();
+    pbo.add_file("init.sqf", Cursor::new(b"a".to_vec()))
+        .unwrap();
+    pbo.add_file("image.paa", Cursor::new(SQM.repeat(20).into_bytes()))
+        .unwrap();
+    let mut output = Cursor::new(Vec::new());
+    let checksum = pbo.write(&mut output, true).unwrap();
+
+    output.set_position(0);
+    let mut pbo = ReadablePbo::from(output).unwrap();
+    assert_eq!(pbo.checksum(), &checksum);
+    assert_eq!(pbo.gen_checksum().unwrap(), checksum);
+
+    let header = pbo.header("mission.sqm").unwrap().clone();
+    assert_eq!(header.mime(), &Mime::Cprs);
+    assert!(header.is_compressed());
+    assert_eq!(header.original() as usize, sqm.len());
+    assert_eq!(header.unpacked_size() as usize, sqm.len());
+    assert!((header.size() as usize) < sqm.len());
+
+    // Too small to be smaller once compressed
+    assert_eq!(pbo.header("init.sqf").unwrap().mime(), &Mime::Blank);
+    // Already compressed
+    assert_eq!(pbo.header("image.paa").unwrap().mime(), &Mime::Blank);
+
+    let mut data = String::new();
+    pbo.file("mission.sqm")
+        .unwrap()
+        .unwrap()
+        .read_to_string(&mut data)
+        .unwrap();
+    assert_eq!(data, sqm);
+
+    let mut raw = Vec::new();
+    pbo.file_raw("mission.sqm")
+        .unwrap()
+        .unwrap()
+        .read_to_end(&mut raw)
+        .unwrap();
+    assert_eq!(raw.len(), header.size() as usize);
+
+    let mut data = String::new();
+    pbo.file("init.sqf")
+        .unwrap()
+        .unwrap()
+        .read_to_string(&mut data)
+        .unwrap();
+    assert_eq!(data, "a");
+}
@@ -0,0 +1,38 @@
+#![allow(clippy::unwrap_used)]
+
+use std::io::Cursor;
+
+use hemtt_pbo::{ReadablePbo, WritablePbo};
+
+fn write(files: &[&str]) -> Vec<u8> {
+    let mut pbo = WritablePbo::new();
+    pbo.timestamp(1_700_000_000);
+    pbo.add_property("prefix", "z\\abe\\addons\\main");
+    for file in files {
+        pbo.add_file(*file, Cursor::new(file.as_bytes().to_vec()))
+            .unwrap();
+    }
+    let mut output = Cursor::new(Vec::new());
+    pbo.write(&mut output, true).unwrap();
+    output.into_inner()
+}
+
+#[test]
+fn order_independent() {
+    let first = write(&["b.sqf", "A.sqf", "a.sqf", "config.cpp"]);
+    let second = write(&["config.cpp", "a.sqf", "b.sqf", "A.sqf"]);
+    assert_eq!(first, second);
+
+    let pbo = ReadablePbo::from(Cursor::new(first)).unwrap();
+    let names = pbo
+        .files()
+        .iter()
+        .map(|header| header.filename().to_string())
+        .collect::<Vec<_>>();
+    assert_eq!(names, ["A.sqf", "a.sqf", "b.sqf", "config.cpp"]);
+    assert!(
+        pbo.files()
+            .iter()
+            .all(|header| header.timestamp() == 1_700_000_000)
+    );
+}
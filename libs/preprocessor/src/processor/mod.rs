@@ -96,6 +96,7 @@ impl Processor {
 
         Processed::new(
             buffer,
+            processor.included_files.clone(),
             processor.macros,
             #[cfg(feature = "lsp")]
             processor.usage,
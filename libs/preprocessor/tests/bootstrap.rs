@@ -90,3 +90,28 @@ bootstrap!(sqf);
 bootstrap!(strings);
 bootstrap!(triple_backslash);
 bootstrap!(utf);
+
+#[test]
+fn included_without_output() {
+    let folder = std::path::PathBuf::from(ROOT).join("include_empty");
+    let workspace = hemtt_workspace::Workspace::builder()
+        .physical(&folder, LayerType::Source)
+        .finish(None, false, &hemtt_common::config::PDriveOption::Disallow)
+        .unwrap();
+    let processed = Processor::run(&workspace.join("source.hpp").unwrap()).unwrap();
+    assert_eq!(
+        processed
+            .included()
+            .iter()
+            .map(hemtt_workspace::WorkspacePath::filename)
+            .collect::<Vec<_>>(),
+        vec!["empty.hpp", "comments.hpp"]
+    );
+    // The empty file produces no output, so it is not a source
+    assert!(
+        processed
+            .sources()
+            .iter()
+            .all(|(path, _)| path.filename() != "empty.hpp")
+    );
+}
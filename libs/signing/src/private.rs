@@ -62,6 +62,12 @@ impl BIPrivateKey {
         })
     }
 
+    #[must_use]
+    /// Returns the authority of the private key
+    pub fn authority(&self) -> &str {
+        &self.authority
+    }
+
     #[must_use]
     /// Returns the public key for this private key.
     pub fn to_public_key(&self) -> BIPublicKey {
@@ -0,0 +1,241 @@
+use std::{ops::Range, sync::Arc};
+
+use hemtt_common::config::{LintConfig, ProjectConfig};
+use hemtt_workspace::{
+    lint::{AnyLintRunner, Lint, LintRunner},
+    reporting::{Code, Codes, Diagnostic, Label, Processed, Severity},
+    WorkspacePath,
+};
+
+use crate::{
+    analyze::LintData,
+    header::{self, Header, Mismatch},
+    Statements,
+};
+
+crate::analyze::lint!(LintS30FunctionHeader);
+
+impl Lint<LintData> for LintS30FunctionHeader {
+    fn ident(&self) -> &'static str {
+        "function_header"
+    }
+
+    fn sort(&self) -> u32 {
+        300
+    }
+
+    fn description(&self) -> &'static str {
+        "Checks that function headers match the function's `params`"
+    }
+
+    fn documentation(&self) -> &'static str {
+        r#"### Example
+
+**Incorrect**
+```sqf
+/*
+ * Arguments:
+ * 0: Piano <OBJECT>
+ * 1: Volume <NUMBER> (default: 1)
+ */
+params ["_piano", ["_volume", 0.5], "_notes"];
+```
+**Correct**
+```sqf
+/*
+ * Arguments:
+ * 0: Piano <OBJECT>
+ * 1: Volume <NUMBER> (default: 0.5)
+ * 2: Notes <ARRAY>
+ */
+params ["_piano", ["_volume", 0.5], "_notes"];
+```
+
+### Explanation
+
+A header that does not match the function's `params` documents the function incorrectly. The number of arguments is checked, along with the names of arguments written as `_name` and the documented defaults."#
+    }
+
+    fn default_config(&self) -> LintConfig {
+        LintConfig::warning()
+    }
+
+    fn runners(&self) -> Vec<Box<dyn AnyLintRunner<LintData>>> {
+        vec![Box::new(Runner)]
+    }
+}
+
+struct Runner;
+impl LintRunner<LintData> for Runner {
+    type Target = Statements;
+
+    fn run(
+        &self,
+        _project: Option<&ProjectConfig>,
+        config: &LintConfig,
+        processed: Option<&Processed>,
+        _runtime: &hemtt_common::config::RuntimeArguments,
+        target: &Self::Target,
+        _data: &LintData,
+    ) -> Codes {
+        let Some(processed) = processed else {
+            return Vec::new();
+        };
+        if !header::is_file(target, processed) {
+            return Vec::new();
+        }
+        let Some((path, source)) = header::main_source(processed) else {
+            return Vec::new();
+        };
+        let Some(header) = Header::parse(&source) else {
+            return Vec::new();
+        };
+        let Some(params) = header::params(target) else {
+            return Vec::new();
+        };
+        header::validate(&header, &params)
+            .into_iter()
+            .map(|mismatch| {
+                let (documented, declared) = match &mismatch {
+                    Mismatch::Count { .. } => (
+                        header
+                            .arguments()
+                            .and_then(<[header::Argument]>::last)
+                            .map_or_else(|| header.span(), header::Argument::span),
+                        params.last().map(header::Param::span),
+                    ),
+                    Mismatch::Name { index, .. } | Mismatch::Default { index, .. } => (
+                        header
+                            .arguments()
+                            .and_then(|arguments| arguments.get(*index))
+                            .map_or_else(|| header.span(), header::Argument::span),
+                        params.get(*index).map(header::Param::span),
+                    ),
+                };
+                Arc::new(CodeS30FunctionHeader::new(
+                    mismatch,
+                    path.clone(),
+                    header::char_span(&source, documented),
+                    declared,
+                    processed,
+                    config.severity(),
+                )) as Arc<dyn Code>
+            })
+            .collect()
+    }
+}
+
+#[allow(clippy::module_name_repetitions)]
+pub struct CodeS30FunctionHeader {
+    mismatch: Mismatch,
+    file: WorkspacePath,
+    documented: Range<usize>,
+    declared: Option<Range<usize>>,
+    severity: Severity,
+    diagnostic: Option<Diagnostic>,
+}
+
+impl Code for CodeS30FunctionHeader {
+    fn ident(&self) -> &'static str {
+        "L-S30"
+    }
+
+    fn link(&self) -> Option<&str> {
+        Some("/analysis/sqf.html#function_header")
+    }
+
+    fn severity(&self) -> Severity {
+        self.severity
+    }
+
+    fn message(&self) -> String {
+        match &self.mismatch {
+            Mismatch::Count {
+                documented,
+                declared,
+            } => format!(
+                "Function header documents {documented} arguments, but `params` declares {declared}"
+            ),
+            Mismatch::Name {
+                index,
+                documented,
+                declared,
+            } => format!(
+                "Argument {index} is documented as `{documented}`, but declared as `{declared}`"
+            ),
+            Mismatch::Default {
+                index,
+                documented,
+                declared,
+            } => format!(
+                "Argument {index} is documented with the default `{documented}`, but declared with {}",
+                declared
+                    .as_ref()
+                    .map_or_else(|| "no default".to_string(), |declared| format!("`{declared}`"))
+            ),
+        }
+    }
+
+    fn label_message(&self) -> String {
+        "documented here".to_string()
+    }
+
+    fn help(&self) -> Option<String> {
+        Some("update the header to match `params`".to_string())
+    }
+
+    fn diagnostic(&self) -> Option<Diagnostic> {
+        self.diagnostic.clone()
+    }
+}
+
+impl CodeS30FunctionHeader {
+    #[must_use]
+    pub fn new(
+        mismatch: Mismatch,
+        file: WorkspacePath,
+        documented: Range<usize>,
+        declared: Option<Range<usize>>,
+        processed: &Processed,
+        severity: Severity,
+    ) -> Self {
+        Self {
+            mismatch,
+            file,
+            documented,
+            declared,
+            severity,
+            diagnostic: None,
+        }
+        .generate_processed(processed)
+    }
+
+    fn generate_processed(mut self, processed: &Processed) -> Self {
+        let mut diag = Diagnostic::from_code(&self).with_label(
+            Label::primary(self.file.clone(), self.documented.clone())
+                .with_message(self.label_message()),
+        );
+        if let Some(info) = self
+            .declared
+            .as_ref()
+            .and_then(|span| get_span_info(span, processed))
+        {
+            diag = diag.with_label(Label::secondary(info.0, info.1).with_message("declared here"));
+        }
+        self.diagnostic = Some(diag);
+        self
+    }
+}
+
+fn get_span_info(
+    span: &Range<usize>,
+    processed: &Processed,
+) -> Option<(WorkspacePath, Range<usize>)> {
+    let map_start = processed.mapping(span.start)?;
+    let map_end = processed.mapping(span.end)?;
+    let map_file = processed.source(map_start.source())?;
+    Some((
+        map_file.0.clone(),
+        map_start.original_start()..map_end.original_start(),
+    ))
+}
@@ -0,0 +1,224 @@
+use std::{ops::Range, sync::Arc};
+
+use hemtt_common::config::{LintConfig, ProjectConfig};
+use hemtt_workspace::{
+    lint::{AnyLintRunner, Lint, LintRunner},
+    reporting::{Code, Codes, Diagnostic, Label, Processed, Severity},
+    WorkspacePath,
+};
+
+use crate::{
+    analyze::LintData,
+    header::{self, Header},
+    Statements,
+};
+
+crate::analyze::lint!(LintS31FunctionUndocumented);
+
+impl Lint<LintData> for LintS31FunctionUndocumented {
+    fn ident(&self) -> &'static str {
+        "function_undocumented"
+    }
+
+    fn sort(&self) -> u32 {
+        310
+    }
+
+    fn description(&self) -> &'static str {
+        "Reports public functions that are not documented"
+    }
+
+    fn documentation(&self) -> &'static str {
+        r#"### Configuration
+
+- **require_header**: Also report functions without a header, they may be public. Defaults to `false`.
+
+```toml
+[lints.sqf.function_undocumented]
+options.require_header = true
+```
+
+### Example
+
+**Incorrect**
+```sqf
+/*
+ * Public: Yes
+ */
+params ["_piano"];
+```
+**Correct**
+```sqf
+/*
+ * Author: Brett
+ * Plays a piano
+ *
+ * Arguments:
+ * 0: Piano <OBJECT>
+ *
+ * Return Value:
+ * None
+ *
+ * Example:
+ * [_piano] call abe_main_fnc_play
+ *
+ * Public: Yes
+ */
+params ["_piano"];
+```
+
+### Explanation
+
+Functions marked with `Public: Yes` are part of the mod's API, and are used by other mods and mission makers. Their header should have a description, the arguments, the return value, and an example."#
+    }
+
+    fn default_config(&self) -> LintConfig {
+        LintConfig::warning()
+    }
+
+    fn runners(&self) -> Vec<Box<dyn AnyLintRunner<LintData>>> {
+        vec![Box::new(Runner)]
+    }
+}
+
+struct Runner;
+impl LintRunner<LintData> for Runner {
+    type Target = Statements;
+
+    fn run(
+        &self,
+        _project: Option<&ProjectConfig>,
+        config: &LintConfig,
+        processed: Option<&Processed>,
+        _runtime: &hemtt_common::config::RuntimeArguments,
+        target: &Self::Target,
+        _data: &LintData,
+    ) -> Codes {
+        let Some(processed) = processed else {
+            return Vec::new();
+        };
+        if !header::is_file(target, processed) {
+            return Vec::new();
+        }
+        let Some((path, source)) = header::main_source(processed) else {
+            return Vec::new();
+        };
+        if !header::is_function(&path) {
+            return Vec::new();
+        }
+        let Some(header) = Header::parse(&source) else {
+            if !matches!(config.option("require_header"), Some(toml::Value::Boolean(true))) {
+                return Vec::new();
+            }
+            let Some(map) = processed.mapping_no_macros(target.span().start) else {
+                return Vec::new();
+            };
+            return vec![Arc::new(CodeS31FunctionUndocumented::new(
+                path.filename(),
+                Vec::new(),
+                path,
+                map.original_start()..map.original_end(),
+                config.severity(),
+            ))];
+        };
+        if header.public() != Some(true) {
+            return Vec::new();
+        }
+        let missing = header.missing();
+        if missing.is_empty() {
+            return Vec::new();
+        }
+        let span = header::char_span(&source, header.span().start..header.span().start + 2);
+        vec![Arc::new(CodeS31FunctionUndocumented::new(
+            path.filename(),
+            missing,
+            path,
+            span,
+            config.severity(),
+        ))]
+    }
+}
+
+#[allow(clippy::module_name_repetitions)]
+pub struct CodeS31FunctionUndocumented {
+    function: String,
+    /// The missing sections of the header, empty if there is no header
+    missing: Vec<&'static str>,
+    file: WorkspacePath,
+    span: Range<usize>,
+    severity: Severity,
+    diagnostic: Option<Diagnostic>,
+}
+
+impl Code for CodeS31FunctionUndocumented {
+    fn ident(&self) -> &'static str {
+        "L-S31"
+    }
+
+    fn link(&self) -> Option<&str> {
+        Some("/analysis/sqf.html#function_undocumented")
+    }
+
+    fn severity(&self) -> Severity {
+        self.severity
+    }
+
+    fn message(&self) -> String {
+        if self.missing.is_empty() {
+            format!("Function `{}` has no header", self.function)
+        } else {
+            format!(
+                "Public function `{}` is missing its {}",
+                self.function,
+                self.missing.join(", ")
+            )
+        }
+    }
+
+    fn label_message(&self) -> String {
+        if self.missing.is_empty() {
+            "function starts here".to_string()
+        } else {
+            "header starts here".to_string()
+        }
+    }
+
+    fn help(&self) -> Option<String> {
+        Some("document the function with the standard header".to_string())
+    }
+
+    fn diagnostic(&self) -> Option<Diagnostic> {
+        self.diagnostic.clone()
+    }
+}
+
+impl CodeS31FunctionUndocumented {
+    #[must_use]
+    pub fn new(
+        function: String,
+        missing: Vec<&'static str>,
+        file: WorkspacePath,
+        span: Range<usize>,
+        severity: Severity,
+    ) -> Self {
+        Self {
+            function,
+            missing,
+            file,
+            span,
+            severity,
+            diagnostic: None,
+        }
+        .generate()
+    }
+
+    fn generate(mut self) -> Self {
+        self.diagnostic = Some(
+            Diagnostic::from_code(&self).with_label(
+                Label::primary(self.file.clone(), self.span.clone())
+                    .with_message(self.label_message()),
+            ),
+        );
+        self
+    }
+}
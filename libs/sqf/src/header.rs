@@ -0,0 +1,936 @@
+//! Function headers, the block comment at the start of a function that documents it
+//!
+//! The format used by ACE and most mods built on CBA is supported:
+//!
+//! ```text
+//! /*
+//!  * Author: Brett
+//!  * Sets the volume of a piano
+//!  *
+//!  * Arguments:
+//!  * 0: Piano <OBJECT>
+//!  * 1: Volume <NUMBER> (default: 1)
+//!  *
+//!  * Return Value:
+//!  * None
+//!  *
+//!  * Example:
+//!  * [_piano, 0.5] call abe_main_fnc_setVolume
+//!  *
+//!  * Public: Yes
+//!  */
+//! ```
+//!
+//! CBA's own format, with `Description:`, `Parameters:`, and `Returns:` sections and parameters
+//! written as `_name - description`, is also understood.
+
+use std::{fmt::Write as _, ops::Range};
+
+use hemtt_workspace::{WorkspacePath, reporting::Processed};
+
+use crate::{BinaryCommand, Expression, Statement, Statements, UnaryCommand};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// A function header
+pub struct Header {
+    span: Range<usize>,
+    authors: Vec<String>,
+    description: String,
+    arguments: Option<Vec<Argument>>,
+    return_value: Option<String>,
+    examples: Vec<String>,
+    public: Option<bool>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// A documented argument of a function
+pub struct Argument {
+    span: Range<usize>,
+    name: Option<String>,
+    description: String,
+    types: Vec<String>,
+    default: Option<String>,
+    optional: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Section {
+    Authors,
+    Description,
+    Arguments,
+    ReturnValue,
+    Examples,
+    Public,
+    Ignored,
+}
+
+impl Header {
+    #[must_use]
+    /// Parse the header at the start of a function's source
+    ///
+    /// Only comments, blank lines, and preprocessor directives may come before the header.
+    /// A block comment without any of the header's sections is not a header.
+    pub fn parse(source: &str) -> Option<Self> {
+        let start = start(source)?;
+        let end = source[start + 2..].find("*/").map(|end| start + end + 4)?;
+        let mut header = Self {
+            span: start..end,
+            authors: Vec::new(),
+            description: String::new(),
+            arguments: None,
+            return_value: None,
+            examples: Vec::new(),
+            public: None,
+        };
+        let mut recognized = false;
+        let mut section = Section::Description;
+        let mut offset = start + 2;
+        // Where the first argument starts on its line, items indented further belong to an argument
+        let mut arguments_column = None;
+        for raw in source[start + 2..end - 2].split_inclusive('\n') {
+            let (line, span) = strip(raw, offset);
+            let column = span.start - offset;
+            offset += raw.len();
+            if line.chars().all(|c| c == '-' || c == '=' || c == '*') && !line.is_empty() {
+                continue;
+            }
+            if let Some((next, rest)) = heading(line) {
+                recognized |= next != Section::Description && next != Section::Ignored;
+                section = next;
+                match next {
+                    Section::Arguments => {
+                        header.arguments.get_or_insert_with(Vec::new);
+                    }
+                    Section::ReturnValue => {
+                        header.return_value.get_or_insert_with(String::new);
+                    }
+                    Section::Examples => header.examples.push(String::new()),
+                    _ => {}
+                }
+                if rest.is_empty() {
+                    continue;
+                }
+                header.line(section, rest, span.end - rest.len()..span.end, false);
+                if section == Section::Authors {
+                    // `Author: Name` is followed by the description
+                    section = Section::Description;
+                }
+                continue;
+            }
+            let nested = arguments_column.is_some_and(|first| column > first);
+            header.line(section, line, span, nested);
+            if arguments_column.is_none()
+                && header.arguments.as_ref().is_some_and(|a| !a.is_empty())
+            {
+                arguments_column = Some(column);
+            }
+        }
+        if !recognized {
+            return None;
+        }
+        header.description = header.description.trim().to_string();
+        if let Some(return_value) = &mut header.return_value {
+            *return_value = return_value.trim().to_string();
+        }
+        header.examples = header
+            .examples
+            .iter()
+            .flat_map(|examples| examples.split("\n\n"))
+            .map(|example| example.trim().to_string())
+            .filter(|example| !example.is_empty())
+            .collect();
+        Some(header)
+    }
+
+    /// Add a line to a section, `nested` if it is indented further than the first argument
+    fn line(&mut self, section: Section, line: &str, span: Range<usize>, nested: bool) {
+        match section {
+            Section::Authors => {
+                self.authors.extend(
+                    line.split(',')
+                        .map(str::trim)
+                        .filter(|author| !author.is_empty())
+                        .map(ToString::to_string),
+                );
+            }
+            Section::Description => {
+                self.description.push_str(line);
+                self.description.push('\n');
+            }
+            Section::Arguments => {
+                let arguments = self.arguments.get_or_insert_with(Vec::new);
+                if arguments.is_empty() && line.eq_ignore_ascii_case("none") {
+                    return;
+                }
+                if let Some(argument) =
+                    Argument::parse(line, span, arguments.len()).filter(|_| !nested)
+                {
+                    arguments.push(argument);
+                } else if let Some(last) = arguments.last_mut() {
+                    // Continued descriptions and the items of an array argument
+                    if !line.is_empty() {
+                        last.description.push('\n');
+                        last.description.push_str(line);
+                    }
+                }
+            }
+            Section::ReturnValue => {
+                let return_value = self.return_value.get_or_insert_with(String::new);
+                return_value.push_str(line);
+                return_value.push('\n');
+            }
+            Section::Examples => {
+                if line.eq_ignore_ascii_case("(begin example)")
+                    || line.eq_ignore_ascii_case("(end)")
+                    || line.eq_ignore_ascii_case("(end example)")
+                {
+                    return;
+                }
+                if self.examples.is_empty() {
+                    self.examples.push(String::new());
+                }
+                if let Some(example) = self.examples.last_mut() {
+                    example.push_str(line);
+                    example.push('\n');
+                }
+            }
+            Section::Public => {
+                if self.public.is_none() && !line.is_empty() {
+                    let lower = line.to_ascii_lowercase();
+                    self.public = Some(lower.starts_with("yes") || lower.starts_with("true"));
+                }
+            }
+            Section::Ignored => {}
+        }
+    }
+
+    #[must_use]
+    /// The span of the header in the source, including the comment markers
+    pub fn span(&self) -> Range<usize> {
+        self.span.clone()
+    }
+
+    #[must_use]
+    /// The authors of the function
+    pub fn authors(&self) -> &[String] {
+        &self.authors
+    }
+
+    #[must_use]
+    /// The description of the function, empty if there is none
+    pub fn description(&self) -> &str {
+        &self.description
+    }
+
+    #[must_use]
+    /// The documented arguments, `None` if the header has no arguments section
+    pub fn arguments(&self) -> Option<&[Argument]> {
+        self.arguments.as_deref()
+    }
+
+    #[must_use]
+    /// The documented return value, `None` if the header has no return value section
+    pub fn return_value(&self) -> Option<&str> {
+        self.return_value.as_deref()
+    }
+
+    #[must_use]
+    /// The examples of calling the function
+    pub fn examples(&self) -> &[String] {
+        &self.examples
+    }
+
+    #[must_use]
+    /// Is the function part of the public API, `None` if the header does not say
+    pub const fn public(&self) -> Option<bool> {
+        self.public
+    }
+
+    #[must_use]
+    /// The sections a public function should have, but this header is missing
+    pub fn missing(&self) -> Vec<&'static str> {
+        let mut missing = Vec::new();
+        if self.description.is_empty() {
+            missing.push("description");
+        }
+        if self.arguments.is_none() {
+            missing.push("arguments");
+        }
+        if self.return_value.as_deref().is_none_or(str::is_empty) {
+            missing.push("return value");
+        }
+        if self.examples.is_empty() {
+            missing.push("example");
+        }
+        missing
+    }
+
+    #[must_use]
+    /// Render the header as Markdown, under a heading with the function's name
+    pub fn markdown(&self, name: &str) -> String {
+        let mut out = format!("### {name}\n\n");
+        if !self.description.is_empty() {
+            out.push_str(&self.description);
+            out.push_str("\n\n");
+        }
+        if let Some(arguments) = &self.arguments {
+            out.push_str("**Arguments**\n\n");
+            if arguments.is_empty() {
+                out.push_str("None\n");
+            } else {
+                out.push_str("| # | Description | Type | Default |\n| - | - | - | - |\n");
+                for (index, argument) in arguments.iter().enumerate() {
+                    let _ = writeln!(
+                        out,
+                        "| {index} | {} | {} | {} |",
+                        argument.description_with_name().replace('\n', "<br>"),
+                        argument.types.join(", "),
+                        argument.default.as_ref().map_or_else(
+                            || if argument.optional { "*optional*" } else { "" }.to_string(),
+                            |default| format!("`{default}`")
+                        ),
+                    );
+                }
+            }
+            out.push('\n');
+        }
+        if let Some(return_value) = &self.return_value {
+            let _ = write!(out, "**Return Value**\n\n{return_value}\n\n");
+        }
+        for example in &self.examples {
+            let _ = write!(out, "**Example**\n\n```sqf\n{example}\n```\n\n");
+        }
+        if let Some(public) = self.public {
+            let _ = write!(out, "**Public**: {}\n\n", if public { "Yes" } else { "No" });
+        }
+        if !self.authors.is_empty() {
+            let _ = write!(out, "**Author**: {}\n\n", self.authors.join(", "));
+        }
+        out.trim_end().to_string()
+    }
+}
+
+impl Argument {
+    /// Parse the argument at index `next`, any other index continues the previous argument
+    fn parse(line: &str, span: Range<usize>, next: usize) -> Option<Self> {
+        let indexed = line
+            .split_once(':')
+            .filter(|(index, _)| !index.is_empty() && index.chars().all(|c| c.is_ascii_digit()));
+        if indexed.is_some_and(|(index, _)| index.parse::<usize>().ok() != Some(next)) {
+            return None;
+        }
+        let (name, text) = if let Some((_, text)) = indexed {
+            let text = text.trim();
+            variable(text).map_or((None, text), |name| {
+                (Some(name), text[name.len()..].trim_start())
+            })
+        } else {
+            let name = variable(line)?;
+            (Some(name), line[name.len()..].trim_start())
+        };
+        let mut description = text.trim_start_matches(['-', ':']).trim_start().to_string();
+        let mut types = Vec::new();
+        let brackets = description.rfind('<').and_then(|open| {
+            description[open..]
+                .find('>')
+                .map(|close| (open, open + close))
+        });
+        if let Some((open, close)) = brackets {
+            types = description[open + 1..close]
+                .split([',', '|', '/'])
+                .flat_map(|typ| typ.split(" or "))
+                .map(str::trim)
+                .filter(|typ| !typ.is_empty())
+                .map(ToString::to_string)
+                .collect();
+            description.replace_range(open..=close, "");
+        }
+        let mut default = None;
+        let mut optional = false;
+        let mut search = 0;
+        while let Some(open) = description[search..].find('(').map(|open| search + open) {
+            let Some(close) = closing(&description[open..]).map(|close| open + close) else {
+                break;
+            };
+            let inner = &description[open + 1..close];
+            let lower = inner.to_ascii_lowercase();
+            if !lower.contains("optional") && !lower.contains("default") {
+                search = close + 1;
+                continue;
+            }
+            optional = true;
+            if let Some(index) = lower.find("default") {
+                let value = inner[index + "default".len()..]
+                    .trim_start_matches([':', '='])
+                    .trim();
+                if !value.is_empty() {
+                    default = Some(value.to_string());
+                }
+            }
+            description.replace_range(open..=close, "");
+            search = open;
+        }
+        Some(Self {
+            span,
+            name: name.map(ToString::to_string),
+            description: description.split_whitespace().collect::<Vec<_>>().join(" "),
+            types,
+            default,
+            optional,
+        })
+    }
+
+    #[must_use]
+    /// The span of the argument's line in the source
+    pub fn span(&self) -> Range<usize> {
+        self.span.clone()
+    }
+
+    #[must_use]
+    /// The name of the argument, if the header gives one, `_unit`
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    #[must_use]
+    /// The description of the argument, without its name, types, or default
+    pub fn description(&self) -> &str {
+        &self.description
+    }
+
+    #[must_use]
+    /// The types the argument accepts, `<OBJECT, STRING>`
+    pub fn types(&self) -> &[String] {
+        &self.types
+    }
+
+    #[must_use]
+    /// The documented default value, `(default: 1)`
+    pub fn default(&self) -> Option<&str> {
+        self.default.as_deref()
+    }
+
+    #[must_use]
+    /// Is the argument optional, either marked as such or with a default value
+    pub const fn optional(&self) -> bool {
+        self.optional
+    }
+
+    fn description_with_name(&self) -> String {
+        match &self.name {
+            Some(name) if self.description.is_empty() => format!("`{name}`"),
+            Some(name) => format!("`{name}` {}", self.description),
+            None => self.description.clone(),
+        }
+    }
+}
+
+/// The offset of the header's `/*`, if only comments and directives come before it
+fn start(source: &str) -> Option<usize> {
+    let mut offset = 0;
+    for line in source.split_inclusive('\n') {
+        let trimmed = line.trim();
+        if trimmed.starts_with("/*") {
+            return Some(offset + line.find("/*")?);
+        }
+        if !trimmed.is_empty() && !trimmed.starts_with("//") && !trimmed.starts_with('#') {
+            return None;
+        }
+        offset += line.len();
+    }
+    None
+}
+
+/// Strip the leading `*` and whitespace of a line in a block comment
+fn strip(raw: &str, offset: usize) -> (&str, Range<usize>) {
+    let trimmed = raw.trim_start();
+    let trimmed = trimmed.strip_prefix('*').unwrap_or(trimmed).trim();
+    let start = offset + raw.find(trimmed).unwrap_or_default();
+    (trimmed, start..start + trimmed.len())
+}
+
+/// The section a line starts, and the text after the heading
+fn heading(line: &str) -> Option<(Section, &str)> {
+    let (key, rest) = line.split_once(':')?;
+    let section = match key.trim().to_ascii_lowercase().as_str() {
+        "author" | "authors" => Section::Authors,
+        "description" => Section::Description,
+        "arguments" | "argument" | "parameters" | "parameter" | "params" => Section::Arguments,
+        "return value" | "return values" | "returns" | "return" => Section::ReturnValue,
+        "example" | "examples" => Section::Examples,
+        "public" => Section::Public,
+        "function" | "file" => Section::Ignored,
+        _ => return None,
+    };
+    Some((section, rest.trim()))
+}
+
+/// A local variable at the start of the text, `_unit`
+fn variable(text: &str) -> Option<&str> {
+    if !text.starts_with('_') {
+        return None;
+    }
+    let end = text
+        .find(|c: char| !c.is_ascii_alphanumeric() && c != '_')
+        .unwrap_or(text.len());
+    Some(&text[..end]).filter(|name| name.len() > 1)
+}
+
+/// The offset of the `)` that closes the `(` at the start of the text
+fn closing(text: &str) -> Option<usize> {
+    let mut depth = 0;
+    let mut quote = None;
+    for (index, c) in text.char_indices() {
+        if let Some(open) = quote {
+            if c == open {
+                quote = None;
+            }
+            continue;
+        }
+        match c {
+            '"' | '\'' => quote = Some(c),
+            '(' | '[' | '{' => depth += 1,
+            ')' | ']' | '}' => {
+                depth -= 1;
+                if depth == 0 {
+                    return (c == ')').then_some(index);
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// A parameter declared with `params`
+pub struct Param {
+    name: String,
+    default: Option<String>,
+    span: Range<usize>,
+}
+
+impl Param {
+    #[must_use]
+    pub const fn new(name: String, default: Option<String>, span: Range<usize>) -> Self {
+        Self {
+            name,
+            default,
+            span,
+        }
+    }
+
+    #[must_use]
+    /// The name of the parameter, empty for a skipped parameter, `""`
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    #[must_use]
+    /// The default value of the parameter, as written
+    pub fn default(&self) -> Option<&str> {
+        self.default.as_deref()
+    }
+
+    #[must_use]
+    /// The span of the parameter in the processed output
+    pub fn span(&self) -> Range<usize> {
+        self.span.clone()
+    }
+}
+
+#[must_use]
+/// The parameters of the first `params` for `_this` in the statements
+///
+/// Only the statements themselves are searched, not code blocks inside of them
+pub fn params(statements: &Statements) -> Option<Vec<Param>> {
+    statements.content().iter().find_map(|statement| {
+        let Statement::Expression(expression, _) = statement else {
+            return None;
+        };
+        let array = match expression {
+            Expression::UnaryCommand(UnaryCommand::Named(command), array, _)
+                if command.eq_ignore_ascii_case("params") =>
+            {
+                array
+            }
+            Expression::BinaryCommand(BinaryCommand::Named(command), this, array, _)
+                if command.eq_ignore_ascii_case("params")
+                    && matches!(&**this, Expression::Variable(name, _) if name.eq_ignore_ascii_case("_this")) =>
+            {
+                array
+            }
+            _ => return None,
+        };
+        let Expression::Array(items, _) = &**array else {
+            return None;
+        };
+        Some(
+            items
+                .iter()
+                .map(|item| match item {
+                    Expression::String(name, span, _) => {
+                        Param::new(name.to_string(), None, span.clone())
+                    }
+                    Expression::Array(values, span) => match values.first() {
+                        Some(Expression::String(name, _, _)) => Param::new(
+                            name.to_string(),
+                            values.get(1).map(Expression::source),
+                            span.clone(),
+                        ),
+                        _ => Param::new(String::new(), None, span.clone()),
+                    },
+                    _ => Param::new(String::new(), None, item.full_span()),
+                })
+                .collect(),
+        )
+    })
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// A difference between a header and the function's `params`
+pub enum Mismatch {
+    /// The header documents a different number of arguments than `params` declares
+    Count { documented: usize, declared: usize },
+    /// An argument is documented with a different name than it is declared with
+    Name {
+        index: usize,
+        documented: String,
+        declared: String,
+    },
+    /// An argument is documented with a different default than it is declared with
+    Default {
+        index: usize,
+        documented: String,
+        declared: Option<String>,
+    },
+}
+
+#[must_use]
+/// Compare a header's arguments with the function's `params`
+///
+/// Headers without an arguments section are not compared, and defaults are only compared when
+/// the header documents one
+pub fn validate(header: &Header, params: &[Param]) -> Vec<Mismatch> {
+    let Some(arguments) = header.arguments() else {
+        return Vec::new();
+    };
+    let mut mismatches = Vec::new();
+    if arguments.len() != params.len() {
+        mismatches.push(Mismatch::Count {
+            documented: arguments.len(),
+            declared: params.len(),
+        });
+    }
+    for (index, (argument, param)) in arguments.iter().zip(params).enumerate() {
+        if let Some(name) = argument
+            .name()
+            .filter(|name| !param.name().is_empty() && !name.eq_ignore_ascii_case(param.name()))
+        {
+            mismatches.push(Mismatch::Name {
+                index,
+                documented: name.to_string(),
+                declared: param.name().to_string(),
+            });
+        }
+        if let Some(default) = argument.default().filter(|default| {
+            param
+                .default()
+                .is_none_or(|declared| !same(default, declared))
+        }) {
+            mismatches.push(Mismatch::Default {
+                index,
+                documented: default.to_string(),
+                declared: param.default().map(ToString::to_string),
+            });
+        }
+    }
+    mismatches
+}
+
+/// Are two values the same, ignoring whitespace and case
+fn same(documented: &str, declared: &str) -> bool {
+    let strip = |value: &str| {
+        let value = value.trim().trim_matches('`');
+        value
+            .chars()
+            .filter(|c| !c.is_whitespace())
+            .flat_map(char::to_lowercase)
+            .collect::<String>()
+    };
+    strip(documented) == strip(declared)
+}
+
+#[must_use]
+/// The file that was processed, and its source
+///
+/// Every other source was pulled in with `#include`
+pub fn main_source(processed: &Processed) -> Option<(WorkspacePath, String)> {
+    processed
+        .sources()
+        .into_iter()
+        .find(|(path, _)| !path.is_include() && !processed.included().contains(path))
+}
+
+#[must_use]
+/// Are the statements the whole file, rather than a code block inside of it
+pub fn is_file(statements: &Statements, processed: &Processed) -> bool {
+    processed
+        .as_str()
+        .chars()
+        .take(statements.span().start)
+        .all(char::is_whitespace)
+}
+
+#[must_use]
+/// Convert a span of bytes in the source to a span of characters, as used by labels
+pub fn char_span(source: &str, span: Range<usize>) -> Range<usize> {
+    let start = source[..span.start].chars().count();
+    start..start + source[span].chars().count()
+}
+
+#[must_use]
+/// Is the file a function, `fnc_name.sqf`
+pub fn is_function(path: &WorkspacePath) -> bool {
+    let filename = path.filename();
+    filename
+        .get(..4)
+        .is_some_and(|start| start.eq_ignore_ascii_case("fnc_"))
+        && filename
+            .rsplit_once('.')
+            .is_some_and(|(_, extension)| extension.eq_ignore_ascii_case("sqf"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ACE: &str = r#"#include "..\script_component.hpp"
+/*
+ * Author: Brett, Mike
+ * Sets the volume of a piano
+ * that is playing.
+ *
+ * Arguments:
+ * 0: Piano <OBJECT>
+ * 1: Volume <NUMBER> (default: 1)
+ * 2: Notes <ARRAY>
+ * - 0: Key <STRING>
+ * 3: Loud <BOOL> (Optional)
+ *
+ * Return Value:
+ * Volume was changed <BOOL>
+ *
+ * Example:
+ * [_piano, 0.5] call abe_main_fnc_setVolume
+ *
+ * [_piano] call abe_main_fnc_setVolume
+ *
+ * Public: Yes
+ */
+
+params ["_piano", ["_volume", 1]];
+"#;
+
+    const CBA: &str = r"/* ----------------------------------------------------------------------------
+Function: CBA_fnc_setVolume
+
+Description:
+    Sets the volume of a piano.
+
+Parameters:
+    _piano - The piano <OBJECT>
+    _volume - The volume, (Optional, default: [0, 1]) <NUMBER, ARRAY>
+
+Returns:
+    Nothing
+
+Examples:
+    (begin example)
+        [_piano, 0.5] call CBA_fnc_setVolume;
+    (end)
+
+Author:
+    Brett
+---------------------------------------------------------------------------- */
+";
+
+    const NESTED: &str = r"/*
+ * Author: Brett
+ * Spawns a group of units
+ *
+ * Arguments:
+ * 0: Group <GROUP>
+ * 1: Units <ARRAY>
+ *   0: Class <STRING>
+ *   1: Rank <STRING>
+ *   2: Position <ARRAY>
+ *     0: X <NUMBER>
+ *     1: Y <NUMBER>
+ * 2: Delay <NUMBER> (default: 0)
+ * 3: Waypoints <ARRAY>
+ * 0: Position <ARRAY>
+ *
+ * Return Value:
+ * None
+ *
+ * Example:
+ * [_group, [[_class, _rank, [0, 0]]]] call abe_main_fnc_spawn
+ *
+ * Public: No
+ */
+";
+
+    #[test]
+    fn ace() {
+        let header = Header::parse(ACE).expect("header");
+        assert_eq!(header.authors(), &["Brett", "Mike"]);
+        assert_eq!(
+            header.description(),
+            "Sets the volume of a piano\nthat is playing."
+        );
+        let arguments = header.arguments().expect("arguments");
+        assert_eq!(arguments.len(), 4);
+        assert_eq!(arguments[0].description(), "Piano");
+        assert_eq!(arguments[0].types(), &["OBJECT"]);
+        assert_eq!(arguments[0].default(), None);
+        assert!(!arguments[0].optional());
+        assert_eq!(&ACE[arguments[0].span()], "0: Piano <OBJECT>");
+        assert_eq!(arguments[1].description(), "Volume");
+        assert_eq!(arguments[1].default(), Some("1"));
+        assert!(arguments[1].optional());
+        assert_eq!(arguments[2].description(), "Notes\n- 0: Key <STRING>");
+        assert_eq!(arguments[3].default(), None);
+        assert!(arguments[3].optional());
+        assert_eq!(header.return_value(), Some("Volume was changed <BOOL>"));
+        assert_eq!(
+            header.examples(),
+            &[
+                "[_piano, 0.5] call abe_main_fnc_setVolume",
+                "[_piano] call abe_main_fnc_setVolume"
+            ]
+        );
+        assert_eq!(header.public(), Some(true));
+        assert!(header.missing().is_empty());
+        assert!(ACE[header.span()].starts_with("/*"));
+        assert!(ACE[header.span()].ends_with("*/"));
+    }
+
+    #[test]
+    fn nested() {
+        let header = Header::parse(NESTED).expect("header");
+        let arguments = header.arguments().expect("arguments");
+        assert_eq!(arguments.len(), 4);
+        assert_eq!(arguments[0].description(), "Group");
+        assert_eq!(
+            arguments[1].description(),
+            "Units\n0: Class <STRING>\n1: Rank <STRING>\n2: Position <ARRAY>\n0: X <NUMBER>\n1: Y <NUMBER>"
+        );
+        assert_eq!(arguments[1].types(), &["ARRAY"]);
+        assert_eq!(&NESTED[arguments[1].span()], "1: Units <ARRAY>");
+        assert_eq!(arguments[2].description(), "Delay");
+        assert_eq!(arguments[2].types(), &["NUMBER"]);
+        assert_eq!(arguments[2].default(), Some("0"));
+        // not indented, but not the next index either
+        assert_eq!(arguments[3].description(), "Waypoints\n0: Position <ARRAY>");
+        assert_eq!(header.public(), Some(false));
+    }
+
+    #[test]
+    fn cba() {
+        let header = Header::parse(CBA).expect("header");
+        assert_eq!(header.authors(), &["Brett"]);
+        assert_eq!(header.description(), "Sets the volume of a piano.");
+        let arguments = header.arguments().expect("arguments");
+        assert_eq!(arguments.len(), 2);
+        assert_eq!(arguments[0].name(), Some("_piano"));
+        assert_eq!(arguments[0].description(), "The piano");
+        assert_eq!(arguments[1].name(), Some("_volume"));
+        assert_eq!(arguments[1].default(), Some("[0, 1]"));
+        assert_eq!(arguments[1].types(), &["NUMBER", "ARRAY"]);
+        assert_eq!(header.return_value(), Some("Nothing"));
+        assert_eq!(
+            header.examples(),
+            &["[_piano, 0.5] call CBA_fnc_setVolume;"]
+        );
+        assert_eq!(header.public(), None);
+    }
+
+    #[test]
+    fn not_a_header() {
+        assert!(Header::parse("/* Copyright 2024 */\nparams [];").is_none());
+        assert!(Header::parse("params [];\n/*\n * Arguments:\n * None\n */").is_none());
+        assert!(Header::parse("params [];").is_none());
+    }
+
+    #[test]
+    fn no_arguments() {
+        let header =
+            Header::parse("/*\n * Arguments:\n * None\n *\n * Public: No\n */").expect("header");
+        assert_eq!(header.arguments(), Some([].as_slice()));
+        assert_eq!(header.public(), Some(false));
+        assert_eq!(
+            header.missing(),
+            vec!["description", "return value", "example"]
+        );
+        assert!(validate(&header, &[]).is_empty());
+    }
+
+    #[test]
+    fn mismatches() {
+        let header = Header::parse(CBA).expect("header");
+        let param = |name: &str, default: Option<&str>| {
+            Param::new(name.to_string(), default.map(ToString::to_string), 0..0)
+        };
+        assert!(
+            validate(
+                &header,
+                &[param("_piano", None), param("_volume", Some("[0,1]"))]
+            )
+            .is_empty()
+        );
+        assert_eq!(
+            validate(&header, &[param("_instrument", None)]),
+            vec![
+                Mismatch::Count {
+                    documented: 2,
+                    declared: 1
+                },
+                Mismatch::Name {
+                    index: 0,
+                    documented: "_piano".to_string(),
+                    declared: "_instrument".to_string()
+                }
+            ]
+        );
+        assert_eq!(
+            validate(
+                &header,
+                &[param("_piano", None), param("_volume", Some("1"))]
+            ),
+            vec![Mismatch::Default {
+                index: 1,
+                documented: "[0, 1]".to_string(),
+                declared: Some("1".to_string())
+            }]
+        );
+        assert_eq!(
+            validate(&header, &[param("", None), param("_volume", None)]),
+            vec![Mismatch::Default {
+                index: 1,
+                documented: "[0, 1]".to_string(),
+                declared: None
+            }]
+        );
+    }
+
+    #[test]
+    fn markdown() {
+        let header = Header::parse(ACE).expect("header");
+        let markdown = header.markdown("abe_main_fnc_setVolume");
+        assert!(markdown.starts_with("### abe_main_fnc_setVolume\n\nSets the volume"));
+        assert!(markdown.contains("| 1 | Volume | NUMBER | `1` |"));
+        assert!(markdown.contains("| 3 | Loud | BOOL | *optional* |"));
+        assert!(markdown.ends_with("**Author**: Brett, Mike"));
+    }
+}
@@ -5,6 +5,7 @@ pub mod parser;
 
 pub mod analyze;
 mod error;
+pub mod header;
 mod misc;
 
 use std::{ops::Range, sync::Arc};
@@ -44,6 +44,7 @@ lint!(s24_marker_spam);
 lint!(s26_short_circuit_bool_var);
 lint!(s27_select_count);
 lint!(s28_banned_macros);
+lint!(s30_function_header);
 
 #[test]
 fn test_s29_function_undefined() {
@@ -59,6 +60,16 @@ fn test_s29_function_undefined() {
     insta::assert_compact_debug_snapshot!((functions_defined, functions_used));
 }
 
+#[test]
+fn test_s31_function_undocumented() {
+    // Only files named like a function are checked
+    let codes = [
+        lint("s31_function_undocumented/fnc_play").0,
+        lint("s31_function_undocumented/fnc_tune").0,
+    ];
+    insta::assert_snapshot!(codes.join("\n"));
+}
+
 fn lint(file: &str) -> (String, SqfReport) {
     let folder = std::path::PathBuf::from(ROOT);
     let workspace = hemtt_workspace::Workspace::builder()
@@ -242,6 +242,42 @@ impl Key {
         self.danish.as_deref()
     }
 
+    #[must_use]
+    /// The languages that have a value, by their lowercase name, including `original`
+    pub fn translations(&self) -> Vec<(&'static str, &str)> {
+        [
+            ("original", &self.original),
+            ("english", &self.english),
+            ("czech", &self.czech),
+            ("french", &self.french),
+            ("spanish", &self.spanish),
+            ("italian", &self.italian),
+            ("polish", &self.polish),
+            ("portuguese", &self.portuguese),
+            ("russian", &self.russian),
+            ("german", &self.german),
+            ("korean", &self.korean),
+            ("japanese", &self.japanese),
+            ("chinese", &self.chinese),
+            ("chinesesimp", &self.chinesesimp),
+            ("turkish", &self.turkish),
+            ("swedish", &self.swedish),
+            ("slovak", &self.slovak),
+            ("serbocroatian", &self.serbocroatian),
+            ("norwegian", &self.norwegian),
+            ("icelandic", &self.icelandic),
+            ("hungarian", &self.hungarian),
+            ("greek", &self.greek),
+            ("finnish", &self.finnish),
+            ("dutch", &self.dutch),
+            ("ukrainian", &self.ukrainian),
+            ("danish", &self.danish),
+        ]
+        .into_iter()
+        .filter_map(|(language, value)| value.as_deref().map(|value| (language, value)))
+        .collect()
+    }
+
     /// Set the value for a specific language.
     ///
     /// # Panics
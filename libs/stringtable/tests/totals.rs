@@ -16,3 +16,26 @@ fn totals_ace_arsenal() {
     assert_eq!(arsenal.name(), "Arsenal");
     insta::assert_debug_snapshot!(arsenal.totals());
 }
+
+#[test]
+fn translations_ace_arsenal() {
+    let stringtable =
+        Project::read(WorkspacePath::slim_file("tests/ace_arsenal.xml").unwrap()).unwrap();
+    let key = stringtable
+        .packages()
+        .first()
+        .unwrap()
+        .keys()
+        .first()
+        .unwrap();
+    assert_eq!(key.id(), "STR_ACE_Arsenal_buttonHideText");
+    let translations = key.translations();
+    assert_eq!(translations.len(), 14);
+    assert_eq!(translations.first(), Some(&("english", "Hide")));
+    assert!(translations.contains(&("german", "Verstecken")));
+    assert!(
+        !translations
+            .iter()
+            .any(|(language, _)| *language == "original")
+    );
+}
@@ -1,6 +1,10 @@
 //! A workspace (directory) containing addons and / or missions
 
-use std::{collections::HashMap, path::PathBuf, sync::Arc};
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
 
 use hemtt_common::{
     config::{PDriveOption, ProjectConfig},
@@ -18,6 +22,7 @@ pub mod addons;
 pub mod error;
 pub mod lint;
 pub mod path;
+pub mod pbo;
 pub mod pdrive;
 pub mod position;
 pub mod reporting;
@@ -146,6 +151,8 @@ impl Workspace {
 pub enum LayerType {
     Source,
     Include,
+    /// PBOs from other mods, mounted read-only at their prefix
+    Pbo,
     Build,
 }
 
@@ -167,6 +174,16 @@ impl WorkspaceBuilder {
         self
     }
 
+    /// Add a read-only layer of the PBOs in a folder, each mounted at its prefix
+    ///
+    /// # Errors
+    /// [`Error::Io`] if the folder could not be read
+    pub fn pbos(mut self, path: &Path) -> Result<Self, Error> {
+        self.layers
+            .push((pbo::PboFS::new(path)?.into(), LayerType::Pbo));
+        Ok(self)
+    }
+
     #[must_use]
     /// Add a memory layer to the virtual filesystem
     pub fn memory(mut self) -> Self {
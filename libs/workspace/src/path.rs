@@ -75,13 +75,13 @@ impl WorkspacePath {
     }
 
     #[must_use]
-    /// Is the file from an include path
+    /// Is the file from an include path, or from the PBO of another mod
     pub fn is_include(&self) -> bool {
         self.data
             .workspace
             .layers
             .iter()
-            .filter(|(_, t)| *t == LayerType::Include)
+            .filter(|(_, t)| matches!(t, LayerType::Include | LayerType::Pbo))
             .any(|(p, _)| {
                 p.join(self.data.path.as_str())
                     .and_then(|p| p.exists())
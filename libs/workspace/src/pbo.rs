@@ -0,0 +1,205 @@
+//! A read-only filesystem backed by PBOs, each mounted at its prefix
+//!
+//! Used to resolve includes from other mods without extracting them or setting up a P drive.
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    fs::File,
+    io::{Cursor, Read},
+    path::{Path, PathBuf},
+};
+
+use hemtt_pbo::ReadablePbo;
+use tracing::{trace, warn};
+use vfs::{
+    FileSystem, SeekAndRead, SeekAndWrite, VfsFileType, VfsMetadata, VfsResult, error::VfsErrorKind,
+};
+
+use crate::Error;
+
+#[derive(Debug)]
+struct Entry {
+    /// Index into [`PboFS::pbos`]
+    pbo: usize,
+    /// The name of the file inside the PBO
+    name: String,
+    size: u64,
+}
+
+#[derive(Debug, Default)]
+/// A read-only filesystem of the files inside a folder of PBOs
+///
+/// Paths are case-insensitive, the same as the game
+pub struct PboFS {
+    pbos: Vec<PathBuf>,
+    /// Files by their lowercase path
+    files: HashMap<String, Entry>,
+    /// The children of each directory, by the directory's lowercase path
+    dirs: HashMap<String, BTreeMap<String, String>>,
+}
+
+impl PboFS {
+    /// Index every PBO in a folder and its subfolders
+    ///
+    /// PBOs without a `prefix` property are mounted at their file name, the same as the game.
+    /// When two PBOs contain the same file, the first in alphabetical order is used.
+    ///
+    /// # Errors
+    /// [`Error::Io`] if the folder does not exist or could not be read
+    pub fn new(folder: &Path) -> Result<Self, Error> {
+        if !folder.is_dir() {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::NotFound,
+                format!("{} is not a folder", folder.display()),
+            )
+            .into());
+        }
+        let mut fs = Self::default();
+        fs.dirs.insert(String::new(), BTreeMap::new());
+        let mut paths = Vec::new();
+        find(folder, &mut paths)?;
+        paths.sort();
+        for path in paths {
+            let pbo = match ReadablePbo::from(File::open(&path)?) {
+                Ok(pbo) => pbo,
+                Err(e) => {
+                    warn!("Skipping {}, it could not be read: {e}", path.display());
+                    continue;
+                }
+            };
+            let prefix = pbo.properties().get("prefix").map_or_else(
+                || {
+                    path.file_stem()
+                        .unwrap_or_default()
+                        .to_string_lossy()
+                        .to_string()
+                },
+                Clone::clone,
+            );
+            let prefix = prefix.replace('\\', "/");
+            let prefix = prefix.trim_matches('/');
+            trace!("Mounting {} at {prefix}", path.display());
+            let index = fs.pbos.len();
+            for header in pbo.files() {
+                let inner = header.filename().replace('\\', "/");
+                fs.insert(
+                    &format!("/{prefix}/{inner}"),
+                    Entry {
+                        pbo: index,
+                        name: header.filename().to_string(),
+                        size: u64::from(header.unpacked_size()),
+                    },
+                );
+            }
+            fs.pbos.push(path);
+        }
+        Ok(fs)
+    }
+
+    fn insert(&mut self, path: &str, entry: Entry) {
+        let lower = path.to_lowercase();
+        if self.files.contains_key(&lower) {
+            return;
+        }
+        let mut child = path;
+        while let Some((parent, name)) = child.rsplit_once('/') {
+            let children = self.dirs.entry(parent.to_lowercase()).or_default();
+            let existed = !children.is_empty();
+            children
+                .entry(name.to_lowercase())
+                .or_insert_with(|| name.to_string());
+            if existed {
+                break;
+            }
+            child = parent;
+        }
+        self.files.insert(lower, entry);
+    }
+}
+
+/// Find every PBO in a folder and its subfolders
+fn find(folder: &Path, paths: &mut Vec<PathBuf>) -> Result<(), Error> {
+    for entry in std::fs::read_dir(folder)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            find(&path, paths)?;
+        } else if path
+            .extension()
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("pbo"))
+        {
+            paths.push(path);
+        }
+    }
+    Ok(())
+}
+
+impl FileSystem for PboFS {
+    fn read_dir(&self, path: &str) -> VfsResult<Box<dyn Iterator<Item = String> + Send>> {
+        let children = self
+            .dirs
+            .get(&path.to_lowercase())
+            .ok_or(VfsErrorKind::FileNotFound)?;
+        Ok(Box::new(
+            children.values().cloned().collect::<Vec<_>>().into_iter(),
+        ))
+    }
+
+    fn create_dir(&self, _path: &str) -> VfsResult<()> {
+        Err(VfsErrorKind::NotSupported.into())
+    }
+
+    fn open_file(&self, path: &str) -> VfsResult<Box<dyn SeekAndRead + Send>> {
+        let entry = self
+            .files
+            .get(&path.to_lowercase())
+            .ok_or(VfsErrorKind::FileNotFound)?;
+        let mut pbo = ReadablePbo::from(File::open(&self.pbos[entry.pbo])?)
+            .map_err(|e| VfsErrorKind::Other(e.to_string()))?;
+        let mut file = pbo
+            .file(&entry.name)
+            .map_err(|e| VfsErrorKind::Other(e.to_string()))?
+            .ok_or(VfsErrorKind::FileNotFound)?;
+        let mut buffer = Vec::new();
+        file.read_to_end(&mut buffer)?;
+        Ok(Box::new(Cursor::new(buffer)))
+    }
+
+    fn create_file(&self, _path: &str) -> VfsResult<Box<dyn SeekAndWrite + Send>> {
+        Err(VfsErrorKind::NotSupported.into())
+    }
+
+    fn append_file(&self, _path: &str) -> VfsResult<Box<dyn SeekAndWrite + Send>> {
+        Err(VfsErrorKind::NotSupported.into())
+    }
+
+    fn metadata(&self, path: &str) -> VfsResult<VfsMetadata> {
+        let lower = path.to_lowercase();
+        let (file_type, len) = if let Some(entry) = self.files.get(&lower) {
+            (VfsFileType::File, entry.size)
+        } else if self.dirs.contains_key(&lower) {
+            (VfsFileType::Directory, 0)
+        } else {
+            return Err(VfsErrorKind::FileNotFound.into());
+        };
+        Ok(VfsMetadata {
+            file_type,
+            len,
+            created: None,
+            modified: None,
+            accessed: None,
+        })
+    }
+
+    fn exists(&self, path: &str) -> VfsResult<bool> {
+        let lower = path.to_lowercase();
+        Ok(self.files.contains_key(&lower) || self.dirs.contains_key(&lower))
+    }
+
+    fn remove_file(&self, _path: &str) -> VfsResult<()> {
+        Err(VfsErrorKind::NotSupported.into())
+    }
+
+    fn remove_dir(&self, _path: &str) -> VfsResult<()> {
+        Err(VfsErrorKind::NotSupported.into())
+    }
+}
@@ -15,6 +15,9 @@ pub type Sources = Vec<(WorkspacePath, String)>;
 pub struct Processed {
     sources: Sources,
 
+    /// Every file pulled in with `#include`
+    included: Vec<WorkspacePath>,
+
     output: String,
     clean_output: String,
     clean_output_line_indexes: Vec<(usize, usize)>,
@@ -281,6 +284,7 @@ impl Processed {
     /// [`Error::Workspace`] if a workspace path could not be read
     pub fn new(
         output: Vec<Output>,
+        included: Vec<WorkspacePath>,
         macros: HashMap<String, Vec<(Position, Definition)>>,
         #[cfg(feature = "lsp")] usage: HashMap<Position, Vec<Position>>,
         warnings: Codes,
@@ -298,6 +302,7 @@ impl Processed {
 
         let mut processed = Self {
             sources: processing.sources,
+            included,
             output: processing.output,
             clean_output: String::new(),
             clean_output_line_indexes: Vec::new(),
@@ -351,6 +356,12 @@ impl Processed {
         self.sources.clone()
     }
 
+    #[must_use]
+    /// Get every file pulled in with `#include`, including files that only define macros
+    pub fn included(&self) -> &[WorkspacePath] {
+        &self.included
+    }
+
     #[must_use]
     /// Get a source by index
     ///
@@ -401,6 +412,15 @@ impl Processed {
             .find(|m| !m.was_macro)
     }
 
+    #[must_use]
+    /// Get the tree mappings that originate from a line in a source file
+    pub fn mappings_on_line(&self, source: &WorkspacePath, line: usize) -> Vec<&Mapping> {
+        self.mappings
+            .iter()
+            .filter(|m| m.original().start().line() == line && m.original().path() == source)
+            .collect()
+    }
+
     #[must_use]
     /// Get the macros defined
     pub const fn macros(&self) -> &HashMap<String, Vec<(Position, Definition)>> {
@@ -0,0 +1,98 @@
+#![allow(clippy::unwrap_used)]
+
+use std::{io::Cursor, path::PathBuf};
+
+use hemtt_common::config::PDriveOption;
+use hemtt_pbo::WritablePbo;
+use hemtt_workspace::{LayerType, Workspace};
+
+/// A folder of PBOs, like a mod downloaded from the workshop
+fn mod_folder() -> PathBuf {
+    let folder = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("pbos/@cba/addons");
+    std::fs::create_dir_all(&folder).unwrap();
+    let mut pbo = WritablePbo::new();
+    pbo.add_property("prefix", "x\\cba\\addons\\main");
+    pbo.add_file(
+        "script_macros_common.hpp",
+        Cursor::new(b"#define CBA_MAIN 1\n".to_vec()),
+    )
+    .unwrap();
+    pbo.add_file("data/icon.paa", Cursor::new(Vec::new()))
+        .unwrap();
+    pbo.write(
+        &mut std::fs::File::create(folder.join("cba_main.pbo")).unwrap(),
+        true,
+    )
+    .unwrap();
+    let mut pbo = WritablePbo::new();
+    pbo.add_file("readme.txt", Cursor::new(b"no prefix".to_vec()))
+        .unwrap();
+    pbo.write(
+        &mut std::fs::File::create(folder.join("cba_noprefix.pbo")).unwrap(),
+        true,
+    )
+    .unwrap();
+    folder.parent().unwrap().to_path_buf()
+}
+
+#[test]
+fn locate() {
+    let workspace = Workspace::builder()
+        .physical(&PathBuf::from("tests/pbos/project"), LayerType::Source)
+        .pbos(&mod_folder())
+        .unwrap()
+        .finish(None, true, &PDriveOption::Disallow)
+        .unwrap();
+    let config = workspace.join("addons/main/config.cpp").unwrap();
+    assert!(!config.is_include());
+
+    let found = config
+        .locate("\\x\\cba\\addons\\main\\script_macros_common.hpp")
+        .unwrap()
+        .unwrap();
+    assert!(found.path.is_include());
+    assert_eq!(found.path.read_to_string().unwrap(), "#define CBA_MAIN 1\n");
+
+    // Paths are case-insensitive, the same as the game
+    let found = config
+        .locate("\\X\\CBA\\Addons\\Main\\Script_Macros_Common.hpp")
+        .unwrap()
+        .unwrap();
+    assert_eq!(found.path.read_to_string().unwrap(), "#define CBA_MAIN 1\n");
+
+    // Without a prefix, the PBO is mounted at its name
+    let found = config
+        .locate("\\cba_noprefix\\readme.txt")
+        .unwrap()
+        .unwrap();
+    assert_eq!(found.path.read_to_string().unwrap(), "no prefix");
+
+    let main = workspace.join("x/cba/addons/main").unwrap();
+    assert!(main.is_dir().unwrap());
+    let mut children = main
+        .read_dir()
+        .unwrap()
+        .iter()
+        .map(hemtt_workspace::WorkspacePath::filename)
+        .collect::<Vec<_>>();
+    children.sort();
+    assert_eq!(children, vec!["data", "script_macros_common.hpp"]);
+
+    // The project's own addons are still discovered, and nothing is written to the PBOs
+    assert_eq!(workspace.addons().len(), 1);
+    assert!(
+        config
+            .locate("\\x\\cba\\addons\\main\\missing.hpp")
+            .unwrap()
+            .is_none()
+    );
+}
+
+#[test]
+fn missing_folder() {
+    assert!(
+        Workspace::builder()
+            .pbos(&PathBuf::from("tests/pbos/missing"))
+            .is_err()
+    );
+}
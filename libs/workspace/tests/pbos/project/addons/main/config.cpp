@@ -0,0 +1 @@
+#include "\x\cba\addons\main\script_macros_common.hpp"